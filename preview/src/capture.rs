@@ -0,0 +1,178 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::scene::{Model, point};
+use bevy::{
+    camera_controller::free_camera::FreeCamera,
+    prelude::*,
+    render::view::screenshot::{Screenshot, save_to_disk},
+};
+use std::collections::VecDeque;
+use std::f32::consts::TAU;
+use std::path::PathBuf;
+
+/// Frames in a full turn of the turntable
+const TURNTABLE_FRAMES: usize = 120;
+/// Frames to wait before and after captures, so the scene is drawn and files are written
+const SETTLE_FRAMES: u32 = 10;
+
+enum Job {
+    Screenshot(PathBuf),
+    Turntable {
+        dir: PathBuf,
+        frame: usize,
+        /// Center of the model and the camera offset from it when the turn started
+        orbit: Option<(Vec3, Vec3)>,
+    },
+}
+
+/// Screenshots and turntables waiting to be captured, one job at a time
+#[derive(Resource)]
+pub struct Capture {
+    jobs: VecDeque<Job>,
+    /// Exit once every job is done, for captures requested on the command line
+    exit_when_done: bool,
+    wait: u32,
+    taken: usize,
+}
+
+impl Capture {
+    pub fn new(screenshot: Option<PathBuf>, turntable: Option<PathBuf>) -> Self {
+        let mut capture = Self {
+            jobs: VecDeque::new(),
+            exit_when_done: screenshot.is_some() || turntable.is_some(),
+            wait: SETTLE_FRAMES,
+            taken: 0,
+        };
+        capture.jobs.extend(screenshot.map(Job::Screenshot));
+        capture.jobs.extend(turntable.map(Capture::turntable));
+        capture
+    }
+
+    fn turntable(dir: PathBuf) -> Job {
+        Job::Turntable {
+            dir,
+            frame: 0,
+            orbit: None,
+        }
+    }
+}
+
+/// F12 takes a screenshot, F10 records a turntable, both into the working directory
+pub fn capture_keys(keys: Res<ButtonInput<KeyCode>>, mut capture: ResMut<Capture>) {
+    if keys.just_pressed(KeyCode::F12) {
+        let path = PathBuf::from(format!("screenshot-{}.png", capture.taken));
+        capture.taken += 1;
+        capture.jobs.push_back(Job::Screenshot(path));
+    }
+    if keys.just_pressed(KeyCode::F10) {
+        let dir = PathBuf::from(format!("turntable-{}", capture.taken));
+        capture.taken += 1;
+        capture.jobs.push_back(Capture::turntable(dir));
+    }
+}
+
+pub fn capture(
+    mut capture: ResMut<Capture>,
+    models: Query<(&Model, &GlobalTransform, &InheritedVisibility)>,
+    mut commands: Commands,
+    mut cameras: Query<(Entity, &mut Transform), With<Camera3d>>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    if capture.wait > 0 {
+        capture.wait -= 1;
+        return;
+    }
+
+    let Some(job) = capture.jobs.front_mut() else {
+        if capture.exit_when_done {
+            exit.write(AppExit::Success);
+        }
+        return;
+    };
+
+    match job {
+        Job::Screenshot(path) => {
+            info!("saving screenshot to {}", path.display());
+            commands
+                .spawn(Screenshot::primary_window())
+                .observe(save_to_disk(path.clone()));
+            capture.jobs.pop_front();
+            capture.wait = SETTLE_FRAMES;
+        }
+        Job::Turntable { dir, frame, orbit } => {
+            let Ok((camera, mut transform)) = cameras.single_mut() else {
+                return;
+            };
+
+            let (center, offset) = *orbit.get_or_insert_with(|| {
+                // the free camera would fight the turntable for the transform
+                commands.entity(camera).remove::<FreeCamera>();
+                let center = scene_center(&models);
+                (center, transform.translation - center)
+            });
+            if *frame == 0
+                && let Err(e) = std::fs::create_dir_all(&*dir)
+            {
+                error!("can not create {}: {e}", dir.display());
+                capture.jobs.pop_front();
+                return;
+            }
+
+            let angle = TAU * *frame as f32 / TURNTABLE_FRAMES as f32;
+            *transform =
+                Transform::from_translation(center + Quat::from_rotation_y(angle) * offset)
+                    .looking_at(center, Vec3::Y);
+            commands
+                .spawn(Screenshot::primary_window())
+                .observe(save_to_disk(dir.join(format!("frame-{frame:04}.png"))));
+
+            *frame += 1;
+            if *frame == TURNTABLE_FRAMES {
+                info!(
+                    "turntable saved, encode it with: ffmpeg -framerate 30 -i {}/frame-%04d.png -pix_fmt yuv420p turntable.mp4",
+                    dir.display()
+                );
+                commands.entity(camera).insert(crate::free_camera());
+                capture.jobs.pop_front();
+                capture.wait = SETTLE_FRAMES;
+            }
+        }
+    }
+}
+
+/// Center of the bounding box of the visible tessellations, the origin if there is none
+fn scene_center(models: &Query<(&Model, &GlobalTransform, &InheritedVisibility)>) -> Vec3 {
+    let points = models
+        .iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .flat_map(|(model, transform, _)| {
+            model
+                .points
+                .iter()
+                .map(|p| transform.transform_point(point(p)))
+        });
+    let (min, max) = points.fold(
+        (Vec3::splat(f32::INFINITY), Vec3::splat(f32::NEG_INFINITY)),
+        |(min, max), p| (min.min(p), max.max(p)),
+    );
+    if min.cmple(max).all() {
+        (min + max) / 2.
+    } else {
+        Vec3::ZERO
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::{Result, bail};
+use bevy::{color::palettes::tailwind, prelude::*};
+use fixed::types::{I16F16, I32F32};
+use t_spline::uv_mesh::Boundary;
+use t_spline::{F16, Numeric, Point3, TSpline};
+use t_spline_commands::tessellate::{TessellationReport, tessellate_with_report};
+
+/// Number of colors the deviation heat map is drawn with
+const HEAT_STEPS: usize = 16;
+
+/// How the compared tessellation is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    /// Next to the `f64` tessellation
+    SideBySide,
+    /// In place of the `f64` tessellation, colored by the distance to it
+    HeatMap,
+}
+
+/// A model tessellated in `f64` and again in a compact [Numeric] type
+#[derive(Resource)]
+pub struct Comparison {
+    pub unit: String,
+    pub view: View,
+    /// Points of the compact type with their distance to the `f64` surface, samples
+    /// dropped by either type are left out
+    pub samples: Vec<(Point3<f64>, f64)>,
+    pub max_error: f64,
+    /// Shift along x between the two tessellations when shown side by side
+    pub offset: f32,
+}
+
+impl Comparison {
+    pub fn new(
+        spline: &TSpline,
+        unit: &str,
+        view: View,
+        resolution: usize,
+        boundary: Boundary,
+    ) -> Result<Self> {
+        let samples = match unit {
+            "f32" => deviations::<f32>(spline, resolution, boundary)?,
+            "f16" => deviations::<F16>(spline, resolution, boundary)?,
+            "i16f16" => deviations::<I16F16>(spline, resolution, boundary)?,
+            "i32f32" => deviations::<I32F32>(spline, resolution, boundary)?,
+            _ => bail!("unknown unit {unit}, expected f32, f16, i16f16 or i32f32"),
+        };
+
+        let max_error = samples.iter().map(|(_, e)| *e).fold(0., f64::max);
+        let (min_x, max_x) = samples
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (p, _)| {
+                (lo.min(p.x), hi.max(p.x))
+            });
+        let offset = if min_x <= max_x {
+            ((max_x - min_x) * 1.2) as f32 + 1.
+        } else {
+            0.
+        };
+
+        Ok(Self {
+            unit: unit.to_string(),
+            view,
+            samples,
+            max_error,
+            offset,
+        })
+    }
+}
+
+fn deviations<U: Numeric + Send + Sync + 'static>(
+    spline: &TSpline,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<Vec<(Point3<f64>, f64)>> {
+    let (compact, _) = spline.convert::<U>()?;
+    let reference = by_sample(tessellate_with_report(spline, resolution, boundary)?);
+    let compact = by_sample(tessellate_with_report(&compact, resolution, boundary)?);
+
+    Ok(reference
+        .into_iter()
+        .zip(compact)
+        .filter_map(|(r, c)| {
+            let (r, c) = (r?, c?);
+            let c = Point3::new(c.x.to_f64()?, c.y.to_f64()?, c.z.to_f64()?);
+            let d = c - r;
+            Some((c, d.dot(&d).sqrt()))
+        })
+        .collect())
+}
+
+/// Points of a tessellation by sample index, `None` for dropped samples
+fn by_sample<T: Numeric + 'static>(report: TessellationReport<T>) -> Vec<Option<Point3<T>>> {
+    let mut points = report.points.into_iter();
+    let mut dropped = report.dropped.iter().map(|d| d.index).peekable();
+    (0..report.samples)
+        .map(|i| match dropped.next_if_eq(&i) {
+            Some(_) => None,
+            None => points.next(),
+        })
+        .collect()
+}
+
+pub fn draw_comparison(
+    comparison: Res<Comparison>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    info!(
+        "{}: max deviation {:e} over {} samples",
+        comparison.unit,
+        comparison.max_error,
+        comparison.samples.len()
+    );
+
+    let point_mesh = meshes.add(Sphere::new(0.005));
+    let flat_mat = materials.add(StandardMaterial {
+        base_color: tailwind::PURPLE_500.into(),
+        unlit: true,
+        ..default()
+    });
+    // one material per step keeps the number of materials small
+    let heat_mats: Vec<_> = (0..=HEAT_STEPS)
+        .map(|step| {
+            materials.add(StandardMaterial {
+                base_color: heat_color(step as f32 / HEAT_STEPS as f32),
+                unlit: true,
+                ..default()
+            })
+        })
+        .collect();
+
+    for (p, error) in &comparison.samples {
+        let (material, shift) = match comparison.view {
+            View::SideBySide => (flat_mat.clone(), comparison.offset),
+            View::HeatMap => {
+                let heat = if comparison.max_error > 0. {
+                    error / comparison.max_error
+                } else {
+                    0.
+                };
+                let step = (heat * HEAT_STEPS as f64).round() as usize;
+                (heat_mats[step.min(HEAT_STEPS)].clone(), 0.)
+            }
+        };
+
+        commands.spawn((
+            Mesh3d(point_mesh.clone()),
+            MeshMaterial3d(material),
+            Transform::from_xyz(p.x as f32 + shift, p.y as f32, p.z as f32),
+        ));
+    }
+}
+
+/// Blue for no deviation through to red for the largest
+fn heat_color(heat: f32) -> Color {
+    Color::hsl(240. * (1. - heat), 0.9, 0.5)
+}
@@ -15,161 +15,190 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-use anyhow::Result;
+mod capture;
+mod compare;
+mod parametric;
+mod scene;
+mod timeline;
+
+use crate::capture::{Capture, capture, capture_keys};
+use crate::compare::{Comparison, View, draw_comparison};
+use crate::parametric::{
+    ParametricGizmos, Selection, draw_parametric, draw_selection, pick, place_viewport,
+    setup_parametric,
+};
+use crate::scene::{
+    Model, Outliner, Palette, Primary, draw_cages, load_scene, redraw_models, setup_outliner,
+    show_outliner, toggle_models,
+};
+use crate::timeline::{Timeline, scrub, setup_timeline, show_timeline};
+use anyhow::{Context, Result, bail};
 use bevy::{
     camera_controller::free_camera::{FreeCamera, FreeCameraPlugin},
     color::palettes::tailwind,
     prelude::*,
 };
-use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
-use t_spline::uv_mesh::ids::{EdgeID, VertID};
-use t_spline::uv_mesh::{Boundary, UVMesh};
-use t_spline::{Point3, TSpline};
-use t_spline_commands::align_control_points_to_cage::align_control_points_to_cage;
-use t_spline_commands::extrude_edge::extrude_edge;
-use t_spline_commands::tessellate::tessellate;
+use std::path::PathBuf;
+use t_spline::uv_mesh::Boundary;
 use t_spline_commands::unit_square::unit_square;
 
-fn main() -> Result<()> {
-    let mut spline: TSpline = unit_square();
-    extrude_edge(&mut spline, EdgeID(0))?;
-    extrude_edge(&mut spline, EdgeID(1))?;
-    extrude_edge(&mut spline, EdgeID(2))?;
-    extrude_edge(&mut spline, EdgeID(3))?;
-    spline.control_point_mut(VertID(0)).unwrap().z = 1.;
+const USAGE: &str =
+    "usage: preview [<mode> <argument>] [--scene <file>] [--screenshot <file.png>] [--turntable <dir>]
+
+modes:
+  script <file>     apply the commands in <file> to a unit square, one per line
+  compare <unit>    show the model evaluated in <unit> next to the f64 evaluation
+  deviation <unit>  color the model by its distance to the f64 evaluation
+
+options:
+  --scene <file>           add the models in <file>, one per line as <shape> [x y z]
+  --screenshot <file.png>  save a screenshot once the model is drawn, then exit
+  --turntable <dir>        save a full turn around the model as PNG frames, then exit
 
-    align_control_points_to_cage(&mut spline)?;
+units: f32, f16, i16f16, i32f32
+keys: left and right step through the commands, home and end jump to either end,
+      1 to 9 show and hide models, right click selects a vertex in either view and
+      escape clears it, F12 saves a screenshot and F10 a turntable into the working
+      directory
 
-    let points = tessellate(&spline, 50, Boundary::Clamped)?;
+shapes: unit_square, plane, bump, t_junction, cross, cuboid";
 
-    App::new()
-        .insert_resource(ClearColor(tailwind::BLUE_50.into()))
-        .insert_resource(Render { points, spline })
+/// The commands building the model shown when no script is given
+const MODEL: &str = "extrude_edge edge=0
+extrude_edge edge=1
+extrude_edge edge=2
+extrude_edge edge=3
+lift vertex=0 z=1
+align_control_points_to_cage";
+
+const RESOLUTION: usize = 50;
+
+fn main() -> Result<()> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let screenshot = take_option(&mut args, "--screenshot")?;
+    let turntable = take_option(&mut args, "--turntable")?;
+    let scene = take_option(&mut args, "--scene")?;
+    let mut args = args.into_iter();
+    let mode = args.next();
+    let mut argument = || args.next().context(USAGE);
+    let (script, view) = match mode.as_deref() {
+        None => (MODEL.to_string(), None),
+        Some("script") => {
+            let path = argument()?;
+            let script =
+                std::fs::read_to_string(&path).with_context(|| format!("reading {path}"))?;
+            (script, None)
+        }
+        Some("compare") => (MODEL.to_string(), Some((View::SideBySide, argument()?))),
+        Some("deviation") => (MODEL.to_string(), Some((View::HeatMap, argument()?))),
+        Some(mode) => bail!("unknown mode {mode}\n\n{USAGE}"),
+    };
+
+    let timeline = Timeline::record(unit_square(), &script, RESOLUTION)?;
+    let primary = Model::new("model", timeline.current().clone(), RESOLUTION)?;
+    let comparison = view
+        .map(|(view, unit)| {
+            Comparison::new(&primary.spline, &unit, view, RESOLUTION, Boundary::Clamped)
+        })
+        .transpose()?;
+    let models = match scene {
+        Some(path) => {
+            let scene =
+                std::fs::read_to_string(&path).with_context(|| format!("reading {path}"))?;
+            load_scene(&scene, RESOLUTION).with_context(|| format!("loading {path}"))?
+        }
+        None => Vec::new(),
+    };
+
+    let mut app = App::new();
+    app.insert_resource(ClearColor(tailwind::BLUE_50.into()))
+        .insert_resource(timeline)
+        .insert_resource(Capture::new(
+            screenshot.map(PathBuf::from),
+            turntable.map(PathBuf::from),
+        ))
         .add_plugins(DefaultPlugins)
         .add_plugins(FreeCameraPlugin)
+        .init_resource::<Palette>()
+        .init_resource::<Selection>()
+        .init_gizmo_group::<ParametricGizmos>()
         .add_systems(
             Startup,
-            (setup, draw_points, draw_uv_controls, draw_control),
+            (setup, setup_timeline, setup_outliner, setup_parametric),
         )
-        .add_systems(Update, (draw_cage, draw_uv_cage))
-        .run();
+        .add_systems(
+            Update,
+            (
+                scrub,
+                show_timeline,
+                toggle_models,
+                show_outliner,
+                redraw_models,
+                draw_cages,
+                place_viewport,
+                pick,
+                draw_parametric,
+                draw_selection,
+                capture_keys,
+                capture,
+            )
+                .chain(),
+        );
+
+    let mut outliner = Outliner::default();
+    let primary = app
+        .world_mut()
+        .spawn((primary, Transform::IDENTITY, Visibility::Visible, Primary))
+        .id();
+    outliner.0.push(primary);
+    for (model, transform) in models {
+        let model = app
+            .world_mut()
+            .spawn((model, transform, Visibility::Visible))
+            .id();
+        outliner.0.push(model);
+    }
+    app.insert_resource(outliner);
+
+    if let Some(comparison) = comparison {
+        app.insert_resource(comparison)
+            .add_systems(Startup, draw_comparison);
+    }
+    app.run();
 
     Ok(())
 }
 
+/// Remove `option` and its value from `args`
+fn take_option(args: &mut Vec<String>, option: &str) -> Result<Option<String>> {
+    let Some(i) = args.iter().position(|a| a == option) else {
+        return Ok(None);
+    };
+    if i + 1 >= args.len() {
+        bail!("{option} needs a value\n\n{USAGE}");
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Ok(Some(value))
+}
+
 fn setup(mut commands: Commands) {
     commands.spawn((
         Camera3d::default(),
         Transform::from_xyz(10.0, 12.0, 16.0).looking_at(Vec3::ZERO, Vec3::Y),
         DirectionalLight { ..default() },
-        FreeCamera {
-            sensitivity: 0.2,
-            friction: 25.0,
-            walk_speed: 3.0,
-            run_speed: 9.0,
-            ..default()
-        },
+        // the parametric viewport would otherwise clip the interface
+        IsDefaultUiCamera,
+        free_camera(),
     ));
 }
 
-#[derive(Resource)]
-struct Render {
-    points: Vec<Point3<f64>>,
-    spline: TSpline,
-}
-
-fn draw_uv_controls(
-    render: Res<Render>,
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    let point_mesh = meshes.add(Sphere::new(0.05));
-    let point_mat = materials.add(StandardMaterial {
-        base_color: tailwind::RED_500.into(),
-        unlit: true,
-        ..default()
-    });
-
-    for p in render.spline.points() {
-        commands.spawn((
-            Mesh3d(point_mesh.clone()),
-            MeshMaterial3d(point_mat.clone()),
-            Transform::from_xyz(p.s as f32, p.t as f32, 0_f32),
-        ));
-    }
-}
-
-fn draw_points(
-    render: Res<Render>,
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    let point_mesh = meshes.add(Sphere::new(0.005));
-    let point_mat = materials.add(StandardMaterial {
-        base_color: tailwind::GREEN_500.into(),
-        unlit: true,
-        ..default()
-    });
-
-    for p in &render.points {
-        commands.spawn((
-            Mesh3d(point_mesh.clone()),
-            MeshMaterial3d(point_mat.clone()),
-            Transform::from_xyz(p.x as f32, p.y as f32, p.z as f32),
-        ));
-    }
-}
-
-fn draw_control(
-    render: Res<Render>,
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    let control_mesh = meshes.add(Sphere::new(0.05));
-    let control_mat = materials.add(StandardMaterial {
-        base_color: tailwind::AMBER_500.into(),
-        unlit: true,
+fn free_camera() -> FreeCamera {
+    FreeCamera {
+        sensitivity: 0.2,
+        friction: 25.0,
+        walk_speed: 3.0,
+        run_speed: 9.0,
         ..default()
-    });
-
-    for p in render.spline.control_points() {
-        commands.spawn((
-            Mesh3d(control_mesh.clone()),
-            MeshMaterial3d(control_mat.clone()),
-            Transform::from_xyz(p.x as f32, p.y as f32, p.z as f32),
-        ));
-    }
-}
-
-fn draw_uv_cage(render: Res<Render>, mut gizmos: Gizmos) {
-    for e in render.spline.edges() {
-        let from = render.spline.point(e.origin).unwrap();
-        let to = render
-            .spline
-            .point(render.spline.edge(e.next).unwrap().origin)
-            .unwrap();
-        gizmos.line(
-            Vec3::new(from.s as f32, from.t as f32, 0f32),
-            Vec3::new(to.s as f32, to.t as f32, 0f32),
-            tailwind::GREEN_500,
-        );
-    }
-}
-
-fn draw_cage(render: Res<Render>, mut gizmos: Gizmos) {
-    for e in render.spline.edges() {
-        let from = render.spline.control_point(e.origin).unwrap();
-        let to = render
-            .spline
-            .control_point(render.spline.edge(e.next).unwrap().origin)
-            .unwrap();
-        gizmos.line(
-            Vec3::new(from.x as f32, from.y as f32, from.z as f32),
-            Vec3::new(to.x as f32, to.y as f32, to.z as f32),
-            tailwind::RED_500,
-        );
     }
 }
@@ -0,0 +1,293 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::scene::{Model, Primary};
+use bevy::{
+    camera::{ScalingMode, Viewport, visibility::RenderLayers},
+    color::palettes::tailwind,
+    prelude::*,
+    window::PrimaryWindow,
+};
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::{Boundary, UVMesh};
+
+/// Render layer only the parametric viewport shows
+const PARAMETRIC_LAYER: usize = 1;
+/// Share of the window the parametric viewport covers along each side
+const VIEWPORT_SHARE: u32 = 3;
+/// How close in pixels a click has to be to pick a vertex
+const PICK_RADIUS: f32 = 12.;
+
+/// Gizmos drawn in the parametric viewport only
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct ParametricGizmos;
+
+/// The model and vertex picked in either viewport, the primary model if none was picked
+#[derive(Resource, Default)]
+pub struct Selection {
+    pub model: Option<Entity>,
+    pub vertex: Option<VertID>,
+}
+
+#[derive(Component)]
+pub struct ParametricCamera;
+
+pub fn setup_parametric(mut commands: Commands, mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<ParametricGizmos>();
+    config.render_layers = RenderLayers::layer(PARAMETRIC_LAYER);
+
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::Custom(tailwind::SLATE_50.into()),
+            ..default()
+        },
+        RenderLayers::layer(PARAMETRIC_LAYER),
+        ParametricCamera,
+    ));
+}
+
+/// Keep the parametric viewport in the top right corner of the window
+pub fn place_viewport(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cameras: Query<&mut Camera, With<ParametricCamera>>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let size = window.physical_size() / VIEWPORT_SHARE;
+    if size.min_element() == 0 {
+        return;
+    }
+
+    let viewport = Viewport {
+        physical_position: UVec2::new(window.physical_width() - size.x, 0),
+        physical_size: size,
+        ..default()
+    };
+    for mut camera in &mut cameras {
+        if camera
+            .viewport
+            .as_ref()
+            .map(|v| (v.physical_position, v.physical_size))
+            != Some((viewport.physical_position, viewport.physical_size))
+        {
+            camera.viewport = Some(viewport.clone());
+        }
+    }
+}
+
+/// The selected model, falling back to the primary model
+fn selected_model(selection: &Selection, primary: &Query<Entity, With<Primary>>) -> Option<Entity> {
+    selection.model.or_else(|| primary.iter().next())
+}
+
+/// Right click picks the closest vertex, the UV point in the parametric viewport or the
+/// control point in the 3D view, escape clears the selection
+#[allow(clippy::too_many_arguments)]
+pub fn pick(
+    buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    parametric: Query<(&Camera, &GlobalTransform), With<ParametricCamera>>,
+    views: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    models: Query<(Entity, &Model, &GlobalTransform, &InheritedVisibility)>,
+    primary: Query<Entity, With<Primary>>,
+    mut selection: ResMut<Selection>,
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        *selection = Selection::default();
+        return;
+    }
+    if !buttons.just_pressed(MouseButton::Right) {
+        return;
+    }
+    let Some(cursor) = windows.single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+
+    let mut closest: Option<(f32, Entity, VertID)> = None;
+    let mut consider = |distance: f32, model: Entity, vertex: VertID| {
+        if distance < PICK_RADIUS && closest.is_none_or(|(d, _, _)| distance < d) {
+            closest = Some((distance, model, vertex));
+        }
+    };
+
+    let in_parametric = parametric.iter().find(|(camera, _)| {
+        camera
+            .logical_viewport_rect()
+            .is_some_and(|rect| rect.contains(cursor))
+    });
+    if let Some((camera, transform)) = in_parametric {
+        if let Some(entity) = selected_model(&selection, &primary)
+            && let Ok((_, model, _, _)) = models.get(entity)
+        {
+            for (v, p) in model.spline.points().iter().enumerate() {
+                let at = Vec3::new(p.s as f32, p.t as f32, 0.);
+                if let Ok(on_screen) = camera.world_to_viewport(transform, at) {
+                    consider(on_screen.distance(cursor), entity, VertID(v));
+                }
+            }
+        }
+    } else {
+        for (camera, view) in &views {
+            for (entity, model, transform, visibility) in &models {
+                if !visibility.get() {
+                    continue;
+                }
+                for (v, p) in model.spline.control_points().iter().enumerate() {
+                    let at =
+                        transform.transform_point(Vec3::new(p.x as f32, p.y as f32, p.z as f32));
+                    if let Ok(on_screen) = camera.world_to_viewport(view, at) {
+                        consider(on_screen.distance(cursor), entity, VertID(v));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some((_, model, vertex)) = closest {
+        *selection = Selection {
+            model: Some(model),
+            vertex: Some(vertex),
+        };
+    }
+}
+
+/// Draw the parametric layout of the selected model: faces, knot lines and T-junction
+/// extensions, with the selected vertex and the support of its basis function
+pub fn draw_parametric(
+    selection: Res<Selection>,
+    models: Query<&Model>,
+    primary: Query<Entity, With<Primary>>,
+    mut cameras: Query<(&mut Projection, &mut Transform), With<ParametricCamera>>,
+    mut gizmos: Gizmos<ParametricGizmos>,
+) {
+    let Some(model) = selected_model(&selection, &primary).and_then(|m| models.get(m).ok()) else {
+        return;
+    };
+    let mesh = &model.spline;
+    let layout = mesh.layout();
+
+    // frame the whole layout with a margin
+    let (mut min, mut max) = (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY));
+    for face in &layout.faces {
+        min = min.min(Vec2::new(face.s.0 as f32, face.t.0 as f32));
+        max = max.max(Vec2::new(face.s.1 as f32, face.t.1 as f32));
+    }
+    if min.cmpgt(max).any() {
+        return;
+    }
+    for (mut projection, mut transform) in &mut cameras {
+        let size = (max - min) * 1.2 + 1.;
+        *projection = Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::AutoMin {
+                min_width: size.x,
+                min_height: size.y,
+            },
+            ..OrthographicProjection::default_2d()
+        });
+        transform.translation = ((min + max) / 2.).extend(transform.translation.z);
+    }
+
+    // faces are inset so holes and missing faces stand out
+    for face in &layout.faces {
+        let lo = Vec2::new(face.s.0 as f32, face.t.0 as f32);
+        let hi = Vec2::new(face.s.1 as f32, face.t.1 as f32);
+        gizmos.rect_2d(
+            Isometry2d::from_translation((lo + hi) / 2.),
+            hi - lo - 0.2,
+            tailwind::SLATE_300,
+        );
+    }
+
+    for line in &layout.s_lines {
+        gizmos.line_2d(
+            Vec2::new(line.value as f32, line.extent.0 as f32),
+            Vec2::new(line.value as f32, line.extent.1 as f32),
+            tailwind::SLATE_700,
+        );
+    }
+    for line in &layout.t_lines {
+        gizmos.line_2d(
+            Vec2::new(line.extent.0 as f32, line.value as f32),
+            Vec2::new(line.extent.1 as f32, line.value as f32),
+            tailwind::SLATE_700,
+        );
+    }
+
+    for extension in &layout.extensions {
+        let violation = layout.extensions.iter().any(|e| e.intersects(extension));
+        let color = if violation {
+            tailwind::RED_500
+        } else {
+            tailwind::AMBER_500
+        };
+        for line in [extension.face, extension.edge] {
+            gizmos.line_2d(
+                Vec2::new(line.s0() as f32, line.t0() as f32),
+                Vec2::new(line.s1() as f32, line.t1() as f32),
+                color,
+            );
+        }
+        let junction = Vec2::new(extension.face.s0() as f32, extension.face.t0() as f32);
+        gizmos.circle_2d(Isometry2d::from_translation(junction), 0.1, color);
+    }
+
+    for p in mesh.points() {
+        let at = Vec2::new(p.s as f32, p.t as f32);
+        gizmos.circle_2d(Isometry2d::from_translation(at), 0.05, tailwind::RED_500);
+    }
+
+    if let Some(vertex) = selection.vertex
+        && let Some(p) = mesh.point(vertex)
+    {
+        let at = Vec2::new(p.s as f32, p.t as f32);
+        gizmos.circle_2d(Isometry2d::from_translation(at), 0.15, tailwind::PURPLE_500);
+
+        let knots = mesh.infer_local_knots(vertex, Boundary::Clamped);
+        let lo = Vec2::new(knots.s_knots[0] as f32, knots.t_knots[0] as f32);
+        let hi = Vec2::new(knots.s_knots[4] as f32, knots.t_knots[4] as f32);
+        gizmos.rect_2d(
+            Isometry2d::from_translation((lo + hi) / 2.),
+            hi - lo,
+            tailwind::PURPLE_500,
+        );
+    }
+}
+
+/// Mark the selected control point in the 3D view
+pub fn draw_selection(
+    selection: Res<Selection>,
+    models: Query<(&Model, &GlobalTransform)>,
+    mut gizmos: Gizmos,
+) {
+    let (Some(model), Some(vertex)) = (selection.model, selection.vertex) else {
+        return;
+    };
+    let Ok((model, transform)) = models.get(model) else {
+        return;
+    };
+    let Some(cp) = model.spline.control_point(vertex) else {
+        return;
+    };
+
+    let at = transform.transform_point(Vec3::new(cp.x as f32, cp.y as f32, cp.z as f32));
+    gizmos.sphere(Isometry3d::from_translation(at), 0.1, tailwind::PURPLE_500);
+}
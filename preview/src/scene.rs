@@ -0,0 +1,277 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::compare::{Comparison, View};
+use anyhow::{Context, Result, bail};
+use bevy::{color::palettes::tailwind, prelude::*};
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, UVMesh};
+use t_spline::{Point3, TSpline};
+use t_spline_commands::cuboid::cuboid;
+use t_spline_commands::gallery::shapes;
+use t_spline_commands::tessellate::tessellate;
+
+/// A spline in the scene together with its tessellation, its children draw it
+#[derive(Component)]
+pub struct Model {
+    pub name: String,
+    pub spline: TSpline,
+    pub points: Vec<Point3<f64>>,
+}
+
+impl Model {
+    pub fn new(name: impl Into<String>, spline: TSpline, resolution: usize) -> Result<Self> {
+        let name = name.into();
+        let points = tessellate(&spline, resolution, Boundary::Clamped)
+            .with_context(|| format!("tessellating {name}"))?;
+        Ok(Self {
+            name,
+            spline,
+            points,
+        })
+    }
+}
+
+/// The model edited by the script, the timeline scrubs through it
+#[derive(Component)]
+pub struct Primary;
+
+/// Load the models of a scene file, one per line as `<shape> [x y z]`.
+///
+/// Shapes are the gallery shapes or `cuboid`, which adds its six patches as an
+/// assembly. Empty lines and lines starting with `#` are skipped.
+pub fn load_scene(scene: &str, resolution: usize) -> Result<Vec<(Model, Transform)>> {
+    let gallery = shapes::<TSpline>()?;
+
+    let mut models = Vec::new();
+    for (n, line) in scene.lines().enumerate() {
+        let mut words = line.split_whitespace();
+        let Some(shape) = words.next().filter(|shape| !shape.starts_with('#')) else {
+            continue;
+        };
+        let offset: Vec<f32> = words
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("line {}: {line}", n + 1))?;
+        let transform = match offset[..] {
+            [] => Transform::IDENTITY,
+            [x, y, z] => Transform::from_xyz(x, y, z),
+            _ => bail!("line {}: expected <shape> [x y z], got {line}", n + 1),
+        };
+
+        if shape == "cuboid" {
+            let patches: Vec<TSpline> = cuboid([1., 1., 1.])?;
+            for (i, patch) in patches.into_iter().enumerate() {
+                models.push((
+                    Model::new(format!("cuboid/{i}"), patch, resolution)?,
+                    transform,
+                ));
+            }
+        } else {
+            let Some((name, spline)) = gallery.iter().find(|(name, _)| *name == shape) else {
+                bail!("line {}: unknown shape {shape}", n + 1);
+            };
+            models.push((Model::new(*name, spline.clone(), resolution)?, transform));
+        }
+    }
+    Ok(models)
+}
+
+/// Meshes and materials shared by every model
+#[derive(Resource)]
+pub struct Palette {
+    sample: Handle<Mesh>,
+    control: Handle<Mesh>,
+    surface_mat: Handle<StandardMaterial>,
+    uv_mat: Handle<StandardMaterial>,
+    control_mat: Handle<StandardMaterial>,
+}
+
+impl FromWorld for Palette {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let sample = meshes.add(Sphere::new(0.005));
+        let control = meshes.add(Sphere::new(0.05));
+
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let mut unlit = |color: Srgba| {
+            materials.add(StandardMaterial {
+                base_color: color.into(),
+                unlit: true,
+                ..default()
+            })
+        };
+
+        Self {
+            sample,
+            control,
+            surface_mat: unlit(tailwind::GREEN_500),
+            uv_mat: unlit(tailwind::RED_500),
+            control_mat: unlit(tailwind::AMBER_500),
+        }
+    }
+}
+
+/// Replace the children of every changed model with its samples, UV points and control
+/// points
+pub fn redraw_models(
+    models: Query<(Entity, &Model, Has<Primary>), Changed<Model>>,
+    comparison: Option<Res<Comparison>>,
+    palette: Res<Palette>,
+    mut commands: Commands,
+) {
+    // the heat map takes the place of the f64 samples of the primary model
+    let heat_map = comparison.is_some_and(|c| c.view == View::HeatMap);
+
+    for (entity, model, primary) in &models {
+        let mut entity = commands.entity(entity);
+        entity.despawn_related::<Children>();
+
+        let marker = |mesh: &Handle<Mesh>, material: &Handle<StandardMaterial>, at: Vec3| {
+            (
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_translation(at),
+            )
+        };
+        entity.with_children(|children| {
+            if !(primary && heat_map) {
+                for p in &model.points {
+                    children.spawn(marker(&palette.sample, &palette.surface_mat, point(p)));
+                }
+            }
+            for p in model.spline.points() {
+                let at = Vec3::new(p.s as f32, p.t as f32, 0.);
+                children.spawn(marker(&palette.control, &palette.uv_mat, at));
+            }
+            for p in model.spline.control_points() {
+                let at = Vec3::new(p.x as f32, p.y as f32, p.z as f32);
+                children.spawn(marker(&palette.control, &palette.control_mat, at));
+            }
+        });
+    }
+}
+
+/// Draw the UV cage and the control cage of every visible model
+pub fn draw_cages(
+    models: Query<(&Model, &GlobalTransform, &InheritedVisibility)>,
+    mut gizmos: Gizmos,
+) {
+    for (model, transform, visibility) in &models {
+        if !visibility.get() {
+            continue;
+        }
+
+        let spline = &model.spline;
+        for e in spline.edges() {
+            let next = spline.edge(e.next).unwrap().origin;
+
+            let (from, to) = (spline.point(e.origin).unwrap(), spline.point(next).unwrap());
+            gizmos.line(
+                transform.transform_point(Vec3::new(from.s as f32, from.t as f32, 0.)),
+                transform.transform_point(Vec3::new(to.s as f32, to.t as f32, 0.)),
+                tailwind::GREEN_500,
+            );
+
+            let (from, to) = (
+                spline.control_point(e.origin).unwrap(),
+                spline.control_point(next).unwrap(),
+            );
+            gizmos.line(
+                transform.transform_point(Vec3::new(from.x as f32, from.y as f32, from.z as f32)),
+                transform.transform_point(Vec3::new(to.x as f32, to.y as f32, to.z as f32)),
+                tailwind::RED_500,
+            );
+        }
+    }
+}
+
+pub fn point(p: &Point3<f64>) -> Vec3 {
+    Vec3::new(p.x as f32, p.y as f32, p.z as f32)
+}
+
+/// The models in the order they are listed and toggled in
+#[derive(Resource, Default)]
+pub struct Outliner(pub Vec<Entity>);
+
+#[derive(Component)]
+pub struct OutlinerLabel;
+
+pub fn setup_outliner(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextColor(tailwind::SLATE_800.into()),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.),
+            left: Val::Px(12.),
+            ..default()
+        },
+        OutlinerLabel,
+    ));
+}
+
+/// The number keys toggle the visibility of the first nine models
+pub fn toggle_models(
+    keys: Res<ButtonInput<KeyCode>>,
+    outliner: Res<Outliner>,
+    mut visibilities: Query<&mut Visibility, With<Model>>,
+) {
+    const DIGITS: [KeyCode; 9] = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+
+    for (key, entity) in DIGITS.into_iter().zip(&outliner.0) {
+        if keys.just_pressed(key)
+            && let Ok(mut visibility) = visibilities.get_mut(*entity)
+        {
+            visibility.toggle_visible_hidden();
+        }
+    }
+}
+
+pub fn show_outliner(
+    outliner: Res<Outliner>,
+    models: Query<(&Model, &Visibility)>,
+    mut labels: Query<&mut Text, With<OutlinerLabel>>,
+) {
+    let mut listing = String::new();
+    for (n, entity) in outliner.0.iter().enumerate() {
+        if let Ok((model, visibility)) = models.get(*entity) {
+            let shown = if *visibility == Visibility::Hidden {
+                " "
+            } else {
+                "x"
+            };
+            listing += &format!("{} [{shown}] {}\n", n + 1, model.name);
+        }
+    }
+
+    for mut label in &mut labels {
+        if label.0 != listing {
+            label.0.clone_from(&listing);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::scene::{Model, Primary};
+use anyhow::{Context, Result};
+use bevy::{color::palettes::tailwind, prelude::*};
+use t_spline::TSpline;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::Boundary;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline_commands::command::{Command, CommandError, CommandRegistry, Parameters};
+use t_spline_commands::knot_cache::Influence;
+use t_spline_commands::tessellate::tessellate;
+
+/// Set the height of a single control point, for shapes the built-in commands do not make
+struct Lift {
+    vertex: VertID,
+    z: f64,
+}
+
+impl Command<TSpline> for Lift {
+    fn apply_mut(&self, mesh: &mut TSpline) -> Result<Influence, CommandError> {
+        let cp =
+            mesh.control_point_mut(self.vertex)
+                .ok_or_else(|| CommandError::InvalidParameter {
+                    name: "vertex".to_string(),
+                    value: self.vertex.0.to_string(),
+                })?;
+        cp.z = self.z;
+        Ok(Influence::Geometry)
+    }
+}
+
+/// The model after every command of a script
+#[derive(Resource)]
+pub struct Timeline {
+    steps: Vec<(String, TSpline)>,
+    current: usize,
+    resolution: usize,
+}
+
+impl Timeline {
+    /// Apply `script` to `start`, one command per line as `name param=value...`, keeping
+    /// the model after every line. Empty lines and lines starting with `#` are skipped.
+    ///
+    /// The timeline starts out on the last step.
+    pub fn record(start: TSpline, script: &str, resolution: usize) -> Result<Self> {
+        let mut registry = CommandRegistry::<TSpline>::default();
+        registry.register("lift", |p| {
+            Ok(Box::new(Lift {
+                vertex: VertID(p.get("vertex")?),
+                z: p.get("z")?,
+            }))
+        });
+
+        let mut steps = vec![("start".to_string(), start)];
+        for (n, line) in script.lines().enumerate() {
+            let line = line.trim();
+            let mut words = line.split_whitespace();
+            let Some(name) = words.next().filter(|name| !name.starts_with('#')) else {
+                continue;
+            };
+
+            let mut mesh = steps[steps.len() - 1].1.clone();
+            Parameters::parse(words)
+                .and_then(|parameters| registry.apply_mut(&mut mesh, name, &parameters))
+                .with_context(|| format!("line {}: {line}", n + 1))?;
+            steps.push((line.to_string(), mesh));
+        }
+
+        Ok(Self {
+            current: steps.len() - 1,
+            steps,
+            resolution,
+        })
+    }
+
+    pub fn current(&self) -> &TSpline {
+        &self.steps[self.current].1
+    }
+}
+
+#[derive(Component)]
+pub struct TimelineLabel;
+
+#[derive(Component)]
+pub struct TimelineHandle;
+
+pub fn setup_timeline(mut commands: Commands) {
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.),
+            right: Val::Px(12.),
+            bottom: Val::Px(12.),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(8.),
+            ..default()
+        })
+        .with_children(|timeline| {
+            timeline.spawn((
+                Text::new(""),
+                TextColor(tailwind::SLATE_800.into()),
+                TimelineLabel,
+            ));
+            timeline
+                .spawn((
+                    Node {
+                        height: Val::Px(4.),
+                        ..default()
+                    },
+                    BackgroundColor(tailwind::SLATE_300.into()),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(-4.),
+                            width: Val::Px(12.),
+                            height: Val::Px(12.),
+                            ..default()
+                        },
+                        BackgroundColor(tailwind::RED_500.into()),
+                        TimelineHandle,
+                    ));
+                });
+        });
+}
+
+/// Step through the timeline with the arrow keys, Home and End jump to either end
+pub fn scrub(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut timeline: ResMut<Timeline>,
+    mut models: Query<&mut Model, With<Primary>>,
+) {
+    let last = timeline.steps.len() - 1;
+    let step = if keys.just_pressed(KeyCode::ArrowLeft) {
+        timeline.current.saturating_sub(1)
+    } else if keys.just_pressed(KeyCode::ArrowRight) {
+        (timeline.current + 1).min(last)
+    } else if keys.just_pressed(KeyCode::Home) {
+        0
+    } else if keys.just_pressed(KeyCode::End) {
+        last
+    } else {
+        return;
+    };
+    if step == timeline.current {
+        return;
+    }
+
+    timeline.current = step;
+    let spline = timeline.current().clone();
+    let points = tessellate(&spline, timeline.resolution, Boundary::Clamped).unwrap_or_else(|e| {
+        warn!("step {step} can not be tessellated: {e}");
+        Vec::new()
+    });
+    for mut model in &mut models {
+        model.spline = spline.clone();
+        model.points = points.clone();
+    }
+}
+
+pub fn show_timeline(
+    timeline: Res<Timeline>,
+    mut labels: Query<&mut Text, With<TimelineLabel>>,
+    mut handles: Query<&mut Node, With<TimelineHandle>>,
+) {
+    if !timeline.is_changed() {
+        return;
+    }
+
+    let last = timeline.steps.len() - 1;
+    for mut label in &mut labels {
+        label.0 = format!(
+            "step {}/{last}: {}",
+            timeline.current, timeline.steps[timeline.current].0
+        );
+    }
+
+    let progress = if last == 0 {
+        100.
+    } else {
+        100. * timeline.current as f32 / last as f32
+    };
+    for mut handle in &mut handles {
+        handle.left = Val::Percent(progress);
+    }
+}
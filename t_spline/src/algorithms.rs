@@ -16,8 +16,12 @@
  */
 
 use crate::Numeric;
+#[cfg(feature = "metrics")]
+use crate::metrics::record_sample;
 use crate::uv_mesh::LocalKnots;
-use nalgebra::{Point3, Vector4};
+use nalgebra::{Point3, Vector3, Vector4};
+use num_traits::Zero;
+use thiserror::Error;
 
 /// Evaluates a univariate cubic B-spline basis function.
 ///
@@ -25,6 +29,33 @@ use nalgebra::{Point3, Vector4};
 /// * `u` - The parameter value to evaluate.
 /// * `knots` - A local knot vector of length 5: [u_i, u_{i+1}, u_{i+2}, u_{i+3}, u_{i+4}].
 pub fn cubic_basis_function<T: Numeric>(u: T, knots: &[isize; 5]) -> T {
+    // The result N_{i,3} is the first entry of the degree 3 layer
+    basis_functions(u, knots, 3)[0]
+}
+
+/// Evaluates the derivative of a univariate cubic B-spline basis function.
+///
+/// See [cubic_basis_function] for the arguments.
+pub fn cubic_basis_derivative<T: Numeric>(u: T, knots: &[isize; 5]) -> T {
+    let knot = |i| T::from_isize(knots[i]).unwrap();
+    let three = T::from_u8(3).unwrap();
+    let n = basis_functions(u, knots, 2);
+
+    // N'_{i,3} = 3 / (u_{i+3} - u_i) * N_{i,2} - 3 / (u_{i+4} - u_{i+1}) * N_{i+1,2}
+    let mut derivative = T::zero();
+    let den1 = knot(3) - knot(0);
+    if den1 != T::zero() {
+        derivative += three / den1 * n[0];
+    }
+    let den2 = knot(4) - knot(1);
+    if den2 != T::zero() {
+        derivative -= three / den2 * n[1];
+    }
+    derivative
+}
+
+/// The basis functions of `degree` over the local knot vector, `4 - degree` are valid.
+fn basis_functions<T: Numeric>(u: T, knots: &[isize; 5], degree: usize) -> [T; 4] {
     let knots = |i| T::from_isize(knots[i]).unwrap();
 
     // 2. Initialize the 0th degree basis (step functions)
@@ -48,8 +79,8 @@ pub fn cubic_basis_function<T: Numeric>(u: T, knots: &[isize; 5]) -> T {
         }
     }
 
-    // 3. Iteratively calculate higher degrees up to degree 3
-    for p in 1..=3 {
+    // 3. Iteratively calculate higher degrees up to `degree`
+    for p in 1..=degree {
         // In each degree layer, we calculate (4 - p) basis functions
         for i in 0..(4 - p) {
             let mut val = T::zero();
@@ -70,61 +101,404 @@ pub fn cubic_basis_function<T: Numeric>(u: T, knots: &[isize; 5]) -> T {
         }
     }
 
-    // The result N_{i,3} is now at n
-    n[0]
+    n
+}
+
+/// Errors raised while evaluating a point on the surface
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum EvalError {
+    #[error("parameter is outside of the surface domain")]
+    OutOfDomain,
+    #[error("rational weights sum to zero or less")]
+    ZeroWeight,
+    #[error("control point or parameter is not a number")]
+    NotANumber,
+    #[error("the result overflowed the number type")]
+    Overflow,
 }
 
+/// How [try_subs] treats parameters outside the domain spanned by the knot vectors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvalPolicy {
+    /// Reject parameters outside the domain with [EvalError::OutOfDomain]
+    #[default]
+    Strict,
+    /// Clamp parameters onto the closest point of the domain, which is found by
+    /// scanning the whole knot cache on every call
+    Clamp,
+}
+
+/// Evaluate the surface at `(s, t)`, returning `None` when the point can not be evaluated.
+///
+/// See [try_subs] for the reason a point could not be evaluated.
 pub fn subs<T: Numeric + 'static>(
     vertices: &[Vector4<T>],
-    (s, t): (T, T),
+    st: (T, T),
     knot_cache: &[LocalKnots],
 ) -> Option<Point3<T>> {
-    let mut point_sum: Point3<T> = Point3::origin();
-    let mut weight_sum = T::zero();
+    try_subs(vertices, st, knot_cache, EvalPolicy::Strict).ok()
+}
+
+/// Evaluate the surface at `(s, t)`.
+///
+/// Sums are accumulated in [Numeric::Accumulator], so compact types only round the result.
+/// NaN control points or parameters trip a debug assertion, release builds report them as
+/// [EvalError::NotANumber] rather than propagating NaN into the result. A result that is
+/// not a number although its inputs were, like an overflowed `Rational`, is reported as
+/// [EvalError::Overflow]. Weights summing to zero or less leave the surface undefined
+/// and are reported as [EvalError::ZeroWeight].
+pub fn try_subs<T: Numeric + 'static>(
+    vertices: &[Vector4<T>],
+    st: (T, T),
+    knot_cache: &[LocalKnots],
+    policy: EvalPolicy,
+) -> Result<Point3<T>, EvalError> {
+    let (s, t) = parameters(st, knot_cache, policy)?;
+    let (s, t) = (s.widen(), t.widen());
+
+    let mut point_sum: Point3<T::Accumulator> = Point3::origin();
+    let mut weight_sum = T::Accumulator::zero();
+    let mut influenced = false;
+    let mut rejected = 0;
 
     for (i, vertex) in vertices.iter().enumerate() {
+        debug_assert!(
+            !vertex.iter().any(|v| is_nan(*v)),
+            "control point {i} is NaN: {vertex:?}"
+        );
+        if vertex.iter().any(|v| is_nan(*v)) {
+            return Err(EvalError::NotANumber);
+        }
+        if !in_support((s, t), &knot_cache[i]) {
+            rejected += 1;
+            continue;
+        }
+
         // 1. Evaluate the 1D basis functions for s and t
         let n_s = cubic_basis_function(s, &knot_cache[i].s_knots);
         let n_t = cubic_basis_function(t, &knot_cache[i].t_knots);
 
         // 2. The 2D basis function B_i(s, t) is the product of the 1D functions
         let b_i = n_s * n_t;
+        if is_nan(b_i) {
+            return Err(EvalError::Overflow);
+        }
 
         // Skip calculations if this control point doesn't influence (s, t)
-        if b_i > T::zero() {
+        if b_i > T::Accumulator::zero() {
+            influenced = true;
+
             // 2. Multiply the basis function by the point's weight w_i
-            let rational_weight = b_i * vertex.w;
+            let rational_weight = b_i * vertex.w.widen();
 
             // 3. Accumulate the weighted point sum (Numerator of Eq. 1)
-            point_sum.x += vertex.x * rational_weight;
-            point_sum.y += vertex.y * rational_weight;
-            point_sum.z += vertex.z * rational_weight;
+            point_sum.x += vertex.x.widen() * rational_weight;
+            point_sum.y += vertex.y.widen() * rational_weight;
+            point_sum.z += vertex.z.widen() * rational_weight;
 
             // 4. Accumulate the total weight (Denominator of Eq. 1)
             weight_sum += rational_weight;
         }
     }
 
-    // 5. Divide by the sum of weights to get the final rational point
-    if weight_sum > T::zero() {
-        Some(Point3::new(
-            point_sum.x / weight_sum,
-            point_sum.y / weight_sum,
-            point_sum.z / weight_sum,
-        ))
-    } else {
+    record_sample(vertices.len(), rejected);
+
+    if !influenced {
         // (s, t) is outside the defined domain of the entire surface
-        None
+        return Err(EvalError::OutOfDomain);
     }
+    if weight_sum <= T::Accumulator::zero() {
+        return Err(EvalError::ZeroWeight);
+    }
+
+    // 5. Divide by the sum of weights to get the final rational point
+    let point = Point3::new(
+        T::narrow(point_sum.x / weight_sum),
+        T::narrow(point_sum.y / weight_sum),
+        T::narrow(point_sum.z / weight_sum),
+    );
+    if point.iter().any(|v| is_nan(*v)) {
+        return Err(EvalError::Overflow);
+    }
+    Ok(point)
+}
+
+/// A surface point together with its partial derivatives
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceDerivatives<T: Numeric + 'static> {
+    pub point: Point3<T>,
+    /// Partial derivative along `s`
+    pub ds: Vector3<T>,
+    /// Partial derivative along `t`
+    pub dt: Vector3<T>,
+}
+
+/// Evaluate the surface and its first partial derivatives at `(s, t)`.
+///
+/// Parameters are checked the same way as in [try_subs].
+pub fn try_subs_derivatives<T: Numeric + 'static>(
+    vertices: &[Vector4<T>],
+    st: (T, T),
+    knot_cache: &[LocalKnots],
+    policy: EvalPolicy,
+) -> Result<SurfaceDerivatives<T>, EvalError> {
+    let (s, t) = parameters(st, knot_cache, policy)?;
+
+    // homogeneous sums of the point, its s derivative and its t derivative
+    let mut sums = [Vector4::<T>::zeros(); 3];
+    let mut influenced = false;
+    let mut rejected = 0;
+
+    for (i, vertex) in vertices.iter().enumerate() {
+        debug_assert!(
+            !vertex.iter().any(|v| is_nan(*v)),
+            "control point {i} is NaN: {vertex:?}"
+        );
+        if vertex.iter().any(|v| is_nan(*v)) {
+            return Err(EvalError::NotANumber);
+        }
+        if !in_support((s, t), &knot_cache[i]) {
+            rejected += 1;
+            continue;
+        }
+
+        let n_s = cubic_basis_function(s, &knot_cache[i].s_knots);
+        let n_t = cubic_basis_function(t, &knot_cache[i].t_knots);
+        let b_i = n_s * n_t;
+        if is_nan(b_i) {
+            return Err(EvalError::Overflow);
+        }
+        if b_i > T::zero() {
+            influenced = true;
+        }
+
+        let d_s = cubic_basis_derivative(s, &knot_cache[i].s_knots);
+        let d_t = cubic_basis_derivative(t, &knot_cache[i].t_knots);
+
+        let homogeneous = Vector4::new(
+            vertex.x * vertex.w,
+            vertex.y * vertex.w,
+            vertex.z * vertex.w,
+            vertex.w,
+        );
+        for (sum, b) in sums.iter_mut().zip([b_i, d_s * n_t, n_s * d_t]) {
+            *sum += homogeneous * b;
+        }
+    }
+
+    record_sample(vertices.len(), rejected);
+
+    if !influenced {
+        return Err(EvalError::OutOfDomain);
+    }
+    let [a, a_s, a_t] = sums;
+    if a.w <= T::zero() {
+        return Err(EvalError::ZeroWeight);
+    }
+
+    // quotient rule on the rational surface S = A / W
+    let point = Point3::new(a.x / a.w, a.y / a.w, a.z / a.w);
+    let derivative = |d: Vector4<T>| {
+        Vector3::new(
+            (d.x - d.w * point.x) / a.w,
+            (d.y - d.w * point.y) / a.w,
+            (d.z - d.w * point.z) / a.w,
+        )
+    };
+
+    let (ds, dt) = (derivative(a_s), derivative(a_t));
+    if point
+        .iter()
+        .chain(ds.iter())
+        .chain(dt.iter())
+        .any(|v| is_nan(*v))
+    {
+        return Err(EvalError::Overflow);
+    }
+    Ok(SurfaceDerivatives { point, ds, dt })
+}
+
+/// Check `(s, t)` for NaN and apply `policy` to keep it within the domain
+fn parameters<T: Numeric>(
+    (mut s, mut t): (T, T),
+    knot_cache: &[LocalKnots],
+    policy: EvalPolicy,
+) -> Result<(T, T), EvalError> {
+    debug_assert!(
+        !is_nan(s) && !is_nan(t),
+        "evaluating NaN parameter ({s}, {t})"
+    );
+    if is_nan(s) || is_nan(t) {
+        return Err(EvalError::NotANumber);
+    }
+
+    // outside of the domain no basis function has support, so strict evaluation finds
+    // nothing influencing the point without scanning for the domain first
+    if policy == EvalPolicy::Clamp {
+        let (s_domain, t_domain) = domain::<T>(knot_cache).ok_or(EvalError::OutOfDomain)?;
+        s = s.max(s_domain.0).min(s_domain.1);
+        t = t.max(t_domain.0).min(t_domain.1);
+    }
+
+    Ok((s, t))
+}
+
+/// The parametric domain `((s_min, s_max), (t_min, t_max))` covered by a knot cache
+fn domain<T: Numeric>(knot_cache: &[LocalKnots]) -> Option<((T, T), (T, T))> {
+    let s_min = knot_cache.iter().map(|k| k.s_knots[0]).min()?;
+    let s_max = knot_cache.iter().map(|k| k.s_knots[4]).max()?;
+    let t_min = knot_cache.iter().map(|k| k.t_knots[0]).min()?;
+    let t_max = knot_cache.iter().map(|k| k.t_knots[4]).max()?;
+
+    Some((
+        (T::from_isize(s_min)?, T::from_isize(s_max)?),
+        (T::from_isize(t_min)?, T::from_isize(t_max)?),
+    ))
+}
+
+/// Whether `(s, t)` lies in the closed box spanned by the knot vectors of a basis
+/// function, everywhere outside of it the function and its derivatives are zero
+fn in_support<T: Numeric>((s, t): (T, T), knots: &LocalKnots) -> bool {
+    let inside = |u: T, knots: &[isize; 5]| match (T::from_isize(knots[0]), T::from_isize(knots[4]))
+    {
+        (Some(lo), Some(hi)) => u >= lo && u <= hi,
+        _ => true,
+    };
+    inside(s, &knots.s_knots) && inside(t, &knots.t_knots)
+}
+
+#[cfg(not(feature = "metrics"))]
+fn record_sample(_control_points: usize, _rejected: usize) {}
+
+/// NaN is the only value not comparable to itself
+fn is_nan<T: PartialOrd>(value: T) -> bool {
+    value.partial_cmp(&value).is_none()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::TSpline;
+    use crate::control_mesh::ControlMesh;
     use crate::uv_mesh::{Boundary, UVMesh};
     use alloc::vec;
 
+    #[cfg(feature = "f16")]
+    #[test]
+    fn it_evaluates_half_precision_control_points() {
+        use crate::F16;
+        use num_traits::FromPrimitive;
+
+        let mesh = TSpline::new_unit_square();
+        let (half, _) = mesh.convert::<F16>().unwrap();
+        let knots = half.local_knots(Boundary::Clamped);
+        let at = |v: f64| F16::from_f64(v).unwrap();
+
+        let point = subs(half.control_points(), (at(0.25), at(0.75)), &knots).unwrap();
+
+        let exact = subs(mesh.control_points(), (0.25, 0.75), &knots).unwrap();
+
+        for (half, exact) in point.iter().zip(exact.iter()) {
+            assert!((half.widen() as f64 - exact).abs() < 1e-3);
+        }
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn it_evaluates_exactly_with_rationals() {
+        use crate::Rational;
+        use num_traits::ToPrimitive;
+
+        let mesh = TSpline::new_unit_square();
+        let (exact, _) = mesh.convert::<Rational>().unwrap();
+        let knots = exact.local_knots(Boundary::Clamped);
+        let st = (Rational::new(1, 3), Rational::new(2, 7));
+
+        let point = subs(exact.control_points(), st, &knots).unwrap();
+        // each direction blends (1 - u)^3 and u^3
+        assert_eq!(
+            Point3::new(Rational::new(1, 9), Rational::new(8, 133), Rational::zero()),
+            point
+        );
+        let float = subs(mesh.control_points(), (1. / 3., 2. / 7.), &knots).unwrap();
+        for (exact, float) in point.iter().zip(float.iter()) {
+            assert!((exact.to_f64().unwrap() - float).abs() < 1e-12);
+        }
+    }
+
+    /// A grid of `n` x `n` control points on unit knot intervals, weighted by their `s`
+    #[cfg(feature = "rational")]
+    fn weighted_grid(n: isize, weight: impl Fn(isize) -> f64) -> TSpline {
+        use crate::builder::TMeshBuilder;
+
+        let mut builder = TMeshBuilder::new();
+        let mut ids = vec::Vec::new();
+        for t in 0..n {
+            for s in 0..n {
+                let z = ((s * t) % 3) as f64;
+                ids.push(builder.vertex(s, t, Vector4::new(s as f64, t as f64, z, weight(s))));
+            }
+        }
+        for t in 0..n as usize - 1 {
+            for s in 0..n as usize - 1 {
+                let at = |s: usize, t: usize| ids[t * n as usize + s];
+                builder.face(&[at(s, t), at(s + 1, t), at(s + 1, t + 1), at(s, t + 1)]);
+            }
+        }
+        builder.build().unwrap()
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn it_evaluates_weighted_grids_exactly() {
+        use crate::Rational;
+        use num_traits::ToPrimitive;
+
+        let mesh = weighted_grid(5, |s| 0.75 + s as f64 / 8.);
+        let (exact, _) = mesh.convert::<Rational>().unwrap();
+        let knots = exact.local_knots(Boundary::Clamped);
+
+        let st = (Rational::new(2, 3), Rational::new(13, 7));
+        let exact =
+            try_subs_derivatives(exact.control_points(), st, &knots, EvalPolicy::Strict).unwrap();
+        let float = try_subs_derivatives(
+            mesh.control_points(),
+            (2. / 3., 13. / 7.),
+            &knots,
+            EvalPolicy::Strict,
+        )
+        .unwrap();
+
+        let pairs = exact.point.iter().zip(float.point.iter());
+        let pairs = pairs.chain(exact.ds.iter().zip(float.ds.iter()));
+        for (exact, float) in pairs.chain(exact.dt.iter().zip(float.dt.iter())) {
+            assert!((exact.to_f64().unwrap() - float).abs() < 1e-12, "{exact}");
+        }
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn it_reports_rational_overflow() {
+        use crate::Rational;
+        use num_traits::FromPrimitive;
+
+        // the binary expansions of these floats outgrow i128 within a few products
+        let mesh = weighted_grid(5, |s| 0.73 + 0.013 * s as f64);
+        let (exact, _) = mesh.convert::<Rational>().unwrap();
+        let knots = exact.local_knots(Boundary::Clamped);
+
+        let at = |v: f64| Rational::from_f64(v).unwrap();
+        let st = (at(2. / 3.), at(13. / 7.));
+        assert_eq!(
+            Err(EvalError::Overflow),
+            try_subs_derivatives(exact.control_points(), st, &knots, EvalPolicy::Strict)
+        );
+        assert_eq!(
+            Err(EvalError::Overflow),
+            try_subs(exact.control_points(), st, &knots, EvalPolicy::Strict)
+        );
+    }
+
     #[test]
     pub fn it_can_find_points_on_a_square() {
         let mesh = TSpline::new_unit_square();
@@ -154,6 +528,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_rejects_points_outside_the_domain() {
+        let mesh = TSpline::new_unit_square();
+        let knots = mesh.local_knots(Boundary::Clamped);
+
+        assert_eq!(
+            Err(EvalError::OutOfDomain),
+            try_subs(mesh.control_points(), (2., 0.5), &knots, EvalPolicy::Strict)
+        );
+        assert_eq!(
+            Ok(Point3::new(1., 0.5, 0.)),
+            try_subs(mesh.control_points(), (2., 0.5), &knots, EvalPolicy::Clamp)
+        );
+    }
+
+    #[test]
+    fn it_rejects_zero_weights() {
+        let mesh = TSpline::new_unit_square();
+        let knots = mesh.local_knots(Boundary::Clamped);
+        let points: Vec<_> = mesh
+            .control_points()
+            .iter()
+            .zip([1., -1., 1., -1.])
+            .map(|(p, w)| Vector4::new(p.x, p.y, p.z, w))
+            .collect();
+
+        assert_eq!(
+            Err(EvalError::ZeroWeight),
+            try_subs(&points, (0.5, 0.5), &knots, EvalPolicy::Strict)
+        );
+
+        let negative: Vec<_> = points
+            .iter()
+            .map(|p| Vector4::new(p.x, p.y, p.z, -1.))
+            .collect();
+        assert_eq!(None, subs(&negative, (0.5, 0.5), &knots));
+        assert_eq!(
+            Err(EvalError::ZeroWeight),
+            try_subs_derivatives(&negative, (0.5, 0.5), &knots, EvalPolicy::Strict)
+        );
+    }
+
     #[test]
     fn test_cubic_basis_function_uniform_knots() {
         let knots = [0, 1, 2, 3, 4];
@@ -174,4 +590,38 @@ mod tests {
         assert!((cubic_basis_function(0.5_f64, &knots) - 0.125).abs() < 1e-6);
         assert_eq!(0.0, cubic_basis_function(1.0, &knots));
     }
+
+    #[test]
+    fn it_differentiates_basis_functions() {
+        let knots = [0, 1, 2, 3, 4];
+        let h = 1e-6;
+
+        for u in [0.5_f64, 1.5, 2.0, 3.25] {
+            let numeric = (cubic_basis_function(u + h, &knots)
+                - cubic_basis_function(u - h, &knots))
+                / (2. * h);
+            assert!((cubic_basis_derivative(u, &knots) - numeric).abs() < 1e-6);
+        }
+        assert!((cubic_basis_derivative(0.0_f64, &[0, 0, 0, 0, 1]) + 3.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn it_differentiates_the_surface() {
+        let mesh = TSpline::new_t_junction();
+        let knots = mesh.local_knots(Boundary::Clamped);
+        let points: Vec<_> = mesh
+            .control_points()
+            .iter()
+            .enumerate()
+            .map(|(i, p)| Vector4::new(p.x, p.y, i as f64 / 3., 1. + i as f64 / 8.))
+            .collect();
+        let h = 1e-6;
+
+        let at = |s, t| subs(&points, (s, t), &knots).unwrap();
+        let d = try_subs_derivatives(&points, (0.75, 1.25), &knots, EvalPolicy::Strict).unwrap();
+
+        assert!((d.point - at(0.75, 1.25)).norm() < 1e-12);
+        assert!((d.ds - (at(0.75 + h, 1.25) - at(0.75 - h, 1.25)) / (2. * h)).norm() < 1e-6);
+        assert!((d.dt - (at(0.75, 1.25 + h) - at(0.75, 1.25 - h)) / (2. * h)).norm() < 1e-6);
+    }
 }
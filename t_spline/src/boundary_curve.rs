@@ -0,0 +1,329 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::Numeric;
+use crate::algorithms::cubic_basis_function;
+use crate::control_mesh::ControlMesh;
+use crate::uv_mesh::Boundary;
+use crate::uv_mesh::direction::Direction;
+use crate::uv_mesh::ids::EdgeID;
+use crate::uv_mesh::uv_point::UVCoord;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use nalgebra::{Point3, Vector4};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum BoundaryCurveError {
+    #[error("no edges given")]
+    Empty,
+    #[error("missing edge {0:?}")]
+    MissingEdge(EdgeID),
+    #[error("edge {0:?} is shared with another face")]
+    NotBoundary(EdgeID),
+    #[error("edge {0:?} does not start where the previous edge ends")]
+    NotConnected(EdgeID),
+    #[error("edge {0:?} is not collinear with the previous edges")]
+    NotStraight(EdgeID),
+    #[error("boundary weights sum to zero")]
+    ZeroWeight,
+    #[error("failed to cast")]
+    FailedToCast,
+}
+
+/// A rational cubic Bézier piece of a [BoundaryCurve] between two knots.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BezierSegment<T> {
+    pub knots: (isize, isize),
+    /// Control points as `(x, y, z, weight)`, like the control points of the mesh
+    pub control_points: [Vector4<T>; 4],
+}
+
+/// The exact surface along a straight run of boundary edges.
+///
+/// The curve is parameterized by the knot value along `axis` and stored as one rational
+/// Bézier segment per knot interval, in increasing knot order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundaryCurve<T> {
+    /// The axis the curve runs along
+    pub axis: Direction,
+    /// The constant knot value on the other axis
+    pub value: isize,
+    pub segments: Vec<BezierSegment<T>>,
+}
+
+impl<T: Numeric + 'static> BoundaryCurve<T> {
+    /// The knot range covered by the curve
+    pub fn domain(&self) -> Option<(isize, isize)> {
+        Some((
+            self.segments.first()?.knots.0,
+            self.segments.last()?.knots.1,
+        ))
+    }
+
+    /// Evaluate the curve at knot value `u`
+    pub fn eval(&self, u: T) -> Option<Point3<T>> {
+        let segment = self.segments.iter().find(|s| {
+            let (a, b) = (T::from_isize(s.knots.0), T::from_isize(s.knots.1));
+            matches!((a, b), (Some(a), Some(b)) if u >= a && u <= b)
+        })?;
+
+        let a = T::from_isize(segment.knots.0)?;
+        let b = T::from_isize(segment.knots.1)?;
+        let x = (u - a) / (b - a);
+        let y = T::one() - x;
+        let three = T::from_u8(3)?;
+        let bernstein = [y * y * y, three * x * y * y, three * x * x * y, x * x * x];
+
+        let mut point: Point3<T> = Point3::origin();
+        let mut weight = T::zero();
+        for (b, cp) in bernstein.iter().zip(&segment.control_points) {
+            let w = *b * cp.w;
+            point.x += cp.x * w;
+            point.y += cp.y * w;
+            point.z += cp.z * w;
+            weight += w;
+        }
+        if weight == T::zero() {
+            return None;
+        }
+        Some(Point3::new(
+            point.x / weight,
+            point.y / weight,
+            point.z / weight,
+        ))
+    }
+
+    /// The curve as a single NURBS curve of degree 3.
+    ///
+    /// Returns the knot vector, with every interior knot repeated three times, and the
+    /// control points as `(x, y, z, weight)`.
+    pub fn to_nurbs(&self) -> (Vec<isize>, Vec<Vector4<T>>) {
+        let mut knots = Vec::new();
+        let mut control_points = Vec::new();
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let copies = if i == 0 { 4 } else { 3 };
+            knots.extend(core::iter::repeat_n(segment.knots.0, copies));
+
+            let skip = if i == 0 { 0 } else { 1 };
+            control_points.extend(segment.control_points.iter().skip(skip));
+        }
+        if let Some(last) = self.segments.last() {
+            knots.extend(core::iter::repeat_n(last.knots.1, 4));
+        }
+
+        (knots, control_points)
+    }
+}
+
+impl<T: Numeric + 'static> BoundaryCurve<T> {
+    /// Restrict the surface of `mesh` to the boundary edges `edges`.
+    ///
+    /// The edges must form a connected, straight chain of edges without twins. Every
+    /// control point whose basis function does not vanish on the boundary contributes to
+    /// the curve, so the result matches evaluating the surface along the chain exactly.
+    pub fn new<M: ControlMesh<Unit = T>>(
+        mesh: &M,
+        edges: &[EdgeID],
+        boundary: Boundary,
+    ) -> Result<Self, BoundaryCurveError> {
+        let first = *edges.first().ok_or(BoundaryCurveError::Empty)?;
+        let edge = mesh
+            .edge(first)
+            .ok_or(BoundaryCurveError::MissingEdge(first))?;
+        let (start, end) = mesh.start_end(edge);
+        let (axis, other) = if start.t == end.t {
+            (Direction::S, Direction::T)
+        } else {
+            (Direction::T, Direction::S)
+        };
+        let value = start.value_in_dir(other);
+
+        let mut span = (start.value_in_dir(axis), start.value_in_dir(axis));
+        let mut previous = None;
+        for &id in edges {
+            let edge = mesh.edge(id).ok_or(BoundaryCurveError::MissingEdge(id))?;
+            if edge.twin.is_some() {
+                return Err(BoundaryCurveError::NotBoundary(id));
+            }
+            if previous.is_some_and(|end| end != edge.origin) {
+                return Err(BoundaryCurveError::NotConnected(id));
+            }
+
+            let (a, b) = mesh.start_end(edge);
+            if a.value_in_dir(other) != value || b.value_in_dir(other) != value {
+                return Err(BoundaryCurveError::NotStraight(id));
+            }
+            let u = b.value_in_dir(axis);
+            span = (Ord::min(span.0, u), Ord::max(span.1, u));
+            previous = Some(mesh.next_edge(edge).origin);
+        }
+
+        let cast = |v: isize| M::Unit::from_isize(v).ok_or(BoundaryCurveError::FailedToCast);
+        let knots = mesh.local_knots(boundary);
+        let knots_in = |k: &crate::uv_mesh::LocalKnots, d: Direction| match d {
+            Direction::S => k.s_knots,
+            Direction::T => k.t_knots,
+        };
+
+        // control points with a non vanishing basis on the boundary, scaled by that basis
+        let mut contributing = Vec::new();
+        let mut breaks = BTreeSet::from([span.0, span.1]);
+        for (k, cp) in knots.iter().zip(mesh.control_points()) {
+            let n = cubic_basis_function(cast(value)?, &knots_in(k, other));
+            if n == M::Unit::zero() {
+                continue;
+            }
+
+            let along = knots_in(k, axis);
+            breaks.extend(along.iter().filter(|&&u| u > span.0 && u < span.1));
+
+            let w = cp.w * n;
+            contributing.push((along, Vector4::new(cp.x * w, cp.y * w, cp.z * w, w)));
+        }
+
+        let breaks: Vec<isize> = breaks.into_iter().collect();
+        let mut segments = Vec::with_capacity(breaks.len() - 1);
+        for pair in breaks.windows(2) {
+            let (a, b) = (cast(pair[0])?, cast(pair[1])?);
+            let three = M::Unit::from_u8(3).ok_or(BoundaryCurveError::FailedToCast)?;
+
+            // sample the homogeneous curve at the thirds of the interval
+            let mut f = [Vector4::zeros(); 4];
+            for (i, sample) in f.iter_mut().enumerate() {
+                let i = M::Unit::from_usize(i).ok_or(BoundaryCurveError::FailedToCast)?;
+                let u = a + (b - a) * i / three;
+                for (along, h) in &contributing {
+                    *sample += h * cubic_basis_function(u, along);
+                }
+            }
+
+            // solve the Bernstein interpolation at 0, 1/3, 2/3 and 1 for the Bézier points
+            let six = M::Unit::from_u8(6).ok_or(BoundaryCurveError::FailedToCast)?;
+            let coefficient = |c: [i8; 4]| -> Result<Vector4<M::Unit>, BoundaryCurveError> {
+                let mut sum = Vector4::zeros();
+                for (c, f) in c.iter().zip(&f) {
+                    sum += f * M::Unit::from_i8(*c).ok_or(BoundaryCurveError::FailedToCast)?;
+                }
+                Ok(sum / six)
+            };
+            let homogeneous = [
+                f[0],
+                coefficient([-5, 18, -9, 2])?,
+                coefficient([2, -9, 18, -5])?,
+                f[3],
+            ];
+
+            let mut control_points = [Vector4::zeros(); 4];
+            for (cp, h) in control_points.iter_mut().zip(homogeneous) {
+                if h.w == M::Unit::zero() {
+                    return Err(BoundaryCurveError::ZeroWeight);
+                }
+                *cp = Vector4::new(h.x / h.w, h.y / h.w, h.z / h.w, h.w);
+            }
+
+            segments.push(BezierSegment {
+                knots: (pair[0], pair[1]),
+                control_points,
+            });
+        }
+
+        Ok(BoundaryCurve {
+            axis,
+            value,
+            segments,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TSpline;
+    use crate::algorithms::subs;
+    use crate::control_mesh::ControlMeshMut;
+    use crate::uv_mesh::UVMesh;
+    use crate::uv_mesh::ids::VertID;
+
+    fn curved_t_junction() -> TSpline {
+        let mut mesh = TSpline::new_t_junction();
+        for (i, z) in [0.5, -1., 2., 0., 1., 0.25, 3., -2.].iter().enumerate() {
+            let cp = mesh.control_point_mut(VertID(i)).unwrap();
+            cp.z = *z;
+            cp.w = 1. + i as f64 / 4.;
+        }
+        mesh
+    }
+
+    #[test]
+    fn it_matches_the_surface_on_the_boundary() {
+        let mesh = curved_t_junction();
+        let knots = mesh.local_knots(Boundary::Clamped);
+
+        // the top side runs from (2, 2) over (1, 2) to (0, 2)
+        let mut top: Vec<EdgeID> = mesh
+            .edges()
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                let (a, b) = mesh.start_end(e);
+                e.twin.is_none() && a.t == 2 && b.t == 2
+            })
+            .map(|(i, _)| EdgeID(i))
+            .collect();
+        top.sort_by_key(|&e| -mesh.start_end(mesh.edge(e).unwrap()).0.s);
+        assert_eq!(2, top.len());
+        let curve = mesh.boundary_curve(&top, Boundary::Clamped).unwrap();
+
+        assert_eq!(Direction::S, curve.axis);
+        assert_eq!(Some((0, 2)), curve.domain());
+        for i in 0..=8 {
+            let s = i as f64 / 4.;
+            let expected = subs(mesh.control_points(), (s, 2.), &knots).unwrap();
+            let actual = curve.eval(s).unwrap();
+            assert!(
+                (expected - actual).norm() < 1e-9,
+                "{s}: {expected} {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_converts_to_nurbs() {
+        let mesh = curved_t_junction();
+        let curve = mesh
+            .boundary_curve(&[EdgeID(0)], Boundary::Clamped)
+            .unwrap();
+
+        let (knots, control_points) = curve.to_nurbs();
+        assert_eq!(vec![0, 0, 0, 0, 2, 2, 2, 2], knots);
+        assert_eq!(4, control_points.len());
+        assert_eq!(mesh.control_points()[0], control_points[0]);
+    }
+
+    #[test]
+    fn it_rejects_interior_edges() {
+        let mesh = curved_t_junction();
+        let interior = mesh.edges().iter().position(|e| e.twin.is_some()).unwrap();
+
+        assert_eq!(
+            Err(BoundaryCurveError::NotBoundary(EdgeID(interior))),
+            mesh.boundary_curve(&[EdgeID(interior)], Boundary::Clamped)
+        );
+    }
+}
@@ -20,12 +20,15 @@ use crate::uv_mesh::UVMesh;
 use crate::uv_mesh::half_edge::HalfEdge;
 use crate::uv_mesh::uv_point::UVPoint;
 
-#[derive(Debug, Clone, Copy)]
+/// A rectangle of the parametric domain, `s.0 <= s.1` and `t.0 <= t.1` unless it is
+/// empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Bounds<T> {
     pub s: (T, T),
     pub t: (T, T),
 }
 
+/// Empty bounds for points to be added to, inverted so the first point sets both ends
 impl<T: Numeric> Default for Bounds<T> {
     fn default() -> Self {
         Self {
@@ -36,9 +39,28 @@ impl<T: Numeric> Default for Bounds<T> {
 }
 
 impl<T: Numeric> Bounds<T> {
-    /// Area of the ST Bounds
+    /// Bounds spanning `s` and `t`, `None` if either range is inverted
+    pub fn new(s: (T, T), t: (T, T)) -> Option<Self> {
+        let bounds = Self { s, t };
+        (!bounds.is_empty()).then_some(bounds)
+    }
+
+    /// Whether no point has been added, so the ranges are still inverted
+    pub fn is_empty(&self) -> bool {
+        !(self.s.0 <= self.s.1 && self.t.0 <= self.t.1)
+    }
+
+    /// Whether the bounds have no area, because they are empty or as thin as a line
+    pub fn is_degenerate(&self) -> bool {
+        !(self.s.0 < self.s.1 && self.t.0 < self.t.1)
+    }
+
+    /// Area of the ST Bounds, zero when they are degenerate
     pub fn area(&self) -> T {
-        (self.s.1) - self.s.0 * (self.t.1 - self.t.0)
+        if self.is_degenerate() {
+            return T::zero();
+        }
+        (self.s.1 - self.s.0) * (self.t.1 - self.t.0)
     }
 
     /// Center of the ST Bounds
@@ -49,6 +71,11 @@ impl<T: Numeric> Bounds<T> {
         )
     }
 
+    /// Check if an ST coordinate lies within the bounds (inclusive)
+    pub fn contains(&self, (s, t): (T, T)) -> bool {
+        s >= self.s.0 && s <= self.s.1 && t >= self.t.0 && t <= self.t.1
+    }
+
     /// Point i in a grid of resolution * resolution
     pub fn interpolate(&self, i: usize, resolution: usize) -> (T, T) {
         if resolution <= 1 {
@@ -89,14 +116,15 @@ impl<T: Numeric> Bounds<T> {
 }
 
 pub trait Bounded<T> {
-    fn bounds(&self) -> Bounds<T>;
+    /// The parametric domain, `None` when it has no area, such as for an empty mesh
+    fn bounds(&self) -> Option<Bounds<T>>;
 }
 
 impl<T: ControlMesh> Bounded<T::Unit> for T {
-    fn bounds(&self) -> Bounds<T::Unit> {
+    fn bounds(&self) -> Option<Bounds<T::Unit>> {
         let mut bounds = Bounds::default();
         bounds.add_mesh(self);
-        bounds
+        (!bounds.is_degenerate()).then_some(bounds)
     }
 }
 
@@ -114,6 +142,43 @@ mod tests {
         assert_eq!(b.center(), (0.5, 0.5));
     }
 
+    #[test]
+    fn it_contains_points() {
+        let b = Bounds {
+            s: (0.0, 1.0),
+            t: (0.0, 2.0),
+        };
+
+        assert!(b.contains((0.0, 0.0)));
+        assert!(b.contains((1.0, 2.0)));
+        assert!(!b.contains((1.5, 1.0)));
+        assert!(!b.contains((0.5, -1.0)));
+    }
+
+    #[test]
+    fn it_measures_the_area() {
+        let b = Bounds {
+            s: (1.0, 3.0),
+            t: (2.0, 5.0),
+        };
+        assert_eq!(6.0, b.area());
+
+        let empty = Bounds::<f64>::default();
+        assert!(empty.is_empty());
+        assert_eq!(0.0, empty.area());
+        assert_eq!(None, Bounds::new((1.0, 0.0), (0.0, 1.0)));
+
+        let line = Bounds::new((0.0, 1.0), (2.0, 2.0)).unwrap();
+        assert!(line.is_degenerate());
+        assert_eq!(0.0, line.area());
+    }
+
+    #[test]
+    fn it_has_no_bounds_without_points() {
+        let mesh: crate::TSpline = crate::TSpline::default();
+        assert_eq!(None, mesh.bounds());
+    }
+
     #[test]
     fn it_interpolates_types() {
         it_interpolates::<f64>();
@@ -0,0 +1,285 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::Numeric;
+use crate::control_mesh::ControlMeshMut;
+use crate::uv_mesh::ValidationError;
+use crate::uv_mesh::half_edge::HalfEdge;
+use crate::uv_mesh::ids::{EdgeID, VertID};
+use crate::uv_mesh::layout::outlines;
+use crate::uv_mesh::uv_point::UVPoint;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use nalgebra::Vector4;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum BuildError {
+    #[error("face {0} has fewer than 3 corners")]
+    TooFewCorners(usize),
+    #[error("face {0} uses unknown vertex {1:?}")]
+    UnknownVertex(usize, VertID),
+    #[error("vertices {0:?} and {1:?} share a parametric position")]
+    DuplicatePosition(VertID, VertID),
+    #[error("face {0} has a side from {1:?} to {2:?} that is not parallel to s or t")]
+    DiagonalSide(usize, VertID, VertID),
+    #[error("face {0} is not an axis aligned rectangle in the parametric domain")]
+    NonRectangularFace(usize),
+    #[error("the side from {0:?} to {1:?} is used by more than one face in that direction")]
+    NonManifoldEdge(VertID, VertID),
+    #[error("vertex {0:?} is not used by any face")]
+    UnusedVertex(VertID),
+    #[error("invalid mesh: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// Builds a T-mesh from control points and faces, wiring up the half-edges.
+///
+/// Faces are given as counter clockwise loops of their corners and must be rectangles in
+/// the parametric domain. Vertices that sit on the side of a face, the T-junctions of its
+/// neighbours, are found and inserted into the loop, so a face only needs its corners. Sides without a neighbour are left on
+/// the boundary without a twin.
+#[derive(Debug, Clone, Default)]
+pub struct TMeshBuilder<T> {
+    points: Vec<((isize, isize), Vector4<T>)>,
+    faces: Vec<Vec<VertID>>,
+}
+
+impl<T: Numeric + Send + Sync + 'static> TMeshBuilder<T> {
+    pub fn new() -> Self {
+        Self {
+            points: Vec::new(),
+            faces: Vec::new(),
+        }
+    }
+
+    /// Add a control point at `(s, t)` in the parametric domain
+    pub fn vertex(&mut self, s: isize, t: isize, control_point: Vector4<T>) -> VertID {
+        self.points.push(((s, t), control_point));
+        VertID(self.points.len() - 1)
+    }
+
+    /// Add a face as a counter clockwise loop of vertices
+    pub fn face(&mut self, corners: &[VertID]) -> &mut Self {
+        self.faces.push(corners.to_vec());
+        self
+    }
+
+    /// Wire up the half-edges and validate the result
+    pub fn build<M: ControlMeshMut<Unit = T> + Default>(&self) -> Result<M, BuildError> {
+        let mut positions = BTreeMap::new();
+        for (v, (st, _)) in self.points.iter().enumerate() {
+            if let Some(&other) = positions.get(st) {
+                return Err(BuildError::DuplicatePosition(other, VertID(v)));
+            }
+            positions.insert(*st, VertID(v));
+        }
+
+        let mut used = alloc::vec![false; self.points.len()];
+        for (f, face) in self.faces.iter().enumerate() {
+            if face.len() < 3 {
+                return Err(BuildError::TooFewCorners(f));
+            }
+            for &v in face {
+                *used.get_mut(v.0).ok_or(BuildError::UnknownVertex(f, v))? = true;
+            }
+        }
+        if let Some(v) = used.iter().position(|used| !used) {
+            return Err(BuildError::UnusedVertex(VertID(v)));
+        }
+
+        let loops = self
+            .faces
+            .iter()
+            .enumerate()
+            .map(|(f, face)| self.with_junctions(f, face))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut mesh = M::default();
+        let mut outgoing = alloc::vec![None; self.points.len()];
+        let mut sides = BTreeMap::new();
+        for face in &loops {
+            let start = mesh.edges().len();
+            for (i, &origin) in face.iter().enumerate() {
+                let next = face[(i + 1) % face.len()];
+                let id = mesh.push_edge(HalfEdge {
+                    origin,
+                    twin: None,
+                    next: EdgeID(start + (i + 1) % face.len()),
+                    prev: EdgeID(start + (i + face.len() - 1) % face.len()),
+                });
+                if sides.insert((origin, next), id).is_some() {
+                    return Err(BuildError::NonManifoldEdge(origin, next));
+                }
+                outgoing[origin.0].get_or_insert(id);
+            }
+        }
+        for (&(from, to), &id) in &sides {
+            if let Some(&twin) = sides.get(&(to, from))
+                && let Some(edge) = mesh.edge_mut(id)
+            {
+                edge.twin = Some(twin);
+            }
+        }
+
+        for (((s, t), control_point), outgoing_edge) in self.points.iter().zip(outgoing) {
+            mesh.push_point(UVPoint {
+                s: *s,
+                t: *t,
+                outgoing_edge: outgoing_edge.unwrap_or(EdgeID(0)),
+            });
+            mesh.push_control_point(*control_point);
+        }
+
+        mesh.validate_control_mesh()?;
+        Ok(mesh)
+    }
+
+    /// The loop of face `f` with every vertex that lies inside one of its sides
+    fn with_junctions(&self, f: usize, face: &[VertID]) -> Result<Vec<VertID>, BuildError> {
+        let mut result = Vec::with_capacity(face.len());
+        for (i, &from) in face.iter().enumerate() {
+            let to = face[(i + 1) % face.len()];
+            let (a, b) = (self.points[from.0].0, self.points[to.0].0);
+            if a.0 != b.0 && a.1 != b.1 {
+                return Err(BuildError::DiagonalSide(f, from, to));
+            }
+
+            let mut between: Vec<(isize, VertID)> = self
+                .points
+                .iter()
+                .enumerate()
+                .filter_map(|(v, ((s, t), _))| {
+                    let along = if a.0 == b.0 {
+                        (*s == a.0).then_some((t - a.1, b.1 - a.1))
+                    } else {
+                        (*t == a.1).then_some((s - a.0, b.0 - a.0))
+                    };
+                    let (distance, length) = along?;
+                    let inside = distance.signum() == length.signum()
+                        && distance.abs() < length.abs()
+                        && distance != 0;
+                    inside.then_some((distance.abs(), VertID(v)))
+                })
+                .collect();
+            between.sort_unstable();
+
+            result.push(from);
+            result.extend(between.into_iter().map(|(_, v)| v));
+        }
+
+        let corners: Vec<(isize, isize)> = face.iter().map(|v| self.points[v.0].0).collect();
+        let (s, t) = corners.iter().fold(
+            ((isize::MAX, isize::MIN), (isize::MAX, isize::MIN)),
+            |(s, t), &(cs, ct)| {
+                (
+                    (Ord::min(s.0, cs), Ord::max(s.1, cs)),
+                    (Ord::min(t.0, ct), Ord::max(t.1, ct)),
+                )
+            },
+        );
+        if !outlines(s, t, &corners) {
+            return Err(BuildError::NonRectangularFace(f));
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TSpline;
+    use crate::control_mesh::ControlMesh;
+    use crate::uv_mesh::UVMesh;
+
+    fn point(s: isize, t: isize) -> Vector4<f64> {
+        Vector4::new(s as f64, t as f64, 0., 1.)
+    }
+
+    #[test]
+    fn it_builds_a_t_junction_from_corners() {
+        let mut builder = TMeshBuilder::new();
+        let [a, b, c, d, e, f, g, h] = [
+            (0, 0),
+            (2, 0),
+            (2, 1),
+            (1, 1),
+            (0, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+        ]
+        .map(|(s, t)| builder.vertex(s, t, point(s, t)));
+        builder
+            .face(&[a, b, c, e])
+            .face(&[d, c, f, g])
+            .face(&[e, d, g, h]);
+
+        let mesh: TSpline = builder.build().unwrap();
+        let expected = TSpline::new_t_junction();
+
+        // the junction at (1, 1) is inserted into the bottom face
+        assert_eq!(expected.edges(), mesh.edges());
+        assert_eq!(expected.points(), mesh.points());
+        assert_eq!(expected.control_points(), mesh.control_points());
+    }
+
+    #[test]
+    fn it_rejects_bad_input() {
+        let mut builder = TMeshBuilder::new();
+        let [a, b, c, d] =
+            [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(s, t)| builder.vertex(s, t, point(s, t)));
+
+        let mut twice = builder.clone();
+        twice.face(&[a, b, c, d]).face(&[a, b, c, d]);
+        assert_eq!(
+            Some(BuildError::NonManifoldEdge(a, b)),
+            twice.build::<TSpline>().err()
+        );
+
+        let mut diagonal = builder.clone();
+        diagonal.face(&[a, b, d, c]);
+        assert_eq!(
+            Some(BuildError::DiagonalSide(0, b, d)),
+            diagonal.build::<TSpline>().err()
+        );
+
+        let mut unused = builder.clone();
+        unused.vertex(5, 5, point(5, 5));
+        unused.face(&[a, b, c, d]);
+        assert_eq!(
+            Some(BuildError::UnusedVertex(VertID(4))),
+            unused.build::<TSpline>().err()
+        );
+
+        let mut l_shape = TMeshBuilder::new();
+        let corners = [(0, 0), (2, 0), (2, 1), (1, 1), (1, 2), (0, 2)]
+            .map(|(s, t)| l_shape.vertex(s, t, point(s, t)));
+        l_shape.face(&corners);
+        assert_eq!(
+            Some(BuildError::NonRectangularFace(0)),
+            l_shape.build::<TSpline>().err()
+        );
+
+        let mut clockwise = builder.clone();
+        clockwise.face(&[a, d, c, b]);
+        assert!(matches!(
+            clockwise.build::<TSpline>(),
+            Err(BuildError::Invalid(ValidationError::InvertedFace(_)))
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::storage::{ElementStorage, MeshStorage};
+use crate::uv_mesh::UVMesh;
+use crate::uv_mesh::ids::{EdgeID, VertID};
+use crate::{Numeric, TSpline};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Where [TSpline::compact] moved every vertex and half-edge, indexed by the old id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remap {
+    /// New id of every old vertex, `None` for removed ones
+    pub vertices: Vec<Option<VertID>>,
+    pub edges: Vec<Option<EdgeID>>,
+}
+
+impl Remap {
+    pub fn vertex(&self, old: VertID) -> Option<VertID> {
+        self.vertices.get(old.0).copied().flatten()
+    }
+
+    pub fn edge(&self, old: EdgeID) -> Option<EdgeID> {
+        self.edges.get(old.0).copied().flatten()
+    }
+}
+
+impl<T: Numeric + Send + Sync + 'static, S: MeshStorage<T>> TSpline<T, S> {
+    /// Drop vertices no half-edge starts at and reorder the storage so elements close in
+    /// parameter space are close in memory.
+    ///
+    /// Vertices are sorted along a Hilbert curve over their parametric position and
+    /// faces by the position of their lower corner, with the half-edges of a face
+    /// stored together in loop order. Long edit sessions append elements wherever they
+    /// are created, compacting afterwards keeps evaluation walking memory in order.
+    pub fn compact(&mut self) -> Remap {
+        let points = self.points.as_slice();
+        let edges = self.edges.as_slice();
+
+        let mut used = vec![false; points.len()];
+        for e in edges {
+            if let Some(used) = used.get_mut(e.origin.0) {
+                *used = true;
+            }
+        }
+        let (s_range, t_range) = points.iter().fold(
+            ((isize::MAX, isize::MIN), (isize::MAX, isize::MIN)),
+            |(s, t), p| {
+                (
+                    (Ord::min(s.0, p.s), Ord::max(s.1, p.s)),
+                    (Ord::min(t.0, p.t), Ord::max(t.1, p.t)),
+                )
+            },
+        );
+        let key = |s: isize, t: isize| {
+            let scale = |v: isize, (lo, hi): (isize, isize)| {
+                ((v - lo) as u64 * u16::MAX as u64 / Ord::max(hi - lo, 1) as u64) as u32
+            };
+            hilbert_index(scale(s, s_range), scale(t, t_range))
+        };
+
+        let mut vertex_order: Vec<usize> = (0..points.len()).filter(|&v| used[v]).collect();
+        vertex_order.sort_by_key(|&v| (key(points[v].s, points[v].t), v));
+
+        let mut faces: Vec<(u64, EdgeID)> = self
+            .faces()
+            .map(|face| {
+                let corner = self
+                    .edge_loop(&edges[face.0])
+                    .map(|(_, e)| (points[e.origin.0].s, points[e.origin.0].t))
+                    .fold((isize::MAX, isize::MAX), |a, b| {
+                        (Ord::min(a.0, b.0), Ord::min(a.1, b.1))
+                    });
+                (key(corner.0, corner.1), face)
+            })
+            .collect();
+        faces.sort();
+        let mut edge_order = Vec::with_capacity(edges.len());
+        for (_, face) in faces {
+            edge_order.extend(self.edge_loop(&edges[face.0]).map(|(id, _)| id.0));
+        }
+
+        let mut remap = Remap {
+            vertices: vec![None; points.len()],
+            edges: vec![None; edges.len()],
+        };
+        for (new, &old) in vertex_order.iter().enumerate() {
+            remap.vertices[old] = Some(VertID(new));
+        }
+        for (new, &old) in edge_order.iter().enumerate() {
+            remap.edges[old] = Some(EdgeID(new));
+        }
+
+        let control_points = self.control_points.as_slice();
+        let mut new_points = S::Points::default();
+        let mut new_control_points = S::ControlPoints::default();
+        for &old in &vertex_order {
+            let mut point = points[old].clone();
+            point.outgoing_edge = remap
+                .edge(point.outgoing_edge)
+                .unwrap_or(point.outgoing_edge);
+            // the new storage never holds more than the old one
+            let _ = new_points.push(point);
+            if let Some(cp) = control_points.get(old) {
+                let _ = new_control_points.push(*cp);
+            }
+        }
+        let mut new_edges = S::Edges::default();
+        for &old in &edge_order {
+            let mut edge = edges[old].clone();
+            edge.origin = remap.vertex(edge.origin).unwrap_or(edge.origin);
+            edge.twin = edge.twin.and_then(|twin| remap.edge(twin));
+            edge.next = remap.edge(edge.next).unwrap_or(edge.next);
+            edge.prev = remap.edge(edge.prev).unwrap_or(edge.prev);
+            let _ = new_edges.push(edge);
+        }
+
+        self.points = new_points;
+        self.control_points = new_control_points;
+        self.edges = new_edges;
+        self.touch();
+        remap
+    }
+}
+
+/// Position of `(x, y)` along a Hilbert curve filling the 2^32 x 2^32 grid
+fn hilbert_index(mut x: u32, mut y: u32) -> u64 {
+    let mut index = 0u64;
+    let mut s = 1u32 << 31;
+    while s > 0 {
+        let rx = (x & s > 0) as u32;
+        let ry = (y & s > 0) as u32;
+        index += s as u64 * s as u64 * ((3 * rx) ^ ry) as u64;
+        // rotate the quadrant so the curve stays continuous
+        if ry == 0 {
+            if rx == 1 {
+                x = !x;
+                y = !y;
+            }
+            core::mem::swap(&mut x, &mut y);
+        }
+        s >>= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::subs;
+    use crate::control_mesh::{ControlMesh, ControlMeshMut};
+    use crate::uv_mesh::uv_point::UVPoint;
+    use crate::uv_mesh::{Boundary, UVMeshMut};
+    use nalgebra::Vector4;
+
+    #[test]
+    fn it_orders_along_a_hilbert_curve() {
+        let order: Vec<_> = [(0, 0), (0, 1), (1, 1), (1, 0)]
+            .map(|(x, y)| hilbert_index(x << 31, y << 31))
+            .into();
+        assert!(order.windows(2).all(|w| w[0] < w[1]), "{order:?}");
+    }
+
+    #[test]
+    fn it_drops_unused_vertices_and_keeps_the_surface() {
+        let mut mesh = TSpline::new_t_junction();
+        let before = mesh.clone();
+        mesh.push_point(UVPoint {
+            s: 7,
+            t: 7,
+            outgoing_edge: EdgeID(0),
+        });
+        mesh.push_control_point(Vector4::new(9., 9., 9., 1.));
+        let edges = mesh.edges().len();
+
+        let remap = mesh.compact();
+
+        assert_eq!(None, remap.vertex(VertID(before.points().len())));
+        assert_eq!(before.points().len(), mesh.points().len());
+        assert_eq!(edges, mesh.edges().len());
+        assert!(mesh.validate_control_mesh().is_ok());
+        for (old, p) in before.points().iter().enumerate() {
+            let new = remap.vertex(VertID(old)).unwrap();
+            assert_eq!((p.s, p.t), (mesh.points()[new.0].s, mesh.points()[new.0].t));
+            assert_eq!(before.control_points()[old], mesh.control_points()[new.0]);
+        }
+        let (a, b) = (
+            before.local_knots(Boundary::Clamped),
+            mesh.local_knots(Boundary::Clamped),
+        );
+        for st in [(0.5, 0.5), (1.5, 0.25), (1.25, 1.75)] {
+            assert_eq!(
+                subs(before.control_points(), st, &a),
+                subs(mesh.control_points(), st, &b)
+            );
+        }
+    }
+
+    #[test]
+    fn it_stores_face_loops_together() {
+        let mut mesh = TSpline::new_t_junction();
+
+        mesh.compact();
+
+        for (i, e) in mesh.edges().iter().enumerate() {
+            let face = mesh.edge_loop(e).map(|(id, _)| id.0);
+            let (lo, hi) = face.fold((usize::MAX, 0usize), |(lo, hi), id| {
+                (lo.min(id), hi.max(id))
+            });
+            assert!(lo <= i && i <= hi);
+            assert_eq!(hi - lo + 1, mesh.edge_loop(e).count());
+        }
+    }
+}
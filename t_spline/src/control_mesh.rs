@@ -16,12 +16,15 @@
  */
 
 use crate::Numeric;
-use crate::uv_mesh::ids::VertID;
-use crate::uv_mesh::{UVMesh, UVMeshMut, ValidationError};
+use crate::boundary_curve::{BoundaryCurve, BoundaryCurveError};
+use crate::diff::MeshDiff;
+use crate::uv_mesh::ids::{EdgeID, VertID};
+use crate::uv_mesh::{Boundary, UVMesh, UVMeshMut, ValidationError};
 use nalgebra::Vector4;
 
 pub trait ControlMeshMut: ControlMesh + UVMeshMut {
     fn push_control_point(&mut self, point: Vector4<Self::Unit>) -> VertID;
+    fn pop_control_point(&mut self) -> Option<Vector4<Self::Unit>>;
     fn control_point_mut(&mut self, id: VertID) -> Option<&mut Vector4<Self::Unit>>;
 }
 
@@ -40,4 +43,24 @@ pub trait ControlMesh: UVMesh {
         }
         UVMesh::validate_uv_mesh_integrity(self)
     }
+
+    /// The changes that turn this mesh into `other`
+    fn diff(&self, other: &impl ControlMesh<Unit = Self::Unit>) -> MeshDiff<Self::Unit>
+    where
+        Self: Sized,
+    {
+        MeshDiff::new(self, other)
+    }
+
+    /// The exact surface along the straight chain of boundary edges `edges`
+    fn boundary_curve(
+        &self,
+        edges: &[EdgeID],
+        boundary: Boundary,
+    ) -> Result<BoundaryCurve<Self::Unit>, BoundaryCurveError>
+    where
+        Self: Sized,
+    {
+        BoundaryCurve::new(self, edges, boundary)
+    }
 }
@@ -0,0 +1,151 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::TSpline;
+use crate::numeric::Numeric;
+use crate::uv_mesh::ids::VertID;
+use crate::uv_mesh::next_revision;
+use alloc::vec::Vec;
+use nalgebra::Vector4;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum ConvertError {
+    #[error("control point {vertex:?} value {value} does not fit the target type")]
+    Overflow { vertex: VertID, value: f64 },
+    #[error("control point {0:?} is not representable as f64")]
+    FailedToCast(VertID),
+}
+
+/// How much precision a conversion lost
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ConversionReport {
+    /// Largest difference between a coordinate and its converted value
+    pub max_error: f64,
+    /// The vertex with the largest difference, `None` if every value converted exactly
+    pub worst: Option<VertID>,
+}
+
+impl<T: Numeric + Send + Sync + 'static> TSpline<T> {
+    /// Convert the control points to another [Numeric] type, going through `f64`.
+    ///
+    /// Values out of range of `U` fail the conversion, values that only lose precision
+    /// are rounded and reported.
+    pub fn convert<U: Numeric + Send + Sync + 'static>(
+        &self,
+    ) -> Result<(TSpline<U>, ConversionReport), ConvertError> {
+        let mut report = ConversionReport::default();
+        let mut control_points = Vec::with_capacity(self.control_points.len());
+        for (v, cp) in self.control_points.iter().enumerate() {
+            let vertex = VertID(v);
+            let mut converted = Vector4::<U>::zeros();
+            for (to, from) in converted.iter_mut().zip(cp.iter()) {
+                let value = from.to_f64().ok_or(ConvertError::FailedToCast(vertex))?;
+                *to = U::from_f64(value)
+                    .filter(|_| value.is_finite())
+                    .ok_or(ConvertError::Overflow { vertex, value })?;
+
+                let back = to.to_f64().ok_or(ConvertError::FailedToCast(vertex))?;
+                if !back.is_finite() {
+                    return Err(ConvertError::Overflow { vertex, value });
+                }
+                let error = (back - value).abs();
+                if error > report.max_error {
+                    report = ConversionReport {
+                        max_error: error,
+                        worst: Some(vertex),
+                    };
+                }
+            }
+            control_points.push(converted);
+        }
+
+        Ok((
+            TSpline {
+                points: self.points.clone(),
+                edges: self.edges.clone(),
+                control_points,
+                revision: next_revision(),
+            },
+            report,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::control_mesh::{ControlMesh, ControlMeshMut};
+
+    #[test]
+    fn it_converts_exact_values() {
+        let mesh = TSpline::new_unit_square();
+
+        let (converted, report) = mesh.convert::<f32>().unwrap();
+
+        assert_eq!(
+            mesh.control_points().len(),
+            converted.control_points().len()
+        );
+        assert_eq!(ConversionReport::default(), report);
+        assert_eq!(1f32, converted.control_points()[2].x);
+    }
+
+    #[test]
+    fn it_reports_lost_precision() {
+        let mut mesh = TSpline::new_unit_square();
+        mesh.control_point_mut(VertID(1)).unwrap().z = 0.1;
+
+        let (_, report) = mesh.convert::<f32>().unwrap();
+
+        assert!(report.max_error > 0. && report.max_error < 1e-8);
+        assert_eq!(Some(VertID(1)), report.worst);
+    }
+
+    #[test]
+    fn it_rejects_values_out_of_range() {
+        let mut mesh = TSpline::new_unit_square();
+        mesh.control_point_mut(VertID(3)).unwrap().y = 1e300;
+
+        assert_eq!(
+            Err(ConvertError::Overflow {
+                vertex: VertID(3),
+                value: 1e300
+            }),
+            mesh.convert::<f32>().map(|_| ())
+        );
+    }
+
+    #[cfg(feature = "fixed")]
+    #[test]
+    fn it_converts_to_fixed_point() {
+        use crate::uv_mesh::UVMesh;
+        use fixed::types::I16F16;
+
+        let mut mesh = TSpline::new_unit_square();
+        mesh.control_point_mut(VertID(0)).unwrap().x = 1e6;
+        assert!(matches!(
+            mesh.convert::<I16F16>(),
+            Err(ConvertError::Overflow { .. })
+        ));
+
+        mesh.control_point_mut(VertID(0)).unwrap().x = 0.3;
+        let (converted, report) = mesh.convert::<I16F16>().unwrap();
+        assert!(report.max_error < 1. / 65536.);
+        assert_eq!(mesh.points(), converted.points());
+    }
+}
@@ -0,0 +1,152 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::Numeric;
+use crate::control_mesh::ControlMesh;
+use crate::uv_mesh::ids::{EdgeID, VertID};
+use alloc::vec::Vec;
+use nalgebra::Vector4;
+
+/// The changes between two versions of a mesh, elements are matched by id.
+///
+/// Edits append new vertices and edges, so matching by id lines up everything an edit
+/// did not touch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshDiff<T: Numeric + 'static> {
+    /// Vertices only in the new mesh
+    pub added: Vec<VertID>,
+    /// Vertices only in the old mesh
+    pub removed: Vec<VertID>,
+    /// Vertices in both meshes whose control point changed
+    pub moved: Vec<MovedVertex<T>>,
+    /// Vertices in both meshes whose parametric position changed
+    pub reparameterized: Vec<VertID>,
+    /// Half edges that were added, removed or connected differently
+    pub edges: Vec<EdgeChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovedVertex<T: Numeric + 'static> {
+    pub id: VertID,
+    pub from: Vector4<T>,
+    pub to: Vector4<T>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeChange {
+    Added(EdgeID),
+    Removed(EdgeID),
+    /// The origin, twin, next or previous edge changed
+    Reconnected(EdgeID),
+}
+
+impl<T: Numeric + 'static> MeshDiff<T> {
+    /// Compare `old` against `new`
+    pub fn new<M: ControlMesh<Unit = T>>(old: &M, new: &impl ControlMesh<Unit = T>) -> Self {
+        let (old_points, new_points) = (old.points(), new.points());
+        let shared = old_points.len().min(new_points.len());
+
+        let mut moved = Vec::new();
+        let mut reparameterized = Vec::new();
+        for v in (0..shared).map(VertID) {
+            if old_points[v.0].st() != new_points[v.0].st() {
+                reparameterized.push(v);
+            }
+            if let (Some(from), Some(to)) = (old.control_point(v), new.control_point(v))
+                && from != to
+            {
+                moved.push(MovedVertex {
+                    id: v,
+                    from: *from,
+                    to: *to,
+                });
+            }
+        }
+
+        let (old_edges, new_edges) = (old.edges(), new.edges());
+        let mut edges: Vec<EdgeChange> = (0..old_edges.len().min(new_edges.len()))
+            .filter(|&e| old_edges[e] != new_edges[e])
+            .map(|e| EdgeChange::Reconnected(EdgeID(e)))
+            .collect();
+        edges.extend((new_edges.len()..old_edges.len()).map(|e| EdgeChange::Removed(EdgeID(e))));
+        edges.extend((old_edges.len()..new_edges.len()).map(|e| EdgeChange::Added(EdgeID(e))));
+
+        Self {
+            added: (shared..new_points.len()).map(VertID).collect(),
+            removed: (shared..old_points.len()).map(VertID).collect(),
+            moved,
+            reparameterized,
+            edges,
+        }
+    }
+
+    /// Whether the meshes are identical
+    pub fn is_empty(&self) -> bool {
+        !self.changes_topology() && self.moved.is_empty() && self.reparameterized.is_empty()
+    }
+
+    /// Whether vertices or edges were added, removed or reconnected
+    pub fn changes_topology(&self) -> bool {
+        !(self.added.is_empty() && self.removed.is_empty() && self.edges.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TSpline;
+    use crate::control_mesh::ControlMeshMut;
+    use crate::uv_mesh::UVMeshMut;
+
+    #[test]
+    fn it_finds_no_changes_in_a_copy() {
+        let mesh = TSpline::new_unit_square();
+
+        let diff = mesh.diff(&mesh.clone());
+
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn it_finds_moved_and_reparameterized_vertices() {
+        let mesh = TSpline::new_unit_square();
+        let mut edited = mesh.clone();
+        edited.control_point_mut(VertID(2)).unwrap().z = 1.;
+        edited.point_mut(VertID(3)).unwrap().s = 2;
+
+        let diff = mesh.diff(&edited);
+
+        assert!(!diff.changes_topology());
+        assert_eq!(1, diff.moved.len());
+        assert_eq!(VertID(2), diff.moved[0].id);
+        assert_eq!(1., diff.moved[0].to.z);
+        assert_eq!(vec![VertID(3)], diff.reparameterized);
+    }
+
+    #[test]
+    fn it_finds_topology_changes() {
+        let mesh = TSpline::new_unit_square();
+        let t_junction = TSpline::new_t_junction();
+
+        let diff = mesh.diff(&t_junction);
+        let reverse = t_junction.diff(&mesh);
+
+        assert!(diff.changes_topology());
+        assert_eq!(diff.added, reverse.removed);
+        assert!(diff.edges.iter().any(|e| matches!(e, EdgeChange::Added(_))));
+    }
+}
@@ -20,70 +20,138 @@
 extern crate alloc;
 
 pub mod algorithms;
+pub mod boundary_curve;
 pub mod bounds;
+pub mod builder;
+pub mod compact;
 pub mod control_mesh;
+pub mod convert;
+pub mod diff;
 pub mod line;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 mod numeric;
+pub mod precomputed;
+pub mod shapes;
+pub mod storage;
 pub mod uv_mesh;
 
 use crate::control_mesh::{ControlMesh, ControlMeshMut};
 pub use crate::numeric::Numeric;
+#[cfg(feature = "f16")]
+pub use crate::numeric::f16_impl::F16;
+#[cfg(feature = "rational")]
+pub use crate::numeric::rational_impl::Rational;
+use crate::storage::{ElementStorage, Heap, MeshStorage};
 use crate::uv_mesh::half_edge::HalfEdge;
 use crate::uv_mesh::ids::{EdgeID, VertID};
 use crate::uv_mesh::uv_point::UVPoint;
-use crate::uv_mesh::{UVMesh, UVMeshMut};
-use alloc::vec::Vec;
-pub use nalgebra::{Point3, Vector4};
-
-#[derive(Debug, Default, Clone)]
-pub struct TSpline {
-    points: Vec<UVPoint>,
-    edges: Vec<HalfEdge>,
-    control_points: Vec<Vector4<f64>>,
+use crate::uv_mesh::{UVMesh, UVMeshMut, next_revision};
+pub use nalgebra::{Matrix4, Point3, Vector3, Vector4};
+
+/// A T-spline with control points in `T`, kept in the [MeshStorage] `S`
+#[derive(Debug, Clone)]
+pub struct TSpline<T = f64, S: MeshStorage<T> = Heap> {
+    points: S::Points,
+    edges: S::Edges,
+    control_points: S::ControlPoints,
+    revision: u64,
 }
 
-impl UVMeshMut for TSpline {
+impl<T, S: MeshStorage<T>> Default for TSpline<T, S> {
+    fn default() -> Self {
+        Self {
+            points: Default::default(),
+            edges: Default::default(),
+            control_points: Default::default(),
+            revision: next_revision(),
+        }
+    }
+}
+
+impl<T, S: MeshStorage<T>> TSpline<T, S> {
+    fn touch(&mut self) {
+        self.revision = next_revision();
+    }
+}
+
+impl<T: Numeric + Send + Sync + 'static, S: MeshStorage<T>> UVMeshMut for TSpline<T, S> {
     fn push_point(&mut self, point: UVPoint) -> VertID {
-        self.points.push(point);
-        VertID(self.points.len() - 1)
+        self.touch();
+        if self.points.push(point).is_err() {
+            panic!("mesh storage is full");
+        }
+        VertID(self.points.as_slice().len() - 1)
     }
 
     fn push_edge(&mut self, edge: HalfEdge) -> EdgeID {
-        self.edges.push(edge);
-        EdgeID(self.edges.len() - 1)
+        self.touch();
+        if self.edges.push(edge).is_err() {
+            panic!("mesh storage is full");
+        }
+        EdgeID(self.edges.as_slice().len() - 1)
+    }
+
+    fn pop_point(&mut self) -> Option<UVPoint> {
+        self.touch();
+        self.points.pop()
+    }
+
+    fn pop_edge(&mut self) -> Option<HalfEdge> {
+        self.touch();
+        self.edges.pop()
+    }
+
+    fn point_mut(&mut self, id: VertID) -> Option<&mut UVPoint> {
+        self.touch();
+        self.points.as_mut_slice().get_mut(id.0)
     }
 
     fn edge_mut(&mut self, id: EdgeID) -> Option<&mut HalfEdge> {
-        self.edges.get_mut(id.0)
+        self.touch();
+        self.edges.as_mut_slice().get_mut(id.0)
     }
 }
 
-impl ControlMeshMut for TSpline {
+impl<T: Numeric + Send + Sync + 'static, S: MeshStorage<T>> ControlMeshMut for TSpline<T, S> {
     fn push_control_point(&mut self, point: Vector4<Self::Unit>) -> VertID {
-        self.control_points.push(point);
-        VertID(self.control_points.len() - 1)
+        self.touch();
+        if self.control_points.push(point).is_err() {
+            panic!("mesh storage is full");
+        }
+        VertID(self.control_points.as_slice().len() - 1)
+    }
+
+    fn pop_control_point(&mut self) -> Option<Vector4<Self::Unit>> {
+        self.touch();
+        self.control_points.pop()
     }
 
     fn control_point_mut(&mut self, id: VertID) -> Option<&mut Vector4<Self::Unit>> {
-        self.control_points.get_mut(id.0)
+        self.touch();
+        self.control_points.as_mut_slice().get_mut(id.0)
     }
 }
 
-impl ControlMesh for TSpline {
-    type Unit = f64;
+impl<T: Numeric + Send + Sync + 'static, S: MeshStorage<T>> ControlMesh for TSpline<T, S> {
+    type Unit = T;
 
-    fn control_points(&self) -> &[Vector4<f64>] {
-        &self.control_points
+    fn control_points(&self) -> &[Vector4<T>] {
+        self.control_points.as_slice()
     }
 }
 
-impl UVMesh for TSpline {
+impl<T: Numeric + Send + Sync + 'static, S: MeshStorage<T>> UVMesh for TSpline<T, S> {
     fn points(&self) -> &[UVPoint] {
-        &self.points
+        self.points.as_slice()
     }
 
     fn edges(&self) -> &[HalfEdge] {
-        &self.edges
+        self.edges.as_slice()
+    }
+
+    fn revision(&self) -> u64 {
+        self.revision
     }
 }
 
@@ -94,10 +162,11 @@ mod test {
 
     impl TSpline {
         pub fn new_unit_square() -> Self {
-            let mut mesh = TSpline {
+            let mut mesh = Self {
                 points: Vec::with_capacity(4),
                 edges: Vec::with_capacity(4),
                 control_points: Vec::with_capacity(4),
+                revision: next_revision(),
             };
 
             // 1. Define 4 Corner Vertices
@@ -129,5 +198,22 @@ mod test {
 
             mesh
         }
+
+        /// A wide face below two narrow faces with a T-junction at (1, 1)
+        pub fn new_t_junction() -> Self {
+            crate::shapes::t_junction()
+        }
+    }
+
+    #[test]
+    fn it_bumps_the_revision_on_mutable_access() {
+        let mut mesh = TSpline::new_unit_square();
+        let before = mesh.revision();
+
+        let _ = mesh.points();
+        assert_eq!(before, mesh.revision());
+
+        mesh.control_point_mut(VertID(0)).unwrap().z = 1.;
+        assert_ne!(before, mesh.revision());
     }
 }
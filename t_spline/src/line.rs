@@ -29,6 +29,12 @@ impl Line<isize> {
     }
 }
 
+impl<T> Line<T> {
+    pub fn new(start: Vector2<T>, end: Vector2<T>) -> Self {
+        Self(start, end)
+    }
+}
+
 impl<T: FromPrimitive> Line<T> {
     pub fn from_isize(v: Line<isize>) -> Option<Self> {
         Self(
@@ -113,6 +119,17 @@ impl<T: Numeric + 'static> Line<T> {
         }
     }
 
+    /// True if the closed axis aligned segments share at least one point
+    pub fn overlaps(&self, other: &Line<T>) -> bool {
+        let range = |a: T, b: T| (T::min(a, b), T::max(a, b));
+        let (s_min, s_max) = range(self.s0(), self.s1());
+        let (t_min, t_max) = range(self.t0(), self.t1());
+        let (os_min, os_max) = range(other.s0(), other.s1());
+        let (ot_min, ot_max) = range(other.t0(), other.t1());
+
+        s_min <= os_max && os_min <= s_max && t_min <= ot_max && ot_min <= t_max
+    }
+
     pub fn is_touching<C: UVCoord<T>>(&self, origin: &C) -> bool {
         let axis = self.direction();
 
@@ -0,0 +1,125 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static SAMPLES: AtomicUsize = AtomicUsize::new(0);
+static CONTROL_POINTS_VISITED: AtomicUsize = AtomicUsize::new(0);
+static SUPPORT_REJECTIONS: AtomicUsize = AtomicUsize::new(0);
+static KNOTS_REUSED: AtomicUsize = AtomicUsize::new(0);
+static KNOTS_INFERRED: AtomicUsize = AtomicUsize::new(0);
+
+/// How much work evaluation did, for measuring caching and culling on a model.
+///
+/// Counters are process wide and only ever grow, take a [snapshot] before and after a
+/// piece of work and compare them with [Metrics::since].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Surface points evaluated
+    pub samples: usize,
+    /// Control points whose basis functions were evaluated
+    pub control_points_visited: usize,
+    /// Control points skipped because the sample is outside their support box
+    pub support_rejections: usize,
+    /// Local knot vectors a cache kept across an edit
+    pub knots_reused: usize,
+    /// Local knot vectors inferred from the mesh
+    pub knots_inferred: usize,
+}
+
+impl Metrics {
+    /// The work done between `earlier` and this snapshot
+    pub fn since(&self, earlier: &Metrics) -> Metrics {
+        Metrics {
+            samples: self.samples.wrapping_sub(earlier.samples),
+            control_points_visited: self
+                .control_points_visited
+                .wrapping_sub(earlier.control_points_visited),
+            support_rejections: self
+                .support_rejections
+                .wrapping_sub(earlier.support_rejections),
+            knots_reused: self.knots_reused.wrapping_sub(earlier.knots_reused),
+            knots_inferred: self.knots_inferred.wrapping_sub(earlier.knots_inferred),
+        }
+    }
+
+    /// Share of control points skipped by the support test, `None` before any sample
+    pub fn rejection_rate(&self) -> Option<f64> {
+        ratio(
+            self.support_rejections,
+            self.support_rejections + self.control_points_visited,
+        )
+    }
+
+    /// Share of knot vectors served from a cache, `None` before any were needed
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        ratio(self.knots_reused, self.knots_reused + self.knots_inferred)
+    }
+}
+
+fn ratio(part: usize, total: usize) -> Option<f64> {
+    (total > 0).then(|| part as f64 / total as f64)
+}
+
+/// The counters as they are now
+pub fn snapshot() -> Metrics {
+    Metrics {
+        samples: SAMPLES.load(Ordering::Relaxed),
+        control_points_visited: CONTROL_POINTS_VISITED.load(Ordering::Relaxed),
+        support_rejections: SUPPORT_REJECTIONS.load(Ordering::Relaxed),
+        knots_reused: KNOTS_REUSED.load(Ordering::Relaxed),
+        knots_inferred: KNOTS_INFERRED.load(Ordering::Relaxed),
+    }
+}
+
+/// Record one evaluated sample over `control_points`, `rejected` of which were skipped
+pub(crate) fn record_sample(control_points: usize, rejected: usize) {
+    SAMPLES.fetch_add(1, Ordering::Relaxed);
+    CONTROL_POINTS_VISITED.fetch_add(control_points - rejected, Ordering::Relaxed);
+    SUPPORT_REJECTIONS.fetch_add(rejected, Ordering::Relaxed);
+}
+
+/// Record how many knot vectors a cache kept and how many it had to infer
+pub fn record_knots(reused: usize, inferred: usize) {
+    KNOTS_REUSED.fetch_add(reused, Ordering::Relaxed);
+    KNOTS_INFERRED.fetch_add(inferred, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TSpline;
+    use crate::algorithms::subs;
+    use crate::control_mesh::ControlMesh;
+    use crate::uv_mesh::{Boundary, UVMesh};
+
+    #[test]
+    fn it_counts_rejected_control_points() {
+        let mesh = TSpline::new_t_junction();
+        let knots = mesh.local_knots(Boundary::Clamped);
+
+        let before = snapshot();
+        subs(mesh.control_points(), (0.5, 0.5), &knots).unwrap();
+        let used = snapshot().since(&before);
+
+        // other tests may evaluate at the same time
+        assert!(used.samples >= 1);
+        assert!(used.support_rejections >= 1);
+        assert!(used.rejection_rate().unwrap() > 0.);
+        assert_eq!(None, Metrics::default().cache_hit_rate());
+    }
+}
@@ -30,6 +30,14 @@ pub trait Numeric:
     + ToPrimitive
     + FromPrimitive
 {
+    /// The type sums are accumulated in during evaluation, wider than `Self` for types
+    /// meant for compact storage
+    type Accumulator: Numeric + 'static;
+
+    fn widen(self) -> Self::Accumulator;
+
+    fn narrow(value: Self::Accumulator) -> Self;
+
     fn max(self, other: Self) -> Self {
         if self > other { self } else { other }
     }
@@ -39,16 +47,32 @@ pub trait Numeric:
     }
 }
 
-impl Numeric for isize {}
-
-macro_rules! impl_numeric_float {
+macro_rules! impl_numeric_exact {
     ($($t:ty),*) => {
         $(
-            impl Numeric for $t {}
+            impl Numeric for $t {
+                type Accumulator = Self;
+
+                fn widen(self) -> Self {
+                    self
+                }
+
+                fn narrow(value: Self) -> Self {
+                    value
+                }
+            }
         )*
     }
 }
 
+impl_numeric_exact!(isize);
+
+macro_rules! impl_numeric_float {
+    ($($t:ty),*) => {
+        impl_numeric_exact!($($t),*);
+    }
+}
+
 impl_numeric_float!(f32, f64);
 
 #[cfg(feature = "fixed")]
@@ -62,6 +86,15 @@ mod fixed_impl {
                 where
                     fixed::$t<Frac>: fixed::traits::FixedSigned + Num + Signed + NumAssign + FromPrimitive + Bounded
                 {
+                    type Accumulator = Self;
+
+                    fn widen(self) -> Self {
+                        self
+                    }
+
+                    fn narrow(value: Self) -> Self {
+                        value
+                    }
                 }
             )*
         }
@@ -70,6 +103,409 @@ mod fixed_impl {
     impl_numeric_fixed!(FixedI8, FixedI16, FixedI32, FixedI64, FixedI128);
 }
 
+#[cfg(feature = "f16")]
+pub mod f16_impl {
+    use super::*;
+    use core::cmp::Ordering;
+    use core::fmt::Formatter;
+    use core::num::FpCategory;
+    use core::ops::{
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+    };
+    use half::f16;
+    use num_traits::{One, Zero};
+
+    /// Half precision float for storing control points compactly.
+    ///
+    /// Arithmetic rounds through `f32` and evaluation accumulates in `f32`, so only the
+    /// stored values lose precision.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+    pub struct F16(pub f16);
+
+    impl Numeric for F16 {
+        type Accumulator = f32;
+
+        fn widen(self) -> f32 {
+            self.0.to_f32()
+        }
+
+        fn narrow(value: f32) -> Self {
+            F16(f16::from_f32(value))
+        }
+    }
+
+    impl Display for F16 {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            Display::fmt(&self.0, f)
+        }
+    }
+
+    macro_rules! impl_op {
+        ($($trait:ident, $method:ident, $assign:ident, $assign_method:ident);*) => {
+            $(
+                impl $trait for F16 {
+                    type Output = F16;
+
+                    fn $method(self, rhs: F16) -> F16 {
+                        F16(f16::from_f32(self.0.to_f32().$method(rhs.0.to_f32())))
+                    }
+                }
+
+                impl $assign for F16 {
+                    fn $assign_method(&mut self, rhs: F16) {
+                        *self = self.$method(rhs);
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_op!(
+        Add, add, AddAssign, add_assign;
+        Sub, sub, SubAssign, sub_assign;
+        Mul, mul, MulAssign, mul_assign;
+        Div, div, DivAssign, div_assign;
+        Rem, rem, RemAssign, rem_assign
+    );
+
+    impl Neg for F16 {
+        type Output = F16;
+
+        fn neg(self) -> F16 {
+            F16(-self.0)
+        }
+    }
+
+    impl Zero for F16 {
+        fn zero() -> Self {
+            F16(f16::ZERO)
+        }
+
+        fn is_zero(&self) -> bool {
+            self.0 == f16::ZERO
+        }
+    }
+
+    impl One for F16 {
+        fn one() -> Self {
+            F16(f16::ONE)
+        }
+    }
+
+    impl Num for F16 {
+        type FromStrRadixErr = <f32 as Num>::FromStrRadixErr;
+
+        fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+            f32::from_str_radix(str, radix).map(|v| F16(f16::from_f32(v)))
+        }
+    }
+
+    impl Signed for F16 {
+        fn abs(&self) -> Self {
+            F16(f16::from_f32(self.0.to_f32().abs()))
+        }
+
+        fn abs_sub(&self, other: &Self) -> Self {
+            match self.partial_cmp(other) {
+                Some(Ordering::Greater) => *self - *other,
+                _ => F16::zero(),
+            }
+        }
+
+        fn signum(&self) -> Self {
+            F16(self.0.signum())
+        }
+
+        fn is_positive(&self) -> bool {
+            self.0.is_sign_positive() && self.0.classify() != FpCategory::Nan
+        }
+
+        fn is_negative(&self) -> bool {
+            self.0.is_sign_negative() && self.0.classify() != FpCategory::Nan
+        }
+    }
+
+    impl Bounded for F16 {
+        fn min_value() -> Self {
+            F16(f16::MIN)
+        }
+
+        fn max_value() -> Self {
+            F16(f16::MAX)
+        }
+    }
+
+    impl ToPrimitive for F16 {
+        fn to_i64(&self) -> Option<i64> {
+            self.0.to_f32().to_i64()
+        }
+
+        fn to_u64(&self) -> Option<u64> {
+            self.0.to_f32().to_u64()
+        }
+
+        fn to_f32(&self) -> Option<f32> {
+            Some(self.0.to_f32())
+        }
+
+        fn to_f64(&self) -> Option<f64> {
+            Some(self.0.to_f64())
+        }
+    }
+
+    impl FromPrimitive for F16 {
+        fn from_i64(n: i64) -> Option<Self> {
+            Self::from_f64(n as f64)
+        }
+
+        fn from_u64(n: u64) -> Option<Self> {
+            Self::from_f64(n as f64)
+        }
+
+        /// `None` for finite values beyond the range of half precision
+        fn from_f64(n: f64) -> Option<Self> {
+            let value = f16::from_f64(n);
+            (value.is_finite() || !n.is_finite()).then_some(F16(value))
+        }
+    }
+}
+
+#[cfg(feature = "rational")]
+pub mod rational_impl {
+    use super::*;
+    use core::cmp::Ordering;
+    use core::fmt::Formatter;
+    use core::ops::{
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+    };
+    use num_rational::Ratio;
+    use num_traits::float::FloatCore;
+    use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Zero};
+
+    /// Exact rational number for verifying the float paths in tests.
+    ///
+    /// Arithmetic is exact until a numerator or denominator outgrows `i128` or a value is
+    /// divided by zero. The result is then an overflow that, like NaN for floats, every
+    /// later operation keeps and no comparison holds for, so evaluation reports it as an
+    /// error instead of a rounded result.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Rational(Option<Ratio<i128>>);
+
+    impl Rational {
+        pub fn new(numer: i128, denom: i128) -> Self {
+            Rational((denom != 0).then(|| Ratio::new(numer, denom)))
+        }
+
+        /// The exact value, `None` once it overflowed
+        pub fn ratio(&self) -> Option<Ratio<i128>> {
+            self.0
+        }
+
+        pub fn is_overflow(&self) -> bool {
+            self.0.is_none()
+        }
+
+        fn map(self, f: impl FnOnce(Ratio<i128>) -> Option<Ratio<i128>>) -> Self {
+            Rational(self.0.and_then(f))
+        }
+    }
+
+    impl From<Ratio<i128>> for Rational {
+        fn from(value: Ratio<i128>) -> Self {
+            Rational(Some(value))
+        }
+    }
+
+    impl Numeric for Rational {
+        type Accumulator = Self;
+
+        fn widen(self) -> Self {
+            self
+        }
+
+        fn narrow(value: Self) -> Self {
+            value
+        }
+    }
+
+    impl Display for Rational {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            match &self.0 {
+                Some(value) => Display::fmt(value, f),
+                None => write!(f, "overflow"),
+            }
+        }
+    }
+
+    impl PartialOrd for Rational {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.0?.cmp(&other.0?))
+        }
+    }
+
+    macro_rules! impl_op {
+        ($($trait:ident, $method:ident, $checked:ident, $assign:ident, $assign_method:ident);*) => {
+            $(
+                impl $trait for Rational {
+                    type Output = Rational;
+
+                    fn $method(self, rhs: Rational) -> Rational {
+                        self.map(|lhs| lhs.$checked(&rhs.0?))
+                    }
+                }
+
+                impl $assign for Rational {
+                    fn $assign_method(&mut self, rhs: Rational) {
+                        *self = self.$method(rhs);
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_op!(
+        Add, add, checked_add, AddAssign, add_assign;
+        Sub, sub, checked_sub, SubAssign, sub_assign;
+        Mul, mul, checked_mul, MulAssign, mul_assign;
+        Div, div, checked_div, DivAssign, div_assign
+    );
+
+    impl Rem for Rational {
+        type Output = Rational;
+
+        fn rem(self, rhs: Rational) -> Rational {
+            self.map(|lhs| {
+                let rhs = rhs.0.filter(|rhs| !rhs.is_zero())?;
+                lhs.checked_sub(&rhs.checked_mul(&(lhs.checked_div(&rhs)?).trunc())?)
+            })
+        }
+    }
+
+    impl RemAssign for Rational {
+        fn rem_assign(&mut self, rhs: Rational) {
+            *self = *self % rhs;
+        }
+    }
+
+    impl Neg for Rational {
+        type Output = Rational;
+
+        fn neg(self) -> Rational {
+            self.map(|value| Ratio::zero().checked_sub(&value))
+        }
+    }
+
+    impl Zero for Rational {
+        fn zero() -> Self {
+            Ratio::zero().into()
+        }
+
+        fn is_zero(&self) -> bool {
+            self.0.is_some_and(|value| value.is_zero())
+        }
+    }
+
+    impl One for Rational {
+        fn one() -> Self {
+            Ratio::one().into()
+        }
+    }
+
+    impl Num for Rational {
+        type FromStrRadixErr = num_rational::ParseRatioError;
+
+        fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+            Ratio::from_str_radix(str, radix).map(Rational::from)
+        }
+    }
+
+    impl Signed for Rational {
+        fn abs(&self) -> Self {
+            if self.is_negative() { -*self } else { *self }
+        }
+
+        fn abs_sub(&self, other: &Self) -> Self {
+            let difference = *self - *other;
+            if difference.is_negative() {
+                Rational::zero()
+            } else {
+                difference
+            }
+        }
+
+        fn signum(&self) -> Self {
+            self.map(|value| Some(value.signum()))
+        }
+
+        fn is_positive(&self) -> bool {
+            self.0.is_some_and(|value| value.is_positive())
+        }
+
+        fn is_negative(&self) -> bool {
+            self.0.is_some_and(|value| value.is_negative())
+        }
+    }
+
+    impl Bounded for Rational {
+        fn min_value() -> Self {
+            Ratio::from_integer(i128::MIN).into()
+        }
+
+        fn max_value() -> Self {
+            Ratio::from_integer(i128::MAX).into()
+        }
+    }
+
+    impl ToPrimitive for Rational {
+        fn to_i64(&self) -> Option<i64> {
+            self.0?.to_integer().to_i64()
+        }
+
+        fn to_u64(&self) -> Option<u64> {
+            self.0?.to_integer().to_u64()
+        }
+
+        /// `None` once the value overflowed
+        fn to_f64(&self) -> Option<f64> {
+            let value = self.0?;
+            Some(*value.numer() as f64 / *value.denom() as f64)
+        }
+    }
+
+    impl FromPrimitive for Rational {
+        fn from_i64(n: i64) -> Option<Self> {
+            Some(Ratio::from_integer(n.into()).into())
+        }
+
+        fn from_u64(n: u64) -> Option<Self> {
+            Some(Ratio::from_integer(n.into()).into())
+        }
+
+        /// The exact value of `n`, `None` if it is not finite or needs more than `i128`
+        fn from_f64(n: f64) -> Option<Self> {
+            if !n.is_finite() {
+                return None;
+            }
+            if n == 0. {
+                return Some(Rational::zero());
+            }
+            let (mantissa, exponent, sign) = FloatCore::integer_decode(n);
+            let shift = mantissa.trailing_zeros();
+            let (mantissa, exponent) = (
+                i128::from(mantissa >> shift),
+                i32::from(exponent) + shift as i32,
+            );
+            let power = |e: i32| 1i128.checked_shl(e as u32).filter(|_| e < 127);
+            let value = if exponent >= 0 {
+                Ratio::from_integer(mantissa.checked_mul(power(exponent)?)?)
+            } else {
+                Ratio::new(mantissa, power(-exponent)?)
+            };
+            Some((value * Ratio::from_integer(sign.into())).into())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,6 +525,45 @@ mod tests {
         needs_numeric(fixed::types::I10F22::one());
     }
 
+    #[cfg(feature = "f16")]
+    #[test]
+    fn it_supports_half_precision() {
+        use crate::F16;
+        use num_traits::One;
+
+        needs_numeric(F16::one());
+        assert_eq!(Some(F16::one()), F16::from_f64(1.));
+        assert_eq!(None, F16::from_f64(1e6));
+        assert_eq!(1.5f32, (F16::one() + F16::from_f64(0.5).unwrap()).widen());
+    }
+
+    #[cfg(feature = "rational")]
+    #[test]
+    fn it_supports_exact_rationals() {
+        use crate::Rational;
+        use num_traits::{One, Zero};
+
+        needs_numeric(Rational::new(1, 3));
+        assert_eq!(
+            Some(Rational::new(3602879701896397, 1 << 55)),
+            Rational::from_f64(0.1)
+        );
+        assert_eq!(Some(Rational::new(-3, 8)), Rational::from_f64(-0.375));
+        assert_eq!(Some(Rational::new(0, 1)), Rational::from_f64(0.));
+        assert_eq!(None, Rational::from_f64(f64::NAN));
+        assert_eq!(
+            Rational::new(1, 1),
+            Rational::new(1, 3) + Rational::new(2, 3)
+        );
+
+        let overflow = Rational::max_value() * Rational::new(2, 1);
+        assert!(overflow.is_overflow());
+        assert!((overflow - Rational::max_value()).is_overflow());
+        assert_eq!(None, overflow.partial_cmp(&Rational::zero()));
+        assert_eq!(None, overflow.to_f64());
+        assert!((Rational::one() / Rational::zero()).is_overflow());
+    }
+
     fn needs_numeric(value: impl Numeric) {
         black_box(value);
     }
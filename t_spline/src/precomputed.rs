@@ -0,0 +1,310 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::uv_mesh::ids::EdgeID;
+use crate::uv_mesh::layout::FaceRect;
+use crate::uv_mesh::{Boundary, LocalKnots, UVMesh};
+use alloc::vec::Vec;
+use thiserror::Error;
+
+const MAGIC: &[u8; 4] = b"TSKN";
+const VERSION: u8 = 1;
+const HEADER: usize = 4 + 1 + 1 + 4 + 4 + 8;
+const KNOTS: usize = 10 * 8;
+const FACE: usize = 8 + 4 * 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum PrecomputedError {
+    #[error("data ends before the header or the listed elements")]
+    Truncated,
+    #[error("data does not start with precomputed knots")]
+    NotPrecomputed,
+    #[error("unsupported format version {0}")]
+    Version(u8),
+    #[error("data was computed for a different T-mesh")]
+    Mismatch,
+    #[error("room for {0} knot vectors is needed")]
+    Capacity(usize),
+}
+
+/// Local knot vectors and face rectangles of a mesh computed ahead of time, so
+/// read-only models can skip knot inference when they are loaded.
+///
+/// The encoding stores a fingerprint of the T-mesh and is only accepted for a mesh with
+/// the same vertices and connectivity, control points may differ.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Precomputed {
+    pub boundary: Boundary,
+    pub knots: Vec<LocalKnots>,
+    /// Parametric rectangle of every face, for finding the face of a parameter
+    pub faces: Vec<FaceRect>,
+}
+
+impl Precomputed {
+    /// Infer the knot vectors and layout of `mesh`
+    pub fn new(mesh: &impl UVMesh, boundary: Boundary) -> Self {
+        Self {
+            boundary,
+            knots: mesh.local_knots(boundary),
+            faces: mesh.layout().faces,
+        }
+    }
+
+    /// The face whose rectangle contains `(s, t)`, the first one on shared sides
+    pub fn face_at(&self, (s, t): (isize, isize)) -> Option<EdgeID> {
+        let within = |(lo, hi): (isize, isize), v: isize| lo <= v && v <= hi;
+        self.faces
+            .iter()
+            .find(|rect| within(rect.s, s) && within(rect.t, t))
+            .map(|rect| rect.face)
+    }
+
+    /// Encode for `mesh`, which must be the mesh the data was computed for
+    pub fn to_bytes(&self, mesh: &impl UVMesh) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(HEADER + self.knots.len() * KNOTS + self.faces.len() * FACE);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(match self.boundary {
+            Boundary::Clamped => 0,
+            Boundary::Periodic => 1,
+        });
+        bytes.extend_from_slice(&(self.knots.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.faces.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&fingerprint(mesh).to_le_bytes());
+        for k in &self.knots {
+            for v in k.s_knots.iter().chain(&k.t_knots) {
+                bytes.extend_from_slice(&(*v as i64).to_le_bytes());
+            }
+        }
+        for rect in &self.faces {
+            bytes.extend_from_slice(&(rect.face.0 as u64).to_le_bytes());
+            for v in [rect.s.0, rect.s.1, rect.t.0, rect.t.1] {
+                bytes.extend_from_slice(&(v as i64).to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Decode data [Precomputed::to_bytes] wrote for `mesh`
+    pub fn from_bytes(bytes: &[u8], mesh: &impl UVMesh) -> Result<Self, PrecomputedError> {
+        let header = Header::read(bytes, mesh)?;
+        let mut knots = Vec::with_capacity(header.knots);
+        let mut reader = Reader(&bytes[HEADER..]);
+        for _ in 0..header.knots {
+            knots.push(reader.knots()?);
+        }
+        let mut faces = Vec::with_capacity(header.faces);
+        for _ in 0..header.faces {
+            let face = EdgeID(reader.u64()? as usize);
+            let [s0, s1, t0, t1] = [reader.i64()?, reader.i64()?, reader.i64()?, reader.i64()?];
+            faces.push(FaceRect {
+                face,
+                s: (s0 as isize, s1 as isize),
+                t: (t0 as isize, t1 as isize),
+            });
+        }
+        Ok(Self {
+            boundary: header.boundary,
+            knots,
+            faces,
+        })
+    }
+
+    /// Decode only the knot vectors into `out` without allocating, like
+    /// [UVMesh::local_knots_into] does by inference
+    pub fn knots_into<'a>(
+        bytes: &[u8],
+        mesh: &impl UVMesh,
+        out: &'a mut [LocalKnots],
+    ) -> Result<(Boundary, &'a [LocalKnots]), PrecomputedError> {
+        let header = Header::read(bytes, mesh)?;
+        let out = out
+            .get_mut(..header.knots)
+            .ok_or(PrecomputedError::Capacity(header.knots))?;
+        let mut reader = Reader(&bytes[HEADER..]);
+        for knots in out.iter_mut() {
+            *knots = reader.knots()?;
+        }
+        Ok((header.boundary, out))
+    }
+}
+
+struct Header {
+    boundary: Boundary,
+    knots: usize,
+    faces: usize,
+}
+
+impl Header {
+    fn read(bytes: &[u8], mesh: &impl UVMesh) -> Result<Self, PrecomputedError> {
+        let mut reader = Reader(bytes);
+        if reader.take(4)? != MAGIC {
+            return Err(PrecomputedError::NotPrecomputed);
+        }
+        match reader.take(1)?[0] {
+            VERSION => {}
+            version => return Err(PrecomputedError::Version(version)),
+        }
+        let boundary = match reader.take(1)?[0] {
+            0 => Boundary::Clamped,
+            1 => Boundary::Periodic,
+            _ => return Err(PrecomputedError::NotPrecomputed),
+        };
+        let knots = reader.u32()? as usize;
+        let faces = reader.u32()? as usize;
+        if reader.u64()? != fingerprint(mesh) || knots != mesh.points().len() {
+            return Err(PrecomputedError::Mismatch);
+        }
+        if reader.0.len() < knots * KNOTS + faces * FACE {
+            return Err(PrecomputedError::Truncated);
+        }
+        Ok(Self {
+            boundary,
+            knots,
+            faces,
+        })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], PrecomputedError> {
+        if self.0.len() < n {
+            return Err(PrecomputedError::Truncated);
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], PrecomputedError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u32(&mut self) -> Result<u32, PrecomputedError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, PrecomputedError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, PrecomputedError> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    fn knots(&mut self) -> Result<LocalKnots, PrecomputedError> {
+        let mut knots = LocalKnots::default();
+        for v in knots.s_knots.iter_mut().chain(knots.t_knots.iter_mut()) {
+            *v = self.i64()? as isize;
+        }
+        Ok(knots)
+    }
+}
+
+/// FNV-1a hash of the parametric positions and connectivity of `mesh`
+fn fingerprint(mesh: &impl UVMesh) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    let mut add = |v: u64| {
+        for byte in v.to_le_bytes() {
+            hash = (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+    add(mesh.points().len() as u64);
+    for p in mesh.points() {
+        add(p.s as u64);
+        add(p.t as u64);
+    }
+    add(mesh.edges().len() as u64);
+    for e in mesh.edges() {
+        add(e.origin.0 as u64);
+        add(e.twin.map_or(u64::MAX, |t| t.0 as u64));
+        add(e.next.0 as u64);
+        add(e.prev.0 as u64);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TSpline;
+    use crate::control_mesh::ControlMeshMut;
+    use crate::uv_mesh::UVMeshMut;
+    use crate::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_round_trips_knots_and_faces() {
+        let mesh = TSpline::new_t_junction();
+        let precomputed = Precomputed::new(&mesh, Boundary::Clamped);
+
+        let bytes = precomputed.to_bytes(&mesh);
+        let loaded = Precomputed::from_bytes(&bytes, &mesh).unwrap();
+
+        assert_eq!(precomputed, loaded);
+        assert_eq!(mesh.local_knots(Boundary::Clamped), loaded.knots);
+        assert_eq!(
+            Some(loaded.faces[0].face),
+            loaded.face_at((loaded.faces[0].s.0, loaded.faces[0].t.0))
+        );
+    }
+
+    #[test]
+    fn it_loads_knots_without_allocating() {
+        let mut mesh = TSpline::new_t_junction();
+        let bytes = Precomputed::new(&mesh, Boundary::Clamped).to_bytes(&mesh);
+        mesh.control_point_mut(VertID(0)).unwrap().z = 1.;
+        let mut out = [LocalKnots::default(); 16];
+
+        let (boundary, knots) = Precomputed::knots_into(&bytes, &mesh, &mut out).unwrap();
+
+        assert_eq!(Boundary::Clamped, boundary);
+        assert_eq!(mesh.local_knots(Boundary::Clamped), knots);
+        assert_eq!(
+            Err(PrecomputedError::Capacity(mesh.points().len())),
+            Precomputed::knots_into(&bytes, &mesh, &mut out[..1]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn it_rejects_data_of_other_meshes() {
+        let mut mesh = TSpline::new_t_junction();
+        let bytes = Precomputed::new(&mesh, Boundary::Clamped).to_bytes(&mesh);
+
+        assert_eq!(
+            Err(PrecomputedError::Mismatch),
+            Precomputed::from_bytes(&bytes, &TSpline::new_unit_square())
+        );
+        assert_eq!(
+            Err(PrecomputedError::Truncated),
+            Precomputed::from_bytes(&bytes[..bytes.len() - 1], &mesh)
+        );
+        assert_eq!(
+            Err(PrecomputedError::NotPrecomputed),
+            Precomputed::from_bytes(b"glTF", &mesh)
+        );
+        mesh.point_mut(VertID(0)).unwrap().s -= 1;
+        assert_eq!(
+            Err(PrecomputedError::Mismatch),
+            Precomputed::from_bytes(&bytes, &mesh)
+        );
+    }
+}
@@ -0,0 +1,76 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::control_mesh::ControlMeshMut;
+use crate::uv_mesh::half_edge::HalfEdge;
+use crate::uv_mesh::ids::{EdgeID, VertID};
+use crate::uv_mesh::uv_point::UVPoint;
+use nalgebra::Vector4;
+use num_traits::{FromPrimitive, One, Zero};
+
+/// A wide face below two narrow faces, forming a single T-junction at (1, 1)
+pub fn t_junction<T: ControlMeshMut + Default>() -> T {
+    let mut mesh = T::default();
+
+    // 1. Define the vertices, the T-junction is vertex 3
+    let coords: [(isize, isize); _] = [
+        (0, 0),
+        (2, 0),
+        (2, 1),
+        (1, 1),
+        (0, 1),
+        (2, 2),
+        (1, 2),
+        (0, 2),
+    ];
+    let outgoing = [0, 1, 2, 3, 4, 7, 8, 12];
+    for ((s, t), e) in coords.into_iter().zip(outgoing) {
+        mesh.push_point(UVPoint {
+            s,
+            t,
+            outgoing_edge: EdgeID(e),
+        });
+
+        mesh.push_control_point(Vector4::new(
+            T::Unit::from_isize(s).unwrap(),
+            T::Unit::from_isize(t).unwrap(),
+            T::Unit::zero(),
+            T::Unit::one(),
+        ));
+    }
+
+    // 2. Define the (origin, twin) of each CCW face loop
+    let faces: [&[(usize, Option<usize>)]; 3] = [
+        &[(0, None), (1, None), (2, Some(5)), (3, Some(9)), (4, None)],
+        &[(3, Some(2)), (2, None), (5, None), (6, Some(10))],
+        &[(4, Some(3)), (3, Some(8)), (6, None), (7, None)],
+    ];
+    let mut start = 0;
+    for face in faces {
+        for (i, (origin, twin)) in face.iter().enumerate() {
+            mesh.push_edge(HalfEdge {
+                origin: VertID(*origin),
+                twin: twin.map(EdgeID),
+                next: EdgeID(start + (i + 1) % face.len()),
+                prev: EdgeID(start + (i + face.len() - 1) % face.len()),
+            });
+        }
+        start += face.len();
+    }
+
+    mesh
+}
@@ -0,0 +1,342 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::TSpline;
+use crate::control_mesh::ControlMesh;
+use crate::uv_mesh::half_edge::HalfEdge;
+use crate::uv_mesh::uv_point::UVPoint;
+use crate::{Numeric, Vector4};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use thiserror::Error;
+
+/// Why a mesh could not be copied into a storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum CapacityError {
+    #[error("mesh has {0} points, more than the storage holds")]
+    Points(usize),
+    #[error("mesh has {0} edges, more than the storage holds")]
+    Edges(usize),
+    #[error("mesh has {0} points but {1} control points")]
+    Mismatched(usize, usize),
+}
+
+/// A growable list of one kind of mesh element, indexed by its ids
+pub trait ElementStorage<Item>: Default + Clone + Debug {
+    fn as_slice(&self) -> &[Item];
+    fn as_mut_slice(&mut self) -> &mut [Item];
+    /// Append `item`, handing it back if there is no room
+    fn push(&mut self, item: Item) -> Result<(), Item>;
+    fn pop(&mut self) -> Option<Item>;
+    /// True if another push would fail
+    fn is_full(&self) -> bool;
+}
+
+/// How a [TSpline] keeps its elements.
+///
+/// Traversal, knot inference and the commands only see slices, so a new backend only
+/// has to provide [ElementStorage] for points, half-edges and control points.
+pub trait MeshStorage<T>: Clone + Debug {
+    type Points: ElementStorage<UVPoint>;
+    type Edges: ElementStorage<HalfEdge>;
+    type ControlPoints: ElementStorage<Vector4<T>>;
+}
+
+/// Elements in heap allocated vectors, the default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Heap;
+
+impl<T: Clone + Debug> MeshStorage<T> for Heap {
+    type Points = Vec<UVPoint>;
+    type Edges = Vec<HalfEdge>;
+    type ControlPoints = Vec<Vector4<T>>;
+}
+
+impl<Item: Clone + Debug> ElementStorage<Item> for Vec<Item> {
+    fn as_slice(&self) -> &[Item] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Item] {
+        self
+    }
+
+    fn push(&mut self, item: Item) -> Result<(), Item> {
+        Vec::push(self, item);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<Item> {
+        Vec::pop(self)
+    }
+
+    fn is_full(&self) -> bool {
+        false
+    }
+}
+
+/// Room for `V` vertices and `E` half-edges stored inline, for meshes small enough to
+/// live on the stack or in a `static` without an allocator. Faces need no room of their
+/// own, they are the loops of the half-edges.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Inline<const V: usize, const E: usize>;
+
+impl<T: Clone + Debug, const V: usize, const E: usize> MeshStorage<T> for Inline<V, E> {
+    type Points = heapless::Vec<UVPoint, V>;
+    type Edges = heapless::Vec<HalfEdge, E>;
+    type ControlPoints = heapless::Vec<Vector4<T>, V>;
+}
+
+impl<Item: Clone + Debug, const N: usize> ElementStorage<Item> for heapless::Vec<Item, N> {
+    fn as_slice(&self) -> &[Item] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Item] {
+        self
+    }
+
+    fn push(&mut self, item: Item) -> Result<(), Item> {
+        heapless::Vec::push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<Item> {
+        heapless::Vec::pop(self)
+    }
+
+    fn is_full(&self) -> bool {
+        heapless::Vec::is_full(self)
+    }
+}
+
+/// Elements behind reference counted buffers that clones share until one of them is
+/// changed, which copies only the buffer that changes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Shared;
+
+impl<T: Clone + Debug> MeshStorage<T> for Shared {
+    type Points = SharedVec<UVPoint>;
+    type Edges = SharedVec<HalfEdge>;
+    type ControlPoints = SharedVec<Vector4<T>>;
+}
+
+/// A vector behind an [Arc], copied on the first mutable access while it is shared
+#[derive(Debug, Clone)]
+pub struct SharedVec<Item>(Arc<Vec<Item>>);
+
+impl<Item> SharedVec<Item> {
+    /// True if both point to the same buffer
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<Item> Default for SharedVec<Item> {
+    fn default() -> Self {
+        Self(Arc::new(Vec::new()))
+    }
+}
+
+impl<Item: Clone + Debug> ElementStorage<Item> for SharedVec<Item> {
+    fn as_slice(&self) -> &[Item] {
+        &self.0
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Item] {
+        Arc::make_mut(&mut self.0).as_mut_slice()
+    }
+
+    fn push(&mut self, item: Item) -> Result<(), Item> {
+        Arc::make_mut(&mut self.0).push(item);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+        Arc::make_mut(&mut self.0).pop()
+    }
+
+    fn is_full(&self) -> bool {
+        false
+    }
+}
+
+/// A T-spline with inline storage for `V` vertices and `E` half-edges.
+///
+/// Faces are loops of half-edges and are not stored, a mesh of `F` quads without
+/// T-junctions fits in `E = 4 * F`. Meshes are usually built with alloc elsewhere and
+/// copied in with [TSpline::from_mesh].
+/// Pushing past the capacity panics, check [TSpline::is_full] first when edits are made
+/// in place.
+pub type SmallTSpline<T, const V: usize, const E: usize> = TSpline<T, Inline<V, E>>;
+
+/// A T-spline that clones in constant time.
+///
+/// Meant for previews: clone the mesh, run a command on the clone and keep or drop it.
+/// Only the buffers the command writes to are copied, a command that just moves
+/// control points leaves the topology shared with the original.
+pub type SharedTSpline<T = f64> = TSpline<T, Shared>;
+
+impl<T: Clone + Debug> SharedTSpline<T> {
+    /// True if `self` and `other` still share all of their buffers
+    pub fn shares_storage_with(&self, other: &Self) -> bool {
+        self.points.ptr_eq(&other.points)
+            && self.edges.ptr_eq(&other.edges)
+            && self.control_points.ptr_eq(&other.control_points)
+    }
+}
+
+impl<T: Numeric + Send + Sync + 'static, S: MeshStorage<T>> TSpline<T, S> {
+    /// Copy `mesh` into this storage, failing if it does not fit or its points and
+    /// control points do not pair up
+    pub fn from_mesh(mesh: &impl ControlMesh<Unit = T>) -> Result<Self, CapacityError> {
+        let (points, control_points) = (mesh.points().len(), mesh.control_points().len());
+        if points != control_points {
+            return Err(CapacityError::Mismatched(points, control_points));
+        }
+
+        let mut copy = Self::default();
+        for (p, cp) in mesh.points().iter().zip(mesh.control_points()) {
+            copy.points
+                .push(p.clone())
+                .map_err(|_| CapacityError::Points(mesh.points().len()))?;
+            copy.control_points
+                .push(*cp)
+                .map_err(|_| CapacityError::Points(mesh.points().len()))?;
+        }
+        for e in mesh.edges() {
+            copy.edges
+                .push(e.clone())
+                .map_err(|_| CapacityError::Edges(mesh.edges().len()))?;
+        }
+        Ok(copy)
+    }
+
+    /// True if another vertex or half-edge would not fit
+    pub fn is_full(&self) -> bool {
+        self.points.is_full() || self.control_points.is_full() || self.edges.is_full()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::subs;
+    use crate::control_mesh::ControlMeshMut;
+    use crate::uv_mesh::ids::VertID;
+    use crate::uv_mesh::{Boundary, LocalKnots, UVMesh};
+
+    #[test]
+    fn it_evaluates_like_the_heap_mesh() {
+        let mesh = TSpline::new_t_junction();
+        let small = SmallTSpline::<f64, 16, 48>::from_mesh(&mesh).unwrap();
+
+        assert!(small.validate_control_mesh().is_ok());
+        let mut knots = [LocalKnots::default(); 16];
+        let knots = small
+            .local_knots_into(Boundary::Clamped, &mut knots)
+            .unwrap();
+        assert_eq!(mesh.local_knots(Boundary::Clamped), knots);
+        assert_eq!(
+            subs(
+                mesh.control_points(),
+                (1.5, 0.5),
+                &mesh.local_knots(Boundary::Clamped)
+            ),
+            subs(small.control_points(), (1.5, 0.5), knots)
+        );
+    }
+
+    #[test]
+    fn it_rejects_meshes_that_do_not_fit() {
+        let mesh = TSpline::new_unit_square();
+
+        assert_eq!(
+            Err(CapacityError::Points(4)),
+            SmallTSpline::<f64, 3, 4>::from_mesh(&mesh).map(|_| ())
+        );
+        assert_eq!(
+            Err(CapacityError::Edges(4)),
+            SmallTSpline::<f64, 4, 3>::from_mesh(&mesh).map(|_| ())
+        );
+        assert!(
+            SmallTSpline::<f64, 4, 4>::from_mesh(&mesh)
+                .unwrap()
+                .is_full()
+        );
+    }
+
+    /// A mesh that lost the control point of its last vertex
+    struct Truncated(TSpline);
+
+    impl UVMesh for Truncated {
+        fn points(&self) -> &[UVPoint] {
+            self.0.points()
+        }
+
+        fn edges(&self) -> &[HalfEdge] {
+            self.0.edges()
+        }
+    }
+
+    impl ControlMesh for Truncated {
+        type Unit = f64;
+
+        fn control_points(&self) -> &[Vector4<f64>] {
+            let control_points = self.0.control_points();
+            &control_points[..control_points.len() - 1]
+        }
+    }
+
+    #[test]
+    fn it_rejects_mismatched_control_points() {
+        let mesh = Truncated(TSpline::new_unit_square());
+
+        assert_eq!(
+            Err(CapacityError::Mismatched(4, 3)),
+            TSpline::<f64>::from_mesh(&mesh).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn it_moves_between_storages() {
+        let mesh = TSpline::new_t_junction();
+        let small = SmallTSpline::<f64, 16, 48>::from_mesh(&mesh).unwrap();
+        let back = TSpline::<f64>::from_mesh(&small).unwrap();
+
+        assert!(mesh.diff(&back).is_empty());
+        assert!(!back.is_full());
+    }
+
+    #[test]
+    fn it_copies_shared_buffers_on_write() {
+        let mesh = SharedTSpline::<f64>::from_mesh(&TSpline::new_t_junction()).unwrap();
+        let mut preview = mesh.clone();
+        assert!(preview.shares_storage_with(&mesh));
+
+        preview.control_point_mut(VertID(4)).unwrap().z = 1.;
+
+        assert!(!preview.shares_storage_with(&mesh));
+        assert!(preview.points.ptr_eq(&mesh.points));
+        assert!(preview.edges.ptr_eq(&mesh.edges));
+        assert_eq!(0., mesh.control_points()[4].z);
+        assert_eq!(1., preview.control_points()[4].z);
+    }
+}
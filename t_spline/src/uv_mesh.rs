@@ -16,18 +16,28 @@
  */
 
 pub mod direction;
+pub mod face_adjacency;
 pub mod half_edge;
 pub mod ids;
+pub mod layout;
+pub mod t_junction;
+pub mod topology;
 pub mod uv_point;
 
 use crate::Numeric;
 use crate::line::Line;
 use crate::uv_mesh::direction::Direction;
+use crate::uv_mesh::face_adjacency::FaceAdjacency;
 use crate::uv_mesh::half_edge::HalfEdge;
 use crate::uv_mesh::ids::{EdgeID, VertID};
+use crate::uv_mesh::layout::{FaceRect, ParametricLayout, merge_lines};
+use crate::uv_mesh::t_junction::{TJunction, TJunctionExtension};
+use crate::uv_mesh::topology::{TopologyReport, check_topology};
 use crate::uv_mesh::uv_point::{UVCoord, UVPoint};
-use alloc::collections::BTreeSet;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+use nalgebra::Vector2;
 use smallvec::SmallVec;
 use thiserror::Error;
 
@@ -44,7 +54,7 @@ const INVALID_MESH: &str = "invalid mesh, validate to avoid panics";
 pub type KnotVector = [isize; 5];
 
 /// Two directional knot vectors for S & T directions.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct LocalKnots {
     pub s_knots: KnotVector,
     pub t_knots: KnotVector,
@@ -59,19 +69,40 @@ pub enum TracePoint {
 pub trait UVMeshMut: UVMesh {
     fn push_point(&mut self, point: UVPoint) -> VertID;
     fn push_edge(&mut self, edge: HalfEdge) -> EdgeID;
+    /// Remove the last point, references to it are left for the caller to fix
+    fn pop_point(&mut self) -> Option<UVPoint>;
+    /// Remove the last edge, references to it are left for the caller to fix
+    fn pop_edge(&mut self) -> Option<HalfEdge>;
+    fn point_mut(&mut self, id: VertID) -> Option<&mut UVPoint>;
     fn edge_mut(&mut self, id: EdgeID) -> Option<&mut HalfEdge>;
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Boundary {
     Clamped,
     Periodic,
 }
 
+static REVISION: AtomicU64 = AtomicU64::new(1);
+
+/// A revision no mesh has reported before, unique across all meshes
+pub fn next_revision() -> u64 {
+    REVISION.fetch_add(1, Ordering::Relaxed)
+}
+
 pub trait UVMesh {
     fn points(&self) -> &[UVPoint];
     fn edges(&self) -> &[HalfEdge];
 
+    /// An id of the current state of the mesh, taken from [next_revision].
+    ///
+    /// Every mutable access takes a new one, so results computed from a mesh can be cached
+    /// until its revision moves on. Meshes that do not track their changes report a fresh
+    /// revision on every call, so nothing computed from them is reused.
+    fn revision(&self) -> u64 {
+        next_revision()
+    }
+
     fn edge(&self, id: EdgeID) -> Option<&HalfEdge> {
         self.edges().get(id.0)
     }
@@ -137,6 +168,20 @@ pub trait UVMesh {
             .collect()
     }
 
+    /// Compute all local knots into `out` without allocating, for example into an array on
+    /// the stack. Returns `None` if `out` has fewer entries than the mesh has points.
+    fn local_knots_into<'a>(
+        &self,
+        boundary: Boundary,
+        out: &'a mut [LocalKnots],
+    ) -> Option<&'a [LocalKnots]> {
+        let out = out.get_mut(..self.points().len())?;
+        for (v, knots) in out.iter_mut().enumerate() {
+            *knots = self.infer_local_knots(VertID(v), boundary);
+        }
+        Some(out)
+    }
+
     fn line(&self, edge: &HalfEdge) -> Line<isize> {
         Line::from_uv_points(
             self.point(edge.origin).expect(INVALID_MESH),
@@ -153,20 +198,15 @@ pub trait UVMesh {
 
     fn edge_loop<'a>(&'a self, edge: &'a HalfEdge) -> impl Iterator<Item = (EdgeID, &'a HalfEdge)> {
         let start = edge.origin;
-        let mut current = edge;
-        let mut edges = Vec::new();
-
-        loop {
-            let next_id = current.next;
-            current = self.next_edge(current);
-            edges.push((next_id, current));
-
-            if current.origin == start {
-                break;
-            }
-        }
-
-        edges.into_iter()
+        let mut current = Some(edge);
+
+        // walked lazily so tracing knots does not allocate
+        core::iter::from_fn(move || {
+            let edge = current?;
+            let next = self.next_edge(edge);
+            current = (next.origin != start).then_some(next);
+            Some((edge.next, next))
+        })
     }
 
     /// Loops around a vertex `id` and returns all verteces connected to it
@@ -203,6 +243,35 @@ pub trait UVMesh {
         faces.into_iter()
     }
 
+    /// The dual graph of the mesh, connecting faces through their twin edges
+    fn face_adjacency(&self) -> FaceAdjacency {
+        let faces: Vec<EdgeID> = self.faces().collect();
+
+        let mut edge_faces = alloc::vec![0; self.edges().len()];
+        for (f, face) in faces.iter().enumerate() {
+            for (e, _) in self.edge_loop(self.edge(*face).expect(INVALID_MESH)) {
+                edge_faces[e.0] = f;
+            }
+        }
+
+        let mut neighbours = alloc::vec![Vec::new(); faces.len()];
+        for (e, edge) in self.edges().iter().enumerate() {
+            if let Some(twin) = edge.twin {
+                neighbours[edge_faces[e]].push(edge_faces[twin.0]);
+            }
+        }
+        for n in &mut neighbours {
+            n.sort();
+            n.dedup();
+        }
+
+        FaceAdjacency {
+            faces,
+            edge_faces,
+            neighbours,
+        }
+    }
+
     fn contains_uv<T: Numeric + 'static>(&self, point: impl UVCoord<T>) -> bool {
         let mut intersections = 0;
         for edge in self.edges() {
@@ -402,6 +471,191 @@ pub trait UVMesh {
         None
     }
 
+    /// Find all T-junctions, vertices where a knot line ends inside the mesh.
+    ///
+    /// A T-junction is a vertex of valence 3 that sits on a straight side of one of its
+    /// faces, the missing edge points into that face.
+    fn t_junctions(&self) -> Vec<TJunction> {
+        let mut junctions = Vec::new();
+
+        for edge in self.edges() {
+            let prev = self.edge(edge.prev).expect(INVALID_MESH);
+            let (from, v) = self.start_end(prev);
+            let (_, to) = self.start_end(edge);
+
+            let incoming = (v.s - from.s, v.t - from.t);
+            let outgoing = (to.s - v.s, to.t - v.t);
+            let collinear = incoming.0.signum() == outgoing.0.signum()
+                && incoming.1.signum() == outgoing.1.signum();
+
+            if !collinear || self.connected_verteces(edge.origin).len() != 3 {
+                continue;
+            }
+
+            // faces are wound CCW so the inside is to the left of the edge
+            let (axis, positive) = match (outgoing.0.signum(), outgoing.1.signum()) {
+                (1, 0) => (Direction::T, true),
+                (-1, 0) => (Direction::T, false),
+                (0, 1) => (Direction::S, false),
+                _ => (Direction::S, true),
+            };
+
+            junctions.push(TJunction {
+                vertex: edge.origin,
+                axis,
+                positive,
+            });
+        }
+
+        junctions
+    }
+
+    /// Compute the face and edge extensions of a T-junction
+    fn t_junction_extension(&self, junction: &TJunction) -> TJunctionExtension {
+        let p = self.point(junction.vertex).expect(INVALID_MESH);
+        let segment = |hits: &[isize], bays: usize| {
+            let end = hits
+                .get(bays - 1)
+                .or(hits.last())
+                .copied()
+                .unwrap_or(p.value_in_dir(junction.axis));
+            let mut end_point = (p.s, p.t);
+            match junction.axis {
+                Direction::S => end_point.0 = end,
+                Direction::T => end_point.1 = end,
+            }
+            Line::new(
+                Vector2::new(p.s, p.t),
+                Vector2::new(end_point.0, end_point.1),
+            )
+        };
+
+        let face = self.ray_hits(p.st(), junction.axis, junction.positive);
+        let edge = self.ray_hits(p.st(), junction.axis, !junction.positive);
+
+        TJunctionExtension {
+            junction: *junction,
+            face: segment(&face, 2),
+            edge: segment(&edge, 1),
+        }
+    }
+
+    /// Pairs of T-junctions with perpendicular extensions that intersect.
+    ///
+    /// The mesh is analysis suitable if there are none, which makes its blending
+    /// functions linearly independent.
+    fn extension_intersections(&self) -> Vec<(TJunction, TJunction)> {
+        let extensions: Vec<_> = self
+            .t_junctions()
+            .iter()
+            .map(|j| self.t_junction_extension(j))
+            .collect();
+
+        let mut pairs = Vec::new();
+        for (i, a) in extensions.iter().enumerate() {
+            for b in &extensions[i + 1..] {
+                if a.intersects(b) {
+                    pairs.push((a.junction, b.junction));
+                }
+            }
+        }
+        pairs
+    }
+
+    fn is_analysis_suitable(&self) -> bool {
+        self.extension_intersections().is_empty()
+    }
+
+    /// Describe the parametric layout: knot lines, face rectangles and T-junction
+    /// extensions.
+    fn layout(&self) -> ParametricLayout {
+        let mut s_lines: BTreeMap<isize, Vec<(isize, isize)>> = BTreeMap::new();
+        let mut t_lines: BTreeMap<isize, Vec<(isize, isize)>> = BTreeMap::new();
+
+        for edge in self.edges() {
+            let (a, b) = self.start_end(edge);
+            if a.s == b.s {
+                s_lines
+                    .entry(a.s)
+                    .or_default()
+                    .push((Ord::min(a.t, b.t), Ord::max(a.t, b.t)));
+            } else if a.t == b.t {
+                t_lines
+                    .entry(a.t)
+                    .or_default()
+                    .push((Ord::min(a.s, b.s), Ord::max(a.s, b.s)));
+            }
+        }
+
+        let faces = self
+            .faces()
+            .map(|face| {
+                let mut rect = FaceRect {
+                    face,
+                    s: (isize::MAX, isize::MIN),
+                    t: (isize::MAX, isize::MIN),
+                };
+                for (_, e) in self.edge_loop(self.edge(face).expect(INVALID_MESH)) {
+                    let p = self.point(e.origin).expect(INVALID_MESH);
+                    rect.s = (Ord::min(rect.s.0, p.s), Ord::max(rect.s.1, p.s));
+                    rect.t = (Ord::min(rect.t.0, p.t), Ord::max(rect.t.1, p.t));
+                }
+                rect
+            })
+            .collect();
+
+        ParametricLayout {
+            s_lines: merge_lines(s_lines),
+            t_lines: merge_lines(t_lines),
+            faces,
+            extensions: self
+                .t_junctions()
+                .iter()
+                .map(|j| self.t_junction_extension(j))
+                .collect(),
+        }
+    }
+
+    /// Cast a ray from `origin` along `axis` and return the distinct coordinates of all
+    /// edges it crosses or touches, nearest first.
+    fn ray_hits(&self, origin: (isize, isize), axis: Direction, positive: bool) -> Vec<isize> {
+        let mut hits = BTreeSet::new();
+
+        for edge in self.edges() {
+            let (a, b) = self.start_end(edge);
+            let c = a.value_in_dir(axis);
+            if c != b.value_in_dir(axis) {
+                continue; // edge runs along the ray
+            }
+
+            let o = origin.value_in_dir(axis);
+            let ahead = if positive { c > o } else { c < o };
+
+            let other = axis.opposite();
+            let (lo, hi) = (
+                Ord::min(a.value_in_dir(other), b.value_in_dir(other)),
+                Ord::max(a.value_in_dir(other), b.value_in_dir(other)),
+            );
+            let within = origin.value_in_dir(other) >= lo && origin.value_in_dir(other) <= hi;
+
+            if ahead && within {
+                hits.insert(c);
+            }
+        }
+
+        if positive {
+            hits.into_iter().collect()
+        } else {
+            hits.into_iter().rev().collect()
+        }
+    }
+
+    /// Every broken half-edge invariant, where [Self::validate_uv_mesh_integrity] stops
+    /// at the first
+    fn topology_report(&self) -> TopologyReport {
+        check_topology(self)
+    }
+
     fn validate_uv_mesh_integrity(&self) -> Result<(), ValidationError> {
         for point in self.points() {
             if let Some(edge) = self.edge(point.outgoing_edge) {
@@ -465,6 +719,34 @@ pub trait UVMesh {
             }
         }
 
+        self.validate_faces()
+    }
+
+    /// Check that every face has a positive parametric area, counter-clockwise winding
+    /// and no collapsed edges. Degenerate faces produce zero knot intervals which
+    /// otherwise surface as NaNs during basis evaluation.
+    fn validate_faces(&self) -> Result<(), ValidationError> {
+        for face in self.faces() {
+            let edge = self
+                .edge(face)
+                .ok_or(ValidationError::InvalidOutgoingEdge())?;
+
+            let mut area = 0;
+            for (id, e) in self.edge_loop(edge) {
+                let (a, b) = self.start_end(e);
+                if a.st() == b.st() {
+                    return Err(ValidationError::CollapsedEdge(id));
+                }
+                area += a.s * b.t - b.s * a.t;
+            }
+
+            if area == 0 {
+                return Err(ValidationError::DegenerateFace(face));
+            } else if area < 0 {
+                return Err(ValidationError::InvertedFace(face));
+            }
+        }
+
         Ok(())
     }
 
@@ -501,12 +783,21 @@ pub enum ValidationError {
     DisconnectedPoints(),
     #[error("twin does not align")]
     MisalignedTwin(),
+    #[error("edge {0:?} has a zero knot interval")]
+    CollapsedEdge(EdgeID),
+    #[error("face {0:?} has no parametric area")]
+    DegenerateFace(EdgeID),
+    #[error("face {0:?} is wound clockwise")]
+    InvertedFace(EdgeID),
+    #[error("mesh has no parametric area")]
+    EmptyDomain(),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::TSpline;
+    use crate::builder::TMeshBuilder;
 
     #[test]
     fn it_has_valid_unit_square() {
@@ -514,6 +805,126 @@ mod tests {
         assert_eq!(Ok(()), mesh.validate_uv_mesh_integrity());
     }
 
+    #[test]
+    fn it_detects_inverted_faces() {
+        let mut mesh = TSpline::new_unit_square();
+        for p in &mut mesh.points {
+            p.s = -p.s;
+        }
+
+        assert_eq!(
+            Err(ValidationError::InvertedFace(EdgeID(0))),
+            mesh.validate_faces()
+        );
+    }
+
+    #[test]
+    fn it_detects_degenerate_faces() {
+        let mut mesh = TSpline::new_unit_square();
+        for (p, s) in mesh.points.iter_mut().zip([0, 1, 2, 1]) {
+            p.s = s;
+            p.t = 0;
+        }
+
+        assert_eq!(
+            Err(ValidationError::DegenerateFace(EdgeID(0))),
+            mesh.validate_faces()
+        );
+    }
+
+    #[test]
+    fn it_detects_collapsed_edges() {
+        let mut mesh = TSpline::new_unit_square();
+        mesh.points[1].s = 0;
+
+        assert_eq!(
+            Err(ValidationError::CollapsedEdge(EdgeID(0))),
+            mesh.validate_faces()
+        );
+    }
+
+    #[test]
+    fn it_has_valid_t_junction() {
+        let mesh = TSpline::new_t_junction();
+        assert_eq!(Ok(()), mesh.validate_uv_mesh_integrity());
+    }
+
+    #[test]
+    fn it_finds_t_junctions() {
+        assert!(TSpline::new_unit_square().t_junctions().is_empty());
+
+        assert_eq!(
+            vec![TJunction {
+                vertex: VertID(3),
+                axis: Direction::T,
+                positive: false,
+            }],
+            TSpline::new_t_junction().t_junctions()
+        );
+    }
+
+    #[test]
+    fn it_extends_t_junctions() {
+        let mesh = TSpline::new_t_junction();
+        let extension = mesh.t_junction_extension(&mesh.t_junctions()[0]);
+
+        assert_eq!((1, 0), (extension.face.s1(), extension.face.t1()));
+        assert_eq!((1, 2), (extension.edge.s1(), extension.edge.t1()));
+    }
+
+    /// A 5 x 5 grid of unit faces with the faces inside `merged` joined into one
+    fn grid_with_merged(merged: &[[isize; 4]]) -> TSpline {
+        let mut builder = TMeshBuilder::new();
+        let mut ids = BTreeMap::new();
+        for t in 0..=5 {
+            for s in 0..=5 {
+                let v = builder.vertex(s, t, nalgebra::Vector4::new(s as f64, t as f64, 0., 1.));
+                ids.insert((s, t), v);
+            }
+        }
+        let corners = |[s0, t0, s1, t1]: [isize; 4]| {
+            [(s0, t0), (s1, t0), (s1, t1), (s0, t1)].map(|c| ids[&c])
+        };
+        for t in 0..5 {
+            for s in 0..5 {
+                let inside =
+                    |[s0, t0, s1, t1]: &[isize; 4]| s >= *s0 && s < *s1 && t >= *t0 && t < *t1;
+                if !merged.iter().any(inside) {
+                    builder.face(&corners([s, t, s + 1, t + 1]));
+                }
+            }
+        }
+        for &rect in merged {
+            builder.face(&corners(rect));
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn it_checks_analysis_suitability() {
+        assert!(TSpline::new_t_junction().is_analysis_suitable());
+
+        let parallel = grid_with_merged(&[[1, 1, 3, 2]]);
+        assert_eq!(2, parallel.t_junctions().len());
+        assert!(parallel.is_analysis_suitable());
+
+        // the edge extension of the junction at (3, 2) reaches the vertical extensions
+        // through s = 2
+        let crossing = grid_with_merged(&[[1, 1, 3, 2], [3, 1, 4, 3]]);
+        let pairs = crossing.extension_intersections();
+        assert!(!crossing.is_analysis_suitable());
+        for (a, b) in &pairs {
+            assert_ne!(a.axis, b.axis);
+        }
+        // vertices are numbered row by row
+        let junction = VertID(2 * 6 + 3);
+        assert!(
+            pairs
+                .iter()
+                .any(|(a, b)| a.vertex == junction || b.vertex == junction)
+        );
+    }
+
     #[test]
     fn it_finds_edge_loops() {
         let mesh = TSpline::new_unit_square();
@@ -0,0 +1,133 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::uv_mesh::ids::EdgeID;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+/// The dual graph of a mesh, faces are connected if they share an edge.
+///
+/// Faces are addressed by their index in [crate::uv_mesh::UVMesh::faces].
+#[derive(Debug, Clone, Default)]
+pub struct FaceAdjacency {
+    pub(crate) faces: Vec<EdgeID>,
+    pub(crate) edge_faces: Vec<usize>,
+    pub(crate) neighbours: Vec<Vec<usize>>,
+}
+
+impl FaceAdjacency {
+    /// The representative edge of each face
+    pub fn faces(&self) -> &[EdgeID] {
+        &self.faces
+    }
+
+    /// The index of the face `edge` belongs to
+    pub fn face_of(&self, edge: EdgeID) -> Option<usize> {
+        self.edge_faces.get(edge.0).copied()
+    }
+
+    /// The distinct faces sharing at least one edge with `face`, in ascending order
+    pub fn neighbours(&self, face: usize) -> &[usize] {
+        self.neighbours.get(face).map_or(&[], Vec::as_slice)
+    }
+
+    /// The number of faces in the graph
+    pub fn len(&self) -> usize {
+        self.faces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    /// Grow a selection breadth first from `seeds`.
+    ///
+    /// A neighbour is added when `accept(from, to)` holds for the selected face `from`
+    /// it was reached through. Returns the selected faces in ascending order.
+    pub fn grow(
+        &self,
+        seeds: &[usize],
+        mut accept: impl FnMut(usize, usize) -> bool,
+    ) -> Vec<usize> {
+        let mut selected = alloc::vec![false; self.len()];
+        let mut queue = VecDeque::new();
+        for &seed in seeds {
+            if seed < self.len() && !selected[seed] {
+                selected[seed] = true;
+                queue.push_back(seed);
+            }
+        }
+
+        while let Some(from) = queue.pop_front() {
+            for &to in self.neighbours(from) {
+                if !selected[to] && accept(from, to) {
+                    selected[to] = true;
+                    queue.push_back(to);
+                }
+            }
+        }
+
+        (0..self.len()).filter(|&f| selected[f]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TSpline;
+    use crate::uv_mesh::UVMesh;
+    use crate::uv_mesh::ids::EdgeID;
+
+    #[test]
+    fn it_connects_a_single_face_to_nothing() {
+        let graph = TSpline::new_unit_square().face_adjacency();
+
+        assert_eq!(1, graph.len());
+        assert!(graph.neighbours(0).is_empty());
+        assert_eq!(Some(0), graph.face_of(EdgeID(3)));
+    }
+
+    #[test]
+    fn it_connects_faces_around_a_t_junction() {
+        let mesh = TSpline::new_t_junction();
+        let graph = mesh.face_adjacency();
+
+        assert_eq!(3, graph.len());
+        assert_eq!(&[1, 2], graph.neighbours(0));
+        assert_eq!(&[0, 2], graph.neighbours(1));
+        assert_eq!(&[0, 1], graph.neighbours(2));
+
+        for (i, edge) in mesh.edges().iter().enumerate() {
+            if let Some(twin) = edge.twin {
+                let face = graph.face_of(EdgeID(i)).unwrap();
+                assert!(
+                    graph
+                        .neighbours(face)
+                        .contains(&graph.face_of(twin).unwrap())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn it_grows_until_the_predicate_fails() {
+        let graph = TSpline::new_t_junction().face_adjacency();
+
+        assert_eq!(alloc::vec![0, 1, 2], graph.grow(&[0], |_, _| true));
+        assert_eq!(alloc::vec![1], graph.grow(&[1], |_, _| false));
+        assert_eq!(alloc::vec![0, 1], graph.grow(&[1], |_, to| to != 2));
+    }
+}
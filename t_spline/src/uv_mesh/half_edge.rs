@@ -18,7 +18,7 @@
 use crate::uv_mesh::ids::{EdgeID, VertID};
 
 /// Half edge
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HalfEdge {
     /// Vertex where this edge starts
     pub origin: VertID,
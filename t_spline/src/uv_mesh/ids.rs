@@ -15,7 +15,7 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
-#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+#[derive(Eq, PartialEq, PartialOrd, Copy, Clone, Hash, Debug, Ord)]
 pub struct VertID(pub usize);
 
 impl From<VertID> for usize {
@@ -0,0 +1,148 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::uv_mesh::direction::Direction;
+use crate::uv_mesh::ids::EdgeID;
+use crate::uv_mesh::t_junction::TJunctionExtension;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// A maximal straight run of edges in parameter space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnotLine {
+    /// The constant coordinate of the line, an S-line has a constant `s` and runs along `t`
+    pub value: isize,
+    /// The range covered along the line
+    pub extent: (isize, isize),
+}
+
+/// The parametric rectangle of a face
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaceRect {
+    pub face: EdgeID,
+    pub s: (isize, isize),
+    pub t: (isize, isize),
+}
+
+/// The parametric layout of a T-mesh as plain data.
+#[derive(Debug, Clone)]
+pub struct ParametricLayout {
+    /// Lines of constant `s`, sorted by value
+    pub s_lines: Vec<KnotLine>,
+    /// Lines of constant `t`, sorted by value
+    pub t_lines: Vec<KnotLine>,
+    pub faces: Vec<FaceRect>,
+    pub extensions: Vec<TJunctionExtension>,
+}
+
+impl ParametricLayout {
+    /// Knot lines of constant `direction` coordinate
+    pub fn lines(&self, direction: Direction) -> &[KnotLine] {
+        match direction {
+            Direction::S => &self.s_lines,
+            Direction::T => &self.t_lines,
+        }
+    }
+}
+
+/// Merge edge intervals grouped by their constant coordinate into maximal knot lines
+pub(crate) fn merge_lines(intervals: BTreeMap<isize, Vec<(isize, isize)>>) -> Vec<KnotLine> {
+    let mut lines = Vec::new();
+    for (value, mut ranges) in intervals {
+        ranges.sort();
+
+        let mut current: Option<(isize, isize)> = None;
+        for (lo, hi) in ranges {
+            current = match current {
+                Some((c_lo, c_hi)) if lo <= c_hi => Some((c_lo, c_hi.max(hi))),
+                Some(extent) => {
+                    lines.push(KnotLine { value, extent });
+                    Some((lo, hi))
+                }
+                None => Some((lo, hi)),
+            };
+        }
+        if let Some(extent) = current {
+            lines.push(KnotLine { value, extent });
+        }
+    }
+    lines
+}
+
+/// Whether the corners of a face loop outline the rectangle `s` by `t`, every corner lies on
+/// one of its sides and all four of its corners are present
+pub(crate) fn outlines(s: (isize, isize), t: (isize, isize), corners: &[(isize, isize)]) -> bool {
+    let on_side = corners
+        .iter()
+        .all(|&(cs, ct)| cs == s.0 || cs == s.1 || ct == t.0 || ct == t.1);
+    let has_corners = [(s.0, t.0), (s.1, t.0), (s.1, t.1), (s.0, t.1)]
+        .iter()
+        .all(|corner| corners.contains(corner));
+    on_side && has_corners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TSpline;
+    use crate::uv_mesh::UVMesh;
+
+    #[test]
+    fn it_merges_touching_intervals() {
+        let mut intervals = BTreeMap::new();
+        intervals.insert(0, alloc::vec![(1, 2), (0, 1), (3, 4)]);
+
+        assert_eq!(
+            alloc::vec![
+                KnotLine {
+                    value: 0,
+                    extent: (0, 2)
+                },
+                KnotLine {
+                    value: 0,
+                    extent: (3, 4)
+                },
+            ],
+            merge_lines(intervals)
+        );
+    }
+
+    #[test]
+    fn it_describes_a_t_junction() {
+        let layout = TSpline::new_t_junction().layout();
+
+        assert_eq!(
+            alloc::vec![(0, (0, 2)), (1, (1, 2)), (2, (0, 2))],
+            layout
+                .s_lines
+                .iter()
+                .map(|l| (l.value, l.extent))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(3, layout.t_lines.len());
+        assert_eq!(
+            FaceRect {
+                face: EdgeID(0),
+                s: (0, 2),
+                t: (0, 1),
+            },
+            layout.faces[0]
+        );
+        assert_eq!(3, layout.faces.len());
+        assert_eq!(1, layout.extensions.len());
+    }
+}
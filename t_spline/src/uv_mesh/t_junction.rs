@@ -0,0 +1,87 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::line::Line;
+use crate::uv_mesh::direction::Direction;
+use crate::uv_mesh::ids::VertID;
+
+/// A vertex where a knot line terminates inside the mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TJunction {
+    pub vertex: VertID,
+    /// The axis of the missing edge
+    pub axis: Direction,
+    /// True if the missing edge points along the positive axis
+    pub positive: bool,
+}
+
+/// The extension of a [TJunction] used to check analysis suitability.
+///
+/// For a cubic T-spline the face extension crosses two bays in the direction of the
+/// missing edge and the edge extension crosses one bay in the opposite direction.
+#[derive(Debug, Clone, Copy)]
+pub struct TJunctionExtension {
+    pub junction: TJunction,
+    /// Extension from the junction into the faces the missing edge points at
+    pub face: Line<isize>,
+    /// Extension from the junction along the edge opposite of the missing edge
+    pub edge: Line<isize>,
+}
+
+impl TJunctionExtension {
+    /// True if the extensions run along perpendicular axes and touch.
+    pub fn intersects(&self, other: &TJunctionExtension) -> bool {
+        if self.junction.axis == other.junction.axis {
+            return false;
+        }
+
+        [self.face, self.edge]
+            .iter()
+            .any(|a| [other.face, other.edge].iter().any(|b| a.overlaps(b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector2;
+
+    fn extension(vertex: usize, axis: Direction, face: [isize; 4]) -> TJunctionExtension {
+        let start = Vector2::new(face[0], face[1]);
+        TJunctionExtension {
+            junction: TJunction {
+                vertex: VertID(vertex),
+                axis,
+                positive: true,
+            },
+            face: Line::new(start, Vector2::new(face[2], face[3])),
+            edge: Line::new(start, start),
+        }
+    }
+
+    #[test]
+    fn it_intersects_perpendicular_extensions() {
+        let vertical = extension(0, Direction::T, [1, 0, 1, 2]);
+        let horizontal = extension(1, Direction::S, [0, 1, 2, 1]);
+        let parallel = extension(2, Direction::T, [1, 1, 1, 3]);
+        let apart = extension(3, Direction::S, [2, 3, 4, 3]);
+
+        assert!(vertical.intersects(&horizontal));
+        assert!(!vertical.intersects(&parallel));
+        assert!(!vertical.intersects(&apart));
+    }
+}
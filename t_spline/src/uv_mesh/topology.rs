@@ -0,0 +1,243 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::uv_mesh::UVMesh;
+use crate::uv_mesh::ids::{EdgeID, VertID};
+use crate::uv_mesh::layout::outlines;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A broken invariant of the half-edge structure.
+///
+/// Faces are named by the first edge of their loop, the edge [UVMesh::faces] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// The outgoing edge of the vertex does not exist
+    DanglingOutgoingEdge(VertID),
+    /// The outgoing edge of the vertex starts at another vertex
+    ForeignOutgoingEdge(VertID, EdgeID),
+    DanglingOrigin(EdgeID),
+    DanglingNext(EdgeID),
+    DanglingPrev(EdgeID),
+    DanglingTwin(EdgeID),
+    /// The previous edge of the next edge is not this edge
+    BrokenLoop(EdgeID),
+    /// Following the next edges never returns to this edge
+    OpenLoop(EdgeID),
+    /// The twin of the twin is not this edge
+    AsymmetricTwin(EdgeID),
+    /// The twin does not run from the end of this edge back to its start
+    MisalignedTwin(EdgeID),
+    /// The edge is not parallel to `s` or `t`
+    DiagonalEdge(EdgeID),
+    ZeroLengthEdge(EdgeID),
+    DegenerateFace(EdgeID),
+    InvertedFace(EdgeID),
+    /// The corners of the face do not outline its rectangle in the layout
+    NonRectangularFace(EdgeID),
+}
+
+/// Every violation found in a mesh, empty if the mesh is valid
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopologyReport {
+    pub violations: Vec<Violation>,
+}
+
+impl TopologyReport {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Check all half-edge invariants of `mesh` without stopping at the first failure.
+///
+/// Unlike evaluation this never follows a dangling id, so it is safe on any mesh.
+pub fn check_topology(mesh: &(impl UVMesh + ?Sized)) -> TopologyReport {
+    let (points, edges) = (mesh.points(), mesh.edges());
+    let mut violations = Vec::new();
+
+    for (v, point) in points.iter().enumerate() {
+        match edges.get(point.outgoing_edge.0) {
+            None => violations.push(Violation::DanglingOutgoingEdge(VertID(v))),
+            Some(edge) if edge.origin != VertID(v) => violations.push(
+                Violation::ForeignOutgoingEdge(VertID(v), point.outgoing_edge),
+            ),
+            Some(_) => {}
+        }
+    }
+
+    // only edges with a valid origin and loop links are walked below
+    let mut linked = vec![true; edges.len()];
+    for (i, edge) in edges.iter().enumerate() {
+        let id = EdgeID(i);
+        let mut fail = |violation| {
+            violations.push(violation);
+            linked[i] = false;
+        };
+        if points.get(edge.origin.0).is_none() {
+            fail(Violation::DanglingOrigin(id));
+        }
+        match edges.get(edge.next.0) {
+            None => fail(Violation::DanglingNext(id)),
+            Some(next) if next.prev != id => fail(Violation::BrokenLoop(id)),
+            Some(_) => {}
+        }
+        match edges.get(edge.prev.0) {
+            None => fail(Violation::DanglingPrev(id)),
+            Some(prev) if prev.next != id => fail(Violation::BrokenLoop(id)),
+            Some(_) => {}
+        }
+        if let Some(twin) = edge.twin {
+            match edges.get(twin.0) {
+                None => violations.push(Violation::DanglingTwin(id)),
+                Some(other) if other.twin != Some(id) => {
+                    violations.push(Violation::AsymmetricTwin(id))
+                }
+                Some(other) => {
+                    if let Some(next) = edges.get(edge.next.0)
+                        && let Some(twin_next) = edges.get(other.next.0)
+                        && (other.origin != next.origin || twin_next.origin != edge.origin)
+                    {
+                        violations.push(Violation::MisalignedTwin(id));
+                    }
+                }
+            }
+        }
+    }
+
+    // walk the loops of well linked edges, checking their sides and area
+    let mut seen = vec![false; edges.len()];
+    for start in 0..edges.len() {
+        if seen[start] || !linked[start] {
+            continue;
+        }
+
+        let mut face = Vec::new();
+        let mut current = start;
+        while !seen[current] && linked[current] {
+            seen[current] = true;
+            face.push(current);
+            current = edges[current].next.0;
+        }
+        if current != start || !linked[current] {
+            violations.push(Violation::OpenLoop(EdgeID(start)));
+            continue;
+        }
+
+        let mut area = 0;
+        for &e in &face {
+            let a = &points[edges[e].origin.0];
+            let b = &points[edges[edges[e].next.0].origin.0];
+            if a.s == b.s && a.t == b.t {
+                violations.push(Violation::ZeroLengthEdge(EdgeID(e)));
+            } else if a.s != b.s && a.t != b.t {
+                violations.push(Violation::DiagonalEdge(EdgeID(e)));
+            }
+            area += a.s * b.t - b.s * a.t;
+        }
+        if area == 0 {
+            violations.push(Violation::DegenerateFace(EdgeID(start)));
+        } else if area < 0 {
+            violations.push(Violation::InvertedFace(EdgeID(start)));
+        }
+    }
+
+    // the layout follows every id, so it is only built for an otherwise valid mesh
+    if violations.is_empty() {
+        for rect in mesh.layout().faces {
+            let mut corners = Vec::new();
+            let mut current = rect.face.0;
+            loop {
+                let p = &points[edges[current].origin.0];
+                corners.push((p.s, p.t));
+                current = edges[current].next.0;
+                if current == rect.face.0 {
+                    break;
+                }
+            }
+            if !outlines(rect.s, rect.t, &corners) {
+                violations.push(Violation::NonRectangularFace(rect.face));
+            }
+        }
+    }
+
+    TopologyReport { violations }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TSpline;
+    use crate::control_mesh::ControlMeshMut;
+    use crate::uv_mesh::UVMeshMut;
+    use crate::uv_mesh::half_edge::HalfEdge;
+    use crate::uv_mesh::uv_point::UVPoint;
+    use nalgebra::Vector4;
+
+    #[test]
+    fn it_accepts_valid_meshes() {
+        assert!(check_topology(&TSpline::new_unit_square()).is_valid());
+        assert!(TSpline::new_t_junction().topology_report().is_valid());
+    }
+
+    #[test]
+    fn it_reports_every_violation() {
+        let mut mesh = TSpline::new_t_junction();
+        mesh.point_mut(VertID(1)).unwrap().outgoing_edge = EdgeID(0);
+        mesh.edge_mut(EdgeID(5)).unwrap().twin = Some(EdgeID(4));
+        mesh.edge_mut(EdgeID(12)).unwrap().next = EdgeID(99);
+
+        let report = mesh.topology_report();
+
+        assert_eq!(
+            vec![
+                Violation::ForeignOutgoingEdge(VertID(1), EdgeID(0)),
+                Violation::AsymmetricTwin(EdgeID(2)),
+                Violation::AsymmetricTwin(EdgeID(5)),
+                Violation::BrokenLoop(EdgeID(9)),
+                Violation::DanglingNext(EdgeID(12)),
+                Violation::OpenLoop(EdgeID(10)),
+            ],
+            report.violations
+        );
+    }
+
+    #[test]
+    fn it_reports_non_rectangular_faces() {
+        let corners = [(0, 0), (2, 0), (2, 1), (1, 1), (1, 2), (0, 2)];
+        let mut mesh: TSpline = TSpline::default();
+        for (i, (s, t)) in corners.into_iter().enumerate() {
+            mesh.push_point(UVPoint {
+                s,
+                t,
+                outgoing_edge: EdgeID(i),
+            });
+            mesh.push_control_point(Vector4::new(s as f64, t as f64, 0., 1.));
+            mesh.push_edge(HalfEdge {
+                origin: VertID(i),
+                twin: None,
+                next: EdgeID((i + 1) % corners.len()),
+                prev: EdgeID((i + corners.len() - 1) % corners.len()),
+            });
+        }
+
+        assert_eq!(
+            vec![Violation::NonRectangularFace(EdgeID(0))],
+            check_topology(&mesh).violations
+        );
+    }
+}
@@ -0,0 +1,95 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use t_spline::algorithms::subs;
+use t_spline::builder::TMeshBuilder;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, LocalKnots, UVMesh};
+use t_spline::{TSpline, Vector4};
+
+thread_local! {
+    static COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts the allocations of every thread, in a test binary of its own so no other test
+/// runs with it
+struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = COUNT.try_with(|c| c.set(c.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Allocations made by this thread while running `f`
+fn count<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = COUNT.with(Cell::get);
+    let result = f();
+    (result, COUNT.with(Cell::get) - before)
+}
+
+/// A wide face below two narrow faces with a T-junction at (1, 1)
+fn t_junction() -> TSpline {
+    let mut builder = TMeshBuilder::new();
+    let mut vertex =
+        |s: isize, t: isize| builder.vertex(s, t, Vector4::new(s as f64, t as f64, 0., 1.));
+    let [a, b, c, d, e, f, g, h] = [
+        (0, 0),
+        (2, 0),
+        (2, 1),
+        (1, 1),
+        (0, 1),
+        (2, 2),
+        (1, 2),
+        (0, 2),
+    ]
+    .map(|(s, t)| vertex(s, t));
+    builder.face(&[a, b, c, e]);
+    builder.face(&[d, c, f, g]);
+    builder.face(&[e, d, g, h]);
+    builder.build().unwrap()
+}
+
+#[test]
+fn it_evaluates_without_allocating() {
+    let mesh = t_junction();
+
+    let (point, allocations) = count(|| {
+        let mut knots = [LocalKnots::default(); 16];
+        let knots = mesh.local_knots_into(Boundary::Clamped, &mut knots)?;
+        subs(mesh.control_points(), (1.5, 0.5), knots)
+    });
+
+    assert_eq!(0, allocations);
+    let knots = mesh.local_knots(Boundary::Clamped);
+    assert!(point.is_some());
+    assert_eq!(subs(mesh.control_points(), (1.5, 0.5), &knots), point);
+    assert!(
+        mesh.local_knots_into(Boundary::Clamped, &mut [LocalKnots::default(); 2])
+            .is_none()
+    );
+}
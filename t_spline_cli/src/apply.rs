@@ -0,0 +1,65 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use t_spline::TSpline;
+use t_spline::uv_mesh::Boundary;
+use t_spline_commands::command::{CommandRegistry, Parameters};
+use t_spline_commands::gallery::shapes;
+use t_spline_commands::tessellate::tessellate_with_normals;
+use t_spline_io::obj_writer::ObjWriter;
+
+const RESOLUTION: usize = 50;
+
+/// `apply <shape> <command> <out.obj> [name=value...]`
+pub fn run(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let shape = args.next().context("missing shape")?;
+    let command = args.next().context("missing command")?;
+    let path = PathBuf::from(args.next().context("missing output file")?);
+    let parameters = Parameters::parse(args)?;
+
+    let mut mesh: TSpline = shapes()?
+        .into_iter()
+        .find_map(|(name, mesh)| (name == shape).then_some(mesh))
+        .with_context(|| format!("unknown shape {shape}"))?;
+
+    CommandRegistry::default()
+        .apply_mut(&mut mesh, &command, &parameters)
+        .with_context(|| format!("failed to apply {command}"))?;
+
+    let samples = tessellate_with_normals(&mesh, RESOLUTION, Boundary::Clamped)?;
+    let points: Vec<_> = samples.iter().map(|s| s.point).collect();
+    let normals: Vec<_> = samples.iter().map(|s| s.normal).collect();
+    let mut file = BufWriter::new(File::create(&path)?);
+    ObjWriter::default()
+        .with_control_surface("Cage", &mesh)?
+        .with_oriented_points("Surface", &points, &normals)?
+        .write(&mut file)?;
+
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+/// `commands`
+pub fn list() -> Result<()> {
+    for name in CommandRegistry::<TSpline>::default().names() {
+        println!("{name}");
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::{Context, Result};
+use std::fs::{File, create_dir_all};
+use std::io::BufWriter;
+use std::path::PathBuf;
+use t_spline::TSpline;
+use t_spline::uv_mesh::Boundary;
+use t_spline_commands::gallery::gallery;
+use t_spline_io::gltf_writer::{GltfWriter, Region};
+use t_spline_io::obj_writer::ObjWriter;
+use t_spline_io::svg_writer::SvgWriter;
+
+const DEFAULT_RESOLUTIONS: [usize; 2] = [10, 50];
+
+/// `gallery <dir> [resolution...]`
+pub fn run(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let dir = PathBuf::from(args.next().context("missing output directory")?);
+    let mut resolutions = args
+        .map(|r| r.parse().with_context(|| format!("invalid resolution {r}")))
+        .collect::<Result<Vec<usize>>>()?;
+    if resolutions.is_empty() {
+        resolutions.extend(DEFAULT_RESOLUTIONS);
+    }
+
+    create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+
+    for entry in gallery::<TSpline>(&resolutions, Boundary::Clamped)? {
+        let path = dir.join(format!("{}_{}.obj", entry.name, entry.resolution));
+        let mut file = BufWriter::new(File::create(&path)?);
+
+        ObjWriter::default()
+            .with_control_surface("Cage", &entry.mesh)?
+            .with_points("Surface", &entry.points)?
+            .write(&mut file)?;
+
+        println!("wrote {}", path.display());
+
+        let path = dir.join(format!("{}_{}.gltf", entry.name, entry.resolution));
+        let mut file = BufWriter::new(File::create(&path)?);
+        GltfWriter::default()
+            .with_regions(
+                "Surface",
+                &[Region {
+                    material: "Surface",
+                    points: &entry.triangles.points,
+                    triangles: &entry.triangles.triangles,
+                }],
+            )?
+            .with_control_cage("Cage", &entry.mesh)?
+            .write(&mut file)?;
+
+        println!("wrote {}", path.display());
+
+        if entry.resolution == resolutions[0] {
+            let path = dir.join(format!("{}.svg", entry.name));
+            let mut file = BufWriter::new(File::create(&path)?);
+            SvgWriter::default()
+                .with_t_mesh(&entry.mesh)?
+                .write(&mut file)?;
+
+            println!("wrote {}", path.display());
+        }
+    }
+
+    Ok(())
+}
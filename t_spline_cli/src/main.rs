@@ -0,0 +1,45 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+mod apply;
+mod gallery;
+mod render;
+
+use anyhow::{Result, bail};
+
+const USAGE: &str = "usage: t-spline <command> [args]
+
+commands:
+  gallery <dir> [resolution...]
+                                 write the built-in shapes as OBJ, glTF and SVG files into <dir>
+  apply <shape> <command> <out.obj> [name=value...]
+                                 apply a command to a built-in shape and write it as OBJ
+  commands                       list the commands available to apply
+  render <shape> <out.png> [width height]
+                                 render a built-in shape into a PNG image without a window";
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("gallery") => gallery::run(args),
+        Some("apply") => apply::run(args),
+        Some("commands") => apply::list(),
+        Some("render") => render::run(args),
+        Some(command) => bail!("unknown command {command}\n\n{USAGE}"),
+        None => bail!("{USAGE}"),
+    }
+}
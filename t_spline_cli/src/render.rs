@@ -0,0 +1,57 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use t_spline::uv_mesh::Boundary;
+use t_spline::{TSpline, Vector3};
+use t_spline_commands::gallery::shapes;
+use t_spline_commands::triangles::triangle_mesh;
+use t_spline_render::renderer::Renderer;
+use t_spline_render::view::View;
+
+/// Triangle grid per face
+const RESOLUTION: usize = 16;
+const DEFAULT_SIZE: (u32, u32) = (800, 600);
+
+/// `render <shape> <out.png> [width height]`
+pub fn run(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let shape = args.next().context("missing shape")?;
+    let path = PathBuf::from(args.next().context("missing output file")?);
+    let mut size = |default: u32| {
+        args.next().map_or(Ok(default), |v| {
+            v.parse().with_context(|| format!("invalid size {v}"))
+        })
+    };
+    let (width, height) = (size(DEFAULT_SIZE.0)?, size(DEFAULT_SIZE.1)?);
+
+    let spline: TSpline = shapes()?
+        .into_iter()
+        .find_map(|(name, mesh)| (name == shape).then_some(mesh))
+        .with_context(|| format!("unknown shape {shape}"))?;
+    let mesh = triangle_mesh(&spline, RESOLUTION, Boundary::Clamped)?;
+    let view = View::fit(&mesh, Vector3::new(1., -1., 1.))
+        .with_context(|| format!("{shape} has no surface"))?;
+
+    let image = Renderer::new()?.render(&mesh, &view, width, height)?;
+    image.write_png(BufWriter::new(File::create(&path)?))?;
+
+    println!("wrote {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,108 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+//! A cloth pinned at two corners falls under gravity. A mass-spring system moves the
+//! control points of a plane every frame through a [DrivenSurface], the final surface
+//! is written to stdout as OBJ.
+
+use std::error::Error;
+use t_spline::uv_mesh::Boundary;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::{Point3, TSpline, Vector3};
+use t_spline_commands::driven::DrivenSurface;
+use t_spline_commands::plane::new_plane;
+use t_spline_io::obj_writer::ObjWriter;
+
+/// Control points along each side
+const N: usize = 10;
+const FRAMES: usize = 240;
+const STEP: f64 = 1. / 60.;
+/// Relaxation passes over the springs per frame
+const ITERATIONS: usize = 20;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mesh: TSpline = new_plane(N, N, 9., 9.)?;
+    let mut surface = DrivenSurface::new(mesh, 40, Boundary::Clamped)?;
+
+    let vertex = |i: usize, j: usize| j * N + i;
+    let mut springs = Vec::new();
+    for j in 0..N {
+        for i in 0..N {
+            if i + 1 < N {
+                springs.push((vertex(i, j), vertex(i + 1, j)));
+            }
+            if j + 1 < N {
+                springs.push((vertex(i, j), vertex(i, j + 1)));
+            }
+        }
+    }
+    let pinned = [vertex(0, N - 1), vertex(N - 1, N - 1)];
+
+    let mut current: Vec<Point3<f64>> = surface.positions().collect();
+    let rest: Vec<f64> = springs
+        .iter()
+        .map(|&(a, b)| length(current[b] - current[a]))
+        .collect();
+    let mut previous = current.clone();
+    let gravity = Vector3::new(0., 0., -9.81);
+
+    let mut evaluated = 0;
+    for _ in 0..FRAMES {
+        // Verlet integration
+        for v in 0..current.len() {
+            if pinned.contains(&v) {
+                continue;
+            }
+            let next = current[v] + (current[v] - previous[v]) + gravity * STEP * STEP;
+            previous[v] = current[v];
+            current[v] = next;
+        }
+
+        for _ in 0..ITERATIONS {
+            for (&(a, b), &rest) in springs.iter().zip(&rest) {
+                let delta = current[b] - current[a];
+                let correction = delta * ((length(delta) - rest) / length(delta) / 2.);
+                if !pinned.contains(&a) {
+                    current[a] += correction;
+                }
+                if !pinned.contains(&b) {
+                    current[b] -= correction;
+                }
+            }
+        }
+
+        evaluated += surface.set_positions(
+            current
+                .iter()
+                .enumerate()
+                .filter(|(v, _)| !pinned.contains(v))
+                .map(|(v, p)| (VertID(v), *p)),
+        )?;
+    }
+    eprintln!("evaluated {evaluated} samples over {FRAMES} frames");
+
+    let points: Vec<_> = surface.points().copied().collect();
+    ObjWriter::default()
+        .with_control_surface("Cloth", surface.mesh())?
+        .with_points("Surface", &points)?
+        .write(&mut std::io::stdout())?;
+    Ok(())
+}
+
+fn length(v: Vector3<f64>) -> f64 {
+    v.dot(&v).sqrt()
+}
@@ -0,0 +1,403 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64};
+use crate::command::{CommandError, Parameters};
+use crate::remesh::TriangleMesh;
+use crate::tessellate::knot_vectors;
+use crate::triangles::TriangulateError;
+use num_traits::ToPrimitive;
+use std::collections::BTreeMap;
+use t_spline::algorithms::{EvalPolicy, SurfaceDerivatives, try_subs_derivatives};
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, LocalKnots};
+use t_spline::{Point3, Vector3};
+
+/// Builds a [TriangleMesh] with [adaptive_tessellate] from parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveTessellate {
+    /// Largest distance allowed between the surface and a cell, in model units
+    pub tolerance: f64,
+    /// Largest angle allowed between the normals of a cell, in radians
+    pub angle: f64,
+    /// Times a face may be halved in either direction
+    pub max_depth: usize,
+    pub boundary: Boundary,
+}
+
+impl AdaptiveTessellate {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            tolerance: parameters.get_or("tolerance", 1e-2)?,
+            angle: parameters.get_or::<f64>("angle", 15.)?.to_radians(),
+            max_depth: parameters.get_or("max_depth", 6)?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+
+    pub fn apply<T: ControlMesh + Sync>(&self, mesh: &T) -> Result<TriangleMesh, TriangulateError> {
+        adaptive_tessellate(mesh, self)
+    }
+}
+
+/// Tessellate `mesh` into triangles that follow its curvature.
+///
+/// Every face starts as one cell of its parametric rectangle. A cell is split into
+/// quarters while the surface at its center or the middle of a side is further than the
+/// tolerance from the chord between its corners, or its normals turn by more than the
+/// angle. Cells are then fanned around their center through every vertex on
+/// their sides, so cells meet their smaller neighbours without cracks.
+pub fn adaptive_tessellate<T: ControlMesh + Sync>(
+    mesh: &T,
+    settings: &AdaptiveTessellate,
+) -> Result<TriangleMesh, TriangulateError> {
+    mesh.validate_control_mesh()?;
+    let scale: i64 = 1 << settings.max_depth.min(30);
+    let faces = mesh.layout().faces;
+    let scaled = |(lo, hi): (isize, isize)| (lo as i64 * scale, hi as i64 * scale);
+    let domain = faces.iter().fold(
+        Cell {
+            s: (i64::MAX, i64::MIN),
+            t: (i64::MAX, i64::MIN),
+        },
+        |domain, rect| {
+            let (s, t) = (scaled(rect.s), scaled(rect.t));
+            Cell {
+                s: (domain.s.0.min(s.0), domain.s.1.max(s.1)),
+                t: (domain.t.0.min(t.0), domain.t.1.max(t.1)),
+            }
+        },
+    );
+    let sampler = Sampler {
+        mesh,
+        knots: knot_vectors(mesh, settings.boundary),
+        scale,
+        domain,
+    };
+
+    let mut leaves = Vec::new();
+    for rect in faces {
+        let cell = Cell {
+            s: scaled(rect.s),
+            t: scaled(rect.t),
+        };
+        subdivide(&sampler, cell, settings, 0, &mut leaves)?;
+    }
+
+    // every corner of a leaf, looked up along s and along t to find the vertices on the
+    // sides of a cell
+    let mut by_s = BTreeMap::new();
+    let mut by_t = BTreeMap::new();
+    let mut result = TriangleMesh::default();
+    for leaf in &leaves {
+        for (s, t) in leaf.corners() {
+            if by_s.contains_key(&(s, t)) {
+                continue;
+            }
+            let Some(point) = sampler.point((s, t))? else {
+                continue;
+            };
+            result.points.push(point);
+            by_s.insert((s, t), result.points.len() - 1);
+            by_t.insert((t, s), result.points.len() - 1);
+        }
+    }
+
+    for leaf in &leaves {
+        if !leaf.corners().iter().all(|st| by_s.contains_key(st)) {
+            // a corner lies outside of the surface
+            continue;
+        }
+        let (s, t) = (leaf.s, leaf.t);
+        let mut ring: Vec<usize> = Vec::new();
+        ring.extend(by_t.range((t.0, s.0)..(t.0, s.1)).map(|(_, &v)| v));
+        ring.extend(by_s.range((s.1, t.0)..(s.1, t.1)).map(|(_, &v)| v));
+        ring.extend(
+            by_t.range((t.1, s.0 + 1)..=(t.1, s.1))
+                .rev()
+                .map(|(_, &v)| v),
+        );
+        ring.extend(
+            by_s.range((s.0, t.0 + 1)..=(s.0, t.1))
+                .rev()
+                .map(|(_, &v)| v),
+        );
+        if ring.len() == 4 {
+            result.triangles.push([ring[0], ring[1], ring[2]]);
+            result.triangles.push([ring[0], ring[2], ring[3]]);
+            continue;
+        }
+        let Some(center) = sampler.point(leaf.center())? else {
+            continue;
+        };
+        result.points.push(center);
+        let center = result.points.len() - 1;
+        for (i, &v) in ring.iter().enumerate() {
+            result
+                .triangles
+                .push([center, v, ring[(i + 1) % ring.len()]]);
+        }
+    }
+    Ok(result)
+}
+
+/// A parametric rectangle in knot units times [Sampler::scale]
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    s: (i64, i64),
+    t: (i64, i64),
+}
+
+impl Cell {
+    /// Corners counter-clockwise from `(s.0, t.0)`
+    fn corners(&self) -> [(i64, i64); 4] {
+        [
+            (self.s.0, self.t.0),
+            (self.s.1, self.t.0),
+            (self.s.1, self.t.1),
+            (self.s.0, self.t.1),
+        ]
+    }
+
+    fn center(&self) -> (i64, i64) {
+        ((self.s.0 + self.s.1) / 2, (self.t.0 + self.t.1) / 2)
+    }
+
+    fn quarters(&self) -> [Cell; 4] {
+        let (s, t) = self.center();
+        [
+            Cell {
+                s: (self.s.0, s),
+                t: (self.t.0, t),
+            },
+            Cell {
+                s: (s, self.s.1),
+                t: (self.t.0, t),
+            },
+            Cell {
+                s: (s, self.s.1),
+                t: (t, self.t.1),
+            },
+            Cell {
+                s: (self.s.0, s),
+                t: (t, self.t.1),
+            },
+        ]
+    }
+}
+
+struct Sampler<'a, T> {
+    mesh: &'a T,
+    knots: Vec<LocalKnots>,
+    /// Cells are halved on a grid this many times finer than the knots
+    scale: i64,
+    /// Bounds of the parametric domain
+    domain: Cell,
+}
+
+impl<T: ControlMesh> Sampler<'_, T> {
+    fn derivatives(
+        &self,
+        (s, t): (i64, i64),
+    ) -> Result<Option<SurfaceDerivatives<f64>>, TriangulateError> {
+        let cast = |v: i64| from_f64::<T::Unit>(v as f64 / self.scale as f64);
+        let Ok(d) = try_subs_derivatives(
+            self.mesh.control_points(),
+            (cast(s)?, cast(t)?),
+            &self.knots,
+            EvalPolicy::Strict,
+        ) else {
+            return Ok(None);
+        };
+        let point = |p: Point3<T::Unit>| -> Option<Point3<f64>> {
+            Some(Point3::new(p.x.to_f64()?, p.y.to_f64()?, p.z.to_f64()?))
+        };
+        let vector = |v: Vector3<T::Unit>| point(v.into()).map(|p| p.coords);
+        match (point(d.point), vector(d.ds), vector(d.dt)) {
+            (Some(point), Some(ds), Some(dt)) => Ok(Some(SurfaceDerivatives { point, ds, dt })),
+            _ => Err(CastError.into()),
+        }
+    }
+
+    /// Normal at `st`, skipping the edge of the domain where one sided derivatives are
+    /// unreliable
+    fn normal(&self, (s, t): (i64, i64), d: &SurfaceDerivatives<f64>) -> Option<Vector3<f64>> {
+        let inside = |v: i64, (lo, hi): (i64, i64)| lo < v && v < hi;
+        if !inside(s, self.domain.s) || !inside(t, self.domain.t) {
+            return None;
+        }
+        let n = d.ds.cross(&d.dt);
+        let length = n.dot(&n).sqrt();
+        (length > f64::EPSILON).then(|| n / length)
+    }
+
+    fn point(&self, st: (i64, i64)) -> Result<Option<Point3<f64>>, TriangulateError> {
+        Ok(self.derivatives(st)?.map(|d| d.point))
+    }
+}
+
+fn subdivide<T: ControlMesh>(
+    sampler: &Sampler<T>,
+    cell: Cell,
+    settings: &AdaptiveTessellate,
+    depth: usize,
+    leaves: &mut Vec<Cell>,
+) -> Result<(), TriangulateError> {
+    if depth < settings.max_depth && !is_flat(sampler, cell, settings)? {
+        for quarter in cell.quarters() {
+            subdivide(sampler, quarter, settings, depth + 1, leaves)?;
+        }
+    } else {
+        leaves.push(cell);
+    }
+    Ok(())
+}
+
+/// True if the surface stays close to the triangles and sides between the corners of
+/// the cell and its normals agree
+fn is_flat<T: ControlMesh>(
+    sampler: &Sampler<T>,
+    cell: Cell,
+    settings: &AdaptiveTessellate,
+) -> Result<bool, TriangulateError> {
+    let corners = cell.corners().map(|st| sampler.derivatives(st));
+    let [Ok(Some(a)), Ok(Some(b)), Ok(Some(c)), Ok(Some(d))] = corners else {
+        // parts of the cell are not covered, only smaller cells can tell where
+        return Ok(false);
+    };
+    let (s, t) = cell.center();
+    let [pa, pb, pc, pd] = [a, b, c, d].map(|d| d.point);
+    let probes = [
+        ((s, t), Chord::Plane(pa, (pc - pa).cross(&(pd - pb)))),
+        ((s, cell.t.0), Chord::Line(pa, pb)),
+        ((cell.s.1, t), Chord::Line(pb, pc)),
+        ((s, cell.t.1), Chord::Line(pc, pd)),
+        ((cell.s.0, t), Chord::Line(pd, pa)),
+    ];
+
+    let mut normals: Vec<Vector3<f64>> = cell
+        .corners()
+        .iter()
+        .zip([a, b, c, d])
+        .filter_map(|(&st, d)| sampler.normal(st, &d))
+        .collect();
+    for (st, chord) in probes {
+        let Some(probe) = sampler.derivatives(st)? else {
+            return Ok(false);
+        };
+        if chord.distance(probe.point) > settings.tolerance {
+            return Ok(false);
+        }
+        normals.extend(sampler.normal(st, &probe));
+    }
+
+    let cos = settings.angle.cos();
+    Ok(normals
+        .iter()
+        .enumerate()
+        .all(|(i, n)| normals[i + 1..].iter().all(|m| n.dot(m) >= cos)))
+}
+
+/// What a cell approximates the surface with between its corners
+enum Chord {
+    /// The side between two corners
+    Line(Point3<f64>, Point3<f64>),
+    /// The plane through a corner along the cross product of the diagonals
+    Plane(Point3<f64>, Vector3<f64>),
+}
+
+impl Chord {
+    fn distance(&self, p: Point3<f64>) -> f64 {
+        let (to, direction) = match *self {
+            Chord::Line(a, b) => (p - a, b - a),
+            Chord::Plane(a, normal) => (p - a, normal),
+        };
+        let length = direction.dot(&direction);
+        if length <= f64::EPSILON {
+            return to.dot(&to).sqrt();
+        }
+        let along = direction * (to.dot(&direction) / length);
+        match self {
+            Chord::Line(..) => (to - along).dot(&(to - along)).sqrt(),
+            Chord::Plane(..) => along.dot(&along).sqrt(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use crate::triangles::tessellate_mesh;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    fn settings() -> AdaptiveTessellate {
+        AdaptiveTessellate::from_parameters(&Parameters::default().with("max_depth", 4)).unwrap()
+    }
+
+    /// Edges of the mesh used by only one triangle
+    fn open_edges(mesh: &TriangleMesh) -> Vec<(usize, usize)> {
+        let mut edges = BTreeMap::new();
+        for t in &mesh.triangles {
+            for i in 0..3 {
+                let (a, b) = (t[i], t[(i + 1) % 3]);
+                *edges.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+            }
+        }
+        edges
+            .into_iter()
+            .filter(|(_, count)| *count == 1)
+            .map(|(edge, _)| edge)
+            .collect()
+    }
+
+    #[test]
+    fn it_keeps_flat_faces_coarse() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+
+        let triangles = settings().apply(&mesh).unwrap();
+
+        assert_eq!(9 * 2, triangles.triangles.len());
+        assert_eq!(16, triangles.points.len());
+    }
+
+    #[test]
+    fn it_refines_curved_regions_without_cracks() {
+        let mut mesh: TSpline = new_plane(6, 6, 5., 5.).unwrap();
+        mesh.control_point_mut(VertID(14)).unwrap().z = 2.;
+
+        let adaptive = settings().apply(&mesh).unwrap();
+        let uniform = tessellate_mesh(&mesh, 16, Boundary::Clamped).unwrap();
+
+        assert!(adaptive.points.len() < uniform.points.len() / 2);
+        assert!(adaptive.triangles.len() > 25 * 2);
+        for (a, b) in open_edges(&adaptive) {
+            let (a, b) = (adaptive.points[a], adaptive.points[b]);
+            let on_border = |v: f64| v.abs() < 1e-9 || (v - 5.).abs() < 1e-9;
+            assert!(
+                (on_border(a.x) && on_border(b.x)) || (on_border(a.y) && on_border(b.y)),
+                "{a:?} {b:?}"
+            );
+        }
+        for t in &adaptive.triangles {
+            let [a, b, c] = t.map(|v| adaptive.points[v]);
+            assert!((b - a).cross(&(c - a)).z > 0., "{a:?} {b:?} {c:?}");
+        }
+    }
+}
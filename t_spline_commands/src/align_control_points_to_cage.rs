@@ -14,6 +14,9 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
+use crate::cast::CastError;
+use crate::command::{Command, CommandError};
+use crate::knot_cache::Influence;
 use num_traits::FromPrimitive;
 use t_spline::control_mesh::ControlMeshMut;
 use t_spline::uv_mesh::ids::VertID;
@@ -25,8 +28,19 @@ pub enum AlignError {
     MissingPoint,
     #[error("missing control point")]
     MissingControlPoint,
-    #[error("failed to cast")]
-    FailedToCast,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+}
+
+/// [align_control_points_to_cage] as a [Command]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlignControlPointsToCage;
+
+impl<T: ControlMeshMut> Command<T> for AlignControlPointsToCage {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        align_control_points_to_cage(mesh).map_err(CommandError::failed)?;
+        Ok(Influence::Geometry)
+    }
 }
 
 pub fn align_control_points_to_cage<T: ControlMeshMut>(mesh: &mut T) -> Result<(), AlignError> {
@@ -37,8 +51,8 @@ pub fn align_control_points_to_cage<T: ControlMeshMut>(mesh: &mut T) -> Result<(
             .control_point_mut(id)
             .ok_or(AlignError::MissingControlPoint)?;
 
-        cp.x = T::Unit::from_isize(p.s).ok_or(AlignError::FailedToCast)?;
-        cp.y = T::Unit::from_isize(p.t).ok_or(AlignError::FailedToCast)?;
+        cp.x = T::Unit::from_isize(p.s).ok_or(CastError)?;
+        cp.y = T::Unit::from_isize(p.t).ok_or(CastError)?;
     }
 
     Ok(())
@@ -0,0 +1,167 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::command::{Command, CommandError};
+use crate::knot_cache::{Influence, two_ring};
+use crate::split_face::{SplitFaceError, insert_line};
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::ValidationError;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::t_junction::TJunction;
+use t_spline::uv_mesh::uv_point::UVCoord;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum AnalysisSuitableError {
+    #[error("no face holds the T-junction at {0:?}")]
+    MissingFace(VertID),
+    #[error("failed to extend the T-junction at {0:?}: {1}")]
+    Split(VertID, SplitFaceError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// [make_analysis_suitable] as a [Command]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MakeAnalysisSuitable;
+
+impl<T: ControlMeshMut> Command<T> for MakeAnalysisSuitable {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        let inserted = make_analysis_suitable(mesh).map_err(CommandError::failed)?;
+        if inserted.is_empty() {
+            return Ok(Influence::Geometry);
+        }
+        Ok(Influence::Local(two_ring(mesh, &inserted)))
+    }
+}
+
+/// Extend T-junctions until no extensions intersect, making the mesh analysis
+/// suitable.
+///
+/// The first junction of an intersecting pair gets its missing edge, which splits the
+/// face it points into. Where the new edge ends on a side a vertex is inserted, which
+/// may be a T-junction itself and is extended in turn if it still intersects. New
+/// control points are interpolated along the cage, callers fit them to the surface if
+/// they need to keep its shape.
+///
+/// Returns the inserted vertices.
+pub fn make_analysis_suitable<T: ControlMeshMut>(
+    mesh: &mut T,
+) -> Result<Vec<VertID>, AnalysisSuitableError> {
+    mesh.validate_control_mesh()?;
+
+    let mut inserted = Vec::new();
+    while let Some(&(junction, _)) = mesh.extension_intersections().first() {
+        let face = junction_face(mesh, &junction)
+            .ok_or(AnalysisSuitableError::MissingFace(junction.vertex))?;
+        let across = junction.axis.opposite();
+        let value = mesh
+            .point(junction.vertex)
+            .ok_or(AnalysisSuitableError::MissingFace(junction.vertex))?
+            .value_in_dir(across);
+        let split = insert_line(mesh, face, across, value)
+            .map_err(|e| AnalysisSuitableError::Split(junction.vertex, e))?;
+        inserted.extend(split.vertices);
+    }
+    Ok(inserted)
+}
+
+/// The edge leaving `junction` in the face its missing edge points into, where the
+/// junction is a straight point of the loop
+pub(crate) fn junction_face<T: ControlMeshMut>(mesh: &T, junction: &TJunction) -> Option<EdgeID> {
+    let v = mesh.point(junction.vertex)?;
+    mesh.edges().iter().enumerate().find_map(|(i, edge)| {
+        if edge.origin != junction.vertex {
+            return None;
+        }
+        let from = mesh.point(mesh.edge(edge.prev)?.origin)?;
+        let to = mesh.point(mesh.next_edge(edge).origin)?;
+        let incoming = ((v.s - from.s).signum(), (v.t - from.t).signum());
+        let outgoing = ((to.s - v.s).signum(), (to.t - v.t).signum());
+        (incoming == outgoing).then_some(EdgeID(i))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::{CommandRegistry, Parameters};
+    use crate::t_junction::t_junction;
+    use t_spline::TSpline;
+    use t_spline::builder::TMeshBuilder;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::UVMesh;
+
+    /// A 5 x 5 grid of unit faces where `[1, 3] x [1, 2]` and `[3, 4] x [1, 3]` are
+    /// single faces, whose T-junction extensions meet at `(2, 2)`
+    fn crossing() -> TSpline {
+        let mut builder = TMeshBuilder::new();
+        let mut ids = Vec::new();
+        for t in 0..=5 {
+            for s in 0..=5 {
+                ids.push(builder.vertex(s, t, t_spline::Vector4::new(s as f64, t as f64, 0., 1.)));
+            }
+        }
+        let corners = |[s0, t0, s1, t1]: [isize; 4]| {
+            [(s0, t0), (s1, t0), (s1, t1), (s0, t1)].map(|(s, t)| ids[(t * 6 + s) as usize])
+        };
+        let merged = [[1, 1, 3, 2], [3, 1, 4, 3]];
+        for t in 0..5 {
+            for s in 0..5 {
+                let inside = |[s0, t0, s1, t1]: &[isize; 4]| {
+                    (*s0..*s1).contains(&s) && (*t0..*t1).contains(&t)
+                };
+                if !merged.iter().any(inside) {
+                    builder.face(&corners([s, t, s + 1, t + 1]));
+                }
+            }
+        }
+        for rect in merged {
+            builder.face(&corners(rect));
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn it_resolves_intersecting_extensions() {
+        let mut mesh = crossing();
+        assert!(!mesh.is_analysis_suitable());
+        let points = mesh.points().len();
+
+        let inserted = make_analysis_suitable(&mut mesh).unwrap();
+
+        assert!(mesh.is_analysis_suitable());
+        mesh.validate_control_mesh().unwrap();
+        assert_eq!(mesh.points().len(), points + inserted.len());
+        // one edge is enough, it ends on existing vertices
+        assert!(inserted.is_empty());
+        assert!(mesh.t_junctions().len() < crossing().t_junctions().len());
+    }
+
+    #[test]
+    fn it_leaves_suitable_meshes_alone() {
+        let mut mesh: TSpline = t_junction();
+        let edges = mesh.edges().to_vec();
+
+        let registry = CommandRegistry::default();
+        let command = registry
+            .create("make_analysis_suitable", &Parameters::default())
+            .unwrap();
+        assert_eq!(Influence::Geometry, command.apply_mut(&mut mesh).unwrap());
+        assert_eq!(edges, mesh.edges());
+    }
+}
@@ -0,0 +1,207 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::tessellate::knot_vectors;
+use std::collections::BTreeMap;
+use t_spline::Point3;
+use t_spline::algorithms::subs;
+use t_spline::bounds::{Bounded, Bounds};
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum AnchorError {
+    #[error("unknown anchor {0}")]
+    UnknownAnchor(String),
+    #[error("anchor {0} is outside the surface")]
+    OutsideSurface(String),
+    #[error("mesh has no parametric area")]
+    EmptyDomain,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// Named datum points on a surface, for measurements and assembly constraints.
+///
+/// Anchors are kept relative to the bounds of the parametric domain, so they stay on
+/// the same point of the surface when [refine](crate::displace::refine) scales the knot
+/// values or knot lines are inserted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Anchors {
+    anchors: BTreeMap<String, (f64, f64)>,
+}
+
+impl Anchors {
+    /// Place `name` at the parameter `st` of `mesh`, replacing an anchor of that name
+    pub fn insert<T: ControlMesh>(
+        &mut self,
+        mesh: &T,
+        name: impl Into<String>,
+        (s, t): (f64, f64),
+    ) -> Result<(), AnchorError> {
+        let Bounds {
+            s: (s0, s1),
+            t: (t0, t1),
+        } = domain(mesh)?;
+        self.anchors
+            .insert(name.into(), ((s - s0) / (s1 - s0), (t - t0) / (t1 - t0)));
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.anchors.remove(name).is_some()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.anchors.keys().map(String::as_str)
+    }
+
+    /// The parameter of `name` on `mesh`
+    pub fn parameter<T: ControlMesh>(
+        &self,
+        mesh: &T,
+        name: &str,
+    ) -> Result<(f64, f64), AnchorError> {
+        let &(u, v) = self
+            .anchors
+            .get(name)
+            .ok_or_else(|| AnchorError::UnknownAnchor(name.to_string()))?;
+        let Bounds {
+            s: (s0, s1),
+            t: (t0, t1),
+        } = domain(mesh)?;
+        Ok((s0 + u * (s1 - s0), t0 + v * (t1 - t0)))
+    }
+
+    /// The point of the surface at `name`
+    pub fn position<T: ControlMesh + Sync>(
+        &self,
+        mesh: &T,
+        name: &str,
+        boundary: Boundary,
+    ) -> Result<Point3<f64>, AnchorError> {
+        self.positions(mesh, boundary)?
+            .remove(name)
+            .ok_or_else(|| AnchorError::UnknownAnchor(name.to_string()))
+    }
+
+    /// The points of the surface at every anchor, by name
+    pub fn positions<T: ControlMesh + Sync>(
+        &self,
+        mesh: &T,
+        boundary: Boundary,
+    ) -> Result<BTreeMap<&str, Point3<f64>>, AnchorError> {
+        mesh.validate_control_mesh()?;
+        let knot_cache = knot_vectors(mesh, boundary);
+        let cast = from_f64::<T::Unit>;
+
+        self.names()
+            .map(|name| {
+                let (s, t) = self.parameter(mesh, name)?;
+                let p = subs(mesh.control_points(), (cast(s)?, cast(t)?), &knot_cache)
+                    .ok_or_else(|| AnchorError::OutsideSurface(name.to_string()))?;
+                Ok((name, Point3::new(to_f64(p.x)?, to_f64(p.y)?, to_f64(p.z)?)))
+            })
+            .collect()
+    }
+
+    /// The straight distance between two anchors on the surface
+    pub fn distance<T: ControlMesh + Sync>(
+        &self,
+        mesh: &T,
+        a: &str,
+        b: &str,
+        boundary: Boundary,
+    ) -> Result<f64, AnchorError> {
+        let positions = self.positions(mesh, boundary)?;
+        let get = |name: &str| {
+            positions
+                .get(name)
+                .copied()
+                .ok_or_else(|| AnchorError::UnknownAnchor(name.to_string()))
+        };
+        let d = get(b)? - get(a)?;
+        Ok(d.dot(&d).sqrt())
+    }
+}
+
+fn domain<T: ControlMesh>(mesh: &T) -> Result<Bounds<f64>, AnchorError> {
+    let bounds = mesh.bounds().ok_or(AnchorError::EmptyDomain)?;
+    let (s, t) = (
+        (to_f64(bounds.s.0)?, to_f64(bounds.s.1)?),
+        (to_f64(bounds.t.0)?, to_f64(bounds.t.1)?),
+    );
+    if s.1 <= s.0 || t.1 <= t.0 {
+        return Err(AnchorError::EmptyDomain);
+    }
+    Ok(Bounds { s, t })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::displace::refine;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::uv_mesh::ids::VertID;
+    use t_spline::uv_mesh::{UVMesh, UVMeshMut};
+
+    #[test]
+    fn it_keeps_anchors_through_refinement() {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let mut anchors = Anchors::default();
+        anchors.insert(&mesh, "corner", (0., 0.)).unwrap();
+        anchors.insert(&mesh, "inside", (1.5, 2.)).unwrap();
+        let before = anchors.positions(&mesh, Boundary::Clamped).unwrap();
+
+        // scaling the knots, as refinement does first, leaves the surface in place
+        let mut scaled = mesh.clone();
+        for i in 0..scaled.points().len() {
+            let p = scaled.point_mut(VertID(i)).unwrap();
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+        }
+        let after = anchors.positions(&scaled, Boundary::Clamped).unwrap();
+        for name in anchors.names() {
+            assert!((before[name] - after[name]).abs().max() < 1e-9, "{name}");
+        }
+        let distance = anchors
+            .distance(&scaled, "corner", "inside", Boundary::Clamped)
+            .unwrap();
+        let expected = before["inside"] - before["corner"];
+        assert!((distance - expected.dot(&expected).sqrt()).abs() < 1e-9);
+
+        refine(&mut mesh, Boundary::Clamped).unwrap();
+        assert_eq!((3., 4.), anchors.parameter(&mesh, "inside").unwrap());
+    }
+
+    #[test]
+    fn it_rejects_unknown_anchors() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let mut anchors = Anchors::default();
+        anchors.insert(&mesh, "a", (1., 1.)).unwrap();
+
+        assert!(anchors.remove("a"));
+        assert!(matches!(
+            anchors.position(&mesh, "a", Boundary::Clamped),
+            Err(AnchorError::UnknownAnchor(_))
+        ));
+    }
+}
@@ -0,0 +1,216 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64};
+use crate::remesh::TriangleMesh;
+use crate::triangles::{TriangulateError, triangle_mesh};
+use num_traits::ToPrimitive;
+use t_spline::Point3;
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::Boundary;
+use t_spline::uv_mesh::ids::VertID;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum AnimationError {
+    #[error("animation has no keyframes")]
+    Empty,
+    #[error("keyframe has {got} control points, expected {expected}")]
+    CountMismatch { expected: usize, got: usize },
+    #[error("frame rate must be positive")]
+    InvalidFrameRate,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("failed to triangulate frame: {0}")]
+    Triangulate(#[from] TriangulateError),
+}
+
+/// A frame of a baked [Animation]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    /// Time in seconds
+    pub time: f64,
+    pub mesh: TriangleMesh,
+}
+
+/// Control point positions of a cage keyed over time, the T-mesh stays the same.
+///
+/// Positions are interpolated linearly between keyframes and held before the first and
+/// after the last one.
+#[derive(Debug, Clone, Default)]
+pub struct Animation {
+    keys: Vec<(f64, Vec<Point3<f64>>)>,
+}
+
+impl Animation {
+    /// Key the control points of `mesh` at `time` seconds, replacing any key at that time
+    pub fn with_key(mut self, time: f64, mesh: &impl ControlMesh) -> Result<Self, AnimationError> {
+        let positions = mesh
+            .control_points()
+            .iter()
+            .map(|cp| Some(Point3::new(cp.x.to_f64()?, cp.y.to_f64()?, cp.z.to_f64()?)))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(CastError)?;
+        if let Some((_, first)) = self.keys.first()
+            && first.len() != positions.len()
+        {
+            return Err(AnimationError::CountMismatch {
+                expected: first.len(),
+                got: positions.len(),
+            });
+        }
+
+        let at = self.keys.partition_point(|(t, _)| *t < time);
+        if self.keys.get(at).is_some_and(|(t, _)| *t == time) {
+            self.keys[at].1 = positions;
+        } else {
+            self.keys.insert(at, (time, positions));
+        }
+        Ok(self)
+    }
+
+    /// Times of the first and last keyframe
+    pub fn range(&self) -> Option<(f64, f64)> {
+        Some((self.keys.first()?.0, self.keys.last()?.0))
+    }
+
+    /// Control point positions at `time` seconds
+    pub fn positions_at(&self, time: f64) -> Option<Vec<Point3<f64>>> {
+        let next = self.keys.partition_point(|(t, _)| *t <= time);
+        match (
+            next.checked_sub(1).map(|i| &self.keys[i]),
+            self.keys.get(next),
+        ) {
+            (Some((t0, a)), Some((t1, b))) => {
+                let f = (time - t0) / (t1 - t0);
+                Some(a.iter().zip(b).map(|(a, b)| a + (b - a) * f).collect())
+            }
+            (Some((_, a)), None) | (None, Some((_, a))) => Some(a.clone()),
+            (None, None) => None,
+        }
+    }
+
+    /// Triangulate the surface of `mesh` posed at every frame from the first to the last
+    /// keyframe at `fps` frames per second.
+    ///
+    /// Every frame has the same triangles, only the points move, so frames can be
+    /// written as a sequence of meshes or as morph targets of the first frame.
+    pub fn bake<T: ControlMeshMut + Clone + Sync>(
+        &self,
+        mesh: &T,
+        fps: f64,
+        resolution: usize,
+        boundary: Boundary,
+    ) -> Result<Vec<Frame>, AnimationError> {
+        if fps.is_nan() || fps <= 0. {
+            return Err(AnimationError::InvalidFrameRate);
+        }
+        let (start, end) = self.range().ok_or(AnimationError::Empty)?;
+        let expected = self.keys[0].1.len();
+        if mesh.control_points().len() != expected {
+            return Err(AnimationError::CountMismatch {
+                expected,
+                got: mesh.control_points().len(),
+            });
+        }
+
+        let count = ((end - start) * fps).floor() as usize + 1;
+        let mut frames = Vec::with_capacity(count);
+        let mut posed = mesh.clone();
+        for i in 0..count {
+            let time = start + i as f64 / fps;
+            let positions = self.positions_at(time).ok_or(AnimationError::Empty)?;
+            for (v, p) in positions.iter().enumerate() {
+                let cast = from_f64::<T::Unit>;
+                let cp = posed.control_point_mut(VertID(v)).ok_or(CastError)?;
+                (cp.x, cp.y, cp.z) = (cast(p.x)?, cast(p.y)?, cast(p.z)?);
+            }
+            frames.push(Frame {
+                time,
+                mesh: triangle_mesh(&posed, resolution, boundary)?,
+            });
+        }
+        Ok(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+
+    fn lifted(z: f64) -> TSpline {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        mesh.control_point_mut(VertID(5)).unwrap().z = z;
+        mesh
+    }
+
+    #[test]
+    fn it_interpolates_between_keys() {
+        let animation = Animation::default()
+            .with_key(1., &lifted(2.))
+            .unwrap()
+            .with_key(0., &lifted(0.))
+            .unwrap();
+
+        assert_eq!(Some((0., 1.)), animation.range());
+        assert_eq!(0.5, animation.positions_at(0.25).unwrap()[5].z);
+        assert_eq!(0., animation.positions_at(-1.).unwrap()[5].z);
+        assert_eq!(2., animation.positions_at(3.).unwrap()[5].z);
+    }
+
+    #[test]
+    fn it_bakes_frames_with_the_same_triangles() {
+        let animation = Animation::default()
+            .with_key(0., &lifted(0.))
+            .unwrap()
+            .with_key(1., &lifted(2.))
+            .unwrap();
+
+        let frames = animation
+            .bake(&lifted(0.), 4., 2, Boundary::Clamped)
+            .unwrap();
+
+        assert_eq!(
+            vec![0., 0.25, 0.5, 0.75, 1.],
+            frames.iter().map(|f| f.time).collect::<Vec<_>>()
+        );
+        assert!(
+            frames
+                .iter()
+                .all(|f| f.mesh.triangles == frames[0].mesh.triangles)
+        );
+        assert_ne!(frames[0].mesh.points, frames[4].mesh.points);
+    }
+
+    #[test]
+    fn it_rejects_keys_of_other_cages() {
+        let other: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+
+        assert!(matches!(
+            Animation::default()
+                .with_key(0., &lifted(0.))
+                .unwrap()
+                .with_key(1., &other),
+            Err(AnimationError::CountMismatch {
+                expected: 16,
+                got: 9
+            })
+        ));
+    }
+}
@@ -0,0 +1,300 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, to_f64};
+use crate::command::{CommandError, Parameters};
+use crate::tessellate::knot_vectors;
+use std::collections::BTreeSet;
+use t_spline::Vector4;
+use t_spline::algorithms::cubic_basis_function;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::{Boundary, KnotVector, LocalKnots, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum BezierError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// [bezier_extract] with the optional `boundary` parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BezierExtract {
+    pub boundary: Boundary,
+}
+
+impl BezierExtract {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            boundary: parameters.get_boundary()?,
+        })
+    }
+
+    pub fn apply<T: ControlMesh + Sync>(
+        &self,
+        mesh: &T,
+    ) -> Result<Vec<BezierElement>, BezierError> {
+        bezier_extract(mesh, self.boundary)
+    }
+}
+
+/// A part of the surface that is a single rational bicubic Bézier patch.
+///
+/// Bernstein indices run `i + 4 * j` with `i` along `s` and `j` along `t`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BezierElement {
+    /// The face of the T-mesh the element lies in
+    pub face: EdgeID,
+    /// Parametric extent along `s`
+    pub s: (isize, isize),
+    /// Parametric extent along `t`
+    pub t: (isize, isize),
+    /// The blending functions that are not zero on the element, by their vertex
+    pub functions: Vec<VertID>,
+    /// The extraction operator, one row of Bernstein coefficients per function
+    pub extraction: Vec<[f64; 16]>,
+    /// Control points of the patch as `(x, y, z, w)`, like the control points of the mesh
+    pub control_points: [Vector4<f64>; 16],
+}
+
+/// Decompose `mesh` into rational bicubic Bézier patches, the element extraction used
+/// in isogeometric analysis.
+///
+/// A face is split further wherever a knot of a blending function supported on it
+/// lies inside, so every function is a single polynomial on each element. The patch
+/// control points are the weighted control points of the mesh combined by the
+/// extraction rows and projected back by their weight.
+pub fn bezier_extract<T: ControlMesh + Sync>(
+    mesh: &T,
+    boundary: Boundary,
+) -> Result<Vec<BezierElement>, BezierError> {
+    mesh.validate_control_mesh()?;
+    let knot_cache = knot_vectors(mesh, boundary);
+    let control_points = homogeneous(mesh)?;
+
+    let mut elements = Vec::new();
+    for rect in mesh.layout().faces {
+        let supported = supported(&knot_cache, rect.s, rect.t);
+        let breaks = |range: (isize, isize), knots: &dyn Fn(usize) -> KnotVector| {
+            let mut values: BTreeSet<isize> = [range.0, range.1].into();
+            for &v in &supported {
+                values.extend(
+                    knots(v)
+                        .into_iter()
+                        .filter(|k| range.0 < *k && *k < range.1),
+                );
+            }
+            values.into_iter().collect::<Vec<_>>()
+        };
+        let s_breaks = breaks(rect.s, &|v| knot_cache[v].s_knots);
+        let t_breaks = breaks(rect.t, &|v| knot_cache[v].t_knots);
+
+        for t in t_breaks.windows(2) {
+            for s in s_breaks.windows(2) {
+                elements.push(element(
+                    rect.face,
+                    &knot_cache,
+                    &control_points,
+                    &supported,
+                    (s[0], s[1]),
+                    (t[0], t[1]),
+                ));
+            }
+        }
+    }
+    Ok(elements)
+}
+
+/// The control points of `mesh` multiplied by their weight
+pub(crate) fn homogeneous<T: ControlMesh>(mesh: &T) -> Result<Vec<Vector4<f64>>, BezierError> {
+    mesh.control_points()
+        .iter()
+        .map(|cp| {
+            let cast = to_f64::<T::Unit>;
+            let w = cast(cp.w)?;
+            Ok(Vector4::new(
+                cast(cp.x)? * w,
+                cast(cp.y)? * w,
+                cast(cp.z)? * w,
+                w,
+            ))
+        })
+        .collect()
+}
+
+fn overlaps(knots: &KnotVector, (lo, hi): (isize, isize)) -> bool {
+    knots[0] < hi && knots[4] > lo
+}
+
+/// The blending functions that are not zero somewhere on the rectangle `s` x `t`
+pub(crate) fn supported(
+    knot_cache: &[LocalKnots],
+    s: (isize, isize),
+    t: (isize, isize),
+) -> Vec<usize> {
+    (0..knot_cache.len())
+        .filter(|&v| overlaps(&knot_cache[v].s_knots, s) && overlaps(&knot_cache[v].t_knots, t))
+        .collect()
+}
+
+/// The Bézier element over `s` x `t` from the `candidates` functions, where no knot of
+/// them lies inside the rectangle
+pub(crate) fn element(
+    face: EdgeID,
+    knot_cache: &[LocalKnots],
+    homogeneous: &[Vector4<f64>],
+    candidates: &[usize],
+    s: (isize, isize),
+    t: (isize, isize),
+) -> BezierElement {
+    let mut element = BezierElement {
+        face,
+        s,
+        t,
+        functions: Vec::new(),
+        extraction: Vec::new(),
+        control_points: [Vector4::zeros(); 16],
+    };
+    let mut weighted = [Vector4::zeros(); 16];
+    for &v in candidates {
+        let knots = &knot_cache[v];
+        if !overlaps(&knots.s_knots, s) || !overlaps(&knots.t_knots, t) {
+            continue;
+        }
+        let (bs, bt) = (bernstein(&knots.s_knots, s), bernstein(&knots.t_knots, t));
+        let row: [f64; 16] = core::array::from_fn(|k| bs[k % 4] * bt[k / 4]);
+        for (w, c) in weighted.iter_mut().zip(row) {
+            *w += homogeneous[v] * c;
+        }
+        element.functions.push(VertID(v));
+        element.extraction.push(row);
+    }
+    element.control_points = weighted.map(|p| {
+        if p.w == 0. {
+            p
+        } else {
+            Vector4::new(p.x / p.w, p.y / p.w, p.z / p.w, p.w)
+        }
+    });
+    element
+}
+
+/// Bernstein coefficients of the cubic B-spline over `knots` on the span `range`,
+/// where it is a single polynomial.
+///
+/// The function is sampled inside the span, away from knots where the half open
+/// evaluation would jump, and the samples are interpolated by a cubic in Bernstein
+/// form.
+fn bernstein(knots: &KnotVector, (lo, hi): (isize, isize)) -> [f64; 4] {
+    const AT: [f64; 4] = [0.125, 0.375, 0.625, 0.875];
+    let values = AT.map(|x| cubic_basis_function(lo as f64 + x * (hi - lo) as f64, knots));
+
+    // collocation matrix of the Bernstein polynomials at the samples
+    let mut m: [[f64; 5]; 4] = core::array::from_fn(|k| {
+        let x = AT[k];
+        let y = 1. - x;
+        [
+            y * y * y,
+            3. * x * y * y,
+            3. * x * x * y,
+            x * x * x,
+            values[k],
+        ]
+    });
+    for col in 0..4 {
+        let pivot = (col..4)
+            .max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))
+            .unwrap_or(col);
+        m.swap(col, pivot);
+        let pivot = m[col];
+        for (row, r) in m.iter_mut().enumerate() {
+            if row != col {
+                let f = r[col] / pivot[col];
+                for (x, p) in r.iter_mut().zip(pivot).skip(col) {
+                    *x -= f * p;
+                }
+            }
+        }
+    }
+    core::array::from_fn(|i| m[i][4] / m[i][i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::t_junction::t_junction;
+    use t_spline::TSpline;
+    use t_spline::algorithms::subs;
+    use t_spline::control_mesh::ControlMeshMut;
+
+    /// Evaluate a rational Bézier patch at `(u, v)` in `[0, 1]²`
+    fn evaluate(element: &BezierElement, u: f64, v: f64) -> [f64; 3] {
+        let b = |x: f64| {
+            let y = 1. - x;
+            [y * y * y, 3. * x * y * y, 3. * x * x * y, x * x * x]
+        };
+        let (bu, bv) = (b(u), b(v));
+        let mut sum = Vector4::zeros();
+        for (k, p) in element.control_points.iter().enumerate() {
+            let homogeneous = Vector4::new(p.x * p.w, p.y * p.w, p.z * p.w, p.w);
+            sum += homogeneous * bu[k % 4] * bv[k / 4];
+        }
+        [sum.x / sum.w, sum.y / sum.w, sum.z / sum.w]
+    }
+
+    #[test]
+    fn it_reproduces_the_surface() {
+        let mut mesh: TSpline = t_junction();
+        mesh.control_point_mut(VertID(3)).unwrap().z = 1.;
+        mesh.control_point_mut(VertID(5)).unwrap().w = 2.;
+        let knots = knot_vectors(&mesh, Boundary::Clamped);
+
+        let elements = BezierExtract::from_parameters(&Parameters::default())
+            .unwrap()
+            .apply(&mesh)
+            .unwrap();
+
+        assert!(elements.len() >= 3);
+        for element in &elements {
+            assert_eq!(element.functions.len(), element.extraction.len());
+            for (u, v) in [(0.2, 0.3), (0.5, 0.5), (0.9, 0.1)] {
+                let st = (
+                    element.s.0 as f64 + u * (element.s.1 - element.s.0) as f64,
+                    element.t.0 as f64 + v * (element.t.1 - element.t.0) as f64,
+                );
+                let expected = subs(mesh.control_points(), st, &knots).unwrap();
+                let actual = evaluate(element, u, v);
+                for (a, e) in actual.iter().zip(expected.iter()) {
+                    assert!((a - e).abs() < 1e-9, "{actual:?} {expected}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_converts_a_b_spline_span_to_bernstein() {
+        // the uniform cubic B-spline on its second span
+        let coefficients = bernstein(&[0, 1, 2, 3, 4], (1, 2));
+        let expected = [1. / 6., 1. / 3., 2. / 3., 2. / 3.];
+        for (c, e) in coefficients.iter().zip(expected) {
+            assert!((c - e).abs() < 1e-12, "{coefficients:?}");
+        }
+    }
+}
@@ -0,0 +1,194 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, to_f64};
+use crate::fit::closest_on_triangle;
+use crate::tessellate::knot_vectors;
+use num_traits::FromPrimitive;
+use rayon::prelude::*;
+use t_spline::algorithms::{EvalPolicy, try_subs};
+use t_spline::bounds::Bounded;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::ids::EdgeID;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum CageError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// A control point and where the surface is at the parameter of its vertex
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CageProjection {
+    pub cage: Point3<f64>,
+    /// `None` where the surface can not be evaluated
+    pub limit: Option<Point3<f64>>,
+}
+
+/// A point of the surface and the closest face of the control cage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CageSample {
+    pub st: (f64, f64),
+    pub point: Point3<f64>,
+    /// The half edge of the closest face, as in the parametric layout
+    pub face: EdgeID,
+    pub distance: f64,
+}
+
+/// The result of [evaluate_with_cage]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CageEvaluation {
+    /// One projection per control point, by vertex
+    pub control_points: Vec<CageProjection>,
+    pub samples: Vec<CageSample>,
+}
+
+/// Evaluate the control cage and the surface of `mesh` together, for snapping in
+/// editors and for measuring how far the surface is from its cage.
+///
+/// Every control point is paired with the surface point at its vertex, and every point
+/// of a `resolution` x `resolution` grid over the parametric bounds with the closest
+/// face of the cage, whose loop is fanned into triangles. Samples outside of the faces
+/// are left out like in [tessellate](crate::tessellate::tessellate).
+pub fn evaluate_with_cage<T: ControlMesh + Sync>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<CageEvaluation, CageError> {
+    mesh.validate_control_mesh()?;
+    let knot_cache = knot_vectors(mesh, boundary);
+    let point = |p: Point3<T::Unit>| {
+        Ok::<_, CageError>(Point3::new(to_f64(p.x)?, to_f64(p.y)?, to_f64(p.z)?))
+    };
+
+    let control_points = mesh
+        .points()
+        .par_iter()
+        .zip(mesh.control_points())
+        .map(|(uv, cp)| {
+            let st = (uv.s, uv.t);
+            let limit = match (T::Unit::from_isize(st.0), T::Unit::from_isize(st.1)) {
+                (Some(s), Some(t)) => try_subs(
+                    mesh.control_points(),
+                    (s, t),
+                    &knot_cache,
+                    EvalPolicy::Strict,
+                )
+                .ok(),
+                _ => return Err(CageError::from(CastError)),
+            };
+            Ok(CageProjection {
+                cage: Point3::new(to_f64(cp.x)?, to_f64(cp.y)?, to_f64(cp.z)?),
+                limit: limit.map(point).transpose()?,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut triangles = Vec::new();
+    for rect in mesh.layout().faces {
+        let Some(edge) = mesh.edge(rect.face) else {
+            continue;
+        };
+        let corners = mesh
+            .edge_loop(edge)
+            .map(|(_, e)| {
+                let cp = &mesh.control_points()[e.origin.0];
+                Ok(Vector3::new(to_f64(cp.x)?, to_f64(cp.y)?, to_f64(cp.z)?))
+            })
+            .collect::<Result<Vec<_>, CageError>>()?;
+        for pair in corners[1..].windows(2) {
+            triangles.push((rect.face, [corners[0], pair[0], pair[1]]));
+        }
+    }
+
+    let bounds = mesh.bounds().ok_or(ValidationError::EmptyDomain())?;
+    let samples = (0..resolution * resolution)
+        .into_par_iter()
+        .filter_map(|i| {
+            let st = bounds.interpolate(i, resolution);
+            if !mesh.contains_uv(st) {
+                return None;
+            }
+            let p = try_subs(mesh.control_points(), st, &knot_cache, EvalPolicy::Strict).ok()?;
+            Some((st, p))
+        })
+        .map(|(st, p)| {
+            let p = point(p)?;
+            let (face, distance) = triangles
+                .iter()
+                .map(|(face, [a, b, c])| {
+                    let d = closest_on_triangle(p.coords, *a, *b, *c) - p.coords;
+                    (*face, d.dot(&d))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(face, d)| (face, d.sqrt()))
+                .unwrap_or((EdgeID(0), f64::INFINITY));
+            Ok::<_, CageError>(CageSample {
+                st: (to_f64(st.0)?, to_f64(st.1)?),
+                point: p,
+                face,
+                distance,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(CageEvaluation {
+        control_points,
+        samples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::UVMesh;
+    use t_spline::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_pairs_control_points_with_the_surface() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        mesh.control_point_mut(VertID(12)).unwrap().z = 1.;
+
+        let evaluation = evaluate_with_cage(&mesh, 9, Boundary::Clamped).unwrap();
+
+        assert_eq!(25, evaluation.control_points.len());
+        let bump = evaluation.control_points[12];
+        assert_eq!(Point3::new(2., 2., 1.), bump.cage);
+        let limit = bump.limit.unwrap();
+        assert!(limit.z > 0. && limit.z < 1., "{limit:?}");
+        // the flat corner lies on its own control point
+        let corner = evaluation.control_points[0];
+        assert_eq!(Some(corner.cage), corner.limit);
+
+        assert_eq!(81, evaluation.samples.len());
+        let faces: Vec<_> = mesh.layout().faces.iter().map(|r| r.face).collect();
+        for sample in &evaluation.samples {
+            assert!(faces.contains(&sample.face));
+            assert!(sample.distance < 1., "{sample:?}");
+        }
+        // far from the bump the surface lies on the flat part of the cage
+        assert!(evaluation.samples[0].distance < 1e-12);
+    }
+}
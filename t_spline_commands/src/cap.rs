@@ -0,0 +1,242 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::command::{CommandError, Parameters};
+use crate::plane::{GridSide, PlaneError, grid_rows, new_plane};
+use crate::project_curve::{ProjectError, project_polyline};
+use crate::tessellate::knot_vectors;
+use crate::trim::TrimmedSpline;
+use num_traits::{One, ToPrimitive};
+use t_spline::algorithms::subs;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+/// Control points per side of a cap patch
+const CAP_POINTS: usize = 4;
+
+#[derive(Clone, Debug, Error)]
+pub enum CapError {
+    #[error("the side of the tube is not a closed loop")]
+    NotClosed,
+    #[error("the side of the tube is not flat")]
+    NotFlat,
+    #[error("the surface can not be evaluated along the side")]
+    Undefined,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error(transparent)]
+    Plane(#[from] PlaneError),
+    #[error("failed to place the loop on the cap: {0}")]
+    Project(#[from] ProjectError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// Close an open end of a tube, a grid shaped spline whose first and last control
+/// points coincide along one direction, with a flat patch trimmed to the end.
+///
+/// The T-mesh has no star points, so the cap is a separate trimmed patch that faces
+/// away from the tube. Built from the `side` (`s_min`, `s_max`, `t_min` or `t_max`) and
+/// optional `resolution` and `boundary` parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cap {
+    pub side: GridSide,
+    /// Points of the trim loop around the cap
+    pub resolution: usize,
+    pub boundary: Boundary,
+}
+
+impl Cap {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            side: parameters.get_with("side", |v| match v {
+                "s_min" => Some(GridSide::SMin),
+                "s_max" => Some(GridSide::SMax),
+                "t_min" => Some(GridSide::TMin),
+                "t_max" => Some(GridSide::TMax),
+                _ => None,
+            })?,
+            resolution: parameters.get_or("resolution", 32)?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+
+    pub fn apply<T: ControlMeshMut + Default + Sync>(
+        &self,
+        tube: &T,
+    ) -> Result<TrimmedSpline<T>, CapError> {
+        tube.validate_control_mesh()?;
+        let rows = grid_rows(tube, self.side)?;
+        let position = |v: VertID| {
+            let cp = tube.control_point(v).ok_or(CapError::Undefined)?;
+            point(cp.x, cp.y, cp.z)
+        };
+
+        // the loop around the end, sampled on the surface
+        let edge = tube.point(rows.boundary[0]).ok_or(CapError::Undefined)?;
+        let fixed = match self.side {
+            GridSide::SMin | GridSide::SMax => edge.s,
+            GridSide::TMin | GridSide::TMax => edge.t,
+        } as f64;
+        let (first, last) = (
+            rows.knots[0] as f64,
+            rows.knots[rows.knots.len() - 1] as f64,
+        );
+        let knots = knot_vectors(tube, self.boundary);
+        let count = self.resolution.max(3);
+        let mut ring = Vec::with_capacity(count + 1);
+        for i in 0..=count {
+            let along = first + (last - first) * i as f64 / count as f64;
+            let (s, t) = match self.side {
+                GridSide::SMin | GridSide::SMax => (fixed, along),
+                GridSide::TMin | GridSide::TMax => (along, fixed),
+            };
+            let st = (from_f64::<T::Unit>(s)?, from_f64::<T::Unit>(t)?);
+            let p = subs(tube.control_points(), st, &knots).ok_or(CapError::Undefined)?;
+            ring.push(point(p.x, p.y, p.z)?);
+        }
+
+        let size = ring
+            .iter()
+            .map(|p| (p - ring[0]).abs().max())
+            .fold(0., f64::max);
+        let tolerance = 1e-9 * size.max(1.);
+        if (ring[count] - ring[0]).abs().max() > tolerance {
+            return Err(CapError::NotClosed);
+        }
+        ring.pop();
+
+        // plane of the loop, facing away from the tube
+        let center =
+            Point3::from(ring.iter().map(|p| p.coords).sum::<Vector3<f64>>() / count as f64);
+        let mut normal = Vector3::zeros();
+        for (i, a) in ring.iter().enumerate() {
+            normal += (a - center).cross(&(ring[(i + 1) % count] - center));
+        }
+        let inner = rows
+            .inner
+            .iter()
+            .map(|&v| position(v).map(|p| p.coords))
+            .collect::<Result<Vec<_>, _>>()?;
+        let inside = inner.iter().sum::<Vector3<f64>>() / inner.len() as f64;
+        if normal.dot(&(center.coords - inside)) < 0. {
+            normal = -normal;
+        }
+        let normal = normal / normal.dot(&normal).sqrt();
+        if ring
+            .iter()
+            .any(|p| (p - center).dot(&normal).abs() > 1e-6 * size.max(1.))
+        {
+            return Err(CapError::NotFlat);
+        }
+
+        // a square patch around the loop in its plane
+        let axis = ring[0] - center;
+        let u = axis / axis.dot(&axis).sqrt();
+        let v = normal.cross(&u);
+        let extent = ring
+            .iter()
+            .map(|p| (p - center).dot(&u).abs().max((p - center).dot(&v).abs()))
+            .fold(0., f64::max)
+            * 1.1;
+        let mut cap: T = new_plane(CAP_POINTS, CAP_POINTS, T::Unit::one(), T::Unit::one())?;
+        for i in 0..cap.control_points().len() {
+            let cp = cap
+                .control_point_mut(VertID(i))
+                .ok_or(CapError::Undefined)?;
+            let (x, y) = (to_f64(cp.x)?, to_f64(cp.y)?);
+            let p = center + u * (2. * x - 1.) * extent + v * (2. * y - 1.) * extent;
+            let cast = from_f64::<T::Unit>;
+            (cp.x, cp.y, cp.z) = (cast(p.x)?, cast(p.y)?, cast(p.z)?);
+        }
+
+        let mut trim = project_polyline(&cap, &ring, 0, self.boundary)?;
+        let area: f64 = (0..trim.len())
+            .map(|i| {
+                let (a, b) = (trim[i], trim[(i + 1) % trim.len()]);
+                a.0 * b.1 - b.0 * a.1
+            })
+            .sum();
+        if area < 0. {
+            trim.reverse();
+        }
+        Ok(TrimmedSpline { mesh: cap, trim })
+    }
+}
+
+fn point<U: ToPrimitive>(x: U, y: U, z: U) -> Result<Point3<f64>, CapError> {
+    Ok(Point3::new(to_f64(x)?, to_f64(y)?, to_f64(z)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::displace::vertex_normals;
+    use crate::plane::new_plane;
+    use std::f64::consts::TAU;
+    use t_spline::TSpline;
+
+    /// A cylinder of radius about one along z, closed in s by a repeated seam column
+    fn tube() -> TSpline {
+        let mut mesh: TSpline = new_plane(9, 3, 1., 1.).unwrap();
+        for j in 0..3 {
+            for i in 0..9 {
+                let angle = TAU * i as f64 / 8.;
+                let cp = mesh.control_point_mut(VertID(j * 9 + i)).unwrap();
+                (cp.x, cp.y, cp.z) = (angle.cos(), angle.sin(), j as f64);
+            }
+        }
+        mesh
+    }
+
+    fn cap(side: GridSide) -> Cap {
+        Cap {
+            side,
+            resolution: 24,
+            boundary: Boundary::Clamped,
+        }
+    }
+
+    #[test]
+    fn it_caps_both_ends_of_a_tube() {
+        for (side, z, facing) in [(GridSide::TMin, 0., -1.), (GridSide::TMax, 2., 1.)] {
+            let capped = cap(side).apply(&tube()).unwrap();
+
+            assert_eq!(24, capped.trim.len());
+            let points = capped.tessellate(12, Boundary::Clamped).unwrap();
+            assert!(points.len() > 24);
+            for p in points {
+                assert!((p.z - z).abs() < 1e-9, "{p:?}");
+                assert!(p.x * p.x + p.y * p.y < 1. + 1e-6, "{p:?}");
+            }
+            let normals = vertex_normals(&capped.mesh, Boundary::Clamped).unwrap();
+            assert!(normals.iter().all(|n| n.z * facing > 0.99), "{normals:?}");
+        }
+    }
+
+    #[test]
+    fn it_rejects_open_sides() {
+        assert!(matches!(
+            cap(GridSide::SMin).apply(&tube()),
+            Err(CapError::NotClosed)
+        ));
+    }
+}
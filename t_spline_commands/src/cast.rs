@@ -0,0 +1,46 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use num_traits::{FromPrimitive, ToPrimitive};
+use thiserror::Error;
+
+/// A value that does not fit the numeric type it is converted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("failed to cast")]
+pub struct CastError;
+
+/// `value` as an `f64`
+pub fn to_f64<U: ToPrimitive>(value: U) -> Result<f64, CastError> {
+    value.to_f64().ok_or(CastError)
+}
+
+/// `value` in the numeric type `U`, usually the unit of a mesh
+pub fn from_f64<U: FromPrimitive>(value: f64) -> Result<U, CastError> {
+    U::from_f64(value).ok_or(CastError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_fails_on_values_out_of_range() {
+        assert_eq!(Ok(2.5), to_f64(2.5f32));
+        assert_eq!(Ok(3u8), from_f64::<u8>(3.));
+        assert_eq!(Err(CastError), from_f64::<u8>(-1.));
+        assert_eq!(Err(CastError), from_f64::<u8>(f64::NAN));
+    }
+}
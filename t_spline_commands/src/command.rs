@@ -0,0 +1,406 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::align_control_points_to_cage::AlignControlPointsToCage;
+use crate::analysis_suitable::MakeAnalysisSuitable;
+use crate::deform::Deform;
+use crate::displace::Displace;
+use crate::edge_slide::EdgeSlide;
+use crate::expression::MapControlPoints;
+use crate::extrude_edge::ExtrudeEdge;
+use crate::fit::AdaptiveFit;
+use crate::insert_knot_line::InsertKnotLine;
+use crate::knot_cache::Influence;
+use crate::merge_faces::MergeFaces;
+use crate::move_control_point::MoveControlPoint;
+use crate::pattern::Pattern;
+use crate::refine_local::{RefineAround, RefineLocal};
+use crate::reparameterize::ReparameterizeArcLength;
+use crate::split_face::SplitFace;
+use std::collections::BTreeMap;
+use std::error::Error as StdError;
+use std::str::FromStr;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::Boundary;
+use t_spline::uv_mesh::direction::Direction;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CommandError {
+    #[error("unknown command {0}")]
+    UnknownCommand(String),
+    #[error("missing parameter {0}")]
+    MissingParameter(String),
+    #[error("invalid value {value:?} for parameter {name}")]
+    InvalidParameter { name: String, value: String },
+    #[error("reference to undefined parameter ${0}")]
+    UndefinedReference(String),
+    #[error(transparent)]
+    Failed(Box<dyn StdError + Send + Sync>),
+}
+
+impl CommandError {
+    pub fn failed(error: impl StdError + Send + Sync + 'static) -> Self {
+        CommandError::Failed(Box::new(error))
+    }
+}
+
+/// An edit that can be applied to any mesh of type `T`
+pub trait Command<T: ControlMeshMut> {
+    /// Apply the edit, returning which knot vectors it may have changed
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError>;
+}
+
+/// Named string parameters used to construct commands, as given on a command line
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Parameters(BTreeMap<String, String>);
+
+impl Parameters {
+    /// Parse `name=value` pairs, a pair without `=` is a parameter without a value
+    pub fn parse<S: AsRef<str>>(pairs: impl IntoIterator<Item = S>) -> Result<Self, CommandError> {
+        let mut parameters = Parameters::default();
+        for pair in pairs {
+            let pair = pair.as_ref();
+            let (name, value) =
+                pair.split_once('=')
+                    .ok_or_else(|| CommandError::InvalidParameter {
+                        name: pair.to_string(),
+                        value: String::new(),
+                    })?;
+            parameters = parameters.with(name, value);
+        }
+        Ok(parameters)
+    }
+
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.0.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    pub fn get<V: FromStr>(&self, name: &str) -> Result<V, CommandError> {
+        self.get_with(name, |v| v.parse().ok())
+    }
+
+    pub fn get_or<V: FromStr>(&self, name: &str, default: V) -> Result<V, CommandError> {
+        if self.contains(name) {
+            self.get(name)
+        } else {
+            Ok(default)
+        }
+    }
+
+    /// A comma separated list of values
+    pub fn get_list<V: FromStr>(&self, name: &str) -> Result<Vec<V>, CommandError> {
+        self.get_with(name, |v| {
+            v.split(',').map(|v| v.trim().parse().ok()).collect()
+        })
+    }
+
+    /// A parametric direction given as `s` or `t`
+    pub fn get_direction(&self, name: &str) -> Result<Direction, CommandError> {
+        self.get_with(name, |v| match v {
+            "s" | "S" => Some(Direction::S),
+            "t" | "T" => Some(Direction::T),
+            _ => None,
+        })
+    }
+
+    /// The `boundary` parameter, clamped if missing. Periodic knot vectors are not
+    /// implemented yet, so `periodic` is rejected like any other unknown value.
+    pub fn get_boundary(&self) -> Result<Boundary, CommandError> {
+        if !self.contains("boundary") {
+            return Ok(Boundary::Clamped);
+        }
+        self.get_with("boundary", |v| match v {
+            "clamped" => Some(Boundary::Clamped),
+            _ => None,
+        })
+    }
+
+    /// Replace every `$name` in the values by the value of `name` in `bindings`
+    pub fn resolve(&self, bindings: &Parameters) -> Result<Self, CommandError> {
+        let mut resolved = Parameters::default();
+        for (name, value) in &self.0 {
+            let mut out = String::with_capacity(value.len());
+            let mut rest = value.as_str();
+            while let Some(at) = rest.find('$') {
+                out.push_str(&rest[..at]);
+                let reference = &rest[at + 1..];
+                let end = reference
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(reference.len());
+                let key = &reference[..end];
+                let bound = bindings
+                    .0
+                    .get(key)
+                    .ok_or_else(|| CommandError::UndefinedReference(key.to_string()))?;
+                out.push_str(bound);
+                rest = &reference[end..];
+            }
+            out.push_str(rest);
+            resolved.0.insert(name.clone(), out);
+        }
+        Ok(resolved)
+    }
+
+    /// A value parsed by `parse`, which returns `None` for invalid values
+    pub fn get_with<V>(
+        &self,
+        name: &str,
+        parse: impl FnOnce(&str) -> Option<V>,
+    ) -> Result<V, CommandError> {
+        let value = self
+            .0
+            .get(name)
+            .ok_or_else(|| CommandError::MissingParameter(name.to_string()))?;
+        parse(value).ok_or_else(|| CommandError::InvalidParameter {
+            name: name.to_string(),
+            value: value.clone(),
+        })
+    }
+}
+
+/// Builds a command from its parameters
+pub type Constructor<T> =
+    Box<dyn Fn(&Parameters) -> Result<Box<dyn Command<T>>, CommandError> + Send + Sync>;
+
+/// Commands registered by name, so any front end can build them from parameters.
+///
+/// The default registry contains the built-in commands, downstream crates add their
+/// own with [CommandRegistry::register].
+pub struct CommandRegistry<T: ControlMeshMut> {
+    constructors: BTreeMap<String, Constructor<T>>,
+}
+
+impl<T: ControlMeshMut + 'static> Default for CommandRegistry<T> {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register("adaptive_fit", |p| {
+                Ok(Box::new(AdaptiveFit::from_parameters(p)?))
+            })
+            .register("align_control_points_to_cage", |_| {
+                Ok(Box::new(AlignControlPointsToCage))
+            })
+            .register("deform", |p| Ok(Box::new(Deform::from_parameters(p)?)))
+            .register("displace", |p| Ok(Box::new(Displace::from_parameters(p)?)))
+            .register("edge_slide", |p| {
+                Ok(Box::new(EdgeSlide::from_parameters(p)?))
+            })
+            .register("extrude_edge", |p| {
+                Ok(Box::new(ExtrudeEdge::from_parameters(p)?))
+            })
+            .register("insert_knot_line", |p| {
+                Ok(Box::new(InsertKnotLine::from_parameters(p)?))
+            })
+            .register("map_control_points", |p| {
+                Ok(Box::new(MapControlPoints::from_parameters(p)?))
+            })
+            .register("make_analysis_suitable", |_| {
+                Ok(Box::new(MakeAnalysisSuitable))
+            })
+            .register("merge_faces", |p| {
+                Ok(Box::new(MergeFaces::from_parameters(p)?))
+            })
+            .register("move_control_point", |p| {
+                Ok(Box::new(MoveControlPoint::from_parameters(p)?))
+            })
+            .register("pattern", |p| Ok(Box::new(Pattern::from_parameters(p)?)))
+            .register("refine_around", |p| {
+                Ok(Box::new(RefineAround::from_parameters(p)?))
+            })
+            .register("refine_local", |p| {
+                Ok(Box::new(RefineLocal::from_parameters(p)?))
+            })
+            .register("reparameterize_arc_length", |p| {
+                Ok(Box::new(ReparameterizeArcLength::from_parameters(p)?))
+            })
+            .register("split_face", |p| {
+                Ok(Box::new(SplitFace::from_parameters(p)?))
+            });
+        registry
+    }
+}
+
+impl<T: ControlMeshMut> CommandRegistry<T> {
+    /// A registry without any commands
+    pub fn empty() -> Self {
+        Self {
+            constructors: BTreeMap::new(),
+        }
+    }
+
+    /// Register `constructor` under `name`, replacing any command of the same name
+    pub fn register(
+        &mut self,
+        name: &str,
+        constructor: impl Fn(&Parameters) -> Result<Box<dyn Command<T>>, CommandError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self {
+        self.constructors
+            .insert(name.to_string(), Box::new(constructor));
+        self
+    }
+
+    /// Names of the registered commands in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(String::as_str)
+    }
+
+    pub fn create(
+        &self,
+        name: &str,
+        parameters: &Parameters,
+    ) -> Result<Box<dyn Command<T>>, CommandError> {
+        let constructor = self
+            .constructors
+            .get(name)
+            .ok_or_else(|| CommandError::UnknownCommand(name.to_string()))?;
+        constructor(parameters)
+    }
+
+    /// Build the command `name` and apply it to `mesh`
+    pub fn apply_mut(
+        &self,
+        mesh: &mut T,
+        name: &str,
+        parameters: &Parameters,
+    ) -> Result<Influence, CommandError> {
+        self.create(name, parameters)?.apply_mut(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unit_square::unit_square;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::UVMesh;
+    use t_spline::uv_mesh::ids::VertID;
+
+    struct Lift(f64);
+
+    impl Command<TSpline> for Lift {
+        fn apply_mut(&self, mesh: &mut TSpline) -> Result<Influence, CommandError> {
+            for i in 0..mesh.points().len() {
+                if let Some(cp) = mesh.control_point_mut(VertID(i)) {
+                    cp.z += self.0;
+                }
+            }
+            Ok(Influence::Geometry)
+        }
+    }
+
+    #[test]
+    fn it_applies_built_in_commands_by_name() {
+        let registry = CommandRegistry::<TSpline>::default();
+        let mut mesh: TSpline = unit_square();
+
+        registry
+            .apply_mut(
+                &mut mesh,
+                "extrude_edge",
+                &Parameters::parse(["edge=1"]).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(6, mesh.points().len());
+        assert!(registry.names().any(|n| n == "edge_slide"));
+    }
+
+    #[test]
+    fn it_registers_user_commands() {
+        let mut registry = CommandRegistry::<TSpline>::empty();
+        registry.register("lift", |p| Ok(Box::new(Lift(p.get("by")?))));
+        let mut mesh: TSpline = unit_square();
+
+        registry
+            .apply_mut(&mut mesh, "lift", &Parameters::default().with("by", 2.))
+            .unwrap();
+
+        assert!(mesh.control_points().iter().all(|cp| cp.z == 2.));
+    }
+
+    #[test]
+    fn it_resolves_references() {
+        let bindings = Parameters::parse(["edge=3", "h=0.5"]).unwrap();
+        let parameters = Parameters::parse(["edge=$edge", "list=1,$h", "kind=bend"]).unwrap();
+
+        let resolved = parameters.resolve(&bindings).unwrap();
+
+        assert_eq!(3, resolved.get::<usize>("edge").unwrap());
+        assert_eq!(vec![1., 0.5], resolved.get_list::<f64>("list").unwrap());
+        assert_eq!("bend", resolved.get::<String>("kind").unwrap());
+        assert!(matches!(
+            Parameters::parse(["z=$height"]).unwrap().resolve(&bindings),
+            Err(CommandError::UndefinedReference(name)) if name == "height"
+        ));
+    }
+
+    #[test]
+    fn it_reports_bad_parameters() {
+        let registry = CommandRegistry::<TSpline>::default();
+        let mut mesh: TSpline = unit_square();
+
+        assert!(matches!(
+            registry.apply_mut(&mut mesh, "extrude_edge", &Parameters::default()),
+            Err(CommandError::MissingParameter(_))
+        ));
+        assert!(matches!(
+            registry.apply_mut(
+                &mut mesh,
+                "extrude_edge",
+                &Parameters::default().with("edge", "first")
+            ),
+            Err(CommandError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            registry.apply_mut(&mut mesh, "explode", &Parameters::default()),
+            Err(CommandError::UnknownCommand(_))
+        ));
+        assert!(matches!(
+            Parameters::parse(["amount0.5"]),
+            Err(CommandError::InvalidParameter { name, .. }) if name == "amount0.5"
+        ));
+    }
+
+    #[test]
+    fn it_rejects_periodic_boundaries() {
+        let periodic = Parameters::default().with("boundary", "periodic");
+        assert!(matches!(
+            periodic.get_boundary(),
+            Err(CommandError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            Parameters::default().get_boundary(),
+            Ok(Boundary::Clamped)
+        ));
+
+        let registry = CommandRegistry::<TSpline>::default();
+        let mut mesh: TSpline = unit_square();
+        assert!(matches!(
+            registry.apply_mut(&mut mesh, "displace", &periodic.with("amplitude", "0.1")),
+            Err(CommandError::InvalidParameter { .. })
+        ));
+    }
+}
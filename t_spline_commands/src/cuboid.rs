@@ -0,0 +1,58 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::plane::{PlaneError, new_plane};
+use num_traits::{One, Zero};
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::ids::VertID;
+
+/// Build a closed axis aligned box of `size` from six patches with outward facing normals.
+///
+/// Each side is a flat `3` x `3` grid of control points, the box spans from the origin
+/// to `size`. The patches are ordered top, bottom, +x, -x, +y, -y.
+pub fn cuboid<T: ControlMeshMut + Default>(size: [T::Unit; 3]) -> Result<Vec<T>, PlaneError> {
+    cuboid_grid(size, 3)
+}
+
+/// A [cuboid] whose sides are flat `points` x `points` grids of control points
+pub fn cuboid_grid<T: ControlMeshMut + Default>(
+    size: [T::Unit; 3],
+    points: usize,
+) -> Result<Vec<T>, PlaneError> {
+    let (zero, one) = (T::Unit::zero(), T::Unit::one());
+
+    let mut patches = Vec::with_capacity(6);
+    for side in 0..6 {
+        let mut mesh: T = new_plane(points, points, one, one)?;
+        for v in 0..points * points {
+            if let Some(cp) = mesh.control_point_mut(VertID(v)) {
+                // map the plane onto the side, swapping axes where needed to face outwards
+                let (x, y) = (cp.x, cp.y);
+                let [px, py, pz] = match side {
+                    0 => [x, y, one],
+                    1 => [y, x, zero],
+                    2 => [one, x, y],
+                    3 => [zero, y, x],
+                    4 => [y, one, x],
+                    _ => [x, zero, y],
+                };
+                (cp.x, cp.y, cp.z) = (px * size[0], py * size[1], pz * size[2]);
+            }
+        }
+        patches.push(mesh);
+    }
+    Ok(patches)
+}
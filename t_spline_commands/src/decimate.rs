@@ -0,0 +1,351 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::remesh::TriangleMesh;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap};
+use t_spline::{Matrix4, Point3, Vector3, Vector4};
+
+/// When [decimate] stops collapsing edges
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decimate {
+    /// Stop once the mesh has at most this many triangles
+    pub triangles: usize,
+    /// Never collapse an edge that moves the surface further than this, in model units
+    pub max_error: f64,
+}
+
+impl Decimate {
+    /// Reduce to `triangles` however far the surface moves
+    pub fn to_count(triangles: usize) -> Self {
+        Self {
+            triangles,
+            max_error: f64::INFINITY,
+        }
+    }
+
+    /// Reduce as far as possible while staying within `max_error` of the input
+    pub fn to_error(max_error: f64) -> Self {
+        Self {
+            triangles: 0,
+            max_error,
+        }
+    }
+}
+
+/// Simplify a tessellation for light previews by collapsing edges with quadric error
+/// metrics.
+///
+/// Coinciding points are welded first, so the flat shaded output of
+/// [crate::triangles::triangle_mesh] decimates as one surface. The error of a collapse
+/// is the root of its quadric, the distance to the planes of the triangles merged into
+/// the vertex. Open borders are held in place by planes along them and collapses that
+/// would fold a triangle over are skipped.
+pub fn decimate(mesh: &TriangleMesh, target: Decimate) -> TriangleMesh {
+    let (mut points, mut triangles) = weld(mesh);
+    let mut quadrics = vec![Matrix4::zeros(); points.len()];
+    let mut around: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+    let mut edges: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for (i, t) in triangles.iter().enumerate() {
+        let Some(t) = t else { continue };
+        let quadric = plane_quadric(t.map(|v| points[v]));
+        for k in 0..3 {
+            quadrics[t[k]] += quadric;
+            around[t[k]].push(i);
+            let (a, b) = (t[k], t[(k + 1) % 3]);
+            *edges.entry((a.min(b), a.max(b))).or_default() += 1;
+        }
+    }
+    for (&(a, b), &count) in &edges {
+        if count == 1 {
+            let border = border_quadric(&points, &triangles, &around[a], (a, b));
+            quadrics[a] += border;
+            quadrics[b] += border;
+        }
+    }
+
+    let mut version = vec![0usize; points.len()];
+    let mut heap = BinaryHeap::new();
+    for &(a, b) in edges.keys() {
+        heap.push(candidate(&points, &quadrics, &version, a, b));
+    }
+
+    let mut alive = triangles.iter().flatten().count();
+    while alive > target.triangles {
+        let Some(collapse) = heap.pop() else { break };
+        let (a, b) = collapse.edge;
+        if collapse.versions != (version[a], version[b]) {
+            continue;
+        }
+        if collapse.error > target.max_error {
+            break;
+        }
+        if folds(&points, &triangles, &around, a, b, collapse.position)
+            || folds(&points, &triangles, &around, b, a, collapse.position)
+        {
+            continue;
+        }
+
+        // move a to the collapse and hand it the triangles of b
+        points[a] = collapse.position;
+        let merged = quadrics[b];
+        quadrics[a] += merged;
+        let moved = std::mem::take(&mut around[b]);
+        for &i in &moved {
+            let Some(t) = &mut triangles[i] else { continue };
+            for v in t.iter_mut().filter(|v| **v == b) {
+                *v = a;
+            }
+            if t[0] == t[1] || t[1] == t[2] || t[2] == t[0] {
+                triangles[i] = None;
+                alive -= 1;
+            } else if !around[a].contains(&i) {
+                around[a].push(i);
+            }
+        }
+        around[a].retain(|&i| triangles[i].is_some());
+        version[a] += 1;
+        version[b] += 1;
+
+        let mut neighbours: Vec<usize> = around[a]
+            .iter()
+            .flat_map(|&i| triangles[i].into_iter().flatten())
+            .filter(|&v| v != a)
+            .collect();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        for v in neighbours {
+            heap.push(candidate(&points, &quadrics, &version, a, v));
+        }
+    }
+
+    compact(&points, &triangles)
+}
+
+/// A possible edge collapse, ordered so the smallest error pops first
+#[derive(Debug, Clone, Copy)]
+struct Collapse {
+    error: f64,
+    edge: (usize, usize),
+    position: Point3<f64>,
+    /// Versions of the vertices when the collapse was evaluated, later changes make it
+    /// stale
+    versions: (usize, usize),
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .error
+            .total_cmp(&self.error)
+            .then_with(|| other.edge.cmp(&self.edge))
+    }
+}
+
+fn candidate(
+    points: &[Point3<f64>],
+    quadrics: &[Matrix4<f64>],
+    version: &[usize],
+    a: usize,
+    b: usize,
+) -> Collapse {
+    let quadric = quadrics[a] + quadrics[b];
+    let cost = |p: Point3<f64>| {
+        let v = Vector4::new(p.x, p.y, p.z, 1.);
+        (v.transpose() * quadric * v)[0].max(0.)
+    };
+    let (position, cost) = [points[a], points[b], midpoint(points[a], points[b])]
+        .into_iter()
+        .map(|p| (p, cost(p)))
+        .min_by(|x, y| x.1.total_cmp(&y.1))
+        .unwrap_or((points[a], f64::INFINITY));
+    Collapse {
+        error: cost.sqrt(),
+        edge: (a, b),
+        position,
+        versions: (version[a], version[b]),
+    }
+}
+
+fn midpoint(a: Point3<f64>, b: Point3<f64>) -> Point3<f64> {
+    Point3::from((a.coords + b.coords) / 2.)
+}
+
+/// True if moving `v` to `to` turns one of its triangles that does not also hold
+/// `other` upside down
+fn folds(
+    points: &[Point3<f64>],
+    triangles: &[Option<[usize; 3]>],
+    around: &[Vec<usize>],
+    v: usize,
+    other: usize,
+    to: Point3<f64>,
+) -> bool {
+    around[v].iter().any(|&i| {
+        let Some(t) = triangles[i] else { return false };
+        if t.contains(&other) {
+            return false;
+        }
+        let before = normal(t.map(|u| points[u]));
+        let after = normal(t.map(|u| if u == v { to } else { points[u] }));
+        before.dot(&after) <= 0.
+    })
+}
+
+fn normal([a, b, c]: [Point3<f64>; 3]) -> Vector3<f64> {
+    (b - a).cross(&(c - a))
+}
+
+/// The quadric of the distance squared to the plane of a triangle
+fn plane_quadric(corners: [Point3<f64>; 3]) -> Matrix4<f64> {
+    let n = normal(corners);
+    let length = n.dot(&n).sqrt();
+    if length <= f64::EPSILON {
+        return Matrix4::zeros();
+    }
+    let n = n / length;
+    let plane = Vector4::new(n.x, n.y, n.z, -n.dot(&corners[0].coords));
+    plane * plane.transpose()
+}
+
+/// A heavy plane through the border edge from `a` to `b`, upright on its triangle
+fn border_quadric(
+    points: &[Point3<f64>],
+    triangles: &[Option<[usize; 3]>],
+    around: &[usize],
+    (a, b): (usize, usize),
+) -> Matrix4<f64> {
+    const WEIGHT: f64 = 1e3;
+    let Some(face) = around
+        .iter()
+        .filter_map(|&i| triangles[i])
+        .find(|t| t.contains(&b))
+    else {
+        return Matrix4::zeros();
+    };
+    let along = points[b] - points[a];
+    let n = along.cross(&normal(face.map(|v| points[v])));
+    let length = n.dot(&n).sqrt();
+    if length <= f64::EPSILON {
+        return Matrix4::zeros();
+    }
+    let n = n / length;
+    let plane = Vector4::new(n.x, n.y, n.z, -n.dot(&points[a].coords));
+    plane * plane.transpose() * WEIGHT
+}
+
+/// Merge points at the same position
+fn weld(mesh: &TriangleMesh) -> (Vec<Point3<f64>>, Vec<Option<[usize; 3]>>) {
+    let mut index = BTreeMap::new();
+    let mut points = Vec::new();
+    let remap: Vec<usize> = mesh
+        .points
+        .iter()
+        .map(|p| {
+            *index
+                .entry([p.x, p.y, p.z].map(f64::to_bits))
+                .or_insert_with(|| {
+                    points.push(*p);
+                    points.len() - 1
+                })
+        })
+        .collect();
+    let triangles = mesh
+        .triangles
+        .iter()
+        .map(|t| t.map(|v| remap[v]))
+        .map(|t| (t[0] != t[1] && t[1] != t[2] && t[2] != t[0]).then_some(t))
+        .collect();
+    (points, triangles)
+}
+
+/// Drop removed triangles and the points no triangle uses any more
+fn compact(points: &[Point3<f64>], triangles: &[Option<[usize; 3]>]) -> TriangleMesh {
+    let mut remap = vec![None; points.len()];
+    let mut result = TriangleMesh::default();
+    for t in triangles.iter().flatten() {
+        let t = t.map(|v| {
+            *remap[v].get_or_insert_with(|| {
+                result.points.push(points[v]);
+                result.points.len() - 1
+            })
+        });
+        result.triangles.push(t);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use crate::triangles::triangle_mesh;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::Boundary;
+    use t_spline::uv_mesh::ids::VertID;
+
+    fn area(mesh: &TriangleMesh) -> f64 {
+        mesh.triangles
+            .iter()
+            .map(|t| normal(t.map(|v| mesh.points[v])).z / 2.)
+            .sum()
+    }
+
+    #[test]
+    fn it_collapses_a_flat_surface() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let triangles = triangle_mesh(&mesh, 4, Boundary::Clamped).unwrap();
+
+        let decimated = decimate(&triangles, Decimate::to_error(1e-9));
+
+        assert!(decimated.triangles.len() < triangles.triangles.len() / 10);
+        assert!((area(&decimated) - 9.).abs() < 1e-9, "{}", area(&decimated));
+    }
+
+    #[test]
+    fn it_stops_at_the_target() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        mesh.control_point_mut(VertID(12)).unwrap().z = 2.;
+        let triangles = triangle_mesh(&mesh, 4, Boundary::Clamped).unwrap();
+
+        let counted = decimate(&triangles, Decimate::to_count(100));
+        let bounded = decimate(&triangles, Decimate::to_error(1e-3));
+
+        assert!(counted.triangles.len() <= 100);
+        assert!(counted.triangles.len() > 90);
+        assert!(bounded.triangles.len() < triangles.triangles.len());
+        assert!(bounded.triangles.len() > counted.triangles.len());
+        for mesh in [&counted, &bounded] {
+            assert!((area(mesh) - 16.).abs() < 1e-6, "{}", area(mesh));
+        }
+    }
+}
@@ -0,0 +1,355 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::Influence;
+use t_spline::bounds::Bounds;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::ids::VertID;
+use thiserror::Error;
+
+#[derive(Copy, Clone, Debug, Error)]
+pub enum DeformError {
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+}
+
+/// Cartesian axis of the control cage
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    fn index(&self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        }
+    }
+
+    /// The two axes orthogonal to this one, in right-handed order
+    fn others(&self) -> (usize, usize) {
+        match self {
+            Axis::X => (1, 2),
+            Axis::Y => (2, 0),
+            Axis::Z => (0, 1),
+        }
+    }
+}
+
+/// Non-linear deformers applied to the control points of a mesh.
+///
+/// The deformation factor of each point is driven by its position along the
+/// deformer axis, normalized over the extent of the affected points.
+#[derive(Copy, Clone, Debug)]
+pub enum Deformer {
+    /// Bend the points about `axis` by a total `angle` (radians). The length of the
+    /// bend is taken along the first axis orthogonal to `axis`.
+    Bend { axis: Axis, angle: f64 },
+    /// Rotate the points about `axis`, from 0 to `angle` (radians) along the axis.
+    Twist { axis: Axis, angle: f64 },
+    /// Scale the points orthogonal to `axis`, from 1 to `factor` along the axis.
+    Taper { axis: Axis, factor: f64 },
+}
+
+/// [deform] as a [Command], built from the `kind` (`bend`, `twist` or `taper`), `axis`
+/// and `angle` or `factor` parameters
+#[derive(Debug, Clone)]
+pub struct Deform {
+    pub deformer: Deformer,
+    pub region: Option<Bounds<isize>>,
+}
+
+impl Deform {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        let axis = parameters.get_with("axis", |v| match v {
+            "x" | "X" => Some(Axis::X),
+            "y" | "Y" => Some(Axis::Y),
+            "z" | "Z" => Some(Axis::Z),
+            _ => None,
+        })?;
+        let kind: String = parameters.get("kind")?;
+        let deformer = match kind.as_str() {
+            "bend" => Deformer::Bend {
+                axis,
+                angle: parameters.get("angle")?,
+            },
+            "twist" => Deformer::Twist {
+                axis,
+                angle: parameters.get("angle")?,
+            },
+            "taper" => Deformer::Taper {
+                axis,
+                factor: parameters.get("factor")?,
+            },
+            _ => {
+                return Err(CommandError::InvalidParameter {
+                    name: "kind".to_string(),
+                    value: kind,
+                });
+            }
+        };
+        Ok(Self {
+            deformer,
+            region: None,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for Deform {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        deform(mesh, self.deformer, self.region.as_ref()).map_err(CommandError::failed)?;
+        Ok(Influence::Geometry)
+    }
+}
+
+/// Apply `deformer` to the control points of `mesh`.
+///
+/// When `region` is provided, only control points whose UV point lies within it are
+/// deformed.
+pub fn deform<T: ControlMeshMut>(
+    mesh: &mut T,
+    deformer: Deformer,
+    region: Option<&Bounds<isize>>,
+) -> Result<(), DeformError> {
+    let mut selected = Vec::new();
+    for (i, p) in mesh.points().iter().enumerate() {
+        if region.is_none_or(|r| r.contains(p.st())) {
+            selected.push(VertID(i));
+        }
+    }
+
+    let mut positions = Vec::with_capacity(selected.len());
+    for id in &selected {
+        let cp = mesh
+            .control_point(*id)
+            .ok_or(DeformError::MissingControlPoint)?;
+        positions.push([to_f64(cp.x)?, to_f64(cp.y)?, to_f64(cp.z)?]);
+    }
+
+    let driver = match deformer {
+        Deformer::Bend { axis, .. } => axis.others().0,
+        Deformer::Twist { axis, .. } | Deformer::Taper { axis, .. } => axis.index(),
+    };
+    let (min, max) = positions
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), p| {
+            (min.min(p[driver]), max.max(p[driver]))
+        });
+    let extent = max - min;
+
+    for (id, p) in selected.into_iter().zip(positions) {
+        let f = if extent > 0. {
+            (p[driver] - min) / extent
+        } else {
+            0.
+        };
+        let p = apply(deformer, p, f, min, extent);
+
+        let cp = mesh
+            .control_point_mut(id)
+            .ok_or(DeformError::MissingControlPoint)?;
+        cp.x = from_f64::<T::Unit>(p[0])?;
+        cp.y = from_f64::<T::Unit>(p[1])?;
+        cp.z = from_f64::<T::Unit>(p[2])?;
+    }
+
+    Ok(())
+}
+
+fn apply(deformer: Deformer, mut p: [f64; 3], f: f64, min: f64, extent: f64) -> [f64; 3] {
+    match deformer {
+        Deformer::Bend { axis, angle } => {
+            if angle == 0. || extent == 0. {
+                return p;
+            }
+            let (a, b) = axis.others();
+            let radius = extent / angle;
+            let theta = f * angle;
+            let r = radius - p[b];
+
+            p[a] = min + r * theta.sin();
+            p[b] = radius - r * theta.cos();
+        }
+        Deformer::Twist { axis, angle } => {
+            let (a, b) = axis.others();
+            let (sin, cos) = (f * angle).sin_cos();
+            let (pa, pb) = (p[a], p[b]);
+
+            p[a] = pa * cos - pb * sin;
+            p[b] = pa * sin + pb * cos;
+        }
+        Deformer::Taper { axis, factor } => {
+            let (a, b) = axis.others();
+            let scale = 1. + (factor - 1.) * f;
+
+            p[a] *= scale;
+            p[b] *= scale;
+        }
+    }
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use crate::unit_square::unit_square;
+    use core::f64::consts::{FRAC_PI_2, PI};
+    use t_spline::TSpline;
+    use t_spline::Vector4;
+    use t_spline::control_mesh::ControlMesh;
+
+    fn assert_close(a: &Vector4<f64>, b: [f64; 3]) {
+        assert!((a.x - b[0]).abs() < 1e-9, "{a:?} != {b:?}");
+        assert!((a.y - b[1]).abs() < 1e-9, "{a:?} != {b:?}");
+        assert!((a.z - b[2]).abs() < 1e-9, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn it_twists_around_an_axis() {
+        let mut mesh: TSpline = unit_square();
+
+        deform(
+            &mut mesh,
+            Deformer::Twist {
+                axis: Axis::X,
+                angle: PI,
+            },
+            None,
+        )
+        .unwrap();
+
+        // x = 0 is untouched, x = 1 is rotated half a turn
+        assert_close(&mesh.control_points()[0], [0., 0., 0.]);
+        assert_close(&mesh.control_points()[3], [0., 1., 0.]);
+        assert_close(&mesh.control_points()[2], [1., -1., 0.]);
+    }
+
+    #[test]
+    fn it_tapers_along_an_axis() {
+        let mut mesh: TSpline = unit_square();
+
+        deform(
+            &mut mesh,
+            Deformer::Taper {
+                axis: Axis::Y,
+                factor: 0.5,
+            },
+            None,
+        )
+        .unwrap();
+
+        assert_close(&mesh.control_points()[1], [1., 0., 0.]);
+        assert_close(&mesh.control_points()[2], [0.5, 1., 0.]);
+    }
+
+    #[test]
+    fn it_bends_around_an_axis() {
+        let mut mesh: TSpline = unit_square();
+
+        deform(
+            &mut mesh,
+            Deformer::Bend {
+                axis: Axis::Z,
+                angle: FRAC_PI_2,
+            },
+            None,
+        )
+        .unwrap();
+
+        let radius = 1. / FRAC_PI_2;
+        assert_close(&mesh.control_points()[0], [0., 0., 0.]);
+        assert_close(&mesh.control_points()[1], [radius, radius, 0.]);
+    }
+
+    #[test]
+    fn it_limits_deformation_to_a_region() {
+        let mut mesh: TSpline = unit_square();
+        let region = Bounds {
+            s: (0, 0),
+            t: (0, 1),
+        };
+
+        deform(
+            &mut mesh,
+            Deformer::Taper {
+                axis: Axis::Y,
+                factor: 0.,
+            },
+            Some(&region),
+        )
+        .unwrap();
+
+        assert_close(&mesh.control_points()[1], [1., 0., 0.]);
+        assert_close(&mesh.control_points()[2], [1., 1., 0.]);
+        assert_close(&mesh.control_points()[3], [0., 1., 0.]);
+    }
+
+    #[test]
+    fn it_deforms_through_the_command() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let parameters = Parameters::default()
+            .with("kind", "twist")
+            .with("axis", "x")
+            .with("angle", PI);
+
+        let influence = Deform::from_parameters(&parameters)
+            .unwrap()
+            .apply_mut(&mut mesh)
+            .unwrap();
+
+        assert!(matches!(influence, Influence::Geometry));
+        // x = 0 is untouched, x = 1 turns a quarter and x = 2 half a turn
+        assert_close(&mesh.control_points()[3], [0., 1., 0.]);
+        assert_close(&mesh.control_points()[4], [1., 0., 1.]);
+        assert_close(&mesh.control_points()[5], [2., -1., 0.]);
+    }
+
+    #[test]
+    fn it_leaves_points_outside_the_region_untouched() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let before = mesh.control_points().to_vec();
+        let region = Bounds {
+            s: (0, 1),
+            t: (0, 2),
+        };
+
+        deform(
+            &mut mesh,
+            Deformer::Taper {
+                axis: Axis::Y,
+                factor: 0.5,
+            },
+            Some(&region),
+        )
+        .unwrap();
+
+        // the taper runs over the y extent of the selected points, from 0 to 2
+        assert_close(&mesh.control_points()[4], [0.75, 1., 0.]);
+        assert_close(&mesh.control_points()[7], [0.5, 2., 0.]);
+        for v in [2, 5, 8] {
+            assert_eq!(before[v], mesh.control_points()[v]);
+        }
+    }
+}
@@ -0,0 +1,398 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::command::{Command, CommandError, Parameters};
+use crate::frame_field::{FrameError, frame_at};
+use crate::insert_knot_line::{InsertKnotLineError, insert_knot_line};
+use crate::knot_cache::Influence;
+use num_traits::FromPrimitive;
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::uv_point::UVPoint;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum DisplaceError {
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("no normal at control point {0:?}")]
+    NoNormal(VertID),
+    #[error("failed to refine: {0}")]
+    Refine(#[from] InsertKnotLineError),
+    #[error(transparent)]
+    Frame(#[from] FrameError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// Seeded 3D gradient noise after Ken Perlin's improved noise, in about `-1..1`
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    permutation: [u8; 512],
+    /// Sampling offset, so lattice points do not all map to zero
+    offset: Vector3<f64>,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        let mut state = seed;
+        let mut next = || {
+            // splitmix64
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        };
+
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        for i in (1..table.len()).rev() {
+            table.swap(i, (next() % (i as u64 + 1)) as usize);
+        }
+        let mut unit = || (next() >> 11) as f64 / (1u64 << 53) as f64;
+        Self {
+            permutation: std::array::from_fn(|i| table[i % 256]),
+            offset: Vector3::new(unit(), unit(), unit()),
+        }
+    }
+
+    pub fn noise(&self, p: Point3<f64>) -> f64 {
+        let p = p + self.offset;
+        let cell = p.map(f64::floor);
+        let [x, y, z] = [p.x - cell.x, p.y - cell.y, p.z - cell.z];
+        let [i, j, k] = [cell.x, cell.y, cell.z].map(|c| c.rem_euclid(256.) as usize);
+        let hash = |di: usize, dj: usize, dk: usize| {
+            let perm = &self.permutation;
+            perm[perm[perm[i + di] as usize + j + dj] as usize + k + dk]
+        };
+        let corner = |di: usize, dj: usize, dk: usize| {
+            gradient(
+                hash(di, dj, dk),
+                x - di as f64,
+                y - dj as f64,
+                z - dk as f64,
+            )
+        };
+
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+        let lerp = |t: f64, a: f64, b: f64| a + t * (b - a);
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+                lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+            ),
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+                lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+            ),
+        )
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
+
+/// Dot product with one of the twelve edge directions of a cube picked by `hash`
+fn gradient(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = match h {
+        0..4 => y,
+        12 | 14 => x,
+        _ => z,
+    };
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    u + v
+}
+
+/// [displace] by [Perlin] noise as a [Command], built from the `amplitude` and optional
+/// `frequency`, `seed`, `refine` and `boundary` parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Displace {
+    pub amplitude: f64,
+    /// Noise features per unit length of the control cage
+    pub frequency: f64,
+    pub seed: u64,
+    /// Number of times to [refine] the mesh before displacing it
+    pub refine: usize,
+    pub boundary: Boundary,
+}
+
+impl Displace {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            amplitude: parameters.get("amplitude")?,
+            frequency: parameters.get_or("frequency", 1.)?,
+            seed: parameters.get_or("seed", 0)?,
+            refine: parameters.get_or("refine", 0)?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for Displace {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        for _ in 0..self.refine {
+            refine(mesh, self.boundary).map_err(CommandError::failed)?;
+        }
+
+        let noise = Perlin::new(self.seed);
+        displace(mesh, self.boundary, |p, _| {
+            self.amplitude * noise.noise(p * self.frequency)
+        })
+        .map_err(CommandError::failed)?;
+
+        Ok(if self.refine > 0 {
+            Influence::Global
+        } else {
+            Influence::Geometry
+        })
+    }
+}
+
+/// Move every control point along its [vertex_normals] by `offset(position, normal)`.
+///
+/// All offsets are computed before any point moves.
+pub fn displace<T: ControlMeshMut>(
+    mesh: &mut T,
+    boundary: Boundary,
+    offset: impl Fn(Point3<f64>, Vector3<f64>) -> f64,
+) -> Result<(), DisplaceError> {
+    let normals = vertex_normals(mesh, boundary)?;
+
+    let mut moved = Vec::with_capacity(normals.len());
+    for (cp, normal) in mesh.control_points().iter().zip(normals) {
+        let position = Point3::new(to_f64(cp.x)?, to_f64(cp.y)?, to_f64(cp.z)?);
+        moved.push(position + normal * offset(position, normal));
+    }
+
+    for (i, p) in moved.into_iter().enumerate() {
+        let cast = from_f64::<T::Unit>;
+        let cp = mesh
+            .control_point_mut(VertID(i))
+            .ok_or(DisplaceError::MissingControlPoint)?;
+        (cp.x, cp.y, cp.z) = (cast(p.x)?, cast(p.y)?, cast(p.z)?);
+    }
+    Ok(())
+}
+
+/// Unit normal at every control point.
+///
+/// The normal is taken on the surface at the parameter of the control point, or from the
+/// faces of the control cage around it on the boundary of the parametric domain and
+/// where the surface degenerates. Normals face the same side as the cage.
+pub fn vertex_normals<T: ControlMesh>(
+    mesh: &T,
+    boundary: Boundary,
+) -> Result<Vec<Vector3<f64>>, DisplaceError> {
+    mesh.validate_control_mesh()?;
+    let knots = mesh.local_knots(boundary);
+    let cage = cage_normals(mesh)?;
+
+    let extent = |value: fn(&UVPoint) -> isize| {
+        let values = mesh.points().iter().map(value);
+        (values.clone().min(), values.max())
+    };
+    let (s_range, t_range) = (extent(|p| p.s), extent(|p| p.t));
+    let inside = |v: isize, (lo, hi): (Option<isize>, Option<isize>)| {
+        lo.is_some_and(|lo| lo < v) && hi.is_some_and(|hi| v < hi)
+    };
+
+    let mut normals = Vec::with_capacity(mesh.points().len());
+    for (i, p) in mesh.points().iter().enumerate() {
+        let st = (
+            T::Unit::from_isize(p.s).ok_or(CastError)?,
+            T::Unit::from_isize(p.t).ok_or(CastError)?,
+        );
+        // one sided derivatives on the boundary are unreliable, the cage is used there
+        let frame = if inside(p.s, s_range) && inside(p.t, t_range) {
+            frame_at(mesh, &knots, st)?
+        } else {
+            None
+        };
+        normals.push(match frame {
+            Some(frame) if frame.normal.dot(&cage[i]) < 0. => -frame.normal,
+            Some(frame) => frame.normal,
+            None => {
+                let n = cage[i];
+                let length = n.dot(&n).sqrt();
+                if length <= f64::EPSILON {
+                    return Err(DisplaceError::NoNormal(VertID(i)));
+                }
+                n / length
+            }
+        });
+    }
+    Ok(normals)
+}
+
+/// Double the parametric coordinates of the mesh and insert a knot line between every
+/// pair of neighbouring knot values, which roughly quadruples the number of faces.
+///
+/// Scaling all knots alike leaves the surface unchanged, the inserted lines fit the
+/// control points around them to the surface in a least squares sense, so the surface
+/// moves only slightly.
+pub fn refine<T: ControlMeshMut>(mesh: &mut T, boundary: Boundary) -> Result<(), DisplaceError> {
+    mesh.validate_control_mesh()?;
+    for i in 0..mesh.points().len() {
+        let p = mesh
+            .point_mut(VertID(i))
+            .ok_or(DisplaceError::MissingControlPoint)?;
+        (p.s, p.t) = (p.s * 2, p.t * 2);
+    }
+
+    for direction in [Direction::S, Direction::T] {
+        let mut values: Vec<isize> = mesh
+            .points()
+            .iter()
+            .map(|p| match direction {
+                Direction::S => p.s,
+                Direction::T => p.t,
+            })
+            .collect();
+        values.sort_unstable();
+        values.dedup();
+
+        for pair in values.windows(2) {
+            match insert_knot_line(mesh, direction, (pair[0] + pair[1]) / 2, boundary) {
+                Ok(_) | Err(InsertKnotLineError::NoFaceStraddles(_)) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sum of the normals of the control polygons around every vertex, weighted by area
+fn cage_normals<T: ControlMesh>(mesh: &T) -> Result<Vec<Vector3<f64>>, DisplaceError> {
+    let mut normals = vec![Vector3::zeros(); mesh.points().len()];
+    for face in mesh.faces() {
+        let edge = mesh.edge(face).ok_or(DisplaceError::MissingControlPoint)?;
+        let corners: Vec<_> = mesh.edge_loop(edge).map(|(_, e)| e.origin).collect();
+        let positions = corners
+            .iter()
+            .map(|&v| {
+                let cp = mesh
+                    .control_point(v)
+                    .ok_or(DisplaceError::MissingControlPoint)?;
+                Ok(Vector3::new(to_f64(cp.x)?, to_f64(cp.y)?, to_f64(cp.z)?))
+            })
+            .collect::<Result<Vec<_>, DisplaceError>>()?;
+
+        // Newell's method
+        let mut normal = Vector3::zeros();
+        for (i, a) in positions.iter().enumerate() {
+            normal += a.cross(&positions[(i + 1) % positions.len()]);
+        }
+        for v in corners {
+            normals[v.0] += normal;
+        }
+    }
+    Ok(normals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::CommandRegistry;
+    use crate::plane::new_plane;
+    use crate::tessellate::tessellate;
+    use t_spline::TSpline;
+
+    #[test]
+    fn it_makes_repeatable_noise() {
+        let (a, b) = (Perlin::new(1), Perlin::new(2));
+        let samples: Vec<_> = (0..50)
+            .map(|i| Point3::new(i as f64 * 0.37, i as f64 * 0.21, 1.))
+            .collect();
+
+        let values: Vec<_> = samples.iter().map(|&p| a.noise(p)).collect();
+
+        assert_eq!(
+            values,
+            samples
+                .iter()
+                .map(|&p| Perlin::new(1).noise(p))
+                .collect::<Vec<_>>()
+        );
+        assert_ne!(
+            values,
+            samples.iter().map(|&p| b.noise(p)).collect::<Vec<_>>()
+        );
+        assert!(values.iter().all(|v| v.abs() <= 1.5), "{values:?}");
+        assert!(values.iter().any(|v| v.abs() > 0.05), "{values:?}");
+    }
+
+    #[test]
+    fn it_displaces_along_the_normal() {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+
+        displace(&mut mesh, Boundary::Clamped, |p, normal| {
+            assert!((normal - Vector3::z()).abs().max() < 1e-9, "{normal:?}");
+            p.x + p.y
+        })
+        .unwrap();
+
+        for cp in mesh.control_points() {
+            assert_eq!(cp.x + cp.y, cp.z);
+        }
+    }
+
+    #[test]
+    fn it_refines_close_to_the_surface() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+
+        refine(&mut mesh, Boundary::Clamped).unwrap();
+
+        assert_eq!(25, mesh.control_points().len());
+        for p in tessellate(&mesh, 9, Boundary::Clamped).unwrap() {
+            assert!(p.x > -0.1 && p.x < 2.1 && p.y > -0.1 && p.y < 2.1, "{p:?}");
+            assert_eq!(0., p.z);
+        }
+    }
+
+    #[test]
+    fn it_displaces_by_noise() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+
+        CommandRegistry::default()
+            .apply_mut(
+                &mut mesh,
+                "displace",
+                &Parameters::default()
+                    .with("amplitude", 0.5)
+                    .with("seed", 7)
+                    .with("refine", 1),
+            )
+            .unwrap();
+
+        assert_eq!(25, mesh.control_points().len());
+        assert!(mesh.control_points().iter().any(|cp| cp.z != 0.));
+        assert!(mesh.control_points().iter().all(|cp| cp.z.abs() <= 0.75));
+    }
+}
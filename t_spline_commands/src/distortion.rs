@@ -0,0 +1,200 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64};
+use crate::command::{CommandError, Parameters};
+use crate::frame_field::{FrameError, Metric, frame_at};
+use crate::tessellate::knot_vectors;
+use t_spline::Point3;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::ids::EdgeID;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum DistortionError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+impl From<FrameError> for DistortionError {
+    fn from(value: FrameError) -> Self {
+        match value {
+            FrameError::Cast(e) => DistortionError::Cast(e),
+            FrameError::Invalid(e) => DistortionError::Invalid(e),
+        }
+    }
+}
+
+/// [distortion] with its parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Distortion {
+    /// Samples per face along either direction
+    pub resolution: usize,
+    pub boundary: Boundary,
+}
+
+impl Distortion {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            resolution: parameters.get_or("resolution", 4)?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+
+    pub fn apply<T: ControlMesh + Sync>(
+        &self,
+        mesh: &T,
+    ) -> Result<DistortionField, DistortionError> {
+        distortion(mesh, self.resolution, self.boundary)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistortionSample {
+    pub face: EdgeID,
+    pub st: (f64, f64),
+    pub point: Point3<f64>,
+    pub metric: Metric,
+    /// The stretch ratio of [Metric::anisotropy], `1` where the parametrization is
+    /// conformal
+    pub stretch: f64,
+}
+
+/// The stretch ratio sampled over a surface
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DistortionField {
+    pub samples: Vec<DistortionSample>,
+    /// The largest stretch ratio sampled on each face, faces without a valid sample are
+    /// left out
+    pub max_per_face: Vec<(EdgeID, f64)>,
+    /// Smallest and largest stretch ratio, `None` without samples
+    pub range: Option<(f64, f64)>,
+}
+
+impl DistortionField {
+    /// The stretch of every sample scaled to `[0, 1]` over the range, for coloring
+    pub fn heat(&self) -> Vec<f64> {
+        let Some((lo, hi)) = self.range else {
+            return Vec::new();
+        };
+        self.samples
+            .iter()
+            .map(|s| {
+                if hi > lo {
+                    (s.stretch - lo) / (hi - lo)
+                } else {
+                    0.
+                }
+            })
+            .collect()
+    }
+}
+
+/// Sample how unevenly the parametrization of `mesh` stretches, to find where a
+/// reparameterization would help.
+///
+/// Each face is sampled on a `resolution` x `resolution` grid at the centers of its
+/// cells. Samples where the tangent plane degenerates are left out.
+pub fn distortion<T: ControlMesh + Sync>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<DistortionField, DistortionError> {
+    mesh.validate_control_mesh()?;
+    let knot_cache = knot_vectors(mesh, boundary);
+    let cast = from_f64::<T::Unit>;
+
+    let mut field = DistortionField::default();
+    for rect in mesh.layout().faces {
+        let mut max: Option<f64> = None;
+        for i in 0..resolution * resolution {
+            let center = |(lo, hi): (isize, isize), i: usize| {
+                cast(lo as f64 + (hi - lo) as f64 * (i as f64 + 0.5) / resolution as f64)
+            };
+            let st = (
+                center(rect.s, i % resolution)?,
+                center(rect.t, i / resolution)?,
+            );
+            let Some(frame) = frame_at(mesh, &knot_cache, st)? else {
+                continue;
+            };
+
+            let stretch = frame.metric.anisotropy();
+            max = Some(max.map_or(stretch, |m| m.max(stretch)));
+            field.range = Some(field.range.map_or((stretch, stretch), |(lo, hi)| {
+                (lo.min(stretch), hi.max(stretch))
+            }));
+            field.samples.push(DistortionSample {
+                face: rect.face,
+                st: frame.st,
+                point: frame.point,
+                metric: frame.metric,
+                stretch,
+            });
+        }
+
+        if let Some(max) = max {
+            field.max_per_face.push((rect.face, max));
+        }
+    }
+    Ok(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_finds_a_stretched_region() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        // move the right half of the control points away along x, stretching the middle
+        for v in 0..mesh.control_points().len() {
+            let cp = mesh.control_point_mut(VertID(v)).unwrap();
+            if cp.x > 2. {
+                cp.x += 3.;
+            }
+        }
+
+        let field = Distortion::from_parameters(&Parameters::default().with("resolution", 2))
+            .unwrap()
+            .apply(&mesh)
+            .unwrap();
+
+        assert_eq!(16, field.max_per_face.len());
+        let (lo, hi) = field.range.unwrap();
+        assert!(lo >= 1. - 1e-9);
+        assert!(hi > 2., "{hi}");
+
+        let heat = field.heat();
+        assert_eq!(field.samples.len(), heat.len());
+        assert!(heat.iter().all(|h| (0. ..=1.).contains(h)));
+        let hottest = &field.samples[heat.iter().position(|&h| h == 1.).unwrap()];
+        assert!(hottest.point.x > 2. && hottest.point.x < 5., "{hottest:?}");
+    }
+
+    #[test]
+    fn it_has_no_heat_without_samples() {
+        assert!(DistortionField::default().heat().is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::CastError;
+use crate::frame_field::{FrameError, frame_at};
+use crate::tessellate::knot_vectors;
+use num_traits::FromPrimitive;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::ids::EdgeID;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum DraftError {
+    #[error("pull direction has no length")]
+    ZeroPullDirection,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+impl From<FrameError> for DraftError {
+    fn from(value: FrameError) -> Self {
+        match value {
+            FrameError::Cast(e) => DraftError::Cast(e),
+            FrameError::Invalid(e) => DraftError::Invalid(e),
+        }
+    }
+}
+
+/// How a surface sample releases from a mold
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftClass {
+    /// Faces the pull direction by at least the required draft
+    Sufficient,
+    /// Within the required draft of being parallel to the pull direction
+    Insufficient,
+    /// Faces away from the pull direction by at least the required draft
+    Undercut,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DraftSample {
+    pub face: EdgeID,
+    pub st: (f64, f64),
+    pub point: Point3<f64>,
+    /// Angle between the surface and the pull direction in radians, positive when the
+    /// normal leans towards the pull direction
+    pub angle: f64,
+    pub class: DraftClass,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DraftReport {
+    pub samples: Vec<DraftSample>,
+    /// The smallest draft angle sampled on each face, faces without a valid sample are left out
+    pub min_per_face: Vec<(EdgeID, f64)>,
+}
+
+/// Classify the surface by its draft angle relative to `pull`.
+///
+/// Each face is sampled on a `resolution` x `resolution` grid placed at the centers of its
+/// cells, so samples never sit on a knot line. Samples with an angle of at least
+/// `min_draft` radians are sufficient, the ones below `-min_draft` are undercuts.
+pub fn draft_analysis<T: ControlMesh + Sync>(
+    mesh: &T,
+    pull: Vector3<f64>,
+    min_draft: f64,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<DraftReport, DraftError> {
+    let length = pull.dot(&pull).sqrt();
+    if length <= f64::EPSILON {
+        return Err(DraftError::ZeroPullDirection);
+    }
+    let pull = pull / length;
+    mesh.validate_control_mesh()?;
+
+    let knot_cache = knot_vectors(mesh, boundary);
+    let mut report = DraftReport::default();
+
+    for rect in mesh.layout().faces {
+        let mut min: Option<f64> = None;
+        for i in 0..resolution * resolution {
+            let st = (
+                cell_center::<T>(rect.s, i % resolution, resolution)?,
+                cell_center::<T>(rect.t, i / resolution, resolution)?,
+            );
+            let Some(frame) = frame_at(mesh, &knot_cache, st)? else {
+                continue;
+            };
+
+            let angle = frame.normal.dot(&pull).clamp(-1., 1.).asin();
+            let class = if angle >= min_draft {
+                DraftClass::Sufficient
+            } else if angle <= -min_draft {
+                DraftClass::Undercut
+            } else {
+                DraftClass::Insufficient
+            };
+
+            min = Some(min.map_or(angle, |m| m.min(angle)));
+            report.samples.push(DraftSample {
+                face: rect.face,
+                st: frame.st,
+                point: frame.point,
+                angle,
+                class,
+            });
+        }
+
+        if let Some(min) = min {
+            report.min_per_face.push((rect.face, min));
+        }
+    }
+
+    Ok(report)
+}
+
+/// Center of cell `i` when splitting `range` into `n` cells
+pub(crate) fn cell_center<T: ControlMesh>(
+    range: (isize, isize),
+    i: usize,
+    n: usize,
+) -> Result<T::Unit, CastError> {
+    let cast = |v: usize| T::Unit::from_usize(v).ok_or(CastError);
+    let (lo, hi) = (
+        T::Unit::from_isize(range.0).ok_or(CastError)?,
+        T::Unit::from_isize(range.1).ok_or(CastError)?,
+    );
+    Ok(lo + (hi - lo) * (cast(2 * i + 1)? / cast(2 * n)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use std::f64::consts::FRAC_PI_2;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_releases_a_flat_plane_along_its_normal() {
+        let mesh: TSpline = new_plane(4, 4, 1., 1.).unwrap();
+        let report = draft_analysis(&mesh, Vector3::z(), 0.01, 2, Boundary::Clamped).unwrap();
+
+        assert_eq!(9, report.min_per_face.len());
+        assert_eq!(36, report.samples.len());
+        for sample in &report.samples {
+            assert!((sample.angle - FRAC_PI_2).abs() < 1e-9);
+            assert_eq!(DraftClass::Sufficient, sample.class);
+        }
+    }
+
+    #[test]
+    fn it_finds_undercuts() {
+        let mesh: TSpline = new_plane(4, 4, 1., 1.).unwrap();
+        let report = draft_analysis(&mesh, -Vector3::z(), 0.01, 2, Boundary::Clamped).unwrap();
+
+        assert!(
+            report
+                .samples
+                .iter()
+                .all(|s| s.class == DraftClass::Undercut)
+        );
+    }
+
+    #[test]
+    fn it_reports_the_minimum_draft_per_face() {
+        let mut mesh: TSpline = new_plane(4, 4, 1., 1.).unwrap();
+        mesh.control_point_mut(VertID(5)).unwrap().z = 1.;
+
+        let report = draft_analysis(&mesh, Vector3::z(), 0.01, 3, Boundary::Clamped).unwrap();
+        for (face, min) in &report.min_per_face {
+            let samples = report.samples.iter().filter(|s| s.face == *face);
+            assert_eq!(*min, samples.map(|s| s.angle).fold(f64::MAX, f64::min));
+        }
+        assert!(
+            report
+                .min_per_face
+                .iter()
+                .any(|(_, min)| *min < FRAC_PI_2 - 0.1)
+        );
+    }
+
+    #[test]
+    fn it_requires_a_pull_direction() {
+        let mesh: TSpline = new_plane(2, 2, 1., 1.).unwrap();
+
+        assert!(matches!(
+            draft_analysis(&mesh, Vector3::zeros(), 0.01, 2, Boundary::Clamped),
+            Err(DraftError::ZeroPullDirection)
+        ));
+    }
+}
@@ -0,0 +1,242 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::command::{Command, CommandError};
+use crate::knot_cache::{Influence, KnotCache};
+use rayon::prelude::*;
+use std::collections::BTreeSet;
+use t_spline::algorithms::{EvalPolicy, try_subs};
+use t_spline::bounds::Bounded;
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use t_spline::{Numeric, Point3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum DriveError {
+    #[error("control point {0:?} does not exist")]
+    MissingControlPoint(VertID),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// A surface whose control points are moved from outside every frame, e.g. by a physics
+/// engine, with a tessellation that is kept up to date.
+///
+/// Moving control points leaves the knot vectors unchanged, so only the samples within
+/// the knot support of the moved points are evaluated again.
+pub struct DrivenSurface<T: ControlMesh> {
+    mesh: T,
+    resolution: usize,
+    knots: KnotCache,
+    /// Parameters of every sample, `None` outside the mesh
+    parameters: Vec<Option<(T::Unit, T::Unit)>>,
+    samples: Vec<Option<Point3<T::Unit>>>,
+    /// Samples in the support of each control point
+    supports: Vec<Vec<usize>>,
+}
+
+impl<T: ControlMesh + Sync> DrivenSurface<T> {
+    /// Tessellate `mesh` on a `resolution` x `resolution` grid like [crate::tessellate::tessellate]
+    pub fn new(mesh: T, resolution: usize, boundary: Boundary) -> Result<Self, DriveError> {
+        mesh.validate_control_mesh()?;
+        let knots = KnotCache::new(&mesh, boundary);
+        let mut surface = Self {
+            mesh,
+            resolution,
+            knots,
+            parameters: Vec::new(),
+            samples: Vec::new(),
+            supports: Vec::new(),
+        };
+        surface.rebuild();
+        Ok(surface)
+    }
+
+    pub fn mesh(&self) -> &T {
+        &self.mesh
+    }
+
+    pub fn into_mesh(self) -> T {
+        self.mesh
+    }
+
+    /// Every sample of the grid in the order of [crate::tessellate::tessellate], `None`
+    /// where the surface could not be evaluated
+    pub fn samples(&self) -> &[Option<Point3<T::Unit>>] {
+        &self.samples
+    }
+
+    /// The evaluated samples, the same points [crate::tessellate::tessellate] returns
+    pub fn points(&self) -> impl Iterator<Item = &Point3<T::Unit>> {
+        self.samples.iter().flatten()
+    }
+
+    /// Current position of every control point
+    pub fn positions(&self) -> impl Iterator<Item = Point3<T::Unit>> {
+        self.mesh
+            .control_points()
+            .iter()
+            .map(|cp| Point3::new(cp.x, cp.y, cp.z))
+    }
+
+    /// Evaluate the surface again where the knot vectors or control points have changed,
+    /// a mesh without a domain has no samples
+    fn rebuild(&mut self) {
+        let bounds = self.mesh.bounds();
+        let count = self.resolution * self.resolution;
+        self.parameters = (0..count)
+            .map(|i| {
+                let st = bounds?.interpolate(i, self.resolution);
+                self.mesh.contains_uv(st).then_some(st)
+            })
+            .collect();
+
+        self.supports = vec![Vec::new(); self.mesh.control_points().len()];
+        for (i, st) in self.parameters.iter().enumerate() {
+            let Some(st) = st else { continue };
+            for (v, knots) in self.knots.knots().iter().enumerate() {
+                if in_support(*st, knots) {
+                    self.supports[v].push(i);
+                }
+            }
+        }
+
+        self.samples = vec![None; count];
+        self.evaluate((0..count).collect());
+    }
+
+    fn evaluate(&mut self, indices: Vec<usize>) {
+        let (mesh, knots, parameters) = (&self.mesh, self.knots.knots(), &self.parameters);
+        let points: Vec<_> = indices
+            .par_iter()
+            .map(|&i| {
+                let st = parameters[i]?;
+                try_subs(mesh.control_points(), st, knots, EvalPolicy::Strict).ok()
+            })
+            .collect();
+        for (i, point) in indices.into_iter().zip(points) {
+            self.samples[i] = point;
+        }
+    }
+}
+
+impl<T: ControlMeshMut + Sync> DrivenSurface<T> {
+    /// Move control points to new positions in one batch, weights are kept.
+    ///
+    /// Nothing is moved if any of the control points does not exist. Returns the number
+    /// of samples that were evaluated again.
+    pub fn set_positions(
+        &mut self,
+        positions: impl IntoIterator<Item = (VertID, Point3<T::Unit>)>,
+    ) -> Result<usize, DriveError> {
+        let positions: Vec<_> = positions.into_iter().collect();
+        let count = self.mesh.control_points().len();
+        if let Some((v, _)) = positions.iter().find(|(v, _)| v.0 >= count) {
+            return Err(DriveError::MissingControlPoint(*v));
+        }
+
+        let mut stale = BTreeSet::new();
+        for (v, p) in positions {
+            let cp = self
+                .mesh
+                .control_point_mut(v)
+                .ok_or(DriveError::MissingControlPoint(v))?;
+            (cp.x, cp.y, cp.z) = (p.x, p.y, p.z);
+            stale.extend(&self.supports[v.0]);
+        }
+
+        let stale: Vec<_> = stale.into_iter().collect();
+        let evaluated = stale.len();
+        self.evaluate(stale);
+        Ok(evaluated)
+    }
+
+    /// Apply an edit that may change the structure of the mesh and evaluate the surface
+    /// again
+    pub fn apply_mut(&mut self, command: &impl Command<T>) -> Result<Influence, CommandError> {
+        let influence = command.apply_mut(&mut self.mesh)?;
+        self.knots.update(&self.mesh, &influence);
+        self.rebuild();
+        Ok(influence)
+    }
+}
+
+fn in_support<U: Numeric>((s, t): (U, U), knots: &LocalKnots) -> bool {
+    let inside = |u: U, knots: &[isize; 5]| match (U::from_isize(knots[0]), U::from_isize(knots[4]))
+    {
+        (Some(lo), Some(hi)) => u >= lo && u <= hi,
+        _ => true,
+    };
+    inside(s, &knots.s_knots) && inside(t, &knots.t_knots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrude_edge::ExtrudeEdge;
+    use crate::plane::new_plane;
+    use crate::tessellate::tessellate;
+    use t_spline::TSpline;
+    use t_spline::uv_mesh::ids::EdgeID;
+
+    #[test]
+    fn it_evaluates_only_the_support_of_moved_points() {
+        let mesh: TSpline = new_plane(8, 8, 7., 7.).unwrap();
+        let mut surface = DrivenSurface::new(mesh, 20, Boundary::Clamped).unwrap();
+
+        let evaluated = surface
+            .set_positions([(VertID(0), Point3::new(0., 0., 1.))])
+            .unwrap();
+
+        assert!(evaluated > 0 && evaluated < 20 * 20 / 4, "{evaluated}");
+        let expected = tessellate(surface.mesh(), 20, Boundary::Clamped).unwrap();
+        assert_eq!(expected, surface.points().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn it_moves_nothing_for_missing_points() {
+        let mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let mut surface = DrivenSurface::new(mesh, 5, Boundary::Clamped).unwrap();
+        let before: Vec<_> = surface.positions().collect();
+
+        assert!(matches!(
+            surface.set_positions([
+                (VertID(0), Point3::new(0., 0., 1.)),
+                (VertID(9), Point3::new(0., 0., 1.)),
+            ]),
+            Err(DriveError::MissingControlPoint(VertID(9)))
+        ));
+        assert_eq!(before, surface.positions().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn it_follows_structural_edits() {
+        let mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let mut surface = DrivenSurface::new(mesh, 10, Boundary::Clamped).unwrap();
+
+        surface.apply_mut(&ExtrudeEdge(EdgeID(0))).unwrap();
+        let moved = VertID(surface.mesh().control_points().len() - 1);
+        surface
+            .set_positions([(moved, Point3::new(1., -1., 1.))])
+            .unwrap();
+
+        let expected = tessellate(surface.mesh(), 10, Boundary::Clamped).unwrap();
+        assert_eq!(expected, surface.points().copied().collect::<Vec<_>>());
+    }
+}
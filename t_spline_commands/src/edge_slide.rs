@@ -0,0 +1,272 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::{Influence, two_ring};
+use crate::split_face::{SplitFaceError, fit_to_surface, knot_vectors_of};
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::uv_point::{UVCoord, UVPoint};
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum SlideError {
+    #[error("missing point")]
+    MissingPoint,
+    #[error("slide would pass over a neighbouring vertex")]
+    Blocked,
+    #[error("slide produces an invalid mesh: {0}")]
+    Invalid(#[from] ValidationError),
+    #[error("failed to fit the surface: {0}")]
+    Fit(#[from] SplitFaceError),
+}
+
+/// [edge_slide] as a [Command], built from the `vertices`, `direction`, `amount` and
+/// optional `boundary` parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeSlide {
+    pub vertices: Vec<VertID>,
+    pub direction: Direction,
+    pub amount: isize,
+    pub boundary: Boundary,
+}
+
+impl EdgeSlide {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            vertices: parameters
+                .get_list("vertices")?
+                .into_iter()
+                .map(VertID)
+                .collect(),
+            direction: parameters.get_direction("direction")?,
+            amount: parameters.get("amount")?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for EdgeSlide {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        let before = two_ring(mesh, &self.vertices);
+        edge_slide(
+            mesh,
+            &self.vertices,
+            self.direction,
+            self.amount,
+            self.boundary,
+        )
+        .map_err(CommandError::failed)?;
+        Ok(Influence::Local(two_ring(mesh, &self.vertices)).union(Influence::Local(before)))
+    }
+}
+
+/// Slide `vertices` by `amount` along their incident edges in `direction`.
+///
+/// Sliding a whole knot line keeps the perpendicular edges orthogonal, a single vertex
+/// can only slide along a line it is not connected across. Every control point whose
+/// knot vectors changed is then fitted to the old surface in a least squares sense, so
+/// the shape is kept as closely as the new knots allow.
+pub fn edge_slide<T: ControlMeshMut>(
+    mesh: &mut T,
+    vertices: &[VertID],
+    direction: Direction,
+    amount: isize,
+    boundary: Boundary,
+) -> Result<(), SlideError> {
+    mesh.validate_control_mesh()?;
+
+    for &v in vertices {
+        let neighbour = mesh.find_next_vertex_in_direction(v, direction, amount > 0);
+        if let Some(n) = neighbour
+            && !vertices.contains(&n)
+        {
+            let from = mesh.point(v).ok_or(SlideError::MissingPoint)?;
+            let to = mesh.point(n).ok_or(SlideError::MissingPoint)?;
+            let gap = to.value_in_dir(direction) - from.value_in_dir(direction);
+            if amount.abs() >= gap.abs() {
+                return Err(SlideError::Blocked);
+            }
+        }
+    }
+
+    let old_points = mesh.control_points().to_vec();
+    let old_knots = knot_vectors_of(mesh, boundary);
+
+    let originals: Vec<UVPoint> = vertices
+        .iter()
+        .map(|&v| mesh.point(v).cloned().ok_or(SlideError::MissingPoint))
+        .collect::<Result<_, _>>()?;
+    for &v in vertices {
+        mesh.point_mut(v)
+            .ok_or(SlideError::MissingPoint)?
+            .add_in_dir(direction, amount);
+    }
+
+    if let Err(e) = validate_slide(mesh, vertices) {
+        for (&v, original) in vertices.iter().zip(originals) {
+            *mesh.point_mut(v).ok_or(SlideError::MissingPoint)? = original;
+        }
+        return Err(e.into());
+    }
+
+    let fitted: Vec<VertID> = knot_vectors_of(mesh, boundary)
+        .iter()
+        .zip(&old_knots)
+        .enumerate()
+        .filter(|(_, (new, old))| new != old)
+        .map(|(v, _)| VertID(v))
+        .collect();
+    fit_to_surface(mesh, &old_points, &old_knots, &fitted, boundary)?;
+
+    Ok(())
+}
+
+fn validate_slide(mesh: &impl ControlMeshMut, vertices: &[VertID]) -> Result<(), ValidationError> {
+    for &v in vertices {
+        for e in mesh.connected_edges(v) {
+            let edge = mesh.edge(e).ok_or(ValidationError::InvalidOutgoingEdge())?;
+            if !mesh.line(edge).is_orthogonal() {
+                return Err(ValidationError::NonOrthogonal());
+            }
+        }
+    }
+    mesh.validate_uv_mesh_integrity()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::align_control_points_to_cage::align_control_points_to_cage;
+    use crate::extrude_edge::extrude_edge;
+    use crate::plane::new_plane;
+    use crate::tessellate::tessellate;
+    use crate::unit_square::unit_square;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::ids::EdgeID;
+    use t_spline::uv_mesh::{UVMesh, UVMeshMut};
+    use t_spline::{TSpline, Vector3};
+
+    fn two_faces() -> TSpline {
+        let mut mesh: TSpline = unit_square();
+        extrude_edge(&mut mesh, EdgeID(2)).unwrap();
+        for i in 0..mesh.points().len() {
+            let p = mesh.point_mut(VertID(i)).unwrap();
+            p.s *= 2;
+            p.t *= 2;
+        }
+        align_control_points_to_cage(&mut mesh).unwrap();
+        mesh
+    }
+
+    #[test]
+    fn it_slides_a_knot_line() {
+        let mut mesh = two_faces();
+        let before = *mesh.control_point(VertID(2)).unwrap();
+
+        edge_slide(
+            &mut mesh,
+            &[VertID(2), VertID(3)],
+            Direction::T,
+            1,
+            Boundary::Clamped,
+        )
+        .unwrap();
+
+        assert_eq!((2, 3), mesh.point(VertID(2)).unwrap().st());
+        assert_eq!((0, 3), mesh.point(VertID(3)).unwrap().st());
+        let (after, other) = (
+            mesh.control_point(VertID(2)).unwrap(),
+            mesh.control_point(VertID(3)).unwrap(),
+        );
+        assert!((after.x - before.x).abs() < 1e-9);
+        assert!(after.y > before.y && after.y < 4.);
+        assert!((after.y - other.y).abs() < 1e-9);
+        assert!(mesh.control_points().iter().all(|cp| cp.z.abs() < 1e-9));
+        mesh.validate_uv_mesh_integrity().unwrap();
+        mesh.validate_control_mesh().unwrap();
+    }
+
+    #[test]
+    fn it_keeps_the_shape_of_the_surface() {
+        let mut original: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        for v in 0..16 {
+            let p = original.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+        }
+        original.control_point_mut(VertID(5)).unwrap().z = 1.;
+        original.control_point_mut(VertID(10)).unwrap().z = -0.5;
+        let line = [4, 5, 6, 7].map(VertID);
+
+        let mut fitted = original.clone();
+        edge_slide(&mut fitted, &line, Direction::T, 1, Boundary::Clamped).unwrap();
+
+        // the same slide with the control points left where they were
+        let mut naive = original.clone();
+        for v in line {
+            naive.point_mut(v).unwrap().t += 1;
+        }
+
+        let before = tessellate(&original, 21, Boundary::Clamped).unwrap();
+        let deviation = |mesh: &TSpline| {
+            let after = tessellate(mesh, 21, Boundary::Clamped).unwrap();
+            assert_eq!(before.len(), after.len());
+            before
+                .iter()
+                .zip(&after)
+                .map(|(a, b)| {
+                    let d: Vector3<f64> = a - b;
+                    d.dot(&d).sqrt()
+                })
+                .fold(0., f64::max)
+        };
+        let (fitted, naive) = (deviation(&fitted), deviation(&naive));
+        assert!(fitted < 0.25, "fitted {fitted}");
+        assert!(fitted < 0.75 * naive, "fitted {fitted}, naive {naive}");
+    }
+
+    #[test]
+    fn it_blocks_sliding_past_neighbours() {
+        let mut mesh = two_faces();
+
+        let result = edge_slide(
+            &mut mesh,
+            &[VertID(2), VertID(3)],
+            Direction::T,
+            2,
+            Boundary::Clamped,
+        );
+
+        assert!(matches!(result, Err(SlideError::Blocked)));
+        assert_eq!((2, 2), mesh.point(VertID(2)).unwrap().st());
+        mesh.validate_uv_mesh_integrity().unwrap();
+    }
+
+    #[test]
+    fn it_rejects_breaking_orthogonality() {
+        let mut mesh = two_faces();
+
+        let result = edge_slide(&mut mesh, &[VertID(2)], Direction::T, 1, Boundary::Clamped);
+
+        assert!(matches!(result, Err(SlideError::Invalid(_))));
+        assert_eq!((2, 2), mesh.point(VertID(2)).unwrap().st());
+        let cp = mesh.control_point(VertID(2)).unwrap();
+        assert_eq!((2., 2.), (cp.x, cp.y));
+        mesh.validate_uv_mesh_integrity().unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::tessellate::tessellate;
+use std::collections::HashMap;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use t_spline::{Numeric, Point3};
+
+/// Memoized tessellations, keyed on the sampling parameters and checked against the
+/// revision of the mesh.
+///
+/// Revisions are unique across meshes, so passing another mesh or a clone that has since
+/// been modified only misses the cache. Unmodified clones share a revision and a result.
+#[derive(Debug, Clone)]
+pub struct TessellationCache<U: Numeric + 'static> {
+    entries: HashMap<(usize, Boundary), Entry<U>>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<U: Numeric + 'static> {
+    revision: u64,
+    points: Vec<Point3<U>>,
+}
+
+impl<U: Numeric + 'static> Default for TessellationCache<U> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+}
+
+impl<U: Numeric + 'static> TessellationCache<U> {
+    /// Tessellate `mesh`, reusing the previous result if the mesh has not changed since
+    pub fn tessellate<T: ControlMesh<Unit = U> + Sync>(
+        &mut self,
+        mesh: &T,
+        resolution: usize,
+        boundary: Boundary,
+    ) -> Result<&[Point3<U>], ValidationError> {
+        let revision = mesh.revision();
+        let key = (resolution, boundary);
+        let stale = self
+            .entries
+            .get(&key)
+            .is_none_or(|entry| entry.revision != revision);
+        if stale {
+            let points = tessellate(mesh, resolution, boundary)?;
+            self.entries.insert(key, Entry { revision, points });
+        }
+        Ok(&self.entries[&key].points)
+    }
+
+    /// Whether a tessellation of `mesh` at `resolution` is cached and up to date
+    pub fn is_cached(
+        &self,
+        mesh: &impl ControlMesh,
+        resolution: usize,
+        boundary: Boundary,
+    ) -> bool {
+        self.entries
+            .get(&(resolution, boundary))
+            .is_some_and(|entry| entry.revision == mesh.revision())
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_reuses_tessellations_until_the_mesh_changes() {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let mut cache = TessellationCache::default();
+
+        let before = cache
+            .tessellate(&mesh, 4, Boundary::Clamped)
+            .unwrap()
+            .to_vec();
+        assert!(cache.is_cached(&mesh, 4, Boundary::Clamped));
+        assert!(!cache.is_cached(&mesh, 5, Boundary::Clamped));
+
+        mesh.control_point_mut(VertID(5)).unwrap().z = 1.;
+        assert!(!cache.is_cached(&mesh, 4, Boundary::Clamped));
+
+        let after = cache.tessellate(&mesh, 4, Boundary::Clamped).unwrap();
+        assert_ne!(before, after);
+        assert!(cache.is_cached(&mesh, 4, Boundary::Clamped));
+    }
+
+    #[test]
+    fn it_tells_diverged_clones_apart() {
+        let mut a: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let mut b = a.clone();
+        a.control_point_mut(VertID(5)).unwrap().z = 1.;
+        b.control_point_mut(VertID(5)).unwrap().z = -1.;
+        let mut cache = TessellationCache::default();
+
+        let first = cache.tessellate(&a, 4, Boundary::Clamped).unwrap().to_vec();
+        assert!(!cache.is_cached(&b, 4, Boundary::Clamped));
+
+        let second = cache.tessellate(&b, 4, Boundary::Clamped).unwrap();
+        assert_ne!(first, second);
+    }
+}
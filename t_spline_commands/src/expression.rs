@@ -0,0 +1,469 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64};
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::Influence;
+use num_traits::ToPrimitive;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::ids::VertID;
+use thiserror::Error;
+
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum ExpressionError {
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+    #[error("unexpected {found:?} at {position}")]
+    Unexpected { position: usize, found: char },
+    #[error("unknown variable {0}")]
+    UnknownVariable(String),
+    #[error("unknown function {0}")]
+    UnknownFunction(String),
+    #[error("{function} takes {expected} arguments, got {got}")]
+    ArgumentCount {
+        function: String,
+        expected: usize,
+        got: usize,
+    },
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("expression is not finite at control point {0:?}")]
+    NotFinite(VertID),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Function {
+    Unary(fn(f64) -> f64),
+    Binary(fn(f64, f64) -> f64),
+}
+
+impl Function {
+    fn named(name: &str) -> Option<Self> {
+        use Function::*;
+        Some(match name {
+            "sin" => Unary(f64::sin),
+            "cos" => Unary(f64::cos),
+            "tan" => Unary(f64::tan),
+            "asin" => Unary(f64::asin),
+            "acos" => Unary(f64::acos),
+            "atan" => Unary(f64::atan),
+            "sqrt" => Unary(f64::sqrt),
+            "abs" => Unary(f64::abs),
+            "exp" => Unary(f64::exp),
+            "ln" => Unary(f64::ln),
+            "floor" => Unary(f64::floor),
+            "ceil" => Unary(f64::ceil),
+            "atan2" => Binary(f64::atan2),
+            "min" => Binary(f64::min),
+            "max" => Binary(f64::max),
+            "pow" => Binary(f64::powf),
+            _ => return None,
+        })
+    }
+
+    fn arity(&self) -> usize {
+        match self {
+            Function::Unary(_) => 1,
+            Function::Binary(_) => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Number(f64),
+    Variable(usize),
+    Negate(Box<Node>),
+    Binary(char, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+/// An arithmetic expression over named variables, e.g. `0.2 * sin(3 * s) + z`.
+///
+/// Supports `+ - * / % ^`, parentheses, the constant `pi` and the functions `sin`, `cos`,
+/// `tan`, `asin`, `acos`, `atan`, `atan2`, `sqrt`, `abs`, `exp`, `ln`, `floor`, `ceil`,
+/// `min`, `max` and `pow`.
+#[derive(Debug, Clone)]
+pub struct Expression(Node);
+
+impl Expression {
+    /// Parse `source`, variables are resolved to their index in `variables`
+    pub fn parse(source: &str, variables: &[&str]) -> Result<Self, ExpressionError> {
+        let mut parser = Parser {
+            source,
+            position: 0,
+            variables,
+        };
+        let node = parser.sum()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            Some(found) => Err(ExpressionError::Unexpected {
+                position: parser.position,
+                found,
+            }),
+            None => Ok(Self(node)),
+        }
+    }
+
+    /// Value of the expression with every variable set to the value at its index
+    pub fn evaluate(&self, values: &[f64]) -> f64 {
+        evaluate(&self.0, values)
+    }
+}
+
+fn evaluate(node: &Node, values: &[f64]) -> f64 {
+    match node {
+        Node::Number(v) => *v,
+        Node::Variable(i) => values[*i],
+        Node::Negate(a) => -evaluate(a, values),
+        Node::Binary(op, a, b) => {
+            let (a, b) = (evaluate(a, values), evaluate(b, values));
+            match op {
+                '+' => a + b,
+                '-' => a - b,
+                '*' => a * b,
+                '/' => a / b,
+                '%' => a.rem_euclid(b),
+                _ => a.powf(b),
+            }
+        }
+        Node::Call(Function::Unary(f), args) => f(evaluate(&args[0], values)),
+        Node::Call(Function::Binary(f), args) => {
+            f(evaluate(&args[0], values), evaluate(&args[1], values))
+        }
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+    variables: &'a [&'a str],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    /// Consume `c` if it is the next character
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(c);
+        if found {
+            self.position += c.len_utf8();
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ExpressionError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn unexpected(&self) -> ExpressionError {
+        match self.peek() {
+            Some(found) => ExpressionError::Unexpected {
+                position: self.position,
+                found,
+            },
+            None => ExpressionError::UnexpectedEnd,
+        }
+    }
+
+    /// Consume the longest prefix of characters matching `accept`
+    fn take(&mut self, accept: impl Fn(char) -> bool) -> &str {
+        let start = self.position;
+        let len = self.source[start..]
+            .find(|c| !accept(c))
+            .unwrap_or(self.source.len() - start);
+        self.position += len;
+        &self.source[start..start + len]
+    }
+
+    fn sum(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.product()?;
+        loop {
+            let op = if self.eat('+') {
+                '+'
+            } else if self.eat('-') {
+                '-'
+            } else {
+                return Ok(node);
+            };
+            node = Node::Binary(op, Box::new(node), Box::new(self.product()?));
+        }
+    }
+
+    fn product(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.unary()?;
+        loop {
+            let Some(op) = ['*', '/', '%'].into_iter().find(|&op| self.eat(op)) else {
+                return Ok(node);
+            };
+            node = Node::Binary(op, Box::new(node), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Node, ExpressionError> {
+        if self.eat('-') {
+            return Ok(Node::Negate(Box::new(self.unary()?)));
+        }
+        let base = self.atom()?;
+        if self.eat('^') {
+            // right associative and binds tighter than negation, `-2^2` is `-4`
+            return Ok(Node::Binary('^', Box::new(base), Box::new(self.unary()?)));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Node, ExpressionError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                let node = self.sum()?;
+                self.expect(')')?;
+                Ok(node)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let start = self.position;
+                let number = self.take(|c| c.is_ascii_digit() || c == '.');
+                number
+                    .parse()
+                    .map(Node::Number)
+                    .map_err(|_| ExpressionError::Unexpected {
+                        position: start,
+                        found: c,
+                    })
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = self.take(|c| c.is_alphanumeric() || c == '_').to_string();
+                if self.eat('(') {
+                    self.call(name)
+                } else if let Some(i) = self.variables.iter().position(|v| *v == name) {
+                    Ok(Node::Variable(i))
+                } else if name == "pi" {
+                    Ok(Node::Number(std::f64::consts::PI))
+                } else {
+                    Err(ExpressionError::UnknownVariable(name))
+                }
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// Arguments of a call to `name` after the opening parenthesis
+    fn call(&mut self, name: String) -> Result<Node, ExpressionError> {
+        let function =
+            Function::named(&name).ok_or_else(|| ExpressionError::UnknownFunction(name.clone()))?;
+        let mut args = Vec::new();
+        if !self.eat(')') {
+            args.push(self.sum()?);
+            while self.eat(',') {
+                args.push(self.sum()?);
+            }
+            self.expect(')')?;
+        }
+        if args.len() != function.arity() {
+            return Err(ExpressionError::ArgumentCount {
+                function: name,
+                expected: function.arity(),
+                got: args.len(),
+            });
+        }
+        Ok(Node::Call(function, args))
+    }
+}
+
+/// Variables of the expressions of [MapControlPoints]: the control point coordinates,
+/// its parametric coordinates and its index
+pub const VERTEX_VARIABLES: [&str; 7] = ["x", "y", "z", "w", "s", "t", "i"];
+
+/// [map_control_points] as a [Command], built from the `expression` parameter of
+/// assignments separated by `;`, e.g. `z = 0.2 * sin(3 * s); x = x + t`
+#[derive(Debug, Clone)]
+pub struct MapControlPoints {
+    /// Coordinate index (`x`, `y`, `z` or `w`) and its new value
+    pub assignments: Vec<(usize, Expression)>,
+}
+
+impl MapControlPoints {
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let assignments = source
+            .split(';')
+            .filter(|assignment| !assignment.trim().is_empty())
+            .map(|assignment| {
+                let (target, expression) = assignment
+                    .split_once('=')
+                    .ok_or(ExpressionError::UnexpectedEnd)?;
+                let target = target.trim();
+                let axis = VERTEX_VARIABLES[..4]
+                    .iter()
+                    .position(|v| *v == target)
+                    .ok_or_else(|| ExpressionError::UnknownVariable(target.to_string()))?;
+                Ok((axis, Expression::parse(expression, &VERTEX_VARIABLES)?))
+            })
+            .collect::<Result<_, ExpressionError>>()?;
+        Ok(Self { assignments })
+    }
+
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        parameters.get_with("expression", |v| Self::parse(v).ok())
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for MapControlPoints {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        map_control_points(mesh, &self.assignments).map_err(CommandError::failed)?;
+        Ok(Influence::Geometry)
+    }
+}
+
+/// Set coordinates of every control point from expressions over [VERTEX_VARIABLES].
+///
+/// All assignments of a control point see its coordinates from before any of them, so
+/// `x = y; y = x` swaps the two. Nothing is changed if any value is not finite.
+pub fn map_control_points<T: ControlMeshMut>(
+    mesh: &mut T,
+    assignments: &[(usize, Expression)],
+) -> Result<(), ExpressionError> {
+    let mut updates = Vec::with_capacity(mesh.control_points().len());
+    for (i, cp) in mesh.control_points().iter().enumerate() {
+        let id = VertID(i);
+        let p = mesh.point(id).ok_or(ExpressionError::MissingControlPoint)?;
+        let coordinates = [cp.x, cp.y, cp.z, cp.w]
+            .map(|v| v.to_f64())
+            .map(|v| v.ok_or(CastError));
+        let [x, y, z, w] = coordinates;
+        let values = [x?, y?, z?, w?, p.s as f64, p.t as f64, i as f64];
+
+        let mut updated = [cp.x, cp.y, cp.z, cp.w];
+        for (axis, expression) in assignments {
+            let value = expression.evaluate(&values);
+            if !value.is_finite() {
+                return Err(ExpressionError::NotFinite(id));
+            }
+            updated[*axis] = from_f64::<T::Unit>(value)?;
+        }
+        updates.push(updated);
+    }
+
+    for (i, [x, y, z, w]) in updates.into_iter().enumerate() {
+        let cp = mesh
+            .control_point_mut(VertID(i))
+            .ok_or(ExpressionError::MissingControlPoint)?;
+        (cp.x, cp.y, cp.z, cp.w) = (x, y, z, w);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::CommandRegistry;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::UVMesh;
+
+    fn eval(source: &str) -> f64 {
+        Expression::parse(source, &["a", "b"])
+            .unwrap()
+            .evaluate(&[2., 3.])
+    }
+
+    #[test]
+    fn it_evaluates_expressions() {
+        assert_eq!(14., eval("a + b * 4"));
+        assert_eq!(20., eval("(a + b) * 4"));
+        assert_eq!(-4., eval("-a^2"));
+        assert_eq!(512., eval("a ^ b ^ 2"));
+        assert_eq!(1., eval("7 % b"));
+        assert_eq!(3., eval("max(a, b)"));
+        assert_eq!(0., eval("sin(pi * 0)"));
+        assert_eq!(0.5, eval(".5"));
+    }
+
+    #[test]
+    fn it_reports_parse_errors() {
+        let parse = |source| Expression::parse(source, &["a"]).unwrap_err();
+
+        assert_eq!(ExpressionError::UnexpectedEnd, parse("a +"));
+        assert_eq!(
+            ExpressionError::Unexpected {
+                position: 2,
+                found: ')'
+            },
+            parse("a )")
+        );
+        assert_eq!(ExpressionError::UnknownVariable("q".into()), parse("q"));
+        assert_eq!(ExpressionError::UnknownFunction("f".into()), parse("f(a)"));
+        assert_eq!(
+            ExpressionError::ArgumentCount {
+                function: "min".into(),
+                expected: 2,
+                got: 1
+            },
+            parse("min(a)")
+        );
+    }
+
+    #[test]
+    fn it_maps_control_points() {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+
+        CommandRegistry::default()
+            .apply_mut(
+                &mut mesh,
+                "map_control_points",
+                &Parameters::default().with("expression", "z = 0.5 * s + t; x = y; y = x"),
+            )
+            .unwrap();
+
+        for (i, cp) in mesh.control_points().iter().enumerate() {
+            let p = mesh.point(VertID(i)).unwrap();
+            assert_eq!(0.5 * p.s as f64 + p.t as f64, cp.z);
+            assert_eq!((p.t as f64, p.s as f64), (cp.x, cp.y));
+        }
+    }
+
+    #[test]
+    fn it_rejects_values_that_are_not_finite() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let before = mesh.clone();
+
+        assert!(matches!(
+            map_control_points(
+                &mut mesh,
+                &MapControlPoints::parse("z = 1 / s").unwrap().assignments
+            ),
+            Err(ExpressionError::NotFinite(VertID(0)))
+        ));
+        assert_eq!(before.control_points(), mesh.control_points());
+    }
+}
@@ -14,9 +14,11 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::{Influence, two_ring};
 use t_spline::control_mesh::ControlMeshMut;
 use t_spline::uv_mesh::half_edge::HalfEdge;
-use t_spline::uv_mesh::ids::EdgeID;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
 use t_spline::uv_mesh::uv_point::UVCoord;
 use thiserror::Error;
 
@@ -30,8 +32,46 @@ pub enum ExtrudeError {
     HasTwin(),
 }
 
+/// Elements created by [extrude_edge]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extrusion {
+    /// The vertices of the far side, opposite the ends of the extruded edge
+    pub vertices: [VertID; 2],
+    /// The half edges of the new face, starting with the twin of the extruded edge
+    pub edges: [EdgeID; 4],
+    /// The new face
+    pub face: EdgeID,
+}
+
+/// [extrude_edge] as a [Command], built from the `edge` parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtrudeEdge(pub EdgeID);
+
+impl ExtrudeEdge {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self(EdgeID(parameters.get("edge")?)))
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for ExtrudeEdge {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        let edge = mesh
+            .edge(self.0)
+            .ok_or_else(|| CommandError::failed(ExtrudeError::MissingEdge()))?;
+        let mut seeds = vec![edge.origin, mesh.next_edge(edge).origin];
+
+        let extrusion = extrude_edge(mesh, self.0).map_err(CommandError::failed)?;
+
+        seeds.extend(extrusion.vertices);
+        Ok(Influence::Local(two_ring(mesh, &seeds)))
+    }
+}
+
 /// extrude `edge_id` by 1 unit
-pub fn extrude_edge(mesh: &mut impl ControlMeshMut, edge_id: EdgeID) -> Result<(), ExtrudeError> {
+pub fn extrude_edge(
+    mesh: &mut impl ControlMeshMut,
+    edge_id: EdgeID,
+) -> Result<Extrusion, ExtrudeError> {
     let edge = mesh.edge(edge_id).ok_or(ExtrudeError::MissingEdge())?;
     if edge.twin.is_some() {
         return Err(ExtrudeError::HasTwin());
@@ -127,7 +167,11 @@ pub fn extrude_edge(mesh: &mut impl ControlMeshMut, edge_id: EdgeID) -> Result<(
         prev: e3,
     });
 
-    Ok(())
+    Ok(Extrusion {
+        vertices: [c_id, d_id],
+        edges: [e1, e2, e3, e4],
+        face: e1,
+    })
 }
 
 #[cfg(test)]
@@ -137,7 +181,6 @@ mod tests {
     use crate::unit_square::unit_square;
     use t_spline::TSpline;
     use t_spline::control_mesh::ControlMesh;
-    use t_spline::uv_mesh::ids::VertID;
     use t_spline::uv_mesh::{Boundary, UVMesh};
 
     #[test]
@@ -160,6 +203,22 @@ mod tests {
         assert_eq!(2, mesh.faces().count());
     }
 
+    #[test]
+    fn it_returns_the_created_elements() {
+        let mut mesh: TSpline = unit_square();
+
+        let extrusion = extrude_edge(&mut mesh, EdgeID(2)).unwrap();
+
+        assert_eq!([VertID(4), VertID(5)], extrusion.vertices);
+        let mut face: Vec<_> = mesh
+            .edge_loop(mesh.edge(extrusion.face).unwrap())
+            .map(|(id, _)| id)
+            .collect();
+        face.sort();
+        assert_eq!(extrusion.edges.to_vec(), face);
+        assert_eq!(Some(extrusion.edges[0]), mesh.edge(EdgeID(2)).unwrap().twin);
+    }
+
     #[test]
     fn it_tessellates_extrusion() {
         let mut mesh: TSpline = unit_square();
@@ -0,0 +1,263 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::displace::{DisplaceError, vertex_normals};
+use crate::plane::{GridRows, GridSide, PlaneError, grid_rows, new_plane};
+use num_traits::One;
+use std::f64::consts::PI;
+use t_spline::Point3;
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::Boundary;
+use t_spline::uv_mesh::ids::VertID;
+use thiserror::Error;
+
+/// Distance below which boundary control points of two patches count as shared
+const TOLERANCE: f64 = 1e-9;
+
+/// Number of sides of the polygon around each cross section of a blend
+const SEGMENTS: usize = 4;
+
+#[derive(Clone, Debug, Error)]
+pub enum FilletError {
+    #[error("patch {0} does not exist")]
+    MissingPatch(usize),
+    #[error("patch {0} is not a grid of control points")]
+    NotAGrid(usize),
+    #[error("the patches do not share a boundary")]
+    NoSharedBoundary,
+    #[error("radius does not fit between the boundary and the next row of control points")]
+    RadiusTooLarge,
+    #[error("the patches fold back onto each other")]
+    Folded,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("failed to build the blend patch: {0}")]
+    Plane(#[from] PlaneError),
+    #[error(transparent)]
+    Normals(#[from] DisplaceError),
+}
+
+/// Round the edge where patches `a` and `b` of a multi-patch model share a boundary.
+///
+/// Both patches are set back from the edge so a circular arc of `radius` fits between
+/// them, and a blend patch close to the arc that meets both along their new boundaries
+/// with matching tangents is added to `patches`. The patches must be grids of control points like
+/// [new_plane] and [crate::cuboid::cuboid] make. Patches touching the ends of the edge are
+/// not adjusted.
+///
+/// Returns the index of the blend patch.
+pub fn fillet<T: ControlMeshMut + Default>(
+    patches: &mut Vec<T>,
+    a: usize,
+    b: usize,
+    radius: f64,
+) -> Result<usize, FilletError> {
+    let mesh_a = patches.get(a).ok_or(FilletError::MissingPatch(a))?;
+    let mesh_b = patches.get(b).ok_or(FilletError::MissingPatch(b))?;
+    let (side_a, side_b) = shared_side(mesh_a, a, mesh_b, b)?;
+
+    // cross sections of the blend from patch a to patch b, the control polygon of each
+    // arc is its start, the corners of a polygon circumscribing it and its end
+    let mut rows = [const { Vec::new() }; SEGMENTS + 2];
+    for k in 0..side_a.boundary.len() {
+        let edge = position(mesh_a, side_a.boundary[k])?;
+        let to_a = position(mesh_a, side_a.inner[k])? - edge;
+        let to_b = position(mesh_b, side_b.inner[k])? - edge;
+        let (length_a, length_b) = (to_a.dot(&to_a).sqrt(), to_b.dot(&to_b).sqrt());
+        let (ta, tb) = (to_a / length_a, to_b / length_b);
+
+        // the angle between the patches at the edge, the arc turns by its supplement
+        let between = ta.dot(&tb).clamp(-1., 1.).acos();
+        if between < 1e-6 || PI - between < 1e-6 {
+            return Err(FilletError::Folded);
+        }
+        let setback = radius / (between / 2.).tan();
+        if setback >= length_a || setback >= length_b {
+            return Err(FilletError::RadiusTooLarge);
+        }
+
+        let bisector = ta + tb;
+        let center =
+            edge + bisector / bisector.dot(&bisector).sqrt() * radius / (between / 2.).sin();
+        let (start, end) = (edge + ta * setback, edge + tb * setback);
+        let u = (start - center) / radius;
+        let w = -ta;
+        let turn = PI - between;
+        let step = turn / SEGMENTS as f64;
+
+        rows[0].push(start);
+        for (i, row) in rows[1..=SEGMENTS].iter_mut().enumerate() {
+            let angle = step * (i as f64 + 0.5);
+            row.push(center + (u * angle.cos() + w * angle.sin()) * radius / (step / 2.).cos());
+        }
+        rows[SEGMENTS + 1].push(end);
+    }
+
+    // the blend faces the same side as patch a
+    let normal = vertex_normals(mesh_a, Boundary::Clamped)?[side_a.boundary[0].0];
+    let count = rows[0].len();
+    let facing = (rows[0][count - 1] - rows[0][0]).cross(&(rows[SEGMENTS + 1][0] - rows[0][0]));
+    let flip = facing.dot(&normal) < 0.;
+    let last = side_a.knots[count - 1];
+
+    let mut blend: T = new_plane(count, SEGMENTS + 2, T::Unit::one(), T::Unit::one())?;
+    for (j, row) in rows.iter().enumerate() {
+        for i in 0..count {
+            let (k, s) = if flip {
+                (count - 1 - i, last - side_a.knots[count - 1 - i])
+            } else {
+                (i, side_a.knots[i] - side_a.knots[0])
+            };
+            let v = VertID(j * count + i);
+            set_position(&mut blend, v, row[k])?;
+            if let Some(p) = blend.point_mut(v) {
+                p.s = s;
+            }
+        }
+    }
+
+    for (patch, side, row) in [(a, &side_a, &rows[0]), (b, &side_b, &rows[SEGMENTS + 1])] {
+        for (&v, &p) in side.boundary.iter().zip(row) {
+            set_position(&mut patches[patch], v, p)?;
+        }
+    }
+    patches.push(blend);
+    Ok(patches.len() - 1)
+}
+
+/// The sides of `a` and `b` with the same control points, ordered alike
+fn shared_side<T: ControlMesh>(
+    a: &T,
+    index_a: usize,
+    b: &T,
+    index_b: usize,
+) -> Result<(GridRows, GridRows), FilletError> {
+    let sides_b = sides(b, index_b)?;
+    for side_a in sides(a, index_a)? {
+        let points_a = side_a
+            .boundary
+            .iter()
+            .map(|&v| position(a, v))
+            .collect::<Result<Vec<_>, _>>()?;
+        for side_b in &sides_b {
+            if side_b.boundary.len() != points_a.len() {
+                continue;
+            }
+            let points_b = side_b
+                .boundary
+                .iter()
+                .map(|&v| position(b, v))
+                .collect::<Result<Vec<_>, _>>()?;
+            let close = |p: &Point3<f64>, q: &Point3<f64>| (p - q).abs().max() < TOLERANCE;
+            if points_a.iter().zip(&points_b).all(|(p, q)| close(p, q)) {
+                return Ok((side_a, side_b.clone()));
+            }
+            if points_a
+                .iter()
+                .zip(points_b.iter().rev())
+                .all(|(p, q)| close(p, q))
+            {
+                let mut side_b = side_b.clone();
+                side_b.boundary.reverse();
+                side_b.inner.reverse();
+                return Ok((side_a, side_b));
+            }
+        }
+    }
+    Err(FilletError::NoSharedBoundary)
+}
+
+/// The four boundary sides of a grid shaped patch
+fn sides<T: ControlMesh>(mesh: &T, index: usize) -> Result<Vec<GridRows>, FilletError> {
+    GridSide::ALL
+        .iter()
+        .map(|&side| grid_rows(mesh, side).map_err(|_| FilletError::NotAGrid(index)))
+        .collect()
+}
+
+fn position<T: ControlMesh>(mesh: &T, v: VertID) -> Result<Point3<f64>, FilletError> {
+    let cp = mesh.control_point(v).ok_or(FilletError::NotAGrid(v.0))?;
+    let cast = to_f64::<T::Unit>;
+    Ok(Point3::new(cast(cp.x)?, cast(cp.y)?, cast(cp.z)?))
+}
+
+fn set_position<T: ControlMeshMut>(
+    mesh: &mut T,
+    v: VertID,
+    p: Point3<f64>,
+) -> Result<(), FilletError> {
+    let cast = from_f64::<T::Unit>;
+    let cp = mesh
+        .control_point_mut(v)
+        .ok_or(FilletError::NotAGrid(v.0))?;
+    (cp.x, cp.y, cp.z) = (cast(p.x)?, cast(p.y)?, cast(p.z)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cuboid::cuboid;
+    use crate::tessellate::tessellate;
+    use t_spline::{TSpline, Vector3};
+
+    #[test]
+    fn it_rounds_the_edge_of_a_box() {
+        let mut patches: Vec<TSpline> = cuboid([1., 1., 1.]).unwrap();
+
+        // top and +x meet along x = 1, z = 1
+        let blend = fillet(&mut patches, 0, 2, 0.25).unwrap();
+
+        assert_eq!(6, blend);
+        patches[blend].validate_control_mesh().unwrap();
+        let center = Vector3::new(0.75, 0., 0.75);
+        for p in tessellate(&patches[blend], 10, Boundary::Clamped).unwrap() {
+            let r = Vector3::new(p.x, 0., p.z) - center;
+            assert!((r.dot(&r).sqrt() - 0.25).abs() < 5e-3, "{p:?}");
+            assert!(p.x >= 0.75 - 1e-9 && p.z >= 0.75 - 1e-9, "{p:?}");
+        }
+        for p in tessellate(&patches[0], 10, Boundary::Clamped).unwrap() {
+            assert!(p.x <= 0.75 + 1e-9, "{p:?}");
+        }
+    }
+
+    #[test]
+    fn it_faces_outwards() {
+        let mut patches: Vec<TSpline> = cuboid([1., 1., 1.]).unwrap();
+        let blend = fillet(&mut patches, 2, 0, 0.25).unwrap();
+
+        let normals = vertex_normals(&patches[blend], Boundary::Clamped).unwrap();
+        let outward = Vector3::new(1., 0., 1.);
+        assert!(normals.iter().all(|n| n.dot(&outward) > 0.), "{normals:?}");
+    }
+
+    #[test]
+    fn it_rejects_patches_that_do_not_meet() {
+        let mut patches: Vec<TSpline> = cuboid([1., 1., 1.]).unwrap();
+
+        assert!(matches!(
+            fillet(&mut patches, 0, 1, 0.25),
+            Err(FilletError::NoSharedBoundary)
+        ));
+        assert!(matches!(
+            fillet(&mut patches, 0, 2, 0.75),
+            Err(FilletError::RadiusTooLarge)
+        ));
+        assert_eq!(6, patches.len());
+    }
+}
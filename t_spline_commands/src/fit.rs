@@ -0,0 +1,897 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::command::{Command, CommandError, Parameters};
+use crate::expression::Expression;
+use crate::knot_cache::Influence;
+use crate::remesh::TriangleMesh;
+use crate::split_face::{SplitAt, SplitFaceError, knot_vectors_of, solve, split_face};
+use num_traits::ToPrimitive;
+use std::collections::BTreeMap;
+use std::io;
+use std::ops::ControlFlow;
+use t_spline::algorithms::cubic_basis_function;
+use t_spline::bounds::Bounded;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::layout::FaceRect;
+use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum FitError {
+    #[error("no samples to fit")]
+    NoSamples,
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("least squares system is singular")]
+    Singular,
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// A point the surface should pass through at `uv`, given as a fraction of the
+/// parametric bounds of the mesh so it stays put when the knots are scaled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub uv: (f64, f64),
+    pub point: Point3<f64>,
+}
+
+/// Distances of the samples from the surface
+#[derive(Debug, Clone, PartialEq)]
+pub struct Residuals {
+    pub distances: Vec<f64>,
+    pub rms: f64,
+    pub max: f64,
+}
+
+impl Residuals {
+    fn new(distances: Vec<f64>) -> Self {
+        let count = distances.len().max(1) as f64;
+        Self {
+            rms: (distances.iter().map(|d| d * d).sum::<f64>() / count).sqrt(),
+            max: distances.iter().copied().fold(0., f64::max),
+            distances,
+        }
+    }
+}
+
+/// Move every control point so the surface passes through `samples` in a least
+/// squares sense, the T-mesh and weights stay the same.
+///
+/// Every point is weakly held at its start, so points without samples in their
+/// support stay where they are.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), samples = samples.len()))
+)]
+pub fn fit<T: ControlMeshMut>(
+    mesh: &mut T,
+    samples: &[Sample],
+    boundary: Boundary,
+) -> Result<Residuals, FitError> {
+    mesh.validate_control_mesh()?;
+    if samples.is_empty() {
+        return Err(FitError::NoSamples);
+    }
+    let (knots, points) = knots_and_points(mesh, boundary)?;
+
+    let n = points.len();
+    let mut a = vec![vec![0.; n]; n];
+    let mut rhs = vec![[0.; 3]; n];
+    for sample in samples {
+        let row = basis(parameter(mesh, sample.uv), &knots, &points);
+        for &(c0, r0) in &row {
+            for &(c1, r1) in &row {
+                a[c0][c1] += r0 * r1;
+            }
+            (0..3).for_each(|d| rhs[c0][d] += r0 * sample.point[d]);
+        }
+    }
+
+    let diagonal = (0..n).map(|i| a[i][i]).sum::<f64>() / n.max(1) as f64;
+    let lambda = 1e-6 * diagonal.max(1e-12);
+    for (c, p) in points.iter().enumerate() {
+        a[c][c] += lambda;
+        (0..3).for_each(|d| rhs[c][d] += lambda * p[d]);
+    }
+
+    let solution = solve(a, rhs).ok_or(FitError::Singular)?;
+    let uncast = from_f64::<T::Unit>;
+    for (v, p) in solution.iter().enumerate() {
+        let cp = mesh
+            .control_point_mut(VertID(v))
+            .ok_or(FitError::MissingControlPoint)?;
+        (cp.x, cp.y, cp.z) = (uncast(p[0])?, uncast(p[1])?, uncast(p[2])?);
+    }
+    residuals(mesh, samples, boundary)
+}
+
+/// Distance of every sample from the surface of `mesh`
+pub fn residuals<T: ControlMeshMut>(
+    mesh: &T,
+    samples: &[Sample],
+    boundary: Boundary,
+) -> Result<Residuals, FitError> {
+    let (knots, points) = knots_and_points(mesh, boundary)?;
+    let distances = samples
+        .iter()
+        .map(|sample| {
+            let mut p = Vector3::zeros();
+            for (i, r) in basis(parameter(mesh, sample.uv), &knots, &points) {
+                p += Vector3::new(points[i][0], points[i][1], points[i][2]) * r;
+            }
+            let d = p - sample.point.coords;
+            d.dot(&d).sqrt()
+        })
+        .collect();
+    Ok(Residuals::new(distances))
+}
+
+/// What [face_deviations] compares the surface with
+#[derive(Debug, Clone, Copy)]
+pub enum Target<'a> {
+    /// Points at known parameters, each compared with the surface at its parameter
+    Samples(&'a [Sample]),
+    /// A triangle mesh, compared with a `resolution` x `resolution` grid of surface
+    /// points within every face by the distance to its closest triangle
+    Mesh {
+        mesh: &'a TriangleMesh,
+        resolution: usize,
+    },
+}
+
+/// Deviation of the surface from a [Target] within a face
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FaceDeviation {
+    /// Number of distances measured in the face
+    pub count: usize,
+    pub rms: f64,
+    pub max: f64,
+}
+
+/// Deviation of the surface of `mesh` from `target` for every face, keyed by the half
+/// edge of the face as in its parametric layout.
+///
+/// Faces without any sample of the target have a count of zero. [refine] splits the
+/// faces whose `max` exceeds its tolerance.
+pub fn face_deviations<T: ControlMeshMut>(
+    mesh: &T,
+    target: Target,
+    boundary: Boundary,
+) -> Result<BTreeMap<EdgeID, FaceDeviation>, FitError> {
+    let deviation = |distances: Vec<f64>| {
+        let count = distances.len();
+        let residuals = Residuals::new(distances);
+        FaceDeviation {
+            count,
+            rms: residuals.rms,
+            max: residuals.max,
+        }
+    };
+
+    match target {
+        Target::Samples(samples) => {
+            let residuals = residuals(mesh, samples, boundary)?;
+            Ok(samples_by_face(mesh, samples)
+                .into_iter()
+                .map(|(rect, members)| {
+                    let distances = members.iter().map(|&i| residuals.distances[i]).collect();
+                    (rect.face, deviation(distances))
+                })
+                .collect())
+        }
+        Target::Mesh {
+            mesh: target,
+            resolution,
+        } => {
+            let (knots, points) = knots_and_points(mesh, boundary)?;
+            let mut deviations = BTreeMap::new();
+            for rect in mesh.layout().faces {
+                let mut distances = Vec::with_capacity(resolution * resolution);
+                for i in 0..resolution * resolution {
+                    let center = |(lo, hi): (isize, isize), i: usize| {
+                        lo as f64 + (hi - lo) as f64 * (i as f64 + 0.5) / resolution as f64
+                    };
+                    let st = (
+                        center(rect.s, i % resolution),
+                        center(rect.t, i / resolution),
+                    );
+                    let mut p = Vector3::zeros();
+                    for (c, r) in basis(st, &knots, &points) {
+                        p += Vector3::new(points[c][0], points[c][1], points[c][2]) * r;
+                    }
+                    let closest = target
+                        .triangles
+                        .iter()
+                        .map(|tri| {
+                            let [a, b, c] = tri.map(|v| target.points[v].coords);
+                            let d = closest_on_triangle(p, a, b, c) - p;
+                            d.dot(&d)
+                        })
+                        .fold(f64::INFINITY, f64::min);
+                    if closest.is_finite() {
+                        distances.push(closest.sqrt());
+                    }
+                }
+                deviations.insert(rect.face, deviation(distances));
+            }
+            Ok(deviations)
+        }
+    }
+}
+
+/// What [refine] did with a face whose samples were too far from the surface
+#[derive(Debug, Clone)]
+pub enum Decision {
+    /// Split in the middle of its longer parametric side
+    Split(Direction),
+    /// Left as it is because splitting failed
+    Skipped(SplitFaceError),
+}
+
+/// A face [refine] looked at and why
+#[derive(Debug, Clone)]
+pub struct RefinementEntry {
+    pub iteration: usize,
+    /// Parametric extent of the face before the split
+    pub s: (isize, isize),
+    pub t: (isize, isize),
+    /// Number of samples within the face
+    pub samples: usize,
+    /// The face was refined because `error_before` exceeded it
+    pub tolerance: f64,
+    /// Largest and RMS distance of the samples of the face before refining
+    pub error_before: f64,
+    pub rms_before: f64,
+    /// Largest distance of the same samples after the refined mesh was fitted again
+    pub error_after: Option<f64>,
+    pub decision: Decision,
+}
+
+/// Every refinement decision in order, for tuning tolerances and studying convergence
+#[derive(Debug, Clone, Default)]
+pub struct RefinementLog {
+    pub entries: Vec<RefinementEntry>,
+}
+
+impl RefinementLog {
+    /// Write one JSON object per entry and line
+    pub fn write_json_lines(&self, w: &mut impl io::Write) -> io::Result<()> {
+        for e in &self.entries {
+            let decision = match &e.decision {
+                Decision::Split(Direction::S) => "\"split_s\"".to_string(),
+                Decision::Split(Direction::T) => "\"split_t\"".to_string(),
+                Decision::Skipped(_) => "\"skipped\"".to_string(),
+            };
+            write!(
+                w,
+                r#"{{"iteration":{},"s":[{},{}],"t":[{},{}],"samples":{},"tolerance":{},"error_before":{},"rms_before":{},"decision":{decision}"#,
+                e.iteration,
+                e.s.0,
+                e.s.1,
+                e.t.0,
+                e.t.1,
+                e.samples,
+                e.tolerance,
+                e.error_before,
+                e.rms_before
+            )?;
+            if let Decision::Skipped(reason) = &e.decision {
+                write!(w, r#","reason":"{reason}""#)?;
+            }
+            if let Some(error) = e.error_after {
+                write!(w, r#","error_after":{error}"#)?;
+            }
+            writeln!(w, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Split every face with a sample further than `tolerance` from the surface and fit
+/// the refined mesh to `samples` again, recording each decision in `log`.
+///
+/// Faces are split in the middle of their longer parametric side. When a face is a
+/// single knot interval wide all knots are doubled first, which leaves the surface
+/// unchanged. Returns the number of faces that were split.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), iteration = iteration))
+)]
+pub fn refine<T: ControlMeshMut>(
+    mesh: &mut T,
+    samples: &[Sample],
+    tolerance: f64,
+    boundary: Boundary,
+    iteration: usize,
+    log: &mut RefinementLog,
+) -> Result<usize, FitError> {
+    let before = residuals(mesh, samples, boundary)?;
+    let mut faces = over_tolerance(mesh, samples, &before, tolerance);
+    if faces.iter().any(|(rect, _)| extent(rect).0 < 2) {
+        for v in 0..mesh.points().len() {
+            let p = mesh
+                .point_mut(VertID(v))
+                .ok_or(FitError::MissingControlPoint)?;
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+        }
+        faces = over_tolerance(mesh, samples, &before, tolerance);
+    }
+
+    let first = log.entries.len();
+    let mut split = 0;
+    for (rect, members) in &faces {
+        let (_, direction) = extent(rect);
+        let distances = members.iter().map(|&i| before.distances[i]);
+        let decision = match split_face(mesh, rect.face, direction, SplitAt::Middle, boundary) {
+            Ok(_) => {
+                split += 1;
+                Decision::Split(direction)
+            }
+            Err(e) => Decision::Skipped(e),
+        };
+        log.entries.push(RefinementEntry {
+            iteration,
+            s: rect.s,
+            t: rect.t,
+            samples: members.len(),
+            tolerance,
+            error_before: distances.clone().fold(0., f64::max),
+            rms_before: Residuals::new(distances.collect()).rms,
+            error_after: None,
+            decision,
+        });
+    }
+
+    let after = fit(mesh, samples, boundary)?;
+    for (entry, (_, members)) in log.entries[first..].iter_mut().zip(&faces) {
+        let error = members.iter().map(|&i| after.distances[i]);
+        entry.error_after = Some(error.fold(0., f64::max));
+        #[cfg(feature = "tracing")]
+        tracing::debug!(?entry, "refined face");
+    }
+    Ok(split)
+}
+
+/// When [adaptive_fit] stops refining
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FitTargets {
+    /// Stop once the RMS and the largest distance of the samples are both within these
+    pub rms: f64,
+    pub max: f64,
+    /// Never refine beyond this many control points
+    pub control_points: usize,
+    pub iterations: usize,
+}
+
+impl Default for FitTargets {
+    fn default() -> Self {
+        Self {
+            rms: 1e-3,
+            max: 1e-2,
+            control_points: 1024,
+            iterations: 8,
+        }
+    }
+}
+
+/// Why [adaptive_fit] stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// Both error targets were reached
+    Converged,
+    /// Refining again would exceed the control point budget
+    Budget,
+    IterationLimit,
+    /// No face could be split any further
+    Stalled,
+    /// The callback asked to stop
+    Stopped,
+}
+
+/// State of the fit after an iteration of [adaptive_fit]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitIteration {
+    pub iteration: usize,
+    pub control_points: usize,
+    /// Faces split in this iteration, none for the first fit
+    pub split: usize,
+    pub rms: f64,
+    pub max: f64,
+}
+
+/// Result of [adaptive_fit]
+#[derive(Debug, Clone)]
+pub struct FitReport {
+    pub iterations: Vec<FitIteration>,
+    pub stop: StopReason,
+    pub log: RefinementLog,
+}
+
+/// Fit `mesh` to `samples`, then [refine] and fit again until the error `targets` are
+/// reached or the control point or iteration budget runs out.
+///
+/// Faces are split where samples are further than the `max` target, or the `rms`
+/// target once every sample is within `max`. Every split adds at most two control
+/// points, an iteration that could exceed the control point budget is not started. `on_iteration` sees every iteration, including the
+/// first fit, and can stop the loop early.
+pub fn adaptive_fit<T: ControlMeshMut>(
+    mesh: &mut T,
+    samples: &[Sample],
+    targets: FitTargets,
+    boundary: Boundary,
+    mut on_iteration: impl FnMut(&FitIteration, &T) -> ControlFlow<()>,
+) -> Result<FitReport, FitError> {
+    let mut report = FitReport {
+        iterations: Vec::new(),
+        stop: StopReason::IterationLimit,
+        log: RefinementLog::default(),
+    };
+
+    let mut residuals = fit(mesh, samples, boundary)?;
+    let mut split = 0;
+    for iteration in 0.. {
+        let state = FitIteration {
+            iteration,
+            control_points: mesh.control_points().len(),
+            split,
+            rms: residuals.rms,
+            max: residuals.max,
+        };
+        let flow = on_iteration(&state, mesh);
+        report.iterations.push(state);
+
+        if residuals.rms <= targets.rms && residuals.max <= targets.max {
+            report.stop = StopReason::Converged;
+            break;
+        }
+        if flow.is_break() {
+            report.stop = StopReason::Stopped;
+            break;
+        }
+        if iteration >= targets.iterations {
+            report.stop = StopReason::IterationLimit;
+            break;
+        }
+
+        let tolerance = if residuals.max > targets.max {
+            targets.max
+        } else {
+            targets.rms
+        };
+        let faces = over_tolerance(mesh, samples, &residuals, tolerance).len();
+        if mesh.control_points().len() + 2 * faces > targets.control_points {
+            report.stop = StopReason::Budget;
+            break;
+        }
+        split = refine(
+            mesh,
+            samples,
+            tolerance,
+            boundary,
+            iteration,
+            &mut report.log,
+        )?;
+        if split == 0 {
+            report.stop = StopReason::Stalled;
+            break;
+        }
+        residuals = self::residuals(mesh, samples, boundary)?;
+    }
+    Ok(report)
+}
+
+/// [adaptive_fit] as a [Command] that fits the surface to the height field `z` of
+/// `height`, an [Expression] over `x` and `y`, sampled on a `samples` x `samples` grid
+/// of the current surface.
+///
+/// Built from the `height` and optional `rms`, `max`, `control_points`, `iterations`,
+/// `samples` and `boundary` parameters.
+#[derive(Debug, Clone)]
+pub struct AdaptiveFit {
+    pub height: Expression,
+    pub targets: FitTargets,
+    pub samples: usize,
+    pub boundary: Boundary,
+}
+
+impl AdaptiveFit {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        let defaults = FitTargets::default();
+        Ok(Self {
+            height: parameters.get_with("height", |v| Expression::parse(v, &["x", "y"]).ok())?,
+            targets: FitTargets {
+                rms: parameters.get_or("rms", defaults.rms)?,
+                max: parameters.get_or("max", defaults.max)?,
+                control_points: parameters.get_or("control_points", defaults.control_points)?,
+                iterations: parameters.get_or("iterations", defaults.iterations)?,
+            },
+            samples: parameters.get_or("samples", 32)?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for AdaptiveFit {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        let samples = grid_samples(mesh, self.samples, self.boundary, |x, y| {
+            self.height.evaluate(&[x, y])
+        })
+        .map_err(CommandError::failed)?;
+        adaptive_fit(mesh, &samples, self.targets, self.boundary, |_, _| {
+            ControlFlow::Continue(())
+        })
+        .map_err(CommandError::failed)?;
+        Ok(Influence::Global)
+    }
+}
+
+/// Samples on a `resolution` x `resolution` grid of the surface, moved to the height
+/// `z(x, y)`
+fn grid_samples<T: ControlMeshMut>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+    z: impl Fn(f64, f64) -> f64,
+) -> Result<Vec<Sample>, FitError> {
+    let (knots, points) = knots_and_points(mesh, boundary)?;
+    let step = resolution.saturating_sub(1).max(1) as f64;
+    let mut samples = Vec::with_capacity(resolution * resolution);
+    for i in 0..resolution * resolution {
+        let uv = (
+            (i % resolution) as f64 / step,
+            (i / resolution) as f64 / step,
+        );
+        let mut p = Vector3::zeros();
+        for (c, r) in basis(parameter(mesh, uv), &knots, &points) {
+            p += Vector3::new(points[c][0], points[c][1], points[c][2]) * r;
+        }
+        samples.push(Sample {
+            uv,
+            point: Point3::new(p.x, p.y, z(p.x, p.y)),
+        });
+    }
+    Ok(samples)
+}
+
+/// Faces with samples further than `tolerance` from the surface, with the indices of
+/// their samples
+fn over_tolerance<T: ControlMeshMut>(
+    mesh: &T,
+    samples: &[Sample],
+    residuals: &Residuals,
+    tolerance: f64,
+) -> Vec<(FaceRect, Vec<usize>)> {
+    let mut faces = samples_by_face(mesh, samples);
+    faces.retain(|(_, members)| members.iter().any(|&i| residuals.distances[i] > tolerance));
+    faces
+}
+
+/// Every face with the indices of the samples within it, samples on a shared side
+/// belong to the first face
+fn samples_by_face<T: ControlMeshMut>(mesh: &T, samples: &[Sample]) -> Vec<(FaceRect, Vec<usize>)> {
+    let mut faces: Vec<_> = mesh
+        .layout()
+        .faces
+        .into_iter()
+        .map(|rect| (rect, Vec::new()))
+        .collect();
+    for (i, sample) in samples.iter().enumerate() {
+        let (s, t) = parameter(mesh, sample.uv);
+        let inside = |(lo, hi): (isize, isize), v: f64| lo as f64 <= v && v <= hi as f64;
+        if let Some((_, members)) = faces
+            .iter_mut()
+            .find(|(rect, _)| inside(rect.s, s) && inside(rect.t, t))
+        {
+            members.push(i);
+        }
+    }
+    faces
+}
+
+/// Length of the longer side of a face and the direction it runs in
+fn extent(rect: &FaceRect) -> (isize, Direction) {
+    let (ds, dt) = (rect.s.1 - rect.s.0, rect.t.1 - rect.t.0);
+    if ds >= dt {
+        (ds, Direction::S)
+    } else {
+        (dt, Direction::T)
+    }
+}
+
+/// Closest point to `p` on the triangle `abc`
+pub(crate) fn closest_on_triangle(
+    p: Vector3<f64>,
+    a: Vector3<f64>,
+    b: Vector3<f64>,
+    c: Vector3<f64>,
+) -> Vector3<f64> {
+    let (ab, ac, ap) = (b - a, c - a, p - a);
+    let (d1, d2) = (ab.dot(&ap), ac.dot(&ap));
+    if d1 <= 0. && d2 <= 0. {
+        return a;
+    }
+    let bp = p - b;
+    let (d3, d4) = (ab.dot(&bp), ac.dot(&bp));
+    if d3 >= 0. && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0. && d1 >= 0. && d3 <= 0. {
+        return a + ab * (d1 / (d1 - d3));
+    }
+    let cp = p - c;
+    let (d5, d6) = (ab.dot(&cp), ac.dot(&cp));
+    if d6 >= 0. && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0. && d2 >= 0. && d6 <= 0. {
+        return a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0. && d4 - d3 >= 0. && d5 - d6 >= 0. {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let denom = 1. / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Parameter of `uv` within the bounds of `mesh`, `uv` itself when the mesh has no
+/// domain to place it in
+fn parameter<T: ControlMeshMut>(mesh: &T, (u, v): (f64, f64)) -> (f64, f64) {
+    let Some(bounds) = mesh.bounds() else {
+        return (u, v);
+    };
+    let lerp = |(lo, hi): (T::Unit, T::Unit), f: f64| {
+        let (lo, hi) = (lo.to_f64().unwrap_or(0.), hi.to_f64().unwrap_or(0.));
+        lo + (hi - lo) * f
+    };
+    (lerp(bounds.s, u), lerp(bounds.t, v))
+}
+
+type KnotsAndPoints = (Vec<LocalKnots>, Vec<[f64; 4]>);
+
+fn knots_and_points<T: ControlMeshMut>(
+    mesh: &T,
+    boundary: Boundary,
+) -> Result<KnotsAndPoints, FitError> {
+    let cast = to_f64::<T::Unit>;
+    let points = mesh
+        .control_points()
+        .iter()
+        .map(|cp| Ok([cast(cp.x)?, cast(cp.y)?, cast(cp.z)?, cast(cp.w)?]))
+        .collect::<Result<Vec<_>, FitError>>()?;
+    Ok((knot_vectors_of(mesh, boundary), points))
+}
+
+/// Rational basis of every control point with support at `st`
+fn basis(st: (f64, f64), knots: &[LocalKnots], points: &[[f64; 4]]) -> Vec<(usize, f64)> {
+    let mut basis = Vec::new();
+    let mut weight = 0.;
+    for (i, (k, p)) in knots.iter().zip(points).enumerate() {
+        let b =
+            cubic_basis_function(st.0, &k.s_knots) * cubic_basis_function(st.1, &k.t_knots) * p[3];
+        if b != 0. {
+            basis.push((i, b));
+            weight += b;
+        }
+    }
+    if weight != 0. {
+        basis.iter_mut().for_each(|(_, b)| *b /= weight);
+    }
+    basis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::CommandRegistry;
+    use crate::plane::new_plane;
+    use crate::tessellate::tessellate;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::UVMesh;
+
+    /// Samples of a height field over the flat `new_plane(5, 5, 4., 4.)`
+    fn samples(f: impl Fn(f64, f64) -> f64) -> Vec<Sample> {
+        let flat: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let n = 24;
+        let points = tessellate(&flat, n, Boundary::Clamped).unwrap();
+        points
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let uv = (
+                    (i % n) as f64 / (n - 1) as f64,
+                    (i / n) as f64 / (n - 1) as f64,
+                );
+                Sample {
+                    uv,
+                    point: Point3::new(p.x, p.y, f(p.x / 4., p.y / 4.)),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_fits_a_smooth_height_field() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let samples = samples(|u, v| u * v);
+
+        let residuals = fit(&mut mesh, &samples, Boundary::Clamped).unwrap();
+
+        assert!(residuals.max < 1e-3, "{residuals:?}");
+        assert_eq!(25, mesh.control_points().len());
+    }
+
+    #[test]
+    fn it_logs_the_faces_it_refines() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let samples = samples(|u, v| (-40. * ((u - 0.5).powi(2) + (v - 0.5).powi(2))).exp());
+        let before = fit(&mut mesh, &samples, Boundary::Clamped).unwrap();
+        let mut log = RefinementLog::default();
+
+        let split = refine(&mut mesh, &samples, 0.05, Boundary::Clamped, 0, &mut log).unwrap();
+
+        assert!(split > 0 && split < 16, "{split}");
+        assert_eq!(split, log.entries.len());
+        for entry in &log.entries {
+            assert!(entry.error_before > 0.05, "{entry:?}");
+            assert!(entry.error_after.unwrap() < entry.error_before, "{entry:?}");
+        }
+        let after = residuals(&mesh, &samples, Boundary::Clamped).unwrap();
+        assert!(after.max < before.max, "{after:?} {before:?}");
+
+        let mut json = Vec::new();
+        log.write_json_lines(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert_eq!(split, json.lines().count());
+        assert!(json.starts_with(r#"{"iteration":0,"s":["#), "{json}");
+        assert!(json.contains(r#""decision":"split_"#), "{json}");
+    }
+
+    #[test]
+    fn it_refines_until_the_targets_are_met() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let samples = samples(|u, v| (-40. * ((u - 0.5).powi(2) + (v - 0.5).powi(2))).exp());
+        let targets = FitTargets {
+            rms: 0.05,
+            max: 0.1,
+            ..Default::default()
+        };
+        let mut seen = Vec::new();
+
+        let report = adaptive_fit(&mut mesh, &samples, targets, Boundary::Clamped, |it, _| {
+            seen.push(it.max);
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(
+            StopReason::Converged,
+            report.stop,
+            "{:?}",
+            report.iterations
+        );
+        assert_eq!(seen.len(), report.iterations.len());
+        let last = report.iterations.last().unwrap();
+        assert!(last.max <= 0.1 && last.rms <= 0.05, "{last:?}");
+        assert!(report.iterations[0].max > 0.1);
+        assert_eq!(mesh.control_points().len(), last.control_points);
+        assert!(!report.log.entries.is_empty());
+    }
+
+    #[test]
+    fn it_keeps_to_the_control_point_budget() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let samples = samples(|u, v| (-40. * ((u - 0.5).powi(2) + (v - 0.5).powi(2))).exp());
+        let targets = FitTargets {
+            control_points: 30,
+            ..Default::default()
+        };
+
+        let report = adaptive_fit(&mut mesh, &samples, targets, Boundary::Clamped, |_, _| {
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+        assert_eq!(StopReason::Budget, report.stop);
+        assert!(mesh.control_points().len() <= 30);
+    }
+
+    #[test]
+    fn it_fits_height_fields_from_the_registry() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let parameters =
+            Parameters::parse(["height=0.1 * x * y".to_string(), "max=0.001".to_string()]).unwrap();
+
+        CommandRegistry::default()
+            .apply_mut(&mut mesh, "adaptive_fit", &parameters)
+            .unwrap();
+
+        let samples = samples(|u, v| 1.6 * u * v);
+        assert!(residuals(&mesh, &samples, Boundary::Clamped).unwrap().max < 1e-3);
+    }
+
+    #[test]
+    fn it_measures_deviation_per_face() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let samples = samples(|u, v| (-40. * ((u - 0.5).powi(2) + (v - 0.5).powi(2))).exp());
+        fit(&mut mesh, &samples, Boundary::Clamped).unwrap();
+
+        let deviations =
+            face_deviations(&mesh, Target::Samples(&samples), Boundary::Clamped).unwrap();
+
+        assert_eq!(16, deviations.len());
+        let total: usize = deviations.values().map(|d| d.count).sum();
+        assert_eq!(samples.len(), total);
+        let max = deviations.values().map(|d| d.max).fold(0., f64::max);
+        assert_eq!(
+            residuals(&mesh, &samples, Boundary::Clamped).unwrap().max,
+            max
+        );
+        let corner = &deviations[&mesh.layout().faces[0].face];
+        assert!(corner.max < max, "{corner:?}");
+    }
+
+    #[test]
+    fn it_measures_deviation_from_a_triangle_mesh() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let target = TriangleMesh {
+            points: vec![
+                Point3::new(-1., -1., 0.5),
+                Point3::new(3., -1., 0.5),
+                Point3::new(3., 3., 0.5),
+                Point3::new(-1., 3., 0.5),
+            ],
+            triangles: vec![[0, 1, 2], [0, 2, 3]],
+        };
+        let target = Target::Mesh {
+            mesh: &target,
+            resolution: 3,
+        };
+
+        let flat = face_deviations(&mesh, target, Boundary::Clamped).unwrap();
+        mesh.control_point_mut(VertID(4)).unwrap().z = 1.;
+        let lifted = face_deviations(&mesh, target, Boundary::Clamped).unwrap();
+
+        assert_eq!(4, flat.len());
+        for (face, deviation) in &flat {
+            assert_eq!(9, deviation.count);
+            assert!((deviation.max - 0.5).abs() < 1e-9, "{deviation:?}");
+            assert!(lifted[face].rms < deviation.rms, "{:?}", lifted[face]);
+        }
+    }
+
+    #[test]
+    fn it_needs_samples() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+
+        assert!(matches!(
+            fit(&mut mesh, &[], Boundary::Clamped),
+            Err(FitError::NoSamples)
+        ));
+    }
+}
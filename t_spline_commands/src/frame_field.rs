@@ -0,0 +1,259 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, to_f64};
+use crate::tessellate::knot_vectors;
+use rayon::prelude::*;
+use t_spline::algorithms::{EvalPolicy, try_subs_derivatives};
+use t_spline::bounds::Bounded;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum FrameError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// An orthonormal frame on the surface.
+///
+/// `du` follows the `s` direction of the surface, `dv` is perpendicular to it within the
+/// tangent plane and `normal` completes the right handed frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    /// The parameter the frame was sampled at
+    pub st: (f64, f64),
+    pub point: Point3<f64>,
+    pub du: Vector3<f64>,
+    pub dv: Vector3<f64>,
+    pub normal: Vector3<f64>,
+    /// How the parametrization stretches at the sample
+    pub metric: Metric,
+}
+
+impl Frame {
+    /// The surface gradients of `s` and `t`, pointing along the tangent plane in the
+    /// direction the parameter grows fastest with a length of its rate of change
+    pub fn parameter_gradients(&self) -> (Vector3<f64>, Vector3<f64>) {
+        let Metric { e, f, g } = self.metric;
+        let det = e * g - f * f;
+        // the tangents rebuilt in the frame, `ds` along `du` by construction
+        let ds = self.du * e.sqrt();
+        let dt = self.du * (f / e.sqrt()) + self.dv * (det / e).sqrt();
+        ((ds * g - dt * f) / det, (dt * e - ds * f) / det)
+    }
+}
+
+/// The first fundamental form `E ds² + 2F ds dt + G dt²` of the surface, the metric
+/// tensor of the parametrization
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metric {
+    pub e: f64,
+    pub f: f64,
+    pub g: f64,
+}
+
+impl Metric {
+    /// Surface length per unit of `s` and of `t`
+    pub fn stretch(&self) -> (f64, f64) {
+        (self.e.sqrt(), self.g.sqrt())
+    }
+
+    /// Surface area per unit of parametric area
+    pub fn area(&self) -> f64 {
+        (self.e * self.g - self.f * self.f).max(0.).sqrt()
+    }
+
+    /// The angle between the `s` and `t` directions on the surface, `π / 2` where
+    /// they stay perpendicular
+    pub fn angle(&self) -> f64 {
+        (self.f / (self.e * self.g).sqrt()).clamp(-1., 1.).acos()
+    }
+
+    /// The ratio of the largest to the smallest stretch over all directions, `1`
+    /// where the parametrization is conformal
+    pub fn anisotropy(&self) -> f64 {
+        let mean = (self.e + self.g) / 2.;
+        let spread = (((self.e - self.g) / 2.).powi(2) + self.f * self.f).sqrt();
+        ((mean + spread) / (mean - spread)).sqrt()
+    }
+}
+
+/// Sample a `resolution` x `resolution` parametric grid of frames.
+///
+/// Samples outside of the mesh or where the tangent plane degenerates, such as along a
+/// clamped boundary, are skipped.
+pub fn frame_field<T: ControlMesh + Sync>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<Vec<Frame>, FrameError> {
+    mesh.validate_control_mesh()?;
+
+    let bounds = mesh.bounds().ok_or(ValidationError::EmptyDomain())?;
+    let knot_cache = knot_vectors(mesh, boundary);
+
+    let frames: Vec<Option<Frame>> = (0..resolution * resolution)
+        .into_par_iter()
+        .map(|i| {
+            let st = bounds.interpolate(i, resolution);
+            if !mesh.contains_uv(st) {
+                return Ok(None);
+            }
+
+            frame_at(mesh, &knot_cache, st)
+        })
+        .collect::<Result<_, FrameError>>()?;
+
+    Ok(frames.into_iter().flatten().collect())
+}
+
+/// The frame at `st`, or `None` if the surface can not be evaluated or degenerates there
+pub(crate) fn frame_at<T: ControlMesh>(
+    mesh: &T,
+    knot_cache: &[LocalKnots],
+    st: (T::Unit, T::Unit),
+) -> Result<Option<Frame>, FrameError> {
+    let Ok(d) = try_subs_derivatives(mesh.control_points(), st, knot_cache, EvalPolicy::Strict)
+    else {
+        return Ok(None);
+    };
+
+    let st = (to_f64(st.0)?, to_f64(st.1)?);
+    let point = Point3::new(to_f64(d.point.x)?, to_f64(d.point.y)?, to_f64(d.point.z)?);
+    let ds = Vector3::new(to_f64(d.ds.x)?, to_f64(d.ds.y)?, to_f64(d.ds.z)?);
+    let dt = Vector3::new(to_f64(d.dt.x)?, to_f64(d.dt.y)?, to_f64(d.dt.z)?);
+
+    let metric = Metric {
+        e: ds.dot(&ds),
+        f: ds.dot(&dt),
+        g: dt.dot(&dt),
+    };
+    Ok(orthonormal(ds, dt).map(|(du, dv, normal)| Frame {
+        st,
+        point,
+        du,
+        dv,
+        normal,
+        metric,
+    }))
+}
+
+/// Gram-Schmidt the tangents into a right handed orthonormal basis
+fn orthonormal(
+    ds: Vector3<f64>,
+    dt: Vector3<f64>,
+) -> Option<(Vector3<f64>, Vector3<f64>, Vector3<f64>)> {
+    let normal = ds.cross(&dt);
+    let (ds_length, normal_length) = (length(ds), length(normal));
+    if ds_length <= f64::EPSILON || normal_length <= f64::EPSILON * ds_length {
+        return None;
+    }
+
+    let du = ds / ds_length;
+    let normal = normal / normal_length;
+    Some((du, normal.cross(&du), normal))
+}
+
+fn length(v: Vector3<f64>) -> f64 {
+    v.dot(&v).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_follows_a_flat_plane() {
+        let mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let frames = frame_field(&mesh, 4, Boundary::Clamped).unwrap();
+
+        // the tangent plane collapses on the clamped boundary
+        assert_eq!(4, frames.len());
+        for frame in frames {
+            assert!(length(frame.du - Vector3::x()) < 1e-9);
+            assert!(length(frame.dv - Vector3::y()) < 1e-9);
+            assert!(length(frame.normal - Vector3::z()) < 1e-9);
+        }
+    }
+
+    #[test]
+    fn it_produces_orthonormal_frames() {
+        let mut mesh: TSpline = new_plane(4, 4, 1., 1.).unwrap();
+        for (v, z) in [(5, 1.), (6, -0.5), (9, 0.25)] {
+            mesh.control_point_mut(VertID(v)).unwrap().z = z;
+        }
+
+        let frames = frame_field(&mesh, 5, Boundary::Clamped).unwrap();
+        assert!(!frames.is_empty());
+        for frame in frames {
+            for (a, b) in [
+                (frame.du, frame.dv),
+                (frame.dv, frame.normal),
+                (frame.normal, frame.du),
+            ] {
+                assert!(a.dot(&b).abs() < 1e-9);
+                assert!((length(a) - 1.).abs() < 1e-9);
+            }
+            assert!(length(frame.du.cross(&frame.dv) - frame.normal) < 1e-9);
+        }
+    }
+
+    #[test]
+    fn it_measures_the_distortion_of_the_parametrization() {
+        // stretched along x and sheared towards it
+        let mut mesh: TSpline = new_plane(4, 4, 6., 3.).unwrap();
+        for v in 0..mesh.control_points().len() {
+            let cp = mesh.control_point_mut(VertID(v)).unwrap();
+            cp.x += cp.y;
+        }
+        let knot_cache = knot_vectors(&mesh, Boundary::Clamped);
+
+        let frames = frame_field(&mesh, 5, Boundary::Clamped).unwrap();
+        assert!(!frames.is_empty());
+        for frame in frames {
+            let d = try_subs_derivatives(
+                mesh.control_points(),
+                frame.st,
+                &knot_cache,
+                EvalPolicy::Strict,
+            )
+            .unwrap();
+            let metric = frame.metric;
+            assert!((metric.e - d.ds.dot(&d.ds)).abs() < 1e-9, "{metric:?}");
+            assert!((metric.f - d.ds.dot(&d.dt)).abs() < 1e-9, "{metric:?}");
+            assert!((metric.g - d.dt.dot(&d.dt)).abs() < 1e-9, "{metric:?}");
+            assert!((metric.area() - length(d.ds.cross(&d.dt))).abs() < 1e-9);
+            assert!(metric.angle() < core::f64::consts::FRAC_PI_2);
+            assert!(metric.anisotropy() > 1.);
+
+            // the gradients are dual to the tangents
+            let (grad_s, grad_t) = frame.parameter_gradients();
+            assert!((grad_s.dot(&d.ds) - 1.).abs() < 1e-9);
+            assert!(grad_s.dot(&d.dt).abs() < 1e-9);
+            assert!((grad_t.dot(&d.dt) - 1.).abs() < 1e-9);
+            assert!(grad_t.dot(&d.ds).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::align_control_points_to_cage::{AlignError, align_control_points_to_cage};
+use crate::extrude_edge::{ExtrudeError, extrude_edge};
+use crate::plane::{PlaneError, new_plane};
+use crate::remesh::TriangleMesh;
+use crate::t_junction::t_junction;
+use crate::tessellate::tessellate;
+use crate::triangles::{TriangulateError, tessellate_mesh};
+use crate::unit_square::unit_square;
+use num_traits::One;
+use t_spline::Point3;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GalleryError {
+    #[error("failed to build plane: {0}")]
+    Plane(#[from] PlaneError),
+    #[error("failed to extrude: {0}")]
+    Extrude(#[from] ExtrudeError),
+    #[error("failed to align: {0}")]
+    Align(#[from] AlignError),
+    #[error("shape {0} is invalid: {1}")]
+    Invalid(&'static str, ValidationError),
+    #[error("failed to triangulate shape {0}: {1}")]
+    Triangulate(&'static str, TriangulateError),
+}
+
+/// A built-in shape together with its tessellation at one resolution
+#[derive(Debug, Clone)]
+pub struct GalleryEntry<T: ControlMeshMut> {
+    pub name: &'static str,
+    pub resolution: usize,
+    pub mesh: T,
+    pub points: Vec<Point3<T::Unit>>,
+    /// The surface as triangles, `resolution` x `resolution` cells per face
+    pub triangles: TriangleMesh,
+}
+
+/// The built-in shapes, keyed by name
+pub fn shapes<T: ControlMeshMut + Default>() -> Result<Vec<(&'static str, T)>, GalleryError> {
+    let mut cross: T = unit_square();
+    for e in 0..4 {
+        extrude_edge(&mut cross, EdgeID(e))?;
+    }
+    align_control_points_to_cage(&mut cross)?;
+
+    let mut bump: T = new_plane(4, 4, T::Unit::one(), T::Unit::one())?;
+    for v in [5, 6, 9, 10] {
+        if let Some(cp) = bump.control_point_mut(VertID(v)) {
+            cp.z = T::Unit::one();
+        }
+    }
+
+    Ok(vec![
+        ("unit_square", unit_square()),
+        ("plane", new_plane(4, 4, T::Unit::one(), T::Unit::one())?),
+        ("bump", bump),
+        ("t_junction", t_junction()),
+        ("cross", cross),
+    ])
+}
+
+/// Build every built-in shape and tessellate it at each of `resolutions`.
+///
+/// This runs the whole construction, validation and evaluation pipeline, so it doubles
+/// as a smoke test for exporters.
+pub fn gallery<T: ControlMeshMut + Default + Clone + Sync>(
+    resolutions: &[usize],
+    boundary: Boundary,
+) -> Result<Vec<GalleryEntry<T>>, GalleryError> {
+    let mut entries = Vec::new();
+    for (name, mesh) in shapes::<T>()? {
+        for &resolution in resolutions {
+            let points = tessellate(&mesh, resolution, boundary)
+                .map_err(|e| GalleryError::Invalid(name, e))?;
+            let triangles = tessellate_mesh(&mesh, resolution, boundary)
+                .map_err(|e| GalleryError::Triangulate(name, e))?;
+
+            entries.push(GalleryEntry {
+                name,
+                resolution,
+                mesh: mesh.clone(),
+                points,
+                triangles,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::TSpline;
+
+    #[test]
+    fn it_builds_every_shape_at_every_resolution() {
+        let entries: Vec<GalleryEntry<TSpline>> = gallery(&[4, 8], Boundary::Clamped).unwrap();
+
+        assert_eq!(2 * shapes::<TSpline>().unwrap().len(), entries.len());
+        for entry in entries {
+            assert!(!entry.points.is_empty(), "{} is empty", entry.name);
+            assert!(
+                !entry.triangles.triangles.is_empty(),
+                "{} has no triangles",
+                entry.name
+            );
+        }
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::command::{CommandError, CommandRegistry, Parameters};
+use t_spline::control_mesh::ControlMeshMut;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum HistoryError {
+    #[error("step {index} ({command}) failed: {source}")]
+    Step {
+        index: usize,
+        command: String,
+        source: CommandError,
+    },
+}
+
+/// A base mesh and the commands that build a model from it, with arguments that can
+/// reference named parameters as `$name`.
+///
+/// Changing a parameter and calling [History::regenerate] replays every step with the
+/// new value, so models can be driven by a few feature parameters.
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    base: T,
+    parameters: Parameters,
+    steps: Vec<(String, Parameters)>,
+}
+
+impl<T: ControlMeshMut + Clone + 'static> History<T> {
+    pub fn new(base: T) -> Self {
+        Self {
+            base,
+            parameters: Parameters::default(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Set the parameter `name`, it takes effect on the next [History::regenerate]
+    pub fn set(&mut self, name: &str, value: impl ToString) -> &mut Self {
+        self.parameters = std::mem::take(&mut self.parameters).with(name, value);
+        self
+    }
+
+    pub fn parameters(&self) -> &Parameters {
+        &self.parameters
+    }
+
+    /// Append the command `name` of the registry with its arguments
+    pub fn push(&mut self, command: &str, arguments: Parameters) -> &mut Self {
+        self.steps.push((command.to_string(), arguments));
+        self
+    }
+
+    pub fn steps(&self) -> &[(String, Parameters)] {
+        &self.steps
+    }
+
+    /// Replay every step on a copy of the base mesh with the current parameters
+    pub fn regenerate(&self, registry: &CommandRegistry<T>) -> Result<T, HistoryError> {
+        let mut mesh = self.base.clone();
+        for (index, (command, arguments)) in self.steps.iter().enumerate() {
+            arguments
+                .resolve(&self.parameters)
+                .and_then(|arguments| registry.apply_mut(&mut mesh, command, &arguments))
+                .map_err(|source| HistoryError::Step {
+                    index,
+                    command: command.clone(),
+                    source,
+                })?;
+        }
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::ids::VertID;
+
+    fn history() -> History<TSpline> {
+        let mut history = History::new(new_plane(4, 4, 3., 3.).unwrap());
+        history
+            .set("height", 1)
+            .push("extrude_edge", Parameters::default().with("edge", 0))
+            .push(
+                "move_control_point",
+                Parameters::default().with("vertex", 5).with("z", "$height"),
+            );
+        history
+    }
+
+    #[test]
+    fn it_regenerates_with_new_parameters() {
+        let registry = CommandRegistry::default();
+        let mut history = history();
+
+        let low = history.regenerate(&registry).unwrap();
+        let high = history.set("height", 2.5).regenerate(&registry).unwrap();
+
+        assert_eq!(1., low.control_point(VertID(5)).unwrap().z);
+        assert_eq!(2.5, high.control_point(VertID(5)).unwrap().z);
+        assert_eq!(low.control_points().len(), high.control_points().len());
+        assert!(low.control_points().len() > 16);
+    }
+
+    #[test]
+    fn it_reports_the_failing_step() {
+        let mut history = history();
+        history.push(
+            "move_control_point",
+            Parameters::default().with("vertex", "$corner"),
+        );
+
+        assert!(matches!(
+            history.regenerate(&CommandRegistry::default()),
+            Err(HistoryError::Step { index: 2, source: CommandError::UndefinedReference(name), .. })
+                if name == "corner"
+        ));
+    }
+}
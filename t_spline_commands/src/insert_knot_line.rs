@@ -0,0 +1,161 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::{Influence, two_ring};
+use crate::split_face::{SplitFaceError, face_range, fit_to_surface, insert_line, knot_vectors_of};
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum InsertKnotLineError {
+    #[error("no face straddles the knot value {0}")]
+    NoFaceStraddles(isize),
+    #[error("failed to split face {0:?}: {1}")]
+    Split(EdgeID, SplitFaceError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// [insert_knot_line] as a [Command], built from the `direction`, `value` and optional
+/// `boundary` parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertKnotLine {
+    pub direction: Direction,
+    pub value: isize,
+    pub boundary: Boundary,
+}
+
+impl InsertKnotLine {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            direction: parameters.get_direction("direction")?,
+            value: parameters.get("value")?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for InsertKnotLine {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        let seeds = insert_knot_line(mesh, self.direction, self.value, self.boundary)
+            .map_err(CommandError::failed)?;
+        Ok(Influence::Local(two_ring(mesh, &seeds)))
+    }
+}
+
+/// Split every face straddling `value` along `direction`, so the new knot line runs
+/// through all of them.
+///
+/// Faces that share a side share the vertex inserted into it, so a line across the
+/// whole mesh adds no T-junctions. Like [split_face](crate::split_face::split_face) the
+/// control points around the line are fitted to the surface before the edit.
+///
+/// Returns the new vertices and the corners of the split faces.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), ?direction, value = value))
+)]
+pub fn insert_knot_line<T: ControlMeshMut>(
+    mesh: &mut T,
+    direction: Direction,
+    value: isize,
+    boundary: Boundary,
+) -> Result<Vec<VertID>, InsertKnotLineError> {
+    mesh.validate_control_mesh()?;
+
+    let mut straddling = Vec::new();
+    for face in mesh.faces() {
+        let (lo, hi) =
+            face_range(mesh, face, direction).map_err(|e| InsertKnotLineError::Split(face, e))?;
+        if lo < value && value < hi {
+            straddling.push(face);
+        }
+    }
+    if straddling.is_empty() {
+        return Err(InsertKnotLineError::NoFaceStraddles(value));
+    }
+
+    let old_points = mesh.control_points().to_vec();
+    let old_knots = knot_vectors_of(mesh, boundary);
+    let mut fitted = Vec::new();
+    for &face in &straddling {
+        let edge = mesh.edge(face).ok_or(InsertKnotLineError::Split(
+            face,
+            SplitFaceError::MissingEdge,
+        ))?;
+        fitted.extend(mesh.edge_loop(edge).map(|(_, e)| e.origin));
+    }
+
+    for &face in &straddling {
+        let split = insert_line(mesh, face, direction, value)
+            .map_err(|e| InsertKnotLineError::Split(face, e))?;
+        fitted.extend(split.vertices);
+    }
+    fitted.sort();
+    fitted.dedup();
+
+    fit_to_surface(mesh, &old_points, &old_knots, &fitted, boundary)
+        .map_err(|e| InsertKnotLineError::Split(straddling[0], e))?;
+    Ok(fitted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::{UVMesh, UVMeshMut};
+
+    fn spread(size: usize) -> TSpline {
+        let mut mesh: TSpline = new_plane(size, size, 3., 3.).unwrap();
+        for v in 0..mesh.points().len() {
+            let p = mesh.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+        }
+        mesh
+    }
+
+    #[test]
+    fn it_inserts_a_line_across_the_mesh() {
+        let mut mesh = spread(4);
+
+        insert_knot_line(&mut mesh, Direction::S, 3, Boundary::Clamped).unwrap();
+
+        mesh.validate_control_mesh().unwrap();
+        assert_eq!(20, mesh.points().len());
+        assert_eq!(12, mesh.faces().count());
+        assert!(mesh.t_junctions().is_empty());
+        assert_eq!(4, mesh.points().iter().filter(|p| p.s == 3).count());
+        assert!(mesh.control_points().iter().all(|cp| cp.z.abs() < 1e-9));
+    }
+
+    #[test]
+    fn it_rejects_values_on_existing_lines() {
+        let mut mesh = spread(4);
+
+        for value in [2, -1, 7] {
+            assert!(matches!(
+                insert_knot_line(&mut mesh, Direction::T, value, Boundary::Clamped),
+                Err(InsertKnotLineError::NoFaceStraddles(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,353 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::anchors::{AnchorError, Anchors};
+use crate::command::{CommandError, Parameters};
+use crate::fit::{FaceDeviation, FitError, Target, face_deviations};
+use crate::probe::{DistanceProbe, ProbeError, probe_distance, radius};
+use crate::tessellate::knot_vectors;
+use std::collections::BTreeMap;
+use std::io;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::Boundary;
+use t_spline::uv_mesh::ids::EdgeID;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum InspectionError {
+    #[error(transparent)]
+    Fit(#[from] FitError),
+    #[error(transparent)]
+    Probe(#[from] ProbeError),
+    #[error(transparent)]
+    Anchor(#[from] AnchorError),
+}
+
+/// [inspect] with its parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inspect {
+    /// Curvature samples per face along either direction
+    pub resolution: usize,
+    /// Largest deviation that passes, the report has no verdict without it
+    pub tolerance: Option<f64>,
+    /// Anchor pairs to measure between, given as `from:to`
+    pub measurements: Vec<(String, String)>,
+    pub boundary: Boundary,
+}
+
+impl Inspect {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        let measurements = if parameters.contains("measure") {
+            parameters.get_with("measure", |v| {
+                v.split(',')
+                    .map(|pair| {
+                        let (from, to) = pair.trim().split_once(':')?;
+                        Some((from.to_string(), to.to_string()))
+                    })
+                    .collect()
+            })?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            resolution: parameters.get_or("resolution", 4)?,
+            tolerance: if parameters.contains("tolerance") {
+                Some(parameters.get("tolerance")?)
+            } else {
+                None
+            },
+            measurements,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+
+    pub fn apply<T: ControlMeshMut + Sync>(
+        &self,
+        mesh: &T,
+        target: Target,
+        anchors: &Anchors,
+    ) -> Result<InspectionReport, InspectionError> {
+        inspect(mesh, target, anchors, self)
+    }
+}
+
+/// Deviation of the whole surface from the inspected data
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Deviation {
+    pub count: usize,
+    pub rms: f64,
+    pub max: f64,
+    /// Whether `max` is within the tolerance, `None` without one
+    pub passed: Option<bool>,
+}
+
+/// Principal curvatures sampled over the surface
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurvatureStats {
+    pub samples: usize,
+    /// Samples without a tangent plane, which are left out
+    pub degenerate: usize,
+    /// Smallest and largest principal curvature
+    pub range: (f64, f64),
+    /// Smallest radius of curvature and where it is, infinite for a flat surface
+    pub min_radius: f64,
+    pub min_radius_at: Option<(f64, f64)>,
+}
+
+/// A distance between two named anchors
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub from: String,
+    pub to: String,
+    pub probe: DistanceProbe,
+}
+
+/// The result of [inspect]
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectionReport {
+    pub deviation: Deviation,
+    /// Deviation within every face, keyed by the half edge of the face
+    pub faces: BTreeMap<EdgeID, FaceDeviation>,
+    pub curvature: CurvatureStats,
+    pub measurements: Vec<Measurement>,
+}
+
+impl InspectionReport {
+    /// Write the report as a single JSON object, non-finite numbers become `null`
+    pub fn write_json(&self, w: &mut impl io::Write) -> io::Result<()> {
+        let d = &self.deviation;
+        write!(
+            w,
+            r#"{{"deviation":{{"count":{},"rms":{},"max":{}"#,
+            d.count,
+            number(d.rms),
+            number(d.max)
+        )?;
+        if let Some(passed) = d.passed {
+            write!(w, r#","passed":{passed}"#)?;
+        }
+        write!(w, r#"}},"faces":["#)?;
+        for (i, (face, f)) in self.faces.iter().enumerate() {
+            if i > 0 {
+                write!(w, ",")?;
+            }
+            write!(
+                w,
+                r#"{{"face":{},"count":{},"rms":{},"max":{}}}"#,
+                face.0,
+                f.count,
+                number(f.rms),
+                number(f.max)
+            )?;
+        }
+        let c = &self.curvature;
+        write!(
+            w,
+            r#"],"curvature":{{"samples":{},"degenerate":{},"range":[{},{}],"min_radius":{}"#,
+            c.samples,
+            c.degenerate,
+            number(c.range.0),
+            number(c.range.1),
+            number(c.min_radius)
+        )?;
+        if let Some((s, t)) = c.min_radius_at {
+            write!(w, r#","min_radius_at":[{},{}]"#, number(s), number(t))?;
+        }
+        write!(w, r#"}},"measurements":["#)?;
+        for (i, m) in self.measurements.iter().enumerate() {
+            if i > 0 {
+                write!(w, ",")?;
+            }
+            let point = |p: t_spline::Point3<f64>| {
+                format!("[{},{},{}]", number(p.x), number(p.y), number(p.z))
+            };
+            write!(
+                w,
+                r#"{{"from":{},"to":{},"from_point":{},"to_point":{},"distance":{}}}"#,
+                string(&m.from),
+                string(&m.to),
+                point(m.probe.from),
+                point(m.probe.to),
+                number(m.probe.distance)
+            )?;
+        }
+        writeln!(w, "]}}")
+    }
+}
+
+/// Compare `mesh` as the nominal geometry with `target`, sample its curvature and
+/// measure between `anchors`, for scan against CAD inspection.
+///
+/// Curvature is probed on a `resolution` x `resolution` grid of points inside every
+/// face.
+pub fn inspect<T: ControlMeshMut + Sync>(
+    mesh: &T,
+    target: Target,
+    anchors: &Anchors,
+    options: &Inspect,
+) -> Result<InspectionReport, InspectionError> {
+    let faces = face_deviations(mesh, target, options.boundary)?;
+    let count = faces.values().map(|f| f.count).sum::<usize>();
+    let squares = faces
+        .values()
+        .map(|f| f.count as f64 * f.rms * f.rms)
+        .sum::<f64>();
+    let max = faces.values().map(|f| f.max).fold(0., f64::max);
+    let deviation = Deviation {
+        count,
+        rms: (squares / count.max(1) as f64).sqrt(),
+        max,
+        passed: options.tolerance.map(|tolerance| max <= tolerance),
+    };
+
+    let knot_cache = knot_vectors(mesh, options.boundary);
+    let mut curvature = CurvatureStats {
+        samples: 0,
+        degenerate: 0,
+        range: (f64::INFINITY, f64::NEG_INFINITY),
+        min_radius: f64::INFINITY,
+        min_radius_at: None,
+    };
+    let n = options.resolution;
+    for rect in mesh.layout().faces {
+        for i in 0..n * n {
+            let center = |(lo, hi): (isize, isize), i: usize| {
+                lo as f64 + (hi - lo) as f64 * (i as f64 + 0.5) / n as f64
+            };
+            let st = (center(rect.s, i % n), center(rect.t, i / n));
+            curvature.samples += 1;
+            match radius(mesh, &knot_cache, st) {
+                Ok(probe) => {
+                    let [high, low] = probe.curvatures;
+                    curvature.range = (curvature.range.0.min(low), curvature.range.1.max(high));
+                    if probe.radius < curvature.min_radius {
+                        curvature.min_radius = probe.radius;
+                        curvature.min_radius_at = Some(st);
+                    }
+                }
+                Err(ProbeError::Degenerate(_)) => curvature.degenerate += 1,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    let measurements = options
+        .measurements
+        .iter()
+        .map(|(from, to)| {
+            Ok(Measurement {
+                probe: probe_distance(mesh, anchors, from, to, options.boundary)?,
+                from: from.clone(),
+                to: to.clone(),
+            })
+        })
+        .collect::<Result<_, ProbeError>>()?;
+
+    Ok(InspectionReport {
+        deviation,
+        faces,
+        curvature,
+        measurements,
+    })
+}
+
+fn number(v: f64) -> String {
+    if v.is_finite() {
+        v.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+fn string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fit::Sample;
+    use crate::plane::new_plane;
+    use t_spline::algorithms::subs;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::{Point3, TSpline};
+
+    #[test]
+    fn it_reports_a_flat_plate_against_a_raised_scan() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let knots = knot_vectors(&mesh, Boundary::Clamped);
+        let samples: Vec<_> = [(0.2, 0.2), (0.5, 0.5), (0.8, 0.2)]
+            .into_iter()
+            .map(|uv| {
+                // samples are given relative to the parametric bounds
+                let p = subs(mesh.control_points(), (uv.0 * 3., uv.1 * 3.), &knots).unwrap();
+                Sample {
+                    uv,
+                    point: Point3::new(p.x, p.y, p.z + 0.1),
+                }
+            })
+            .collect();
+        let mut anchors = Anchors::default();
+        anchors.insert(&mesh, "a", (0., 0.)).unwrap();
+        anchors.insert(&mesh, "b", (3., 0.)).unwrap();
+
+        let inspect = Inspect::from_parameters(
+            &Parameters::default()
+                .with("tolerance", 0.05)
+                .with("measure", "a:b")
+                .with("resolution", 2),
+        )
+        .unwrap();
+        let report = inspect
+            .apply(&mesh, Target::Samples(&samples), &anchors)
+            .unwrap();
+
+        assert_eq!(3, report.deviation.count);
+        assert!((report.deviation.max - 0.1).abs() < 1e-9);
+        assert!((report.deviation.rms - 0.1).abs() < 1e-9);
+        assert_eq!(Some(false), report.deviation.passed);
+        assert_eq!(report.faces.len() * 4, report.curvature.samples);
+        assert_eq!(f64::INFINITY, report.curvature.min_radius);
+        assert_eq!(1, report.measurements.len());
+        assert!(report.measurements[0].probe.distance > 0.);
+
+        let mut json = Vec::new();
+        report.write_json(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.starts_with(r#"{"deviation":{"count":3,"#), "{json}");
+        assert!(json.contains(r#""passed":false"#), "{json}");
+        assert!(json.contains(r#""min_radius":null"#), "{json}");
+        assert!(json.contains(r#""from":"a","to":"b""#), "{json}");
+    }
+
+    #[test]
+    fn it_rejects_malformed_measurements() {
+        let parameters = Parameters::default().with("measure", "a-b");
+        assert!(Inspect::from_parameters(&parameters).is_err());
+    }
+}
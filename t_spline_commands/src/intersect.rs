@@ -0,0 +1,315 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::tessellate::knot_vectors;
+use t_spline::algorithms::subs;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::layout::FaceRect;
+use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+/// Bisection steps used to place a crossing on a sample cell edge
+const REFINEMENT: usize = 40;
+
+#[derive(Clone, Debug, Error)]
+pub enum IntersectError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// An analytic surface to intersect a T-spline with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Primitive {
+    Plane {
+        point: Point3<f64>,
+        normal: Vector3<f64>,
+    },
+    Sphere {
+        center: Point3<f64>,
+        radius: f64,
+    },
+    /// An infinite cylinder around the line through `origin` along `axis`
+    Cylinder {
+        origin: Point3<f64>,
+        axis: Vector3<f64>,
+        radius: f64,
+    },
+}
+
+impl Primitive {
+    /// Signed distance to the primitive, negative behind the plane or inside the solid
+    pub fn distance(&self, p: &Point3<f64>) -> f64 {
+        match *self {
+            Primitive::Plane { point, normal } => (p - point).dot(&normal) / length(normal),
+            Primitive::Sphere { center, radius } => length(p - center) - radius,
+            Primitive::Cylinder {
+                origin,
+                axis,
+                radius,
+            } => {
+                let offset = p - origin;
+                let along = axis * (offset.dot(&axis) / axis.dot(&axis));
+                length(offset - along) - radius
+            }
+        }
+    }
+}
+
+/// Intersect the surface of `mesh` with an analytic `primitive`.
+///
+/// Faces whose influencing control points are bounded by a sphere that does not touch the
+/// primitive are culled, the convex hull property guarantees they can not intersect it.
+/// The remaining faces are sampled on a `resolution` x `resolution` grid and contoured
+/// with marching squares. Returns the intersection as polylines in parameter space.
+pub fn intersect_primitive<T: ControlMesh + Sync>(
+    mesh: &T,
+    primitive: &Primitive,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<Vec<Vec<(f64, f64)>>, IntersectError> {
+    mesh.validate_control_mesh()?;
+    let knot_cache = knot_vectors(mesh, boundary);
+    let n = resolution.max(1);
+
+    let mut segments = Vec::new();
+    for rect in mesh.layout().faces {
+        if culled(mesh, &knot_cache, &rect, primitive)? {
+            continue;
+        }
+
+        let distance = |st: (f64, f64)| -> Result<Option<f64>, IntersectError> {
+            Ok(point(mesh, &knot_cache, st)?.map(|p| primitive.distance(&p)))
+        };
+        let lerp = |range: (isize, isize), i: usize| {
+            range.0 as f64 + (range.1 - range.0) as f64 * i as f64 / n as f64
+        };
+
+        let mut grid = Vec::with_capacity((n + 1) * (n + 1));
+        for j in 0..=n {
+            for i in 0..=n {
+                let st = (lerp(rect.s, i), lerp(rect.t, j));
+                grid.push((st, distance(st)?));
+            }
+        }
+
+        for j in 0..n {
+            for i in 0..n {
+                let cell = [
+                    grid[j * (n + 1) + i],
+                    grid[j * (n + 1) + i + 1],
+                    grid[(j + 1) * (n + 1) + i + 1],
+                    grid[(j + 1) * (n + 1) + i],
+                ];
+                let Some(values) = cell.iter().map(|c| c.1).collect::<Option<Vec<_>>>() else {
+                    continue;
+                };
+
+                let mut crossings = Vec::new();
+                for k in 0..4 {
+                    let (a, b) = (k, (k + 1) % 4);
+                    if (values[a] < 0.) != (values[b] < 0.) {
+                        crossings.push(refine(&distance, cell[a].0, cell[b].0, values[a])?);
+                    }
+                }
+                for pair in crossings.chunks_exact(2) {
+                    segments.push(vec![pair[0], pair[1]]);
+                }
+            }
+        }
+    }
+
+    Ok(chain(segments))
+}
+
+/// True if the control points influencing `rect` can not reach the primitive
+fn culled<T: ControlMesh>(
+    mesh: &T,
+    knot_cache: &[LocalKnots],
+    rect: &FaceRect,
+    primitive: &Primitive,
+) -> Result<bool, IntersectError> {
+    let mut points = Vec::new();
+    for (knots, cp) in knot_cache.iter().zip(mesh.control_points()) {
+        let overlaps = |k: &[isize; 5], range: (isize, isize)| k[0] < range.1 && k[4] > range.0;
+        if overlaps(&knots.s_knots, rect.s) && overlaps(&knots.t_knots, rect.t) {
+            points.push(Point3::new(to_f64(cp.x)?, to_f64(cp.y)?, to_f64(cp.z)?));
+        }
+    }
+    if points.is_empty() {
+        return Ok(true);
+    }
+
+    let center = points
+        .iter()
+        .fold(Vector3::zeros(), |sum, p| sum + p.coords)
+        / points.len() as f64;
+    let center = Point3::from(center);
+    let radius = points.iter().map(|p| length(p - center)).fold(0., f64::max);
+
+    Ok(match *primitive {
+        Primitive::Plane { .. } => primitive.distance(&center).abs() > radius,
+        Primitive::Sphere { radius: r, .. } | Primitive::Cylinder { radius: r, .. } => {
+            let d = primitive.distance(&center) + r;
+            d > r + radius || d + radius < r
+        }
+    })
+}
+
+/// Bisect the cell edge from `a` to `b` for the zero crossing
+fn refine(
+    distance: &impl Fn((f64, f64)) -> Result<Option<f64>, IntersectError>,
+    mut a: (f64, f64),
+    mut b: (f64, f64),
+    a_value: f64,
+) -> Result<(f64, f64), IntersectError> {
+    let a_inside = a_value < 0.;
+    for _ in 0..REFINEMENT {
+        let mid = ((a.0 + b.0) / 2., (a.1 + b.1) / 2.);
+        match distance(mid)? {
+            Some(v) if (v < 0.) == a_inside => a = mid,
+            Some(_) => b = mid,
+            None => break,
+        }
+    }
+    Ok(((a.0 + b.0) / 2., (a.1 + b.1) / 2.))
+}
+
+/// Join segments sharing end points into polylines
+fn chain(mut segments: Vec<Vec<(f64, f64)>>) -> Vec<Vec<(f64, f64)>> {
+    let close = |a: (f64, f64), b: (f64, f64)| (a.0 - b.0).abs() + (a.1 - b.1).abs() < 1e-9;
+
+    let mut polylines: Vec<Vec<(f64, f64)>> = Vec::new();
+    while let Some(mut line) = segments.pop() {
+        loop {
+            let (first, last) = (line[0], line[line.len() - 1]);
+            let Some(i) = segments.iter().position(|s| {
+                close(s[0], last) || close(s[1], last) || close(s[0], first) || close(s[1], first)
+            }) else {
+                break;
+            };
+
+            let mut s = segments.swap_remove(i);
+            if close(s[0], last) {
+                line.push(s[1]);
+            } else if close(s[1], last) {
+                line.push(s[0]);
+            } else {
+                if close(s[0], first) {
+                    s.reverse();
+                }
+                line.insert(0, s[0]);
+            }
+        }
+        polylines.push(line);
+    }
+    polylines
+}
+
+pub(crate) fn point<T: ControlMesh>(
+    mesh: &T,
+    knot_cache: &[LocalKnots],
+    (s, t): (f64, f64),
+) -> Result<Option<Point3<f64>>, IntersectError> {
+    let st = (from_f64::<T::Unit>(s)?, from_f64::<T::Unit>(t)?);
+    match subs(mesh.control_points(), st, knot_cache) {
+        Some(p) => Ok(Some(Point3::new(to_f64(p.x)?, to_f64(p.y)?, to_f64(p.z)?))),
+        None => Ok(None),
+    }
+}
+
+fn length(v: Vector3<f64>) -> f64 {
+    v.dot(&v).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::uv_mesh::UVMesh;
+
+    fn on_primitive(mesh: &TSpline, primitive: &Primitive, curves: &[Vec<(f64, f64)>]) {
+        let knots = knot_vectors(mesh, Boundary::Clamped);
+        for &st in curves.iter().flatten() {
+            let p = point(mesh, &knots, st).unwrap().unwrap();
+            assert!(primitive.distance(&p).abs() < 1e-6, "{p}");
+        }
+    }
+
+    #[test]
+    fn it_intersects_a_plane() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let cut = Primitive::Plane {
+            point: Point3::new(1.2, 0., 0.),
+            normal: Vector3::x(),
+        };
+
+        let curves = intersect_primitive(&mesh, &cut, 4, Boundary::Clamped).unwrap();
+
+        assert_eq!(1, curves.len());
+        on_primitive(&mesh, &cut, &curves);
+        let (first, last) = (curves[0][0], curves[0][curves[0].len() - 1]);
+        assert!((first.1 - last.1).abs() > 2.9);
+    }
+
+    #[test]
+    fn it_intersects_a_sphere_and_a_cylinder() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        for primitive in [
+            Primitive::Sphere {
+                center: Point3::new(1.5, 1.5, 0.5),
+                radius: 1.,
+            },
+            Primitive::Cylinder {
+                origin: Point3::new(1.5, 1.5, 0.),
+                axis: Vector3::z(),
+                radius: 1.,
+            },
+        ] {
+            let curves = intersect_primitive(&mesh, &primitive, 6, Boundary::Clamped).unwrap();
+
+            // a single closed loop
+            assert_eq!(1, curves.len());
+            let loop_ = &curves[0];
+            let (first, last) = (loop_[0], loop_[loop_.len() - 1]);
+            assert!((first.0 - last.0).abs() + (first.1 - last.1).abs() < 1e-9);
+            on_primitive(&mesh, &primitive, &curves);
+        }
+    }
+
+    #[test]
+    fn it_culls_distant_faces() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let knots = knot_vectors(&mesh, Boundary::Clamped);
+        let far = Primitive::Sphere {
+            center: Point3::new(10., 10., 10.),
+            radius: 1.,
+        };
+
+        for rect in mesh.layout().faces {
+            assert!(culled(&mesh, &knots, &rect, &far).unwrap());
+        }
+        assert!(
+            intersect_primitive(&mesh, &far, 4, Boundary::Clamped)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
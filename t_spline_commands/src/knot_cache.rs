@@ -0,0 +1,270 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::tessellate::knot_vectors;
+use rayon::prelude::*;
+use std::collections::BTreeSet;
+use t_spline::bounds::Bounds;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::precomputed::{Precomputed, PrecomputedError};
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::{Boundary, LocalKnots};
+
+/// The part of a mesh an edit may have changed the knot vectors of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Influence {
+    /// Only control points moved, every knot vector is unchanged
+    Geometry,
+    /// The knot vectors of these vertices may have changed, new vertices are included
+    Local(BTreeSet<VertID>),
+    /// Any knot vector may have changed
+    Global,
+}
+
+impl Influence {
+    /// Combine the influence of two edits
+    pub fn union(self, other: Influence) -> Influence {
+        match (self, other) {
+            (Influence::Global, _) | (_, Influence::Global) => Influence::Global,
+            (Influence::Local(mut a), Influence::Local(b)) => {
+                a.extend(b);
+                Influence::Local(a)
+            }
+            (Influence::Local(a), Influence::Geometry)
+            | (Influence::Geometry, Influence::Local(a)) => Influence::Local(a),
+            (Influence::Geometry, Influence::Geometry) => Influence::Geometry,
+        }
+    }
+
+    /// The parametric region whose surface may have changed shape, `None` if the whole
+    /// surface may have.
+    ///
+    /// Geometry edits report no region, the moved control points are not known here.
+    pub fn region(&self, knots: &[LocalKnots]) -> Option<Bounds<isize>> {
+        match self {
+            Influence::Local(vertices) => {
+                let mut bounds = Bounds::<isize>::default();
+                for v in vertices {
+                    let k = knots.get(v.0)?;
+                    bounds.s = (bounds.s.0.min(k.s_knots[0]), bounds.s.1.max(k.s_knots[4]));
+                    bounds.t = (bounds.t.0.min(k.t_knots[0]), bounds.t.1.max(k.t_knots[4]));
+                }
+                Some(bounds)
+            }
+            Influence::Geometry | Influence::Global => None,
+        }
+    }
+}
+
+/// Vertices whose local knot vectors may change when `seeds` are added, moved or
+/// reconnected: every vertex within two knot lines of a seed, which is the knot support
+/// of the seed.
+pub fn two_ring(mesh: &impl ControlMesh, seeds: &[VertID]) -> BTreeSet<VertID> {
+    let supports: Vec<_> = seeds
+        .iter()
+        .filter(|v| mesh.point(**v).is_some())
+        .map(|v| mesh.infer_local_knots(*v, Boundary::Clamped))
+        .collect();
+
+    let mut ring: BTreeSet<VertID> = seeds.iter().copied().collect();
+    for (i, p) in mesh.points().iter().enumerate() {
+        let inside = supports.iter().any(|k| {
+            (k.s_knots[0]..=k.s_knots[4]).contains(&p.s)
+                && (k.t_knots[0]..=k.t_knots[4]).contains(&p.t)
+        });
+        if inside {
+            ring.insert(VertID(i));
+        }
+    }
+    ring
+}
+
+/// Local knot vectors of every vertex, kept up to date through the [Influence] of edits
+#[derive(Debug, Clone)]
+pub struct KnotCache {
+    boundary: Boundary,
+    knots: Vec<LocalKnots>,
+}
+
+impl KnotCache {
+    pub fn new(mesh: &(impl ControlMesh + Sync), boundary: Boundary) -> Self {
+        #[cfg(feature = "metrics")]
+        t_spline::metrics::record_knots(0, mesh.points().len());
+        Self {
+            boundary,
+            knots: knot_vectors(mesh, boundary),
+        }
+    }
+
+    /// A cache of knot vectors saved with [KnotCache::to_bytes], without inferring any.
+    ///
+    /// Fails unless `mesh` has the same T-mesh the cache was saved for.
+    pub fn from_bytes(bytes: &[u8], mesh: &impl ControlMesh) -> Result<Self, PrecomputedError> {
+        let precomputed = Precomputed::from_bytes(bytes, mesh)?;
+        Ok(Self {
+            boundary: precomputed.boundary,
+            knots: precomputed.knots,
+        })
+    }
+
+    /// Save the knot vectors and face layout of `mesh`, which the cache must be up to
+    /// date with, see [Precomputed]
+    pub fn to_bytes(&self, mesh: &impl ControlMesh) -> Vec<u8> {
+        Precomputed {
+            boundary: self.boundary,
+            knots: self.knots.clone(),
+            faces: mesh.layout().faces,
+        }
+        .to_bytes(mesh)
+    }
+
+    pub fn knots(&self) -> &[LocalKnots] {
+        &self.knots
+    }
+
+    /// Bring the cache up to date with `mesh` after an edit with `influence`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(points = mesh.points().len(), ?influence))
+    )]
+    pub fn update(&mut self, mesh: &(impl ControlMesh + Sync), influence: &Influence) {
+        match influence {
+            Influence::Geometry => {
+                #[cfg(feature = "metrics")]
+                t_spline::metrics::record_knots(self.knots.len(), 0);
+            }
+            Influence::Global => {
+                #[cfg(feature = "metrics")]
+                t_spline::metrics::record_knots(0, mesh.points().len());
+                self.knots = knot_vectors(mesh, self.boundary);
+            }
+            Influence::Local(vertices) => {
+                let count = mesh.points().len();
+                let mut stale: Vec<usize> = vertices
+                    .iter()
+                    .map(|v| v.0)
+                    .filter(|&v| v < count)
+                    .collect();
+                stale.extend(self.knots.len()..count);
+                #[cfg(feature = "metrics")]
+                t_spline::metrics::record_knots(count - stale.len(), stale.len());
+
+                let boundary = self.boundary;
+                let updated: Vec<_> = stale
+                    .par_iter()
+                    .map(|&v| mesh.infer_local_knots(VertID(v), boundary))
+                    .collect();
+
+                self.knots.truncate(count);
+                for (v, knots) in stale.into_iter().zip(updated) {
+                    if v < self.knots.len() {
+                        self.knots[v] = knots;
+                    } else {
+                        self.knots.push(knots);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::edge_slide::EdgeSlide;
+    use crate::extrude_edge::ExtrudeEdge;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::uv_mesh::direction::Direction;
+    use t_spline::uv_mesh::ids::EdgeID;
+    use t_spline::uv_mesh::{UVMesh, UVMeshMut};
+
+    fn assert_matches_full_inference(mesh: &TSpline, cache: &KnotCache) {
+        assert_eq!(knot_vectors(mesh, Boundary::Clamped), cache.knots());
+    }
+
+    #[test]
+    fn it_updates_knots_after_an_extrusion() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let mut cache = KnotCache::new(&mesh, Boundary::Clamped);
+
+        let influence = ExtrudeEdge(EdgeID(0)).apply_mut(&mut mesh).unwrap();
+        let Influence::Local(vertices) = &influence else {
+            panic!("expected a local influence, got {influence:?}");
+        };
+        assert!(vertices.len() < mesh.points().len());
+
+        cache.update(&mesh, &influence);
+        assert_matches_full_inference(&mesh, &cache);
+    }
+
+    #[test]
+    #[cfg(feature = "metrics")]
+    fn it_counts_reused_knots() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let mut cache = KnotCache::new(&mesh, Boundary::Clamped);
+
+        let before = t_spline::metrics::snapshot();
+        let influence = ExtrudeEdge(EdgeID(0)).apply_mut(&mut mesh).unwrap();
+        cache.update(&mesh, &influence);
+        let used = t_spline::metrics::snapshot().since(&before);
+
+        // other tests may use caches at the same time
+        assert!(used.knots_reused > 0);
+        assert!(used.cache_hit_rate().unwrap() > 0.);
+    }
+
+    #[test]
+    fn it_loads_saved_knots() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        ExtrudeEdge(EdgeID(0)).apply_mut(&mut mesh).unwrap();
+        let bytes = KnotCache::new(&mesh, Boundary::Clamped).to_bytes(&mesh);
+
+        let cache = KnotCache::from_bytes(&bytes, &mesh).unwrap();
+
+        assert_matches_full_inference(&mesh, &cache);
+        let other: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        assert!(matches!(
+            KnotCache::from_bytes(&bytes, &other),
+            Err(PrecomputedError::Mismatch)
+        ));
+    }
+
+    #[test]
+    fn it_updates_knots_after_a_slide() {
+        let mut mesh: TSpline = new_plane(6, 6, 5., 5.).unwrap();
+        for p in 0..mesh.points().len() {
+            let p = mesh.point_mut(VertID(p)).unwrap();
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+        }
+        let mut cache = KnotCache::new(&mesh, Boundary::Clamped);
+
+        let column: Vec<_> = (0..6).map(|j| VertID(j * 6 + 2)).collect();
+        let influence = EdgeSlide {
+            vertices: column,
+            direction: Direction::S,
+            amount: 1,
+            boundary: Boundary::Clamped,
+        }
+        .apply_mut(&mut mesh)
+        .unwrap();
+
+        cache.update(&mesh, &influence);
+        assert_matches_full_inference(&mesh, &cache);
+        assert!(influence.region(cache.knots()).is_some());
+    }
+}
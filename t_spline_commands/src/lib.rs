@@ -16,9 +16,66 @@
  */
 use t_spline::control_mesh::ControlMesh;
 
+pub mod adaptive_tessellate;
 pub mod align_control_points_to_cage;
+pub mod analysis_suitable;
+pub mod anchors;
+pub mod animation;
+pub mod bezier;
+pub mod cage;
+pub mod cap;
+pub mod cast;
+pub mod command;
+pub mod cuboid;
+pub mod decimate;
+pub mod deform;
+pub mod displace;
+pub mod distortion;
+pub mod draft;
+pub mod driven;
+pub mod edge_slide;
+pub mod evaluation_cache;
+pub mod expression;
 pub mod extrude_edge;
+pub mod fillet;
+pub mod fit;
+pub mod frame_field;
+pub mod gallery;
+pub mod history;
+pub mod insert_knot_line;
+pub mod inspection;
+pub mod intersect;
+pub mod knot_cache;
+pub mod mass_properties;
+pub mod merge_faces;
+pub mod morph;
+pub mod move_control_point;
+pub mod multi_resolution;
+pub mod nurbs;
+pub mod offset_curve;
+pub mod pattern;
+pub mod plane;
+pub mod probe;
+pub mod project_curve;
+pub mod refine_local;
+pub mod remesh;
+pub mod reparameterize;
+pub mod round_corner;
+pub mod ruled;
+pub mod sdf;
+pub mod sections;
+pub mod select;
+pub mod skin;
+pub mod split;
+pub mod split_face;
+pub mod t_junction;
+pub mod templates;
 pub mod tessellate;
+pub mod texels;
+pub mod thickness;
+pub mod toolpath;
+pub mod triangles;
+pub mod trim;
 pub mod unit_square;
 
 pub trait Op {
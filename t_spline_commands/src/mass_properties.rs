@@ -0,0 +1,232 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::tessellate::knot_vectors;
+use t_spline::algorithms::{EvalError, EvalPolicy, try_subs_derivatives};
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum MassError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("model encloses no volume")]
+    ZeroVolume,
+    #[error("model is not closed")]
+    Open,
+    #[error("patch {0} is invalid: {1}")]
+    Invalid(usize, ValidationError),
+    #[error("failed to evaluate patch {0}: {1}")]
+    Eval(usize, EvalError),
+}
+
+/// Mass properties of a closed model with unit density
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MassProperties {
+    pub volume: f64,
+    pub center_of_mass: Point3<f64>,
+    /// Inertia tensor about the center of mass, rows and columns ordered x, y, z
+    pub inertia: [[f64; 3]; 3],
+}
+
+/// 4 point Gauss-Legendre nodes and weights on [-1, 1]
+const GAUSS: [(f64, f64); 4] = [
+    (-0.861_136_311_594_052_6, 0.347_854_845_137_453_8),
+    (-0.339_981_043_584_856_3, 0.652_145_154_862_546_1),
+    (0.339_981_043_584_856_3, 0.652_145_154_862_546_1),
+    (0.861_136_311_594_052_6, 0.347_854_845_137_453_8),
+];
+
+/// Integrate volume, center of mass and inertia of the closed model bounded by `patches`.
+///
+/// Volume integrals are turned into surface integrals with the divergence theorem and
+/// evaluated on the exact surface, so the normals of all patches must point out of the
+/// model. A model whose area weighted normals do not cancel out has a hole and is
+/// rejected, its volume would depend on the origin. Each face is split into `accuracy` x `accuracy` cells integrated with 4 x 4
+/// point Gauss-Legendre quadrature.
+pub fn mass_properties<T: ControlMesh + Sync>(
+    patches: &[T],
+    accuracy: usize,
+    boundary: Boundary,
+) -> Result<MassProperties, MassError> {
+    let n = accuracy.max(1);
+
+    // volume, first moments and second moments [xx, yy, zz, xy, yz, zx]
+    let mut volume = 0.;
+    let mut first = [0.; 3];
+    let mut second = [0.; 6];
+    // the sum of the area weighted normals, zero for a closed model
+    let mut net = Vector3::zeros();
+    let mut area = 0.;
+
+    for (patch, mesh) in patches.iter().enumerate() {
+        mesh.validate_control_mesh()
+            .map_err(|e| MassError::Invalid(patch, e))?;
+        let knot_cache = knot_vectors(mesh, boundary);
+
+        for rect in mesh.layout().faces {
+            let size = (
+                (rect.s.1 - rect.s.0) as f64 / n as f64,
+                (rect.t.1 - rect.t.0) as f64 / n as f64,
+            );
+
+            for cell in 0..n * n {
+                let origin = (
+                    rect.s.0 as f64 + (cell % n) as f64 * size.0,
+                    rect.t.0 as f64 + (cell / n) as f64 * size.1,
+                );
+
+                for (gs, ws) in GAUSS {
+                    for (gt, wt) in GAUSS {
+                        let s = origin.0 + (gs + 1.) / 2. * size.0;
+                        let t = origin.1 + (gt + 1.) / 2. * size.1;
+                        let st = (from_f64::<T::Unit>(s)?, from_f64::<T::Unit>(t)?);
+
+                        let d = try_subs_derivatives(
+                            mesh.control_points(),
+                            st,
+                            &knot_cache,
+                            EvalPolicy::Clamp,
+                        )
+                        .map_err(|e| MassError::Eval(patch, e))?;
+
+                        let p = Vector3::new(
+                            to_f64(d.point.x)?,
+                            to_f64(d.point.y)?,
+                            to_f64(d.point.z)?,
+                        );
+                        let ds = Vector3::new(to_f64(d.ds.x)?, to_f64(d.ds.y)?, to_f64(d.ds.z)?);
+                        let dt = Vector3::new(to_f64(d.dt.x)?, to_f64(d.dt.y)?, to_f64(d.dt.z)?);
+
+                        // area weighted normal of the quadrature point
+                        let da = ds.cross(&dt) * (ws * wt * size.0 * size.1 / 4.);
+                        let (x, y, z) = (p.x, p.y, p.z);
+                        net += da;
+                        area += da.dot(&da).sqrt();
+
+                        volume += p.dot(&da) / 3.;
+                        for i in 0..3 {
+                            first[i] += p[i] * p[i] * da[i] / 2.;
+                            second[i] += p[i] * p[i] * p[i] * da[i] / 3.;
+                        }
+                        second[3] += x * x * y * da.x / 2.;
+                        second[4] += y * y * z * da.y / 2.;
+                        second[5] += z * z * x * da.z / 2.;
+                    }
+                }
+            }
+        }
+    }
+
+    if net.dot(&net).sqrt() > 1e-9 * area {
+        return Err(MassError::Open);
+    }
+    if volume.abs() <= f64::EPSILON {
+        return Err(MassError::ZeroVolume);
+    }
+
+    let c = Vector3::new(first[0], first[1], first[2]) / volume;
+    let [xx, yy, zz, xy, yz, zx] = second;
+
+    // inertia about the origin, moved to the center of mass with the parallel axis theorem
+    let origin = [
+        [yy + zz, -xy, -zx],
+        [-xy, xx + zz, -yz],
+        [-zx, -yz, xx + yy],
+    ];
+    let mut inertia = [[0.; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            let shift = if i == j { c.dot(&c) } else { 0. } - c[i] * c[j];
+            inertia[i][j] = origin[i][j] - volume * shift;
+        }
+    }
+
+    Ok(MassProperties {
+        volume,
+        center_of_mass: Point3::from(c),
+        inertia,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cuboid::cuboid;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_integrates_a_box() {
+        let props = mass_properties(
+            &cuboid::<TSpline>([1., 2., 3.]).unwrap(),
+            8,
+            Boundary::Clamped,
+        )
+        .unwrap();
+
+        assert!((props.volume - 6.).abs() < 1e-6);
+        let offset = props.center_of_mass - Point3::new(0.5, 1., 1.5);
+        assert!(offset.dot(&offset) < 1e-12);
+
+        let expected = [(4. + 9.) / 2., (1. + 9.) / 2., (1. + 4.) / 2.];
+        for (i, (row, expected)) in props.inertia.iter().zip(expected).enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                let e = if i == j { expected } else { 0. };
+                assert!((value - e).abs() < 1e-6, "{i} {j}");
+            }
+        }
+    }
+
+    #[test]
+    fn it_converges_with_accuracy() {
+        let model: Vec<TSpline> = cuboid([1., 1., 1.]).unwrap();
+        let coarse = mass_properties(&model, 1, Boundary::Clamped).unwrap();
+        let fine = mass_properties(&model, 8, Boundary::Clamped).unwrap();
+
+        assert!((fine.volume - 1.).abs() < (coarse.volume - 1.).abs());
+    }
+
+    #[test]
+    fn it_rejects_open_surfaces() {
+        // off the origin, so the open sheet would enclose a volume with it
+        let mut sheet: TSpline = new_plane(2, 2, 1., 1.).unwrap();
+        for v in 0..4 {
+            sheet.control_point_mut(VertID(v)).unwrap().z = 1.;
+        }
+
+        assert!(matches!(
+            mass_properties(&[sheet], 2, Boundary::Clamped),
+            Err(MassError::Open)
+        ));
+    }
+
+    #[test]
+    fn it_rejects_models_missing_a_side() {
+        let mut model: Vec<TSpline> = cuboid([1., 1., 1.]).unwrap();
+        model.pop();
+
+        assert!(matches!(
+            mass_properties(&model, 2, Boundary::Clamped),
+            Err(MassError::Open)
+        ));
+    }
+}
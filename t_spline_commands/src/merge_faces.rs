@@ -0,0 +1,370 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::Influence;
+use crate::split_face::{SplitFaceError, face_range, fit_to_surface, knot_vectors_of};
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::uv_point::UVCoord;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum MergeFacesError {
+    #[error("missing edge")]
+    MissingEdge,
+    #[error("missing point")]
+    MissingPoint,
+    #[error("edge lies on the boundary")]
+    BoundaryEdge,
+    #[error("merged face would not be rectangular")]
+    NotRectangular,
+    #[error(transparent)]
+    Face(#[from] SplitFaceError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// [merge_faces] as a [Command], built from the `edge` and optional `boundary` parameters.
+///
+/// Merging moves the last vertices and edges into the freed ids, so every knot vector
+/// is reported as changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeFaces {
+    pub edge: EdgeID,
+    pub boundary: Boundary,
+}
+
+impl MergeFaces {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            edge: EdgeID(parameters.get("edge")?),
+            boundary: parameters.get_boundary()?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for MergeFaces {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        merge_faces(mesh, self.edge, self.boundary).map_err(CommandError::failed)?;
+        Ok(Influence::Global)
+    }
+}
+
+/// Remove the interior edge `edge_id` and fuse the two faces on either side, the inverse
+/// of [split_face](crate::split_face::split_face).
+///
+/// Both faces must span exactly the edge so the merged face stays rectangular. Ends of
+/// the edge left with nothing but a straight side through them are removed as well, then
+/// the corners of the merged face are fitted to the surface before the edit.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), edge = edge_id.0))
+)]
+pub fn merge_faces<T: ControlMeshMut>(
+    mesh: &mut T,
+    edge_id: EdgeID,
+    boundary: Boundary,
+) -> Result<(), MergeFacesError> {
+    mesh.validate_control_mesh()?;
+
+    let edge = mesh
+        .edge(edge_id)
+        .ok_or(MergeFacesError::MissingEdge)?
+        .clone();
+    let twin_id = edge.twin.ok_or(MergeFacesError::BoundaryEdge)?;
+    let twin = mesh
+        .edge(twin_id)
+        .ok_or(MergeFacesError::MissingEdge)?
+        .clone();
+    let (a, b) = (edge.origin, twin.origin);
+
+    let pa = mesh.point(a).ok_or(MergeFacesError::MissingPoint)?;
+    let pb = mesh.point(b).ok_or(MergeFacesError::MissingPoint)?;
+    let direction = if pa.s == pb.s {
+        Direction::T
+    } else {
+        Direction::S
+    };
+    let span = (
+        pa.value_in_dir(direction).min(pb.value_in_dir(direction)),
+        pa.value_in_dir(direction).max(pb.value_in_dir(direction)),
+    );
+    if face_range(mesh, edge_id, direction)? != span
+        || face_range(mesh, twin_id, direction)? != span
+    {
+        return Err(MergeFacesError::NotRectangular);
+    }
+
+    let old_points = mesh.control_points().to_vec();
+    let old_knots = knot_vectors_of(mesh, boundary);
+
+    // the loops continue from one face into the other around both ends
+    let (into_a, out_of_a) = (edge.prev, twin.next);
+    let (into_b, out_of_b) = (twin.prev, edge.next);
+    link(mesh, into_a, out_of_a)?;
+    link(mesh, into_b, out_of_b)?;
+    for (v, removed, replacement) in [(a, edge_id, out_of_a), (b, twin_id, out_of_b)] {
+        let point = mesh.point_mut(v).ok_or(MergeFacesError::MissingPoint)?;
+        if point.outgoing_edge == removed {
+            point.outgoing_edge = replacement;
+        }
+    }
+
+    let mut edges = vec![edge_id, twin_id];
+    let mut points = Vec::new();
+    for (v, incoming, outgoing) in [(a, into_a, out_of_a), (b, into_b, out_of_b)] {
+        if let Some(removed) = dissolve(mesh, incoming, outgoing)? {
+            edges.extend(removed);
+            points.push(v);
+        }
+    }
+
+    let mut face = into_a;
+    edges.sort_by_key(|e| std::cmp::Reverse(e.0));
+    for e in edges {
+        remove_edge(mesh, e, &mut face)?;
+    }
+    points.sort_by_key(|v| std::cmp::Reverse(v.0));
+    for v in points {
+        remove_point(mesh, v)?;
+    }
+
+    let edge = mesh.edge(face).ok_or(MergeFacesError::MissingEdge)?;
+    let fitted: Vec<VertID> = mesh.edge_loop(edge).map(|(_, e)| e.origin).collect();
+    fit_to_surface(mesh, &old_points, &old_knots, &fitted, boundary)?;
+    Ok(())
+}
+
+fn link<T: ControlMeshMut>(mesh: &mut T, from: EdgeID, to: EdgeID) -> Result<(), MergeFacesError> {
+    mesh.edge_mut(from)
+        .ok_or(MergeFacesError::MissingEdge)?
+        .next = to;
+    mesh.edge_mut(to).ok_or(MergeFacesError::MissingEdge)?.prev = from;
+    Ok(())
+}
+
+/// Join `incoming` and `outgoing` into one edge if their shared vertex has no other
+/// edges, returning the half edges left unlinked
+fn dissolve<T: ControlMeshMut>(
+    mesh: &mut T,
+    incoming: EdgeID,
+    outgoing: EdgeID,
+) -> Result<Option<Vec<EdgeID>>, MergeFacesError> {
+    let into = mesh
+        .edge(incoming)
+        .ok_or(MergeFacesError::MissingEdge)?
+        .clone();
+    let out = mesh
+        .edge(outgoing)
+        .ok_or(MergeFacesError::MissingEdge)?
+        .clone();
+    let straight = match (into.twin, out.twin) {
+        (None, None) => true,
+        (Some(into_twin), Some(out_twin)) => {
+            mesh.edge(out_twin)
+                .ok_or(MergeFacesError::MissingEdge)?
+                .next
+                == into_twin
+        }
+        _ => false,
+    };
+    if !straight {
+        return Ok(None);
+    }
+
+    // `incoming` and the twin of `outgoing` are stretched over the vertex
+    link(mesh, incoming, out.next)?;
+    let mut removed = vec![outgoing];
+    if let (Some(into_twin), Some(out_twin)) = (into.twin, out.twin) {
+        let after = mesh
+            .edge(into_twin)
+            .ok_or(MergeFacesError::MissingEdge)?
+            .next;
+        link(mesh, out_twin, after)?;
+        mesh.edge_mut(incoming)
+            .ok_or(MergeFacesError::MissingEdge)?
+            .twin = Some(out_twin);
+        mesh.edge_mut(out_twin)
+            .ok_or(MergeFacesError::MissingEdge)?
+            .twin = Some(incoming);
+        removed.push(into_twin);
+    }
+    Ok(Some(removed))
+}
+
+/// Remove an unlinked edge by moving the last edge into its id, `tracked` follows the move
+pub(crate) fn remove_edge<T: ControlMeshMut>(
+    mesh: &mut T,
+    id: EdgeID,
+    tracked: &mut EdgeID,
+) -> Result<(), MergeFacesError> {
+    let last = EdgeID(mesh.edges().len() - 1);
+    let moved = mesh.pop_edge().ok_or(MergeFacesError::MissingEdge)?;
+    if id == last {
+        return Ok(());
+    }
+
+    mesh.edge_mut(moved.next)
+        .ok_or(MergeFacesError::MissingEdge)?
+        .prev = id;
+    mesh.edge_mut(moved.prev)
+        .ok_or(MergeFacesError::MissingEdge)?
+        .next = id;
+    if let Some(twin) = moved.twin {
+        mesh.edge_mut(twin)
+            .ok_or(MergeFacesError::MissingEdge)?
+            .twin = Some(id);
+    }
+    let origin = mesh
+        .point_mut(moved.origin)
+        .ok_or(MergeFacesError::MissingPoint)?;
+    if origin.outgoing_edge == last {
+        origin.outgoing_edge = id;
+    }
+    if *tracked == last {
+        *tracked = id;
+    }
+    *mesh.edge_mut(id).ok_or(MergeFacesError::MissingEdge)? = moved;
+    Ok(())
+}
+
+/// Remove a vertex without edges by moving the last vertex into its id
+pub(crate) fn remove_point<T: ControlMeshMut>(
+    mesh: &mut T,
+    id: VertID,
+) -> Result<(), MergeFacesError> {
+    let last = VertID(mesh.points().len() - 1);
+    let point = mesh.pop_point().ok_or(MergeFacesError::MissingPoint)?;
+    let control_point = mesh
+        .pop_control_point()
+        .ok_or(MergeFacesError::MissingPoint)?;
+    if id == last {
+        return Ok(());
+    }
+
+    for e in 0..mesh.edges().len() {
+        if mesh.edges()[e].origin == last {
+            mesh.edge_mut(EdgeID(e))
+                .ok_or(MergeFacesError::MissingEdge)?
+                .origin = id;
+        }
+    }
+    *mesh.point_mut(id).ok_or(MergeFacesError::MissingPoint)? = point;
+    *mesh
+        .control_point_mut(id)
+        .ok_or(MergeFacesError::MissingPoint)? = control_point;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use crate::split_face::{SplitAt, split_face};
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::{UVMesh, UVMeshMut};
+
+    fn spread() -> TSpline {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        for v in 0..9 {
+            let p = mesh.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+        }
+        mesh
+    }
+
+    fn find_edge(mesh: &TSpline, from: (isize, isize), to: (isize, isize)) -> EdgeID {
+        let position = mesh
+            .edges()
+            .iter()
+            .position(|e| {
+                mesh.points()[e.origin.0].st() == from
+                    && mesh.points()[mesh.next_edge(e).origin.0].st() == to
+            })
+            .unwrap();
+        EdgeID(position)
+    }
+
+    #[test]
+    fn it_undoes_a_split() {
+        let original = spread();
+        let mut mesh = original.clone();
+        split_face(
+            &mut mesh,
+            EdgeID(0),
+            Direction::S,
+            SplitAt::Middle,
+            Boundary::Clamped,
+        )
+        .unwrap();
+
+        let edge = find_edge(&mesh, (1, 0), (1, 2));
+        merge_faces(&mut mesh, edge, Boundary::Clamped).unwrap();
+
+        mesh.validate_control_mesh().unwrap();
+        assert_eq!(9, mesh.points().len());
+        assert_eq!(4, mesh.faces().count());
+        assert!(mesh.t_junctions().is_empty());
+        for (a, b) in original.control_points().iter().zip(mesh.control_points()) {
+            assert!(
+                (a - b).xyz().dot(&(a - b).xyz()).sqrt() < 0.1,
+                "{a:?} {b:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_keeps_vertices_used_by_other_faces() {
+        let mut mesh = spread();
+
+        let edge = find_edge(&mesh, (2, 0), (2, 2));
+        merge_faces(&mut mesh, edge, Boundary::Clamped).unwrap();
+
+        mesh.validate_control_mesh().unwrap();
+        assert_eq!(8, mesh.points().len());
+        assert_eq!(3, mesh.faces().count());
+        // the middle vertex stays as a T-junction into the merged face
+        assert_eq!(1, mesh.t_junctions().len());
+    }
+
+    #[test]
+    fn it_rejects_merges_into_non_rectangles() {
+        let mut mesh = spread();
+        split_face(
+            &mut mesh,
+            EdgeID(0),
+            Direction::S,
+            SplitAt::Middle,
+            Boundary::Clamped,
+        )
+        .unwrap();
+
+        let above = find_edge(&mesh, (2, 2), (1, 2));
+        assert!(matches!(
+            merge_faces(&mut mesh, above, Boundary::Clamped),
+            Err(MergeFacesError::NotRectangular)
+        ));
+        let boundary = find_edge(&mesh, (0, 0), (1, 0));
+        assert!(matches!(
+            merge_faces(&mut mesh, boundary, Boundary::Clamped),
+            Err(MergeFacesError::BoundaryEdge)
+        ));
+    }
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::driven::{DriveError, DrivenSurface};
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::half_edge::HalfEdge;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::uv_point::UVPoint;
+use t_spline::{Numeric, Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum MorphError {
+    #[error("target {0} has a different topology than the base mesh")]
+    TopologyMismatch(String),
+    #[error("expected {expected} weights, got {got}")]
+    WeightCount { expected: usize, got: usize },
+    #[error(transparent)]
+    Drive(#[from] DriveError),
+}
+
+/// Control point positions of a base mesh and a set of named targets with the same
+/// topology, blended by weights. Used to rig faces and bodies with blend shapes.
+#[derive(Debug, Clone)]
+pub struct MorphTargets<U: Numeric + 'static> {
+    points: Vec<UVPoint>,
+    edges: Vec<HalfEdge>,
+    base: Vec<Point3<U>>,
+    /// Offsets of every target from the base
+    targets: Vec<(String, Vec<Vector3<U>>)>,
+}
+
+impl<U: Numeric + 'static> MorphTargets<U> {
+    pub fn new(base: &impl ControlMesh<Unit = U>) -> Self {
+        Self {
+            points: base.points().to_vec(),
+            edges: base.edges().to_vec(),
+            base: positions(base).collect(),
+            targets: Vec::new(),
+        }
+    }
+
+    /// Add the control point positions of `target` as a blend shape, its T-mesh must be
+    /// the same as the one of the base
+    pub fn add_target(
+        &mut self,
+        name: impl Into<String>,
+        target: &impl ControlMesh<Unit = U>,
+    ) -> Result<&mut Self, MorphError> {
+        let name = name.into();
+        if target.points() != self.points.as_slice() || target.edges() != self.edges.as_slice() {
+            return Err(MorphError::TopologyMismatch(name));
+        }
+
+        let offsets = positions(target)
+            .zip(&self.base)
+            .map(|(target, base)| target - base)
+            .collect();
+        self.targets.push((name, offsets));
+        Ok(self)
+    }
+
+    /// Names of the targets in the order their weights are given
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.targets.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Control point positions with every target added by its weight, a weight of one
+    /// moves the points all the way to the target
+    pub fn blend(&self, weights: &[U]) -> Result<Vec<Point3<U>>, MorphError> {
+        if weights.len() != self.targets.len() {
+            return Err(MorphError::WeightCount {
+                expected: self.targets.len(),
+                got: weights.len(),
+            });
+        }
+
+        let mut blended = self.base.clone();
+        for ((_, offsets), &weight) in self.targets.iter().zip(weights) {
+            if weight == U::zero() {
+                continue;
+            }
+            for (p, offset) in blended.iter_mut().zip(offsets) {
+                *p += offset * weight;
+            }
+        }
+        Ok(blended)
+    }
+
+    /// Move the control points of `surface` to the blend of `weights`.
+    ///
+    /// Only control points that change position are moved, so the surface is evaluated
+    /// again only where the blend differs from the current shape.
+    pub fn apply<T: ControlMeshMut<Unit = U> + Sync>(
+        &self,
+        surface: &mut DrivenSurface<T>,
+        weights: &[U],
+    ) -> Result<usize, MorphError> {
+        let blended = self.blend(weights)?;
+        let moved: Vec<_> = surface
+            .positions()
+            .zip(blended)
+            .enumerate()
+            .filter(|(_, (current, blended))| current != blended)
+            .map(|(v, (_, blended))| (VertID(v), blended))
+            .collect();
+        Ok(surface.set_positions(moved)?)
+    }
+}
+
+fn positions<U: Numeric + 'static>(
+    mesh: &impl ControlMesh<Unit = U>,
+) -> impl Iterator<Item = Point3<U>> {
+    mesh.control_points()
+        .iter()
+        .map(|cp| Point3::new(cp.x, cp.y, cp.z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::Command;
+    use crate::extrude_edge::ExtrudeEdge;
+    use crate::plane::new_plane;
+    use crate::tessellate::tessellate;
+    use t_spline::TSpline;
+    use t_spline::uv_mesh::Boundary;
+    use t_spline::uv_mesh::ids::EdgeID;
+
+    fn lifted(v: usize, z: f64) -> TSpline {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        mesh.control_point_mut(VertID(v)).unwrap().z = z;
+        mesh
+    }
+
+    #[test]
+    fn it_blends_targets_by_weight() {
+        let base: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let mut morph = MorphTargets::new(&base);
+        morph
+            .add_target("brow", &lifted(2, 2.))
+            .unwrap()
+            .add_target("chin", &lifted(22, -1.))
+            .unwrap();
+
+        let blended = morph.blend(&[0.5, 1.]).unwrap();
+
+        assert_eq!(vec!["brow", "chin"], morph.names().collect::<Vec<_>>());
+        assert_eq!(1., blended[2].z);
+        assert_eq!(-1., blended[22].z);
+        assert_eq!(0., blended[12].z);
+        assert!(matches!(
+            morph.blend(&[1.]),
+            Err(MorphError::WeightCount {
+                expected: 2,
+                got: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn it_evaluates_blends_locally() {
+        let base: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let mut morph = MorphTargets::new(&base);
+        morph.add_target("brow", &lifted(2, 2.)).unwrap();
+        let mut surface = DrivenSurface::new(base, 12, Boundary::Clamped).unwrap();
+
+        let evaluated = morph.apply(&mut surface, &[0.25]).unwrap();
+
+        assert!(evaluated > 0 && evaluated < 12 * 12, "{evaluated}");
+        assert_eq!(0, morph.apply(&mut surface, &[0.25]).unwrap());
+        let expected = tessellate(&lifted(2, 0.5), 12, Boundary::Clamped).unwrap();
+        assert_eq!(expected, surface.points().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn it_rejects_targets_with_other_topology() {
+        let base: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        let mut extruded = base.clone();
+        ExtrudeEdge(EdgeID(0)).apply_mut(&mut extruded).unwrap();
+
+        assert!(matches!(
+            MorphTargets::new(&base).add_target("extruded", &extruded),
+            Err(MorphError::TopologyMismatch(name)) if name == "extruded"
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64};
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::Influence;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::ids::VertID;
+use thiserror::Error;
+
+#[derive(Copy, Clone, Debug, Error)]
+pub enum MoveError {
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+}
+
+/// [move_control_point] as a [Command], built from the `vertex` and optional `x`, `y`
+/// and `z` parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveControlPoint {
+    pub vertex: VertID,
+    pub position: [Option<f64>; 3],
+}
+
+impl MoveControlPoint {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        let axis = |name| {
+            parameters
+                .contains(name)
+                .then(|| parameters.get(name))
+                .transpose()
+        };
+        Ok(Self {
+            vertex: VertID(parameters.get("vertex")?),
+            position: [axis("x")?, axis("y")?, axis("z")?],
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for MoveControlPoint {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        move_control_point(mesh, self.vertex, self.position).map_err(CommandError::failed)?;
+        Ok(Influence::Geometry)
+    }
+}
+
+/// Set the coordinates of a control point, coordinates that are `None` are kept
+pub fn move_control_point<T: ControlMeshMut>(
+    mesh: &mut T,
+    vertex: VertID,
+    position: [Option<f64>; 3],
+) -> Result<(), MoveError> {
+    let cast = from_f64::<T::Unit>;
+    let [x, y, z] = position;
+    let [x, y, z] = [x.map(cast), y.map(cast), z.map(cast)];
+    let cp = mesh
+        .control_point_mut(vertex)
+        .ok_or(MoveError::MissingControlPoint)?;
+    if let Some(x) = x {
+        cp.x = x?;
+    }
+    if let Some(y) = y {
+        cp.y = y?;
+    }
+    if let Some(z) = z {
+        cp.z = z?;
+    }
+    Ok(())
+}
@@ -0,0 +1,265 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::displace::{DisplaceError, refine, vertex_normals};
+use num_traits::ToPrimitive;
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::Boundary;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum MultiResolutionError {
+    #[error("mesh has a different topology than the refined cage")]
+    TopologyMismatch,
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("failed to refine: {0}")]
+    Displace(#[from] DisplaceError),
+}
+
+/// A coarse cage with fine detail stored as offsets from it, for sculpting.
+///
+/// The fine mesh is the cage [refine]d `levels` times with the detail added to its
+/// control points. Detail is kept in a frame of the surface normal and a cage edge at
+/// every control point, so it follows when the coarse shape is moved, bent or turned.
+#[derive(Debug, Clone)]
+pub struct MultiResolution<T> {
+    coarse: T,
+    levels: usize,
+    boundary: Boundary,
+    /// Offsets of the fine control points in their local frames
+    detail: Vec<Vector3<f64>>,
+}
+
+impl<T: ControlMeshMut + Clone> MultiResolution<T> {
+    /// Start without detail, so the fine mesh is just the refined cage
+    pub fn new(coarse: T, levels: usize, boundary: Boundary) -> Result<Self, MultiResolutionError> {
+        let base = refined(&coarse, levels, boundary)?;
+        Ok(Self {
+            detail: vec![Vector3::zeros(); base.control_points().len()],
+            coarse,
+            levels,
+            boundary,
+        })
+    }
+
+    pub fn coarse(&self) -> &T {
+        &self.coarse
+    }
+
+    pub fn levels(&self) -> usize {
+        self.levels
+    }
+
+    /// Edit the control points of the cage, the detail follows on the next [Self::fine].
+    ///
+    /// Only control points may change, an edit of the T-mesh is rolled back.
+    pub fn edit_coarse(&mut self, edit: impl FnOnce(&mut T)) -> Result<(), MultiResolutionError> {
+        let mut coarse = self.coarse.clone();
+        edit(&mut coarse);
+        if coarse.points() != self.coarse.points() || coarse.edges() != self.coarse.edges() {
+            return Err(MultiResolutionError::TopologyMismatch);
+        }
+        self.coarse = coarse;
+        Ok(())
+    }
+
+    /// The refined cage with the detail applied
+    pub fn fine(&self) -> Result<T, MultiResolutionError> {
+        let mut fine = refined(&self.coarse, self.levels, self.boundary)?;
+        let frames = frames(&fine, self.boundary)?;
+        for (v, (frame, detail)) in frames.iter().zip(&self.detail).enumerate() {
+            let cp = fine
+                .control_point_mut(VertID(v))
+                .ok_or(MultiResolutionError::MissingControlPoint)?;
+            let [along, side, normal] = frame;
+            let p = position(cp.x, cp.y, cp.z)?
+                + along * detail.x
+                + side * detail.y
+                + normal * detail.z;
+            let cast = from_f64::<T::Unit>;
+            (cp.x, cp.y, cp.z) = (cast(p.x)?, cast(p.y)?, cast(p.z)?);
+        }
+        Ok(fine)
+    }
+
+    /// Take the detail from a sculpted copy of [Self::fine], which must keep its T-mesh
+    pub fn set_fine(&mut self, fine: &T) -> Result<(), MultiResolutionError> {
+        let base = refined(&self.coarse, self.levels, self.boundary)?;
+        if fine.points() != base.points() || fine.edges() != base.edges() {
+            return Err(MultiResolutionError::TopologyMismatch);
+        }
+
+        let frames = frames(&base, self.boundary)?;
+        self.detail = base
+            .control_points()
+            .iter()
+            .zip(fine.control_points())
+            .zip(frames)
+            .map(|((base, fine), frame)| {
+                let offset = position(fine.x, fine.y, fine.z)? - position(base.x, base.y, base.z)?;
+                let [along, side, normal] = frame;
+                Ok(Vector3::new(
+                    along.dot(&offset),
+                    side.dot(&offset),
+                    normal.dot(&offset),
+                ))
+            })
+            .collect::<Result<_, MultiResolutionError>>()?;
+        Ok(())
+    }
+}
+
+fn refined<T: ControlMeshMut + Clone>(
+    coarse: &T,
+    levels: usize,
+    boundary: Boundary,
+) -> Result<T, MultiResolutionError> {
+    let mut mesh = coarse.clone();
+    for _ in 0..levels {
+        refine(&mut mesh, boundary)?;
+    }
+    Ok(mesh)
+}
+
+/// An orthonormal frame per control point: the direction of its outgoing cage edge
+/// within the tangent plane, the side direction and the normal
+fn frames<T: ControlMesh>(
+    mesh: &T,
+    boundary: Boundary,
+) -> Result<Vec<[Vector3<f64>; 3]>, MultiResolutionError> {
+    let normals = vertex_normals(mesh, boundary)?;
+    let positions = mesh
+        .control_points()
+        .iter()
+        .map(|cp| position(cp.x, cp.y, cp.z))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut frames = Vec::with_capacity(normals.len());
+    for (v, (p, normal)) in mesh.points().iter().zip(normals).enumerate() {
+        let edge = mesh
+            .edge(p.outgoing_edge)
+            .ok_or(MultiResolutionError::MissingControlPoint)?;
+        let next = mesh.next_edge(edge).origin;
+        let along = positions[next.0] - positions[v];
+        let along = along - normal * along.dot(&normal);
+        let length = along.dot(&along).sqrt();
+        if length <= f64::EPSILON {
+            return Err(DisplaceError::NoNormal(VertID(v)).into());
+        }
+        let along = along / length;
+        frames.push([along, normal.cross(&along), normal]);
+    }
+    Ok(frames)
+}
+
+fn position(
+    x: impl ToPrimitive,
+    y: impl ToPrimitive,
+    z: impl ToPrimitive,
+) -> Result<Point3<f64>, MultiResolutionError> {
+    Ok(Point3::new(to_f64(x)?, to_f64(y)?, to_f64(z)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::uv_mesh::ids::EdgeID;
+    use t_spline::uv_mesh::uv_point::UVPoint;
+    use t_spline::uv_mesh::{UVMesh, UVMeshMut};
+
+    fn sculpted() -> (MultiResolution<TSpline>, usize) {
+        let coarse: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let mut multires = MultiResolution::new(coarse, 1, Boundary::Clamped).unwrap();
+        let mut fine = multires.fine().unwrap();
+        let v = fine
+            .points()
+            .iter()
+            .position(|p| (p.s, p.t) == (3, 3))
+            .unwrap();
+        fine.control_point_mut(VertID(v)).unwrap().z += 0.5;
+        multires.set_fine(&fine).unwrap();
+        (multires, v)
+    }
+
+    #[test]
+    fn it_keeps_detail_when_the_cage_moves() {
+        let (mut multires, v) = sculpted();
+        let before = multires.fine().unwrap();
+
+        multires
+            .edit_coarse(|coarse| {
+                for i in 0..coarse.control_points().len() {
+                    coarse.control_point_mut(VertID(i)).unwrap().x += 2.;
+                }
+            })
+            .unwrap();
+        let after = multires.fine().unwrap();
+
+        let moved = after.control_points()[v] - before.control_points()[v];
+        assert!((moved - Vector3::new(2., 0., 0.).push(0.)).abs().max() < 1e-9);
+    }
+
+    #[test]
+    fn it_turns_detail_with_the_cage() {
+        let (mut multires, v) = sculpted();
+
+        // stand the plane up, turning +z into +y
+        multires
+            .edit_coarse(|coarse| {
+                for i in 0..coarse.control_points().len() {
+                    let cp = coarse.control_point_mut(VertID(i)).unwrap();
+                    (cp.y, cp.z) = (cp.z, -cp.y);
+                }
+            })
+            .unwrap();
+        let fine = multires.fine().unwrap();
+        let base = refined(multires.coarse(), 1, Boundary::Clamped).unwrap();
+
+        let offset = fine.control_points()[v] - base.control_points()[v];
+        assert!((offset - Vector3::new(0., 0.5, 0.).push(0.)).abs().max() < 1e-9);
+    }
+
+    #[test]
+    fn it_rejects_topology_edits() {
+        let (mut multires, _) = sculpted();
+        let coarse = multires.coarse().clone();
+
+        assert!(matches!(
+            multires.edit_coarse(|coarse| {
+                coarse.push_point(UVPoint {
+                    s: 9,
+                    t: 9,
+                    outgoing_edge: EdgeID(0),
+                });
+            }),
+            Err(MultiResolutionError::TopologyMismatch)
+        ));
+        assert!(matches!(
+            multires.set_fine(&coarse),
+            Err(MultiResolutionError::TopologyMismatch)
+        ));
+        assert_eq!(16, multires.coarse().points().len());
+    }
+}
@@ -0,0 +1,480 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::bezier::{BezierError, element, homogeneous, supported};
+use crate::cast::{CastError, from_f64};
+use crate::command::{CommandError, Parameters};
+use crate::fit::{FitError, Residuals, Sample, residuals};
+use crate::plane::{PlaneError, new_plane};
+use crate::split_face::solve;
+use crate::tessellate::knot_vectors;
+use num_traits::One;
+use std::collections::BTreeSet;
+use t_spline::algorithms::cubic_basis_function;
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::layout::FaceRect;
+use t_spline::uv_mesh::{Boundary, KnotVector};
+use t_spline::{Point3, Vector4};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum NurbsError {
+    #[error("a bicubic surface needs at least 4 control points in each direction")]
+    TooFewPoints,
+    #[error("expected {expected} {what}, got {found}")]
+    WrongCount {
+        what: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("knots must not decrease")]
+    DecreasingKnots,
+    #[error("surface has no parametric area")]
+    EmptyDomain,
+    #[error("weights must be positive")]
+    NonPositiveWeight,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error(transparent)]
+    Plane(#[from] PlaneError),
+    #[error(transparent)]
+    Fit(#[from] FitError),
+}
+
+/// [to_nurbs] with the optional `boundary` parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToNurbs {
+    pub boundary: Boundary,
+}
+
+impl ToNurbs {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            boundary: parameters.get_boundary()?,
+        })
+    }
+
+    pub fn apply<T: ControlMesh + Sync>(&self, mesh: &T) -> Result<Vec<NurbsSurface>, BezierError> {
+        to_nurbs(mesh, self.boundary)
+    }
+}
+
+/// A rational bicubic tensor product surface, only built through [NurbsSurface::new] and
+/// [to_nurbs] so its knots and control points always agree
+#[derive(Debug, Clone, PartialEq)]
+pub struct NurbsSurface {
+    s_knots: Vec<isize>,
+    t_knots: Vec<isize>,
+    control_points: Vec<Vector4<f64>>,
+}
+
+impl NurbsSurface {
+    /// A surface of `width` x `height` control points with their `weights`, in rows
+    /// from the lowest `t` up. Knot vectors have `width + 4` and `height + 4` entries.
+    pub fn new(
+        s_knots: Vec<isize>,
+        t_knots: Vec<isize>,
+        control_points: &[Point3<f64>],
+        weights: &[f64],
+    ) -> Result<Self, NurbsError> {
+        if s_knots.len() < 8 || t_knots.len() < 8 {
+            return Err(NurbsError::TooFewPoints);
+        }
+        let count = |what, expected, found| {
+            if expected == found {
+                Ok(())
+            } else {
+                Err(NurbsError::WrongCount {
+                    what,
+                    expected,
+                    found,
+                })
+            }
+        };
+        let expected = (s_knots.len() - 4) * (t_knots.len() - 4);
+        count("control points", expected, control_points.len())?;
+        count("weights", expected, weights.len())?;
+        for knots in [&s_knots, &t_knots] {
+            if knots.windows(2).any(|k| k[1] < k[0]) {
+                return Err(NurbsError::DecreasingKnots);
+            }
+            if knots[3] == knots[knots.len() - 4] {
+                return Err(NurbsError::EmptyDomain);
+            }
+        }
+        if weights.iter().any(|&w| w <= 0.) {
+            return Err(NurbsError::NonPositiveWeight);
+        }
+
+        Ok(Self {
+            s_knots,
+            t_knots,
+            control_points: control_points
+                .iter()
+                .zip(weights)
+                .map(|(p, &w)| Vector4::new(p.x, p.y, p.z, w))
+                .collect(),
+        })
+    }
+
+    /// Clamped knot vector along `s`
+    pub fn s_knots(&self) -> &[isize] {
+        &self.s_knots
+    }
+
+    /// Clamped knot vector along `t`
+    pub fn t_knots(&self) -> &[isize] {
+        &self.t_knots
+    }
+
+    /// Control points as `(x, y, z, w)` in rows from the lowest `t` up, each from the
+    /// lowest `s`
+    pub fn control_points(&self) -> &[Vector4<f64>] {
+        &self.control_points
+    }
+
+    /// Control points along `s`
+    pub fn width(&self) -> usize {
+        self.s_knots.len() - 4
+    }
+
+    /// Control points along `t`
+    pub fn height(&self) -> usize {
+        self.t_knots.len() - 4
+    }
+
+    pub fn control_point(&self, i: usize, j: usize) -> Option<&Vector4<f64>> {
+        if i >= self.width() {
+            return None;
+        }
+        self.control_points.get(j * self.width() + i)
+    }
+
+    /// The point of the surface at `st`, `None` outside of its domain
+    pub fn evaluate(&self, st: (f64, f64)) -> Option<Point3<f64>> {
+        let p = self.weighted(st)?;
+        Some(Point3::new(p.x / p.w, p.y / p.w, p.z / p.w))
+    }
+
+    /// The weighted sum of control points at `st`, with the weight of the surface in `w`
+    fn weighted(&self, (s, t): (f64, f64)) -> Option<Vector4<f64>> {
+        let (i0, bs) = basis(&self.s_knots, s)?;
+        let (j0, bt) = basis(&self.t_knots, t)?;
+        let mut sum = Vector4::zeros();
+        for (b, j) in bt.iter().zip(j0..) {
+            for (a, i) in bs.iter().zip(i0..) {
+                let p = self.control_point(i, j)?;
+                sum += Vector4::new(p.x * p.w, p.y * p.w, p.z * p.w, p.w) * (a * b);
+            }
+        }
+        (sum.w != 0.).then_some(sum)
+    }
+}
+
+/// Convert `mesh` to NURBS surfaces by inserting every knot line across the domain.
+///
+/// A mesh whose faces tile its parametric bounds becomes a single surface, any other
+/// mesh one surface per face. Knots get full multiplicity, so every span is its own
+/// [Bézier element](crate::bezier::BezierElement) and the conversion is exact. Downstream
+/// tools can remove knots where the surface is smoother.
+pub fn to_nurbs<T: ControlMesh + Sync>(
+    mesh: &T,
+    boundary: Boundary,
+) -> Result<Vec<NurbsSurface>, BezierError> {
+    mesh.validate_control_mesh()?;
+    let knot_cache = knot_vectors(mesh, boundary);
+    let homogeneous = homogeneous(mesh)?;
+
+    let faces = mesh.layout().faces;
+    let Some(first) = faces.first() else {
+        return Ok(Vec::new());
+    };
+    let mut bounds = *first;
+    for rect in &faces {
+        bounds.s = (
+            Ord::min(bounds.s.0, rect.s.0),
+            Ord::max(bounds.s.1, rect.s.1),
+        );
+        bounds.t = (
+            Ord::min(bounds.t.0, rect.t.0),
+            Ord::max(bounds.t.1, rect.t.1),
+        );
+    }
+    let area = |r: &FaceRect| (r.s.1 - r.s.0) * (r.t.1 - r.t.0);
+    let regions = if faces.iter().map(area).sum::<isize>() == area(&bounds) {
+        vec![bounds]
+    } else {
+        faces
+    };
+
+    Ok(regions
+        .iter()
+        .map(|region| {
+            let candidates = supported(&knot_cache, region.s, region.t);
+            let breaks = |(lo, hi): (isize, isize), knots: &dyn Fn(usize) -> KnotVector| {
+                let mut values: BTreeSet<isize> = [lo, hi].into();
+                for &v in &candidates {
+                    values.extend(knots(v).into_iter().filter(|k| lo < *k && *k < hi));
+                }
+                values.into_iter().collect::<Vec<_>>()
+            };
+            let s_breaks = breaks(region.s, &|v| knot_cache[v].s_knots);
+            let t_breaks = breaks(region.t, &|v| knot_cache[v].t_knots);
+
+            let width = 3 * (s_breaks.len() - 1) + 1;
+            let height = 3 * (t_breaks.len() - 1) + 1;
+            let mut control_points = vec![Vector4::zeros(); width * height];
+            for (j, t) in t_breaks.windows(2).enumerate() {
+                for (i, s) in s_breaks.windows(2).enumerate() {
+                    let patch = element(
+                        region.face,
+                        &knot_cache,
+                        &homogeneous,
+                        &candidates,
+                        (s[0], s[1]),
+                        (t[0], t[1]),
+                    );
+                    // neighbouring patches share their border rows
+                    for (k, p) in patch.control_points.into_iter().enumerate() {
+                        control_points[(3 * j + k / 4) * width + 3 * i + k % 4] = p;
+                    }
+                }
+            }
+
+            NurbsSurface {
+                s_knots: clamped(&s_breaks),
+                t_knots: clamped(&t_breaks),
+                control_points,
+            }
+        })
+        .collect())
+}
+
+/// Approximate `surface` with a T-mesh that has a full grid of vertices on its knot lines.
+///
+/// The clamped boundary of a T-mesh has one blending function fewer at each end than a
+/// NURBS with the same knots, so the surface can not always be copied exactly. The
+/// weighted control points are instead fit to `samples` x `samples` points of every
+/// knot span in homogeneous space, which is exact when the surface came from a T-mesh
+/// with the same knot lines. The returned residuals are the error of the approximation.
+pub fn fit_nurbs<T: ControlMeshMut + Default + Sync>(
+    surface: &NurbsSurface,
+    samples: usize,
+    boundary: Boundary,
+) -> Result<(T, Residuals), NurbsError> {
+    let breaks = |knots: &[isize]| {
+        let mut values = knots[3..knots.len() - 3].to_vec();
+        values.dedup();
+        values
+    };
+    let (s, t) = (breaks(&surface.s_knots), breaks(&surface.t_knots));
+    let mut mesh: T = new_plane(s.len(), t.len(), T::Unit::one(), T::Unit::one())?;
+    for (j, &tj) in t.iter().enumerate() {
+        for (i, &si) in s.iter().enumerate() {
+            let point = mesh
+                .point_mut(VertID(j * s.len() + i))
+                .ok_or(NurbsError::TooFewPoints)?;
+            (point.s, point.t) = (si, tj);
+        }
+    }
+    let knot_cache = knot_vectors(&mesh, boundary);
+
+    let (s0, s1, t0, t1) = (s[0], s[s.len() - 1], t[0], t[t.len() - 1]);
+    let n = knot_cache.len();
+    let mut a = vec![vec![0.; n]; n];
+    let mut points = vec![[0.; 3]; n];
+    let mut weights = vec![[0.; 3]; n];
+    let mut targets = Vec::new();
+    for span_t in t.windows(2) {
+        for span_s in s.windows(2) {
+            for k in 0..samples * samples {
+                let at = |(lo, hi): (isize, isize), k: usize| {
+                    lo as f64 + (hi - lo) as f64 * (k as f64 + 0.5) / samples as f64
+                };
+                let st = (
+                    at((span_s[0], span_s[1]), k % samples),
+                    at((span_t[0], span_t[1]), k / samples),
+                );
+                let h = surface.weighted(st).ok_or(NurbsError::EmptyDomain)?;
+                let row: Vec<_> = knot_cache
+                    .iter()
+                    .enumerate()
+                    .map(|(v, knots)| {
+                        let b = cubic_basis_function(st.0, &knots.s_knots)
+                            * cubic_basis_function(st.1, &knots.t_knots);
+                        (v, b)
+                    })
+                    .filter(|&(_, b)| b != 0.)
+                    .collect();
+                for &(c0, r0) in &row {
+                    for &(c1, r1) in &row {
+                        a[c0][c1] += r0 * r1;
+                    }
+                    (0..3).for_each(|d| points[c0][d] += r0 * h[d]);
+                    weights[c0][0] += r0 * h.w;
+                }
+                targets.push(Sample {
+                    uv: (
+                        (st.0 - s0 as f64) / (s1 - s0) as f64,
+                        (st.1 - t0 as f64) / (t1 - t0) as f64,
+                    ),
+                    point: Point3::new(h.x / h.w, h.y / h.w, h.z / h.w),
+                });
+            }
+        }
+    }
+
+    let points = solve(a.clone(), points).ok_or(FitError::Singular)?;
+    let weights = solve(a, weights).ok_or(FitError::Singular)?;
+    let cast = from_f64::<T::Unit>;
+    for (v, (p, &[w, ..])) in points.iter().zip(&weights).enumerate() {
+        if w <= 0. {
+            return Err(NurbsError::NonPositiveWeight);
+        }
+        *mesh
+            .control_point_mut(VertID(v))
+            .ok_or(NurbsError::TooFewPoints)? =
+            Vector4::new(cast(p[0] / w)?, cast(p[1] / w)?, cast(p[2] / w)?, cast(w)?);
+    }
+    let residuals = residuals(&mesh, &targets, boundary)?;
+    Ok((mesh, residuals))
+}
+
+/// A cubic knot vector with every break repeated three times, four at the ends
+fn clamped(breaks: &[isize]) -> Vec<isize> {
+    let mut knots = vec![breaks[0]];
+    for &b in breaks {
+        knots.extend([b; 3]);
+    }
+    knots.push(breaks[breaks.len() - 1]);
+    knots
+}
+
+/// The index of the first cubic B-spline over `knots` that is not zero at `u` with the
+/// values of it and the next three, `None` outside of the domain
+fn basis(knots: &[isize], u: f64) -> Option<(usize, [f64; 4])> {
+    let n = knots.len() - 4;
+    let k = |i: usize| knots[i] as f64;
+    if u < k(3) || u > k(n) {
+        return None;
+    }
+    // the last non empty span starting at or before u, so the end of the domain is
+    // inside of it
+    let span = (3..n).rev().find(|&i| k(i) <= u && k(i) < k(i + 1))?;
+
+    let mut values = [1., 0., 0., 0.];
+    let mut left = [0.; 4];
+    let mut right = [0.; 4];
+    for j in 1..4 {
+        left[j] = u - k(span + 1 - j);
+        right[j] = k(span + j) - u;
+        let mut saved = 0.;
+        for r in 0..j {
+            let temp = values[r] / (right[r + 1] + left[j - r]);
+            values[r] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        values[j] = saved;
+    }
+    Some((span - 3, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::t_junction::t_junction;
+    use t_spline::TSpline;
+    use t_spline::algorithms::subs;
+    use t_spline::uv_mesh::UVMesh;
+
+    #[test]
+    fn it_converts_a_t_junction_to_one_surface() {
+        let mut mesh: TSpline = t_junction();
+        mesh.control_point_mut(VertID(3)).unwrap().z = 1.;
+        mesh.control_point_mut(VertID(6)).unwrap().w = 2.;
+        let knots = knot_vectors(&mesh, Boundary::Clamped);
+
+        let surfaces = ToNurbs::from_parameters(&Parameters::default())
+            .unwrap()
+            .apply(&mesh)
+            .unwrap();
+
+        assert_eq!(1, surfaces.len());
+        let surface = &surfaces[0];
+        assert_eq!(
+            surface.width() * surface.height(),
+            surface.control_points().len()
+        );
+        assert_eq!(
+            Some(&0),
+            surface.s_knots().first(),
+            "{:?}",
+            surface.s_knots()
+        );
+        for st in [(0.3, 0.2), (1., 1.), (1.7, 1.4), (0.5, 1.9)] {
+            let expected = subs(mesh.control_points(), st, &knots).unwrap();
+            let actual = surface.evaluate(st).unwrap();
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert!((a - e).abs() < 1e-9, "{st:?} {actual:?} {expected}");
+            }
+        }
+    }
+
+    #[test]
+    fn it_fits_a_nurbs_surface() {
+        let mut original: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        original.control_point_mut(VertID(12)).unwrap().z = 1.;
+        let knots = knot_vectors(&original, Boundary::Clamped);
+        let surface = &to_nurbs(&original, Boundary::Clamped).unwrap()[0];
+
+        let (mesh, residuals): (TSpline, _) = fit_nurbs(surface, 3, Boundary::Clamped).unwrap();
+
+        mesh.validate_control_mesh().unwrap();
+        assert_eq!(original.points(), mesh.points());
+        assert!(residuals.max < 1e-9, "{residuals:?}");
+        let imported = knot_vectors(&mesh, Boundary::Clamped);
+        for st in [(0.5, 0.5), (2., 2.), (3.3, 1.2)] {
+            let expected = subs(original.control_points(), st, &knots).unwrap();
+            let actual = subs(mesh.control_points(), st, &imported).unwrap();
+            assert!((actual - expected).abs().max() < 1e-9, "{st:?}");
+        }
+    }
+
+    #[test]
+    fn it_checks_nurbs_input() {
+        let knots = vec![0, 0, 0, 0, 1, 1, 1, 1];
+        let points = vec![Point3::origin(); 16];
+        let surface = NurbsSurface::new(knots.clone(), knots.clone(), &points, &[1.; 16]);
+        let corner = surface.unwrap().evaluate((1., 1.)).unwrap();
+        assert_eq!(Point3::origin(), corner);
+
+        assert!(matches!(
+            NurbsSurface::new(knots.clone(), knots.clone(), &points, &[1.; 15]),
+            Err(NurbsError::WrongCount { .. })
+        ));
+        assert!(matches!(
+            NurbsSurface::new(vec![0, 0, 0, 0, 1, 1, 1, 0], knots, &points, &[1.; 16]),
+            Err(NurbsError::DecreasingKnots)
+        ));
+    }
+
+    #[test]
+    fn it_repeats_knots() {
+        assert_eq!(vec![0, 0, 0, 0, 1, 1, 1, 3, 3, 3, 3], clamped(&[0, 1, 3]));
+    }
+}
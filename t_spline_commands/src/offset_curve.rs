@@ -0,0 +1,230 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::CastError;
+use crate::project_curve::{ProjectError, evaluate};
+use crate::tessellate::knot_vectors;
+use t_spline::Vector3;
+use t_spline::algorithms::{EvalError, SurfaceDerivatives};
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum OffsetError {
+    #[error("a curve needs at least 2 points")]
+    TooFewPoints,
+    #[error("curve point {0} is not on the surface")]
+    OffSurface(usize),
+    #[error("the surface degenerates at curve point {0}")]
+    Degenerate(usize),
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("failed to evaluate the surface: {0}")]
+    Eval(#[from] EvalError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// Offset a curve on the surface by `distance` measured along the surface.
+///
+/// Each point of the parametric `curve` walks `steps` straight steps across the surface,
+/// starting perpendicular to the curve and keeping its heading within the tangent plane,
+/// which approximates a geodesic. Positive distances offset to the left of the curve as
+/// seen from the surface normal. A walk that reaches the edge of the surface stops there.
+pub fn offset_curve<T: ControlMesh + Sync>(
+    mesh: &T,
+    curve: &[(f64, f64)],
+    distance: f64,
+    steps: usize,
+    boundary: Boundary,
+) -> Result<Vec<(f64, f64)>, OffsetError> {
+    if curve.len() < 2 {
+        return Err(OffsetError::TooFewPoints);
+    }
+    mesh.validate_control_mesh()?;
+    let knot_cache = knot_vectors(mesh, boundary);
+
+    let steps = steps.max(1);
+    let step = distance.abs() / steps as f64;
+
+    let mut offset = Vec::with_capacity(curve.len());
+    for (i, &st) in curve.iter().enumerate() {
+        let d = at(mesh, &knot_cache, st)?.ok_or(OffsetError::OffSurface(i))?;
+
+        let (prev, next) = (
+            curve[i.saturating_sub(1)],
+            curve[(i + 1).min(curve.len() - 1)],
+        );
+        let tangent = d.ds * (next.0 - prev.0) + d.dt * (next.1 - prev.1);
+        let mut heading = normal(&d)
+            .and_then(|n| unit(n.cross(&tangent)))
+            .ok_or(OffsetError::Degenerate(i))?;
+        if distance < 0. {
+            heading = -heading;
+        }
+
+        let mut current = (st, d);
+        for _ in 0..steps {
+            let (st, d) = &current;
+            let Some(mut delta) = solve(d, heading * step) else {
+                break;
+            };
+            let mut next = (st.0 + delta.0, st.1 + delta.1);
+            let Some(mut next_d) = at(mesh, &knot_cache, next)? else {
+                break;
+            };
+
+            // rescale the linear step until it covers `step` on the surface
+            for _ in 0..8 {
+                let chord = next_d.point - d.point;
+                let chord = chord.dot(&chord).sqrt();
+                if chord <= f64::EPSILON || (chord - step).abs() <= step * 1e-12 {
+                    break;
+                }
+                delta = (delta.0 * step / chord, delta.1 * step / chord);
+                let scaled = (st.0 + delta.0, st.1 + delta.1);
+                let Some(scaled_d) = at(mesh, &knot_cache, scaled)? else {
+                    break;
+                };
+                (next, next_d) = (scaled, scaled_d);
+            }
+            let d = next_d;
+
+            // keep heading within the new tangent plane
+            if let Some(n) = normal(&d)
+                && let Some(h) = unit(heading - n * heading.dot(&n))
+            {
+                heading = h;
+            }
+            current = (next, d);
+        }
+        offset.push(current.0);
+    }
+
+    Ok(offset)
+}
+
+fn at<T: ControlMesh>(
+    mesh: &T,
+    knot_cache: &[LocalKnots],
+    st: (f64, f64),
+) -> Result<Option<SurfaceDerivatives<f64>>, OffsetError> {
+    evaluate(mesh, knot_cache, st).map_err(|e| match e {
+        ProjectError::Eval(e) => OffsetError::Eval(e),
+        ProjectError::Invalid(e) => OffsetError::Invalid(e),
+        ProjectError::Cast(e) => OffsetError::Cast(e),
+        ProjectError::Empty | ProjectError::InvalidCurve(..) => OffsetError::Cast(CastError),
+    })
+}
+
+/// Least squares parameter step moving the surface point by `target`
+fn solve(d: &SurfaceDerivatives<f64>, target: Vector3<f64>) -> Option<(f64, f64)> {
+    let (a, b, c) = (d.ds.dot(&d.ds), d.ds.dot(&d.dt), d.dt.dot(&d.dt));
+    let det = a * c - b * b;
+    if det.abs() <= f64::EPSILON * a.max(c) {
+        return None;
+    }
+    let (rs, rt) = (d.ds.dot(&target), d.dt.dot(&target));
+    Some(((c * rs - b * rt) / det, (a * rt - b * rs) / det))
+}
+
+fn normal(d: &SurfaceDerivatives<f64>) -> Option<Vector3<f64>> {
+    unit(d.ds.cross(&d.dt))
+}
+
+fn unit(v: Vector3<f64>) -> Option<Vector3<f64>> {
+    let length = v.dot(&v).sqrt();
+    (length > f64::EPSILON).then(|| v / length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use crate::project_curve::distance_squared;
+    use t_spline::algorithms::subs;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+    use t_spline::{Point3, TSpline};
+
+    fn straight() -> Vec<(f64, f64)> {
+        (0..=6).map(|i| (0.6 + i as f64 * 0.3, 1.5)).collect()
+    }
+
+    #[test]
+    fn it_offsets_parallel_on_a_plane() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let knots = knot_vectors(&mesh, Boundary::Clamped);
+        let curve = straight();
+
+        let left = offset_curve(&mesh, &curve, 0.5, 8, Boundary::Clamped).unwrap();
+        let right = offset_curve(&mesh, &curve, -0.5, 8, Boundary::Clamped).unwrap();
+
+        for ((st, l), r) in curve.iter().zip(left).zip(right) {
+            let p = subs(mesh.control_points(), *st, &knots).unwrap();
+            let l = subs(mesh.control_points(), l, &knots).unwrap();
+            let r = subs(mesh.control_points(), r, &knots).unwrap();
+
+            assert!(distance_squared(&l, &Point3::new(p.x, p.y + 0.5, 0.)) < 1e-12);
+            assert!(distance_squared(&r, &Point3::new(p.x, p.y - 0.5, 0.)) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_measures_along_a_curved_surface() {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        for v in [9, 10] {
+            mesh.control_point_mut(VertID(v)).unwrap().z = 1.;
+        }
+        let knots = knot_vectors(&mesh, Boundary::Clamped);
+        let curve = straight();
+
+        let offset = offset_curve(&mesh, &curve, 0.4, 64, Boundary::Clamped).unwrap();
+
+        for (st, o) in curve.iter().zip(offset) {
+            let p = subs(mesh.control_points(), *st, &knots).unwrap();
+            let o = subs(mesh.control_points(), o, &knots).unwrap();
+
+            // the surface bends, so the chord is shorter than the walked distance
+            let chord = distance_squared(&p, &o).sqrt();
+            assert!(chord <= 0.4 + 1e-6 && chord > 0.3, "{chord}");
+        }
+    }
+
+    #[test]
+    fn it_reports_evaluation_errors() {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        for v in 0..16 {
+            mesh.control_point_mut(VertID(v)).unwrap().w = 0.;
+        }
+
+        assert!(matches!(
+            offset_curve(&mesh, &straight(), 0.5, 4, Boundary::Clamped),
+            Err(OffsetError::Eval(EvalError::ZeroWeight))
+        ));
+    }
+
+    #[test]
+    fn it_requires_a_curve() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+
+        assert!(matches!(
+            offset_curve(&mesh, &[(1., 1.)], 0.5, 4, Boundary::Clamped),
+            Err(OffsetError::TooFewPoints)
+        ));
+    }
+}
@@ -0,0 +1,303 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::command::{Command, CommandError, Parameters};
+use crate::displace::{DisplaceError, vertex_normals};
+use crate::knot_cache::Influence;
+use crate::split_face::{SplitAt, SplitFaceError, face_range, split_face};
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum PatternError {
+    #[error("location {0:?} is outside the mesh")]
+    OutsideMesh((isize, isize)),
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("spacing must be positive")]
+    InvalidSpacing,
+    #[error("failed to refine: {0}")]
+    Split(#[from] SplitFaceError),
+    #[error(transparent)]
+    Displace(#[from] DisplaceError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// A small surface feature, given as offsets along the normal of control points on a
+/// grid around its location
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Feature {
+    /// A single raised control point, a negative height makes a dimple
+    Bump { height: f64 },
+    /// A raised plateau of three by three control points
+    Emboss { height: f64 },
+}
+
+impl Feature {
+    /// Offsets of the feature cage by grid position in units of the spacing.
+    ///
+    /// The ring of zero offsets around the raised points keeps the feature from
+    /// spreading over the coarse faces of the host.
+    pub fn cage(&self) -> Vec<((isize, isize), f64)> {
+        let (radius, raised, height): (isize, isize, f64) = match *self {
+            Feature::Bump { height } => (1, 0, height),
+            Feature::Emboss { height } => (2, 1, height),
+        };
+        let mut cage = Vec::new();
+        for j in -radius..=radius {
+            for i in -radius..=radius {
+                let offset = if i.abs() <= raised && j.abs() <= raised {
+                    height
+                } else {
+                    0.
+                };
+                cage.push(((i, j), offset));
+            }
+        }
+        cage
+    }
+}
+
+/// [pattern] as a [Command], built from the `feature` (`bump` or `emboss`), `height`,
+/// `s` and `t` lists of knot values whose every combination is a location, and optional
+/// `spacing` and `boundary` parameters
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub feature: Feature,
+    pub locations: Vec<(isize, isize)>,
+    /// Knot distance between the control points of the feature cage
+    pub spacing: isize,
+    pub boundary: Boundary,
+}
+
+impl Pattern {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        let height = parameters.get("height")?;
+        let feature = parameters.get_with("feature", |v| match v {
+            "bump" => Some(Feature::Bump { height }),
+            "emboss" => Some(Feature::Emboss { height }),
+            _ => None,
+        })?;
+        let (s, t): (Vec<isize>, Vec<isize>) =
+            (parameters.get_list("s")?, parameters.get_list("t")?);
+        Ok(Self {
+            feature,
+            locations: t
+                .iter()
+                .flat_map(|&t| s.iter().map(move |&s| (s, t)))
+                .collect(),
+            spacing: parameters.get_or("spacing", 1)?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for Pattern {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        pattern(
+            mesh,
+            &self.feature,
+            &self.locations,
+            self.spacing,
+            self.boundary,
+        )
+        .map_err(CommandError::failed)?;
+        Ok(Influence::Global)
+    }
+}
+
+/// Place a copy of `feature` at every parametric location, e.g. on a grid or sampled
+/// along a curve.
+///
+/// The faces under each feature cage are split until the mesh has a vertex at every
+/// point of the cage, then the raised points move along the surface normal. Features
+/// that overlap add up.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), locations = locations.len()))
+)]
+pub fn pattern<T: ControlMeshMut>(
+    mesh: &mut T,
+    feature: &Feature,
+    locations: &[(isize, isize)],
+    spacing: isize,
+    boundary: Boundary,
+) -> Result<(), PatternError> {
+    if spacing <= 0 {
+        return Err(PatternError::InvalidSpacing);
+    }
+    mesh.validate_control_mesh()?;
+
+    let cage = feature.cage();
+    let mut raised = Vec::new();
+    for &(s, t) in locations {
+        for &((i, j), offset) in &cage {
+            let vertex = insert_vertex(mesh, (s + i * spacing, t + j * spacing), boundary)?;
+            if offset != 0. {
+                raised.push((vertex, offset));
+            }
+        }
+    }
+
+    let normals = vertex_normals(mesh, boundary)?;
+    for (vertex, offset) in raised {
+        let cp = mesh
+            .control_point_mut(vertex)
+            .ok_or(PatternError::MissingControlPoint)?;
+        let n = normals[vertex.0] * offset;
+        let moved = |c: T::Unit, d: f64| {
+            let c = to_f64(c)?;
+            from_f64::<T::Unit>(c + d)
+        };
+        (cp.x, cp.y, cp.z) = (moved(cp.x, n.x)?, moved(cp.y, n.y)?, moved(cp.z, n.z)?);
+    }
+    Ok(())
+}
+
+/// The vertex at `(s, t)`, splitting the face around it first if there is none
+fn insert_vertex<T: ControlMeshMut>(
+    mesh: &mut T,
+    (s, t): (isize, isize),
+    boundary: Boundary,
+) -> Result<VertID, PatternError> {
+    // at most one split per direction
+    for _ in 0..3 {
+        if let Some(v) = mesh.points().iter().position(|p| (p.s, p.t) == (s, t)) {
+            return Ok(VertID(v));
+        }
+
+        let face = containing_face(mesh, (s, t))?;
+        let (s0, s1) = face_range(mesh, face, Direction::S)?;
+        let direction = if s0 < s && s < s1 {
+            Direction::S
+        } else {
+            Direction::T
+        };
+        let value = match direction {
+            Direction::S => s,
+            Direction::T => t,
+        };
+        split_face(mesh, face, direction, SplitAt::Absolute(value), boundary)?;
+    }
+    Err(PatternError::OutsideMesh((s, t)))
+}
+
+/// A face whose parametric range contains `(s, t)`, including its sides
+fn containing_face<T: ControlMeshMut>(
+    mesh: &T,
+    (s, t): (isize, isize),
+) -> Result<EdgeID, PatternError> {
+    for face in mesh.faces() {
+        let (s0, s1) = face_range(mesh, face, Direction::S)?;
+        let (t0, t1) = face_range(mesh, face, Direction::T)?;
+        if (s0..=s1).contains(&s) && (t0..=t1).contains(&t) {
+            return Ok(face);
+        }
+    }
+    Err(PatternError::OutsideMesh((s, t)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::CommandRegistry;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::{UVMesh, UVMeshMut};
+
+    /// A plane with knot intervals of four, leaving room for features between the knots
+    fn coarse() -> TSpline {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        for v in 0..mesh.points().len() {
+            let p = mesh.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 4, p.t * 4);
+        }
+        mesh
+    }
+
+    fn z_at<T: ControlMesh<Unit = f64>>(mesh: &T, st: (isize, isize)) -> f64 {
+        let v = mesh.points().iter().position(|p| (p.s, p.t) == st).unwrap();
+        mesh.control_points()[v].z
+    }
+
+    #[test]
+    fn it_inserts_a_bump_between_knots() {
+        let mut mesh = coarse();
+
+        pattern(
+            &mut mesh,
+            &Feature::Bump { height: 0.5 },
+            &[(6, 6)],
+            1,
+            Boundary::Clamped,
+        )
+        .unwrap();
+
+        mesh.validate_control_mesh().unwrap();
+        assert!((z_at(&mesh, (6, 6)) - 0.5).abs() < 1e-9);
+        assert!(z_at(&mesh, (5, 5)).abs() < 1e-9);
+        assert_eq!(0., z_at(&mesh, (0, 0)));
+    }
+
+    #[test]
+    fn it_repeats_features_on_a_grid() {
+        let mut mesh = coarse();
+        let before = mesh.points().len();
+
+        CommandRegistry::default()
+            .apply_mut(
+                &mut mesh,
+                "pattern",
+                &Parameters::default()
+                    .with("feature", "emboss")
+                    .with("height", -0.25)
+                    .with("s", "3,9")
+                    .with("t", "6"),
+            )
+            .unwrap();
+
+        mesh.validate_control_mesh().unwrap();
+        assert!(mesh.points().len() > before + 2 * 9);
+        for st in [(2, 5), (3, 6), (4, 7), (8, 6), (10, 7)] {
+            assert!((z_at(&mesh, st) + 0.25).abs() < 1e-9, "{st:?}");
+        }
+    }
+
+    #[test]
+    fn it_rejects_locations_outside_the_mesh() {
+        let mut mesh = coarse();
+
+        assert!(matches!(
+            pattern(
+                &mut mesh,
+                &Feature::Bump { height: 1. },
+                &[(20, 4)],
+                1,
+                Boundary::Clamped
+            ),
+            Err(PatternError::OutsideMesh(_))
+        ));
+    }
+}
@@ -0,0 +1,226 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::CastError;
+use num_traits::{FromPrimitive, One, Zero};
+use std::collections::BTreeMap;
+use t_spline::Vector4;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::UVMesh;
+use t_spline::uv_mesh::half_edge::HalfEdge;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::uv_point::UVPoint;
+use thiserror::Error;
+
+#[derive(Copy, Clone, Debug, Error)]
+pub enum PlaneError {
+    #[error("a plane needs at least 2 control points in each direction")]
+    TooFewPoints,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is not a grid of control points")]
+    NotAGrid,
+}
+
+/// A side of the parametric rectangle of a grid shaped mesh, like the ones [new_plane] makes
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GridSide {
+    SMin,
+    SMax,
+    TMin,
+    TMax,
+}
+
+impl GridSide {
+    pub const ALL: [GridSide; 4] = [
+        GridSide::SMin,
+        GridSide::SMax,
+        GridSide::TMin,
+        GridSide::TMax,
+    ];
+}
+
+/// The control points along a [GridSide]
+#[derive(Debug, Clone)]
+pub(crate) struct GridRows {
+    /// Control points on the side, in order of their knot values
+    pub boundary: Vec<VertID>,
+    /// Control points of the next row inside the mesh
+    pub inner: Vec<VertID>,
+    /// Knot values along the side
+    pub knots: Vec<isize>,
+}
+
+/// The rows of control points along `side` of a mesh whose points form a full grid
+pub(crate) fn grid_rows(mesh: &impl UVMesh, side: GridSide) -> Result<GridRows, PlaneError> {
+    let grid: BTreeMap<(isize, isize), VertID> = mesh
+        .points()
+        .iter()
+        .enumerate()
+        .map(|(v, p)| ((p.s, p.t), VertID(v)))
+        .collect();
+    let mut s: Vec<_> = grid.keys().map(|&(s, _)| s).collect();
+    let mut t: Vec<_> = grid.keys().map(|&(_, t)| t).collect();
+    s.sort_unstable();
+    s.dedup();
+    t.sort_unstable();
+    t.dedup();
+    if s.len() < 2 || t.len() < 2 || grid.len() != s.len() * t.len() {
+        return Err(PlaneError::NotAGrid);
+    }
+
+    let (ns, nt) = (s.len(), t.len());
+    // sides at a fixed s run along t
+    let fixed_s = matches!(side, GridSide::SMin | GridSide::SMax);
+    let along = if fixed_s { &t } else { &s };
+    let at = |fixed, k| if fixed_s { (fixed, k) } else { (k, fixed) };
+    let (edge, inner) = match side {
+        GridSide::SMin => (s[0], s[1]),
+        GridSide::SMax => (s[ns - 1], s[ns - 2]),
+        GridSide::TMin => (t[0], t[1]),
+        GridSide::TMax => (t[nt - 1], t[nt - 2]),
+    };
+    let row = |fixed| along.iter().map(|&k| grid[&at(fixed, k)]).collect();
+    Ok(GridRows {
+        boundary: row(edge),
+        inner: row(inner),
+        knots: along.to_vec(),
+    })
+}
+
+/// Create a flat `ns` x `nt` grid of control points spanning `width` x `height`.
+///
+/// UV points are placed on a uniform integer grid, faces are wound counter-clockwise
+/// and neighbouring faces are connected through twin edges.
+pub fn new_plane<T: ControlMeshMut + Default>(
+    ns: usize,
+    nt: usize,
+    width: T::Unit,
+    height: T::Unit,
+) -> Result<T, PlaneError> {
+    if ns < 2 || nt < 2 {
+        return Err(PlaneError::TooFewPoints);
+    }
+
+    let mut mesh = T::default();
+    let faces_s = ns - 1;
+    let faces_t = nt - 1;
+
+    let face = |i: usize, j: usize| -> Option<usize> {
+        (i < faces_s && j < faces_t).then(|| j * faces_s + i)
+    };
+    let vert = |i: usize, j: usize| VertID(j * ns + i);
+
+    // Face edges are numbered bottom, right, top, left
+    let edge = |f: usize, k: usize| EdgeID(4 * f + k);
+
+    // 1. Define the grid of vertices
+    for j in 0..nt {
+        for i in 0..ns {
+            // any face edge leaving this vertex
+            let outgoing_edge = face(i, j)
+                .map(|f| edge(f, 0))
+                .or_else(|| face(i.wrapping_sub(1), j).map(|f| edge(f, 1)))
+                .or_else(|| face(i.wrapping_sub(1), j.wrapping_sub(1)).map(|f| edge(f, 2)))
+                .or_else(|| face(i, j.wrapping_sub(1)).map(|f| edge(f, 3)))
+                .expect("every grid vertex touches a face");
+
+            mesh.push_point(UVPoint {
+                s: isize::try_from(i).map_err(|_| CastError)?,
+                t: isize::try_from(j).map_err(|_| CastError)?,
+                outgoing_edge,
+            });
+
+            let x = width * fraction::<T>(i, faces_s)?;
+            let y = height * fraction::<T>(j, faces_t)?;
+            mesh.push_control_point(Vector4::new(x, y, T::Unit::zero(), T::Unit::one()));
+        }
+    }
+
+    // 2. Define 4 half-edges per face in a CCW loop
+    for j in 0..faces_t {
+        for i in 0..faces_s {
+            let f = j * faces_s + i;
+            let corners = [
+                vert(i, j),
+                vert(i + 1, j),
+                vert(i + 1, j + 1),
+                vert(i, j + 1),
+            ];
+            let twins = [
+                face(i, j.wrapping_sub(1)).map(|f| edge(f, 2)),
+                face(i + 1, j).map(|f| edge(f, 3)),
+                face(i, j + 1).map(|f| edge(f, 0)),
+                face(i.wrapping_sub(1), j).map(|f| edge(f, 1)),
+            ];
+
+            for k in 0..4 {
+                mesh.push_edge(HalfEdge {
+                    origin: corners[k],
+                    twin: twins[k],
+                    next: edge(f, (k + 1) % 4),
+                    prev: edge(f, (k + 3) % 4),
+                });
+            }
+        }
+    }
+
+    Ok(mesh)
+}
+
+fn fraction<T: ControlMeshMut>(i: usize, n: usize) -> Result<T::Unit, PlaneError> {
+    let i = T::Unit::from_usize(i).ok_or(CastError)?;
+    let n = T::Unit::from_usize(n).ok_or(CastError)?;
+    Ok(i / n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tessellate::tessellate;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::{Boundary, UVMesh};
+
+    #[test]
+    fn it_creates_a_grid() {
+        let mesh: TSpline = new_plane(4, 3, 6., 4.).unwrap();
+
+        mesh.validate_control_mesh().unwrap();
+        assert_eq!(12, mesh.points().len());
+        assert_eq!(24, mesh.edges().len());
+        assert_eq!(6, mesh.faces().count());
+
+        assert_eq!(2, mesh.connected_verteces(VertID(0)).len());
+        assert_eq!(3, mesh.connected_verteces(VertID(1)).len());
+        assert_eq!(4, mesh.connected_verteces(VertID(5)).len());
+
+        assert_eq!(Vector4::new(6., 4., 0., 1.), mesh.control_points()[11]);
+    }
+
+    #[test]
+    fn it_tessellates_a_grid() {
+        let mesh: TSpline = new_plane(4, 4, 1., 1.).unwrap();
+
+        let points = tessellate(&mesh, 4, Boundary::Clamped).unwrap();
+        assert_eq!(16, points.len());
+    }
+
+    #[test]
+    fn it_requires_two_points() {
+        assert!(new_plane::<TSpline>(1, 3, 1., 1.).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::anchors::{AnchorError, Anchors};
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::tessellate::knot_vectors;
+use t_spline::algorithms::{EvalPolicy, try_subs_derivatives};
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+/// Parametric step of the central differences for second derivatives
+const STEP: f64 = 1e-3;
+
+#[derive(Clone, Debug, Error)]
+pub enum ProbeError {
+    #[error("surface has no tangent plane at {0:?}")]
+    Degenerate((f64, f64)),
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error(transparent)]
+    Anchor(#[from] AnchorError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// The straight distance between two anchors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceProbe {
+    pub from: Point3<f64>,
+    pub to: Point3<f64>,
+    pub distance: f64,
+}
+
+/// The angle between the surface normals at two parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AngleProbe {
+    pub points: [Point3<f64>; 2],
+    pub normals: [Vector3<f64>; 2],
+    /// In radians, from `0` for parallel normals to `π` for opposite ones
+    pub angle: f64,
+}
+
+/// The curvature of the surface at a parameter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RadiusProbe {
+    pub point: Point3<f64>,
+    pub normal: Vector3<f64>,
+    /// Principal curvatures, largest first, positive where the surface bends towards
+    /// the normal
+    pub curvatures: [f64; 2],
+    /// The smallest radius of curvature, infinite where the surface is flat
+    pub radius: f64,
+}
+
+/// Measure between the anchors `from` and `to`
+pub fn probe_distance<T: ControlMesh + Sync>(
+    mesh: &T,
+    anchors: &Anchors,
+    from: &str,
+    to: &str,
+    boundary: Boundary,
+) -> Result<DistanceProbe, ProbeError> {
+    let (from, to) = (
+        anchors.position(mesh, from, boundary)?,
+        anchors.position(mesh, to, boundary)?,
+    );
+    Ok(DistanceProbe {
+        from,
+        to,
+        distance: length(to - from),
+    })
+}
+
+/// Compare the normals at the parameters `a` and `b`
+pub fn probe_angle<T: ControlMesh + Sync>(
+    mesh: &T,
+    a: (f64, f64),
+    b: (f64, f64),
+    boundary: Boundary,
+) -> Result<AngleProbe, ProbeError> {
+    mesh.validate_control_mesh()?;
+    let knot_cache = knot_vectors(mesh, boundary);
+    let (a, b) = (
+        derivatives(mesh, &knot_cache, a)?,
+        derivatives(mesh, &knot_cache, b)?,
+    );
+    let normals = [a.normal()?, b.normal()?];
+    Ok(AngleProbe {
+        points: [a.point, b.point],
+        normals,
+        angle: normals[0].dot(&normals[1]).clamp(-1., 1.).acos(),
+    })
+}
+
+/// Estimate the principal curvatures at `st` from the fundamental forms, with second
+/// derivatives taken by central differences
+pub fn probe_radius<T: ControlMesh + Sync>(
+    mesh: &T,
+    st: (f64, f64),
+    boundary: Boundary,
+) -> Result<RadiusProbe, ProbeError> {
+    mesh.validate_control_mesh()?;
+    radius(mesh, &knot_vectors(mesh, boundary), st)
+}
+
+/// [probe_radius] with the knot vectors of `mesh` already computed
+pub(crate) fn radius<T: ControlMesh>(
+    mesh: &T,
+    knot_cache: &[LocalKnots],
+    st: (f64, f64),
+) -> Result<RadiusProbe, ProbeError> {
+    let at = derivatives(mesh, knot_cache, st)?;
+    let normal = at.normal()?;
+    let [s_lo, s_hi, t_lo, t_hi] = [(-STEP, 0.), (STEP, 0.), (0., -STEP), (0., STEP)]
+        .map(|(ds, dt)| derivatives(mesh, knot_cache, (st.0 + ds, st.1 + dt)));
+    let (s_lo, s_hi, t_lo, t_hi) = (s_lo?, s_hi?, t_lo?, t_hi?);
+
+    let ss = (s_hi.ds - s_lo.ds) / (2. * STEP);
+    let st_ = ((s_hi.dt - s_lo.dt) + (t_hi.ds - t_lo.ds)) / (4. * STEP);
+    let tt = (t_hi.dt - t_lo.dt) / (2. * STEP);
+
+    // first and second fundamental forms
+    let (e, f, g) = (at.ds.dot(&at.ds), at.ds.dot(&at.dt), at.dt.dot(&at.dt));
+    let (l, m, n) = (ss.dot(&normal), st_.dot(&normal), tt.dot(&normal));
+    let det = e * g - f * f;
+    if det <= f64::EPSILON {
+        return Err(ProbeError::Degenerate(st));
+    }
+    let mean = (e * n - 2. * f * m + g * l) / (2. * det);
+    let gaussian = (l * n - m * m) / det;
+    let spread = (mean * mean - gaussian).max(0.).sqrt();
+    let curvatures = [mean + spread, mean - spread];
+
+    let largest = curvatures[0].abs().max(curvatures[1].abs());
+    Ok(RadiusProbe {
+        point: at.point,
+        normal,
+        curvatures,
+        radius: if largest > f64::EPSILON {
+            1. / largest
+        } else {
+            f64::INFINITY
+        },
+    })
+}
+
+struct Derivatives {
+    st: (f64, f64),
+    point: Point3<f64>,
+    ds: Vector3<f64>,
+    dt: Vector3<f64>,
+}
+
+impl Derivatives {
+    fn normal(&self) -> Result<Vector3<f64>, ProbeError> {
+        let normal = self.ds.cross(&self.dt);
+        let l = length(normal);
+        if l <= f64::EPSILON {
+            return Err(ProbeError::Degenerate(self.st));
+        }
+        Ok(normal / l)
+    }
+}
+
+fn derivatives<T: ControlMesh>(
+    mesh: &T,
+    knot_cache: &[LocalKnots],
+    st: (f64, f64),
+) -> Result<Derivatives, ProbeError> {
+    let cast = from_f64::<T::Unit>;
+    let d = try_subs_derivatives(
+        mesh.control_points(),
+        (cast(st.0)?, cast(st.1)?),
+        knot_cache,
+        EvalPolicy::Strict,
+    )
+    .map_err(|_| ProbeError::Degenerate(st))?;
+    let vector = |x, y, z| Ok::<_, ProbeError>(Vector3::new(to_f64(x)?, to_f64(y)?, to_f64(z)?));
+    Ok(Derivatives {
+        st,
+        point: Point3::from(vector(d.point.x, d.point.y, d.point.z)?),
+        ds: vector(d.ds.x, d.ds.y, d.ds.z)?,
+        dt: vector(d.dt.x, d.dt.y, d.dt.z)?,
+    })
+}
+
+fn length(v: Vector3<f64>) -> f64 {
+    v.dot(&v).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    /// A trough along `t` whose control points lie on `z = x² / 10`
+    fn trough() -> TSpline {
+        let mut mesh: TSpline = new_plane(9, 5, 8., 4.).unwrap();
+        for v in 0..mesh.control_points().len() {
+            let cp = mesh.control_point_mut(VertID(v)).unwrap();
+            cp.z = (cp.x - 4.) * (cp.x - 4.) / 10.;
+        }
+        mesh
+    }
+
+    /// The radius of the circle through three points
+    fn circumradius(a: Point3<f64>, b: Point3<f64>, c: Point3<f64>) -> f64 {
+        let (ab, bc, ca) = (length(b - a), length(c - b), length(a - c));
+        ab * bc * ca / (2. * length((b - a).cross(&(c - a))))
+    }
+
+    #[test]
+    fn it_estimates_the_radius_of_a_trough() {
+        let mesh = trough();
+        let probe = probe_radius(&mesh, (4., 2.), Boundary::Clamped).unwrap();
+
+        let knots = knot_vectors(&mesh, Boundary::Clamped);
+        let at = |s| derivatives(&mesh, &knots, (s, 2.)).unwrap().point;
+        let expected = circumradius(at(3.9), at(4.), at(4.1));
+
+        assert!(
+            (probe.radius - expected).abs() < 1e-2 * expected,
+            "{probe:?} {expected}"
+        );
+        // the trough is straight along t and bends up towards the normal along s
+        assert!(probe.curvatures[1].abs() < 1e-6);
+        assert!(probe.curvatures[0] * probe.normal.z > 0.);
+    }
+
+    #[test]
+    fn it_finds_flat_surfaces() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let probe = probe_radius(&mesh, (1.5, 1.5), Boundary::Clamped).unwrap();
+        assert_eq!(f64::INFINITY, probe.radius);
+
+        let angle = probe_angle(&mesh, (1., 1.), (2., 2.), Boundary::Clamped).unwrap();
+        assert!(angle.angle.abs() < 1e-6);
+    }
+
+    #[test]
+    fn it_measures_angles_and_distances() {
+        let mesh = trough();
+        let angle = probe_angle(&mesh, (2., 2.), (6., 2.), Boundary::Clamped).unwrap();
+        // the sides of the trough tilt towards each other
+        assert!(angle.angle > 0.1);
+        assert!((angle.normals[0].x + angle.normals[1].x).abs() < 1e-6);
+
+        let mut anchors = Anchors::default();
+        anchors.insert(&mesh, "left", (2., 2.)).unwrap();
+        anchors.insert(&mesh, "right", (6., 2.)).unwrap();
+        let distance = probe_distance(&mesh, &anchors, "left", "right", Boundary::Clamped).unwrap();
+        assert_eq!(angle.points, [distance.from, distance.to]);
+        assert!((distance.distance - length(distance.to - distance.from)).abs() < 1e-12);
+    }
+}
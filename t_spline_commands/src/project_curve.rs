@@ -0,0 +1,372 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::draft::cell_center;
+use crate::tessellate::knot_vectors;
+use std::cmp::Ordering;
+use t_spline::Point3;
+use t_spline::algorithms::{EvalError, EvalPolicy, SurfaceDerivatives, try_subs_derivatives};
+use t_spline::bounds::Bounded;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use thiserror::Error;
+
+/// Seed samples per face direction used to start the closest point search
+const SEEDS: usize = 4;
+/// Newton iterations per projected point
+const ITERATIONS: usize = 32;
+
+#[derive(Clone, Debug, Error)]
+pub enum ProjectError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh has no surface to project onto")]
+    Empty,
+    #[error("a curve of degree {0} with {1} control points needs {2} non decreasing knots")]
+    InvalidCurve(usize, usize, usize),
+    #[error("failed to evaluate the surface: {0}")]
+    Eval(#[from] EvalError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// A 3D B-spline curve of any degree
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSplineCurve {
+    degree: usize,
+    knots: Vec<f64>,
+    control_points: Vec<Point3<f64>>,
+}
+
+impl BSplineCurve {
+    /// A curve over `control_points` with `control_points.len() + degree + 1` knots
+    pub fn new(
+        degree: usize,
+        knots: Vec<f64>,
+        control_points: Vec<Point3<f64>>,
+    ) -> Result<Self, ProjectError> {
+        let n = control_points.len();
+        let invalid = ProjectError::InvalidCurve(degree, n, n + degree + 1);
+        if n <= degree
+            || knots.len() != n + degree + 1
+            || knots
+                .windows(2)
+                .any(|w| w[0].partial_cmp(&w[1]).is_none_or(Ordering::is_gt))
+            || knots[degree] >= knots[n]
+        {
+            return Err(invalid);
+        }
+        Ok(Self {
+            degree,
+            knots,
+            control_points,
+        })
+    }
+
+    /// The parameter range the curve is defined on
+    pub fn domain(&self) -> (f64, f64) {
+        (
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        )
+    }
+
+    /// The point at parameter `u`, clamped to the [domain](Self::domain), by de Boor's
+    /// algorithm
+    pub fn point(&self, u: f64) -> Point3<f64> {
+        let (p, n) = (self.degree, self.control_points.len());
+        let (lo, hi) = self.domain();
+        let u = u.clamp(lo, hi);
+        let k = (p..n).rev().find(|&k| self.knots[k] <= u).unwrap_or(p);
+
+        let mut d: Vec<_> = (0..=p)
+            .map(|j| self.control_points[j + k - p].coords)
+            .collect();
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let i = j + k - p;
+                let alpha = (u - self.knots[i]) / (self.knots[i + p + 1 - r] - self.knots[i]);
+                d[j] = d[j - 1] * (1. - alpha) + d[j] * alpha;
+            }
+        }
+        Point3::from(d[p])
+    }
+
+    /// `count` points evenly spaced in parameter over the whole curve
+    pub fn sample(&self, count: usize) -> Vec<Point3<f64>> {
+        let (lo, hi) = self.domain();
+        (0..count)
+            .map(|i| self.point(lo + (hi - lo) * i as f64 / (count.max(2) - 1) as f64))
+            .collect()
+    }
+}
+
+/// Project `curve` onto the surface of `mesh` as `samples` points, see [project_polyline]
+pub fn project_b_spline<T: ControlMesh + Sync>(
+    mesh: &T,
+    curve: &BSplineCurve,
+    samples: usize,
+    smoothing: usize,
+    boundary: Boundary,
+) -> Result<Vec<(f64, f64)>, ProjectError> {
+    project_polyline(mesh, &curve.sample(samples), smoothing, boundary)
+}
+
+/// Project the 3D polyline `points` onto the surface of `mesh`.
+///
+/// Every point moves to its closest point on the surface, found with Newton iterations
+/// started from the best of a coarse sampling and the previous projection. The projected
+/// parameters are then relaxed with `smoothing` Laplacian passes in parametric space,
+/// keeping both ends in place. Returns the `(s, t)` parameter of each point.
+pub fn project_polyline<T: ControlMesh + Sync>(
+    mesh: &T,
+    points: &[Point3<f64>],
+    smoothing: usize,
+    boundary: Boundary,
+) -> Result<Vec<(f64, f64)>, ProjectError> {
+    mesh.validate_control_mesh()?;
+    let knot_cache = knot_vectors(mesh, boundary);
+    let bounds = mesh.bounds().ok_or(ValidationError::EmptyDomain())?;
+    let domain = (
+        (to_f64(bounds.s.0)?, to_f64(bounds.s.1)?),
+        (to_f64(bounds.t.0)?, to_f64(bounds.t.1)?),
+    );
+
+    let mut seeds = Vec::new();
+    for rect in mesh.layout().faces {
+        for i in 0..SEEDS * SEEDS {
+            let st = (
+                cell_center::<T>(rect.s, i % SEEDS, SEEDS)?,
+                cell_center::<T>(rect.t, i / SEEDS, SEEDS)?,
+            );
+            let st = (to_f64(st.0)?, to_f64(st.1)?);
+            if let Some(d) = evaluate(mesh, &knot_cache, st)? {
+                seeds.push((st, d.point));
+            }
+        }
+    }
+    if seeds.is_empty() {
+        return Err(ProjectError::Empty);
+    }
+
+    let mut projected: Vec<(f64, f64)> = Vec::with_capacity(points.len());
+    for target in points {
+        let distance = |p: &Point3<f64>| distance_squared(p, target);
+        let (mut best, mut best_point) = *seeds
+            .iter()
+            .min_by(|a, b| distance(&a.1).total_cmp(&distance(&b.1)))
+            .ok_or(ProjectError::Empty)?;
+
+        if let Some(&previous) = projected.last()
+            && let Some(d) = evaluate(mesh, &knot_cache, previous)?
+            && distance(&d.point) < distance(&best_point)
+        {
+            (best, best_point) = (previous, d.point);
+        }
+
+        projected.push(closest_point(
+            mesh,
+            &knot_cache,
+            domain,
+            target,
+            best,
+            best_point,
+        )?);
+    }
+
+    for _ in 0..smoothing {
+        let previous = projected.clone();
+        for i in 1..projected.len().saturating_sub(1) {
+            let (a, b, c) = (previous[i - 1], previous[i], previous[i + 1]);
+            projected[i] = (
+                b.0 + ((a.0 + c.0) / 2. - b.0) / 2.,
+                b.1 + ((a.1 + c.1) / 2. - b.1) / 2.,
+            );
+        }
+    }
+
+    Ok(projected)
+}
+
+/// Gauss-Newton descent of the distance between the surface and `target`
+fn closest_point<T: ControlMesh>(
+    mesh: &T,
+    knot_cache: &[LocalKnots],
+    (s_domain, t_domain): ((f64, f64), (f64, f64)),
+    target: &Point3<f64>,
+    mut st: (f64, f64),
+    mut point: Point3<f64>,
+) -> Result<(f64, f64), ProjectError> {
+    for _ in 0..ITERATIONS {
+        let Some(d) = evaluate(mesh, knot_cache, st)? else {
+            break;
+        };
+        let residual = target - d.point;
+        let (ds, dt) = (d.ds, d.dt);
+
+        // solve the 2x2 normal equations
+        let (a, b, c) = (ds.dot(&ds), ds.dot(&dt), dt.dot(&dt));
+        let det = a * c - b * b;
+        if det.abs() <= f64::EPSILON * a.max(c).max(1.) {
+            break;
+        }
+        let (rs, rt) = (ds.dot(&residual), dt.dot(&residual));
+        let step = ((c * rs - b * rt) / det, (a * rt - b * rs) / det);
+
+        let next = (
+            (st.0 + step.0).clamp(s_domain.0, s_domain.1),
+            (st.1 + step.1).clamp(t_domain.0, t_domain.1),
+        );
+        let Some(next_point) = evaluate(mesh, knot_cache, next)?.map(|d| d.point) else {
+            break;
+        };
+        if distance_squared(&next_point, target) > distance_squared(&point, target) {
+            break;
+        }
+
+        let converged = (next.0 - st.0).abs() + (next.1 - st.1).abs() < 1e-12;
+        (st, point) = (next, next_point);
+        if converged {
+            break;
+        }
+    }
+    Ok(st)
+}
+
+/// The surface and its derivatives at `(s, t)` in `f64`, `None` outside of the mesh.
+///
+/// Fails if the surface can not be evaluated inside of the mesh, for example where its
+/// weights sum to zero.
+pub(crate) fn evaluate<T: ControlMesh>(
+    mesh: &T,
+    knot_cache: &[LocalKnots],
+    (s, t): (f64, f64),
+) -> Result<Option<SurfaceDerivatives<f64>>, ProjectError> {
+    let st = (from_f64::<T::Unit>(s)?, from_f64::<T::Unit>(t)?);
+    if !mesh.contains_uv(st) {
+        return Ok(None);
+    }
+    let d = match try_subs_derivatives(mesh.control_points(), st, knot_cache, EvalPolicy::Clamp) {
+        Ok(d) => d,
+        Err(EvalError::OutOfDomain) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let v = |x: T::Unit, y: T::Unit, z: T::Unit| -> Result<_, ProjectError> {
+        Ok((to_f64(x)?, to_f64(y)?, to_f64(z)?))
+    };
+    let (px, py, pz) = v(d.point.x, d.point.y, d.point.z)?;
+    let (sx, sy, sz) = v(d.ds.x, d.ds.y, d.ds.z)?;
+    let (tx, ty, tz) = v(d.dt.x, d.dt.y, d.dt.z)?;
+    Ok(Some(SurfaceDerivatives {
+        point: Point3::new(px, py, pz),
+        ds: [sx, sy, sz].into(),
+        dt: [tx, ty, tz].into(),
+    }))
+}
+
+pub(crate) fn distance_squared(a: &Point3<f64>, b: &Point3<f64>) -> f64 {
+    let d = a - b;
+    d.dot(&d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use crate::tessellate::knot_vectors;
+    use t_spline::TSpline;
+    use t_spline::algorithms::subs;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_projects_onto_a_plane() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let knots = knot_vectors(&mesh, Boundary::Clamped);
+        let line: Vec<_> = (0..=10)
+            .map(|i| Point3::new(0.3 + i as f64 * 0.24, 1.2, 1.))
+            .collect();
+
+        let st = project_polyline(&mesh, &line, 0, Boundary::Clamped).unwrap();
+
+        assert_eq!(line.len(), st.len());
+        for (p, st) in line.iter().zip(st) {
+            let on_surface = subs(mesh.control_points(), st, &knots).unwrap();
+            assert!(distance_squared(&on_surface, &Point3::new(p.x, p.y, 0.)) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_smooths_in_parametric_space() {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        mesh.control_point_mut(VertID(5)).unwrap().z = 1.;
+        let zigzag: Vec<_> = (0..=10)
+            .map(|i| Point3::new(0.3 + i as f64 * 0.24, 1.5 + (i % 2) as f64 * 0.2, 2.))
+            .collect();
+
+        let rough = project_polyline(&mesh, &zigzag, 0, Boundary::Clamped).unwrap();
+        let smooth = project_polyline(&mesh, &zigzag, 8, Boundary::Clamped).unwrap();
+
+        let wiggle = |st: &[(f64, f64)]| -> f64 {
+            st.windows(3)
+                .map(|w| (w[0].1 + w[2].1 - 2. * w[1].1).abs())
+                .sum()
+        };
+        assert!(wiggle(&smooth) < wiggle(&rough) / 2.);
+        assert_eq!(rough.first(), smooth.first());
+        assert_eq!(rough.last(), smooth.last());
+    }
+
+    #[test]
+    fn it_projects_a_b_spline_curve() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let knots = knot_vectors(&mesh, Boundary::Clamped);
+        let curve = BSplineCurve::new(
+            2,
+            vec![0., 0., 0., 1., 1., 1.],
+            vec![
+                Point3::new(0.3, 1.2, 1.),
+                Point3::new(1.5, 2.4, 1.),
+                Point3::new(2.7, 1.2, 1.),
+            ],
+        )
+        .unwrap();
+
+        // a clamped quadratic passes through its ends and peaks halfway
+        assert_eq!(Point3::new(0.3, 1.2, 1.), curve.point(0.));
+        assert_eq!(Point3::new(2.7, 1.2, 1.), curve.point(1.));
+        assert!(distance_squared(&Point3::new(1.5, 1.8, 1.), &curve.point(0.5)) < 1e-24);
+
+        let st = project_b_spline(&mesh, &curve, 9, 0, Boundary::Clamped).unwrap();
+
+        assert_eq!(9, st.len());
+        for (p, st) in curve.sample(9).iter().zip(st) {
+            let on_surface = subs(mesh.control_points(), st, &knots).unwrap();
+            assert!(distance_squared(&on_surface, &Point3::new(p.x, p.y, 0.)) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn it_rejects_curves_with_bad_knots() {
+        let points = vec![Point3::origin(); 3];
+
+        assert!(BSplineCurve::new(2, vec![0., 0., 0., 1., 1.], points.clone()).is_err());
+        assert!(BSplineCurve::new(2, vec![0., 0., 1., 0., 1., 1.], points.clone()).is_err());
+        assert!(BSplineCurve::new(3, vec![0.; 7], points).is_err());
+    }
+}
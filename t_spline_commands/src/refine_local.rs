@@ -0,0 +1,795 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::analysis_suitable::junction_face;
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::{Influence, two_ring};
+use crate::split_face::{
+    FaceSplit, SplitAt, SplitFace, SplitFaceError, face_range, insert_line, knot_vectors_of,
+};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use t_spline::bounds::Bounds;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::layout::FaceRect;
+use t_spline::uv_mesh::t_junction::TJunction;
+use t_spline::uv_mesh::uv_point::UVCoord;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum RefineLocalError {
+    #[error("the blending function around ({0}, {1}) has no matching control point")]
+    NotExact(isize, isize),
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error(transparent)]
+    Split(#[from] SplitFaceError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// How far [refine_local_with] may reach beyond the split face
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Propagation {
+    /// Insert only what keeps the surface and, if the mesh had it, analysis suitability
+    #[default]
+    Minimal,
+    /// Also run the new knot line through every face at its value, which grows the mesh
+    /// faster but keeps knot lines aligned
+    Aligned,
+}
+
+impl FromStr for Propagation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minimal" => Ok(Propagation::Minimal),
+            "aligned" => Ok(Propagation::Aligned),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Why [refine_local_with] inserted more than the split itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropagationCause {
+    /// A split blending function of the old mesh is centered on no control point
+    MissingControlPoint(isize, isize),
+    /// The extensions of two T-junctions intersect, the first one was extended
+    IntersectingExtensions(TJunction, TJunction),
+    /// The knot line was continued through a face by [Propagation::Aligned]
+    Alignment(FaceRect),
+}
+
+/// The result of [refine_local_with]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalRefinement {
+    pub split: FaceSplit,
+    /// Every insertion beyond the split, in order
+    pub causes: Vec<PropagationCause>,
+}
+
+/// [refine_local] as a [Command], built from the same parameters as [SplitFace] and an
+/// optional `propagation` of `minimal` or `aligned`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefineLocal {
+    pub face: EdgeID,
+    pub direction: Direction,
+    pub at: SplitAt,
+    pub boundary: Boundary,
+    pub propagation: Propagation,
+}
+
+impl RefineLocal {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        let SplitFace {
+            face,
+            direction,
+            at,
+            boundary,
+        } = SplitFace::from_parameters(parameters)?;
+        Ok(Self {
+            face,
+            direction,
+            at,
+            boundary,
+            propagation: parameters.get_or("propagation", Propagation::Minimal)?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for RefineLocal {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        let edge = mesh
+            .edge(self.face)
+            .ok_or_else(|| CommandError::failed(SplitFaceError::MissingEdge))?;
+        let mut seeds: Vec<_> = mesh.edge_loop(edge).map(|(_, e)| e.origin).collect();
+
+        let refinement = refine_local_with(
+            mesh,
+            self.face,
+            self.direction,
+            self.at,
+            self.boundary,
+            self.propagation,
+        )
+        .map_err(CommandError::failed)?;
+
+        seeds.extend(refinement.split.vertices);
+        Ok(Influence::Local(two_ring(mesh, &seeds)))
+    }
+}
+
+/// Split the face of `face` like [split_face](crate::split_face::split_face), but keep
+/// the surface exactly as it was by T-spline knot insertion.
+///
+/// Every blending function of the old mesh is split by the knots the new T-mesh adds
+/// to its support until it matches the blending function of a control point, whose
+/// homogeneous control point gathers it. This changes weights as well as positions.
+/// Where a split function is centered on no control point the new knot line is
+/// extended into the neighbouring faces to add one, so the refinement may reach beyond
+/// `face`. If the old surface still cannot be represented the mesh is left unchanged.
+///
+/// This is [refine_local_with] the [Propagation::Minimal] policy.
+pub fn refine_local<T: ControlMeshMut>(
+    mesh: &mut T,
+    face: EdgeID,
+    direction: Direction,
+    at: SplitAt,
+    boundary: Boundary,
+) -> Result<FaceSplit, RefineLocalError> {
+    refine_local_with(mesh, face, direction, at, boundary, Propagation::Minimal)
+        .map(|refinement| refinement.split)
+}
+
+/// [refine_local] with a `propagation` policy, reporting why the refinement reached
+/// beyond the split face.
+///
+/// Once the surface is represented, the junctions of a mesh that was analysis suitable
+/// are extended exactly as well until no extensions intersect.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), face = face.0, ?direction))
+)]
+pub fn refine_local_with<T: ControlMeshMut>(
+    mesh: &mut T,
+    face: EdgeID,
+    direction: Direction,
+    at: SplitAt,
+    boundary: Boundary,
+    propagation: Propagation,
+) -> Result<LocalRefinement, RefineLocalError> {
+    mesh.validate_control_mesh()?;
+    let value = at.resolve(face_range(mesh, face, direction)?)?;
+
+    let points = mesh.points().to_vec();
+    let edges = mesh.edges().to_vec();
+    let control_points = mesh.control_points().to_vec();
+    let old_knots = knot_vectors_of(mesh, boundary);
+
+    let mut functions = Vec::with_capacity(control_points.len());
+    for (cp, knots) in control_points.iter().zip(old_knots) {
+        let cast = to_f64::<T::Unit>;
+        let w = cast(cp.w)?;
+        let h = [cast(cp.x)? * w, cast(cp.y)? * w, cast(cp.z)? * w, w];
+        functions.push((knots.s_knots, knots.t_knots, h));
+    }
+
+    let suitable = mesh.is_analysis_suitable();
+    let mut causes = Vec::new();
+    let split = insert_line(mesh, face, direction, value)
+        .map_err(RefineLocalError::from)
+        .and_then(|mut split| {
+            // extend the refinement until every blending function has a control point
+            // and the policy is met
+            let limit = mesh.points().len();
+            for _ in 0..limit {
+                let added = match redistribute(mesh, functions.clone(), boundary) {
+                    Err(RefineLocalError::NotExact(s, t)) => {
+                        causes.push(PropagationCause::MissingControlPoint(s, t));
+                        add_vertex(mesh, (s, t))?
+                    }
+                    Err(e) => return Err(e),
+                    Ok(()) => {
+                        let Some((cause, added)) =
+                            propagate(mesh, direction, value, propagation, suitable)?
+                        else {
+                            break;
+                        };
+                        causes.push(cause);
+                        added
+                    }
+                };
+                split.vertices.extend(added.vertices);
+                split.edges.extend(added.edges);
+            }
+            redistribute(mesh, functions, boundary)?;
+            Ok(split)
+        });
+    if split.is_err() {
+        // undo the split, it only appends and reconnects
+        while mesh.points().len() > points.len() {
+            mesh.pop_point();
+            mesh.pop_control_point();
+        }
+        while mesh.edges().len() > edges.len() {
+            mesh.pop_edge();
+        }
+        for (i, e) in edges.into_iter().enumerate() {
+            if let Some(edge) = mesh.edge_mut(EdgeID(i)) {
+                *edge = e;
+            }
+        }
+        for (i, (p, cp)) in points.into_iter().zip(&control_points).enumerate() {
+            if let Some(point) = mesh.point_mut(VertID(i)) {
+                *point = p;
+            }
+            if let Some(c) = mesh.control_point_mut(VertID(i)) {
+                *c = *cp;
+            }
+        }
+    }
+    split.map(|split| LocalRefinement { split, causes })
+}
+
+/// The next insertion `propagation` asks for once the surface is represented
+fn propagate<T: ControlMeshMut>(
+    mesh: &mut T,
+    direction: Direction,
+    value: isize,
+    propagation: Propagation,
+    suitable: bool,
+) -> Result<Option<(PropagationCause, FaceSplit)>, RefineLocalError> {
+    if propagation == Propagation::Aligned {
+        let crossed = mesh.layout().faces.into_iter().find(|rect| {
+            let (lo, hi) = match direction {
+                Direction::S => rect.s,
+                Direction::T => rect.t,
+            };
+            lo < value && value < hi
+        });
+        if let Some(rect) = crossed {
+            let split = insert_line(mesh, rect.face, direction, value)?;
+            return Ok(Some((PropagationCause::Alignment(rect), split)));
+        }
+    }
+    if !suitable {
+        return Ok(None);
+    }
+    let Some(&(junction, other)) = mesh.extension_intersections().first() else {
+        return Ok(None);
+    };
+    let Some(face) = junction_face(mesh, &junction) else {
+        return Ok(None);
+    };
+    let point = mesh
+        .point(junction.vertex)
+        .ok_or(RefineLocalError::MissingControlPoint)?;
+    let across = junction.axis.opposite();
+    let split = insert_line(mesh, face, across, point.value_in_dir(across))?;
+    Ok(Some((
+        PropagationCause::IntersectingExtensions(junction, other),
+        split,
+    )))
+}
+
+/// [refine_around] as a [Command], `levels` defaults to one and `propagation` to
+/// `minimal`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefineAround {
+    pub s: f64,
+    pub t: f64,
+    pub radius: f64,
+    pub levels: usize,
+    pub boundary: Boundary,
+    pub propagation: Propagation,
+}
+
+impl RefineAround {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            s: parameters.get("s")?,
+            t: parameters.get("t")?,
+            radius: parameters.get("radius")?,
+            levels: parameters.get_or("levels", 1)?,
+            boundary: parameters.get_boundary()?,
+            propagation: parameters.get_or("propagation", Propagation::Minimal)?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for RefineAround {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        let refinement = refine_around(
+            mesh,
+            (self.s, self.t),
+            self.radius,
+            self.levels,
+            self.boundary,
+            self.propagation,
+        )
+        .map_err(CommandError::failed)?;
+        Ok(if refinement.scale != 1 {
+            Influence::Global
+        } else if refinement.vertices.is_empty() {
+            Influence::Geometry
+        } else {
+            Influence::Local(two_ring(mesh, &refinement.vertices))
+        })
+    }
+}
+
+/// What [refine_around] changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Refinement {
+    /// Corners of the split faces and the inserted vertices
+    pub vertices: Vec<VertID>,
+    /// Factor every knot value was multiplied by to make room for the splits
+    pub scale: isize,
+    /// Faces that could not be split exactly, like the ones on a clamped boundary, by
+    /// their parametric extent when they were reached
+    pub skipped: Vec<Bounds<isize>>,
+    /// Why the splits reached beyond the quartered faces
+    pub causes: Vec<PropagationCause>,
+}
+
+/// Split every face within `radius` of the parameter `st` into quarters, `levels`
+/// times over, keeping the surface with [refine_local_with] and `propagation`.
+///
+/// `st` and `radius` are in knot values of the mesh as it is passed in. A face that is
+/// a single knot interval wide cannot be split in the middle, so all knots are doubled
+/// first when one is in reach, which leaves the surface unchanged. Knot lines are only
+/// extended beyond the quartered faces where the blending functions require it, faces
+/// that cannot be split exactly are left as they are.
+pub fn refine_around<T: ControlMeshMut>(
+    mesh: &mut T,
+    (s, t): (f64, f64),
+    radius: f64,
+    levels: usize,
+    boundary: Boundary,
+    propagation: Propagation,
+) -> Result<Refinement, RefineLocalError> {
+    mesh.validate_control_mesh()?;
+    let mut refinement = Refinement {
+        vertices: Vec::new(),
+        scale: 1,
+        skipped: Vec::new(),
+        causes: Vec::new(),
+    };
+
+    for _ in 0..levels {
+        let scale = refinement.scale as f64;
+        let gap = |(lo, hi): (isize, isize), v: f64| (lo as f64 - v).max(v - hi as f64).max(0.);
+        let mut targets: Vec<_> = mesh
+            .layout()
+            .faces
+            .into_iter()
+            .filter(|rect| gap(rect.s, s * scale).hypot(gap(rect.t, t * scale)) <= radius * scale)
+            .map(|rect| (rect.s, rect.t))
+            .collect();
+        if targets.is_empty() {
+            break;
+        }
+        if targets.iter().any(|(s, t)| s.1 - s.0 < 2 || t.1 - t.0 < 2) {
+            for v in 0..mesh.points().len() {
+                let p = mesh
+                    .point_mut(VertID(v))
+                    .ok_or(RefineLocalError::MissingControlPoint)?;
+                (p.s, p.t) = (p.s * 2, p.t * 2);
+            }
+            refinement.scale *= 2;
+            for (s, t) in &mut targets {
+                (*s, *t) = ((s.0 * 2, s.1 * 2), (t.0 * 2, t.1 * 2));
+            }
+        }
+
+        for (s, t) in targets {
+            let middle = |(lo, hi): (isize, isize)| lo + (hi - lo) / 2;
+            for (direction, value) in [(Direction::S, middle(s)), (Direction::T, middle(t))] {
+                match cut(
+                    mesh,
+                    (s, t),
+                    (direction, value),
+                    boundary,
+                    propagation,
+                    &mut refinement,
+                ) {
+                    Err(RefineLocalError::NotExact(..)) => {
+                        refinement.skipped.push(Bounds { s, t });
+                    }
+                    result => result?,
+                }
+            }
+        }
+    }
+    refinement.skipped.dedup();
+    refinement.vertices.sort_unstable();
+    refinement.vertices.dedup();
+    Ok(refinement)
+}
+
+/// Split the faces overlapping the rectangle `s` x `t` until a knot line at `value`
+/// crosses all of it.
+///
+/// Faces are tried in turn, as one may only be refined exactly once its neighbours are.
+/// If none can be, the line is run across the whole mesh instead.
+fn cut<T: ControlMeshMut>(
+    mesh: &mut T,
+    (s, t): ((isize, isize), (isize, isize)),
+    (direction, value): (Direction, isize),
+    boundary: Boundary,
+    propagation: Propagation,
+    refinement: &mut Refinement,
+) -> Result<(), RefineLocalError> {
+    const EVERYWHERE: (isize, isize) = (isize::MIN, isize::MAX);
+    let mut across = match direction {
+        Direction::S => t,
+        Direction::T => s,
+    };
+    loop {
+        let crossed: Vec<_> = mesh
+            .layout()
+            .faces
+            .into_iter()
+            .filter(|rect| {
+                let (a, b) = match direction {
+                    Direction::S => (rect.s, rect.t),
+                    Direction::T => (rect.t, rect.s),
+                };
+                a.0 < value && value < a.1 && b.0 < across.1 && b.1 > across.0
+            })
+            .collect();
+        if crossed.is_empty() {
+            return Ok(());
+        }
+
+        let mut failed = None;
+        for rect in crossed {
+            let edge = mesh
+                .edge(rect.face)
+                .ok_or(RefineLocalError::Split(SplitFaceError::MissingEdge))?;
+            let corners: Vec<_> = mesh.edge_loop(edge).map(|(_, e)| e.origin).collect();
+            match refine_local_with(
+                mesh,
+                rect.face,
+                direction,
+                SplitAt::Absolute(value),
+                boundary,
+                propagation,
+            ) {
+                Ok(local) => {
+                    refinement.vertices.extend(corners);
+                    refinement.vertices.extend(local.split.vertices);
+                    refinement.causes.extend(local.causes);
+                    failed = None;
+                    break;
+                }
+                Err(e @ RefineLocalError::NotExact(..)) => failed = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+        match failed {
+            Some(e) if across == EVERYWHERE => return Err(e),
+            Some(_) => across = EVERYWHERE,
+            None => {}
+        }
+    }
+}
+
+/// Split the face around `center` so a vertex lies on it
+fn add_vertex<T: ControlMeshMut>(
+    mesh: &mut T,
+    (s, t): (isize, isize),
+) -> Result<FaceSplit, RefineLocalError> {
+    let within = |(lo, hi): (isize, isize), v: isize| lo <= v && v <= hi;
+    let rect = mesh
+        .layout()
+        .faces
+        .into_iter()
+        .find(|rect| within(rect.s, s) && within(rect.t, t))
+        .ok_or(RefineLocalError::NotExact(s, t))?;
+    let (direction, value) = if rect.s.0 < s && s < rect.s.1 {
+        (Direction::S, s)
+    } else if rect.t.0 < t && t < rect.t.1 {
+        (Direction::T, t)
+    } else {
+        return Err(RefineLocalError::NotExact(s, t));
+    };
+    Ok(insert_line(mesh, rect.face, direction, value)?)
+}
+
+type Function = ([isize; 5], [isize; 5], [f64; 4]);
+
+/// Split the homogeneous blending `functions` of the old mesh until each matches a
+/// control point of `mesh` and set the control points to their sums
+fn redistribute<T: ControlMeshMut>(
+    mesh: &mut T,
+    mut functions: Vec<Function>,
+    boundary: Boundary,
+) -> Result<(), RefineLocalError> {
+    let knots = knot_vectors_of(mesh, boundary);
+    let centers: BTreeMap<(isize, isize), usize> = mesh
+        .points()
+        .iter()
+        .enumerate()
+        .map(|(i, p)| ((p.s, p.t), i))
+        .collect();
+
+    let mut sums = vec![[0.; 4]; knots.len()];
+    while let Some((s, t, h)) = functions.pop() {
+        let center = (s[2], t[2]);
+        let vertex = *centers
+            .get(&center)
+            .ok_or(RefineLocalError::NotExact(center.0, center.1))?;
+        let target = &knots[vertex];
+
+        if let Some(k) = missing_knot(&s, &target.s_knots) {
+            let [(a, ca), (b, cb)] = insert_knot(&s, k);
+            functions.push((a, t, h.map(|h| h * ca)));
+            functions.push((b, t, h.map(|h| h * cb)));
+        } else if let Some(k) = missing_knot(&t, &target.t_knots) {
+            let [(a, ca), (b, cb)] = insert_knot(&t, k);
+            functions.push((s, a, h.map(|h| h * ca)));
+            functions.push((s, b, h.map(|h| h * cb)));
+        } else if s == target.s_knots && t == target.t_knots {
+            (0..4).for_each(|d| sums[vertex][d] += h[d]);
+        } else {
+            return Err(RefineLocalError::NotExact(center.0, center.1));
+        }
+    }
+
+    let uncast = from_f64::<T::Unit>;
+    for (v, h) in sums.into_iter().enumerate() {
+        if h[3] <= 0. {
+            let p = &mesh.points()[v];
+            return Err(RefineLocalError::NotExact(p.s, p.t));
+        }
+        let cp = mesh
+            .control_point_mut(VertID(v))
+            .ok_or(RefineLocalError::MissingControlPoint)?;
+        (cp.x, cp.y, cp.z, cp.w) = (
+            uncast(h[0] / h[3])?,
+            uncast(h[1] / h[3])?,
+            uncast(h[2] / h[3])?,
+            uncast(h[3])?,
+        );
+    }
+    Ok(())
+}
+
+/// A knot strictly inside `knots` that `target` has more often
+fn missing_knot(knots: &[isize; 5], target: &[isize; 5]) -> Option<isize> {
+    let count = |knots: &[isize; 5], k: isize| knots.iter().filter(|v| **v == k).count();
+    target
+        .iter()
+        .copied()
+        .find(|&k| knots[0] < k && k < knots[4] && count(target, k) > count(knots, k))
+}
+
+/// The two cubic B-splines, with their factors, that sum to the one of `knots` once
+/// `k` is inserted
+fn insert_knot(knots: &[isize; 5], k: isize) -> [([isize; 5], f64); 2] {
+    let mut refined = [0; 6];
+    let at = knots.partition_point(|v| *v <= k);
+    refined[..at].copy_from_slice(&knots[..at]);
+    refined[at] = k;
+    refined[at + 1..].copy_from_slice(&knots[at..]);
+
+    let f = |v: isize| v as f64;
+    let first = if k < knots[3] {
+        f(k - knots[0]) / f(knots[3] - knots[0])
+    } else {
+        1.
+    };
+    let second = if k > knots[1] {
+        f(knots[4] - k) / f(knots[4] - knots[1])
+    } else {
+        1.
+    };
+    let mut a = [0; 5];
+    let mut b = [0; 5];
+    a.copy_from_slice(&refined[..5]);
+    b.copy_from_slice(&refined[1..]);
+    [(a, first), (b, second)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::CommandRegistry;
+    use crate::plane::new_plane;
+    use crate::tessellate::tessellate;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::{UVMesh, UVMeshMut};
+
+    fn bumpy() -> TSpline {
+        let mut mesh: TSpline = new_plane(6, 6, 5., 5.).unwrap();
+        for v in 0..36 {
+            let p = mesh.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+            let cp = mesh.control_point_mut(VertID(v)).unwrap();
+            (cp.z, cp.w) = ((v % 7) as f64 * 0.3, 1. + (v % 3) as f64 * 0.5);
+        }
+        mesh
+    }
+
+    fn face_at(mesh: &TSpline, s: (isize, isize), t: (isize, isize)) -> EdgeID {
+        mesh.layout()
+            .faces
+            .into_iter()
+            .find(|rect| rect.s == s && rect.t == t)
+            .unwrap()
+            .face
+    }
+
+    fn assert_same_surface(a: &TSpline, b: &TSpline) {
+        let before = tessellate(a, 30, Boundary::Clamped).unwrap();
+        let after = tessellate(b, 30, Boundary::Clamped).unwrap();
+        assert_eq!(before.len(), after.len());
+        for (p, q) in before.iter().zip(&after) {
+            assert!((p - q).abs().max() < 1e-9, "{p:?} {q:?}");
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_surface_when_splitting_a_face() {
+        let mut mesh = bumpy();
+        let original = mesh.clone();
+        let face = face_at(&mesh, (4, 6), (4, 6));
+
+        let split = refine_local(
+            &mut mesh,
+            face,
+            Direction::S,
+            SplitAt::Middle,
+            Boundary::Clamped,
+        )
+        .unwrap();
+
+        assert!(split.vertices.len() > 2, "{split:?}");
+        for t in [4, 6] {
+            assert!(mesh.points().iter().any(|p| p.s == 5 && p.t == t));
+        }
+        mesh.validate_control_mesh().unwrap();
+        assert_same_surface(&original, &mesh);
+        assert!(mesh.control_points().iter().any(|cp| cp.w.fract() != 0.));
+    }
+
+    #[test]
+    fn it_reports_why_the_refinement_propagated() {
+        let face = face_at(&bumpy(), (4, 6), (4, 6));
+        let refine = |propagation| {
+            let mut mesh = bumpy();
+            let refinement = refine_local_with(
+                &mut mesh,
+                face,
+                Direction::S,
+                SplitAt::Middle,
+                Boundary::Clamped,
+                propagation,
+            )
+            .unwrap();
+            assert_same_surface(&bumpy(), &mesh);
+            assert!(mesh.is_analysis_suitable());
+            (mesh, refinement.causes)
+        };
+
+        let (minimal, causes) = refine(Propagation::Minimal);
+        assert!(!causes.is_empty());
+        assert!(
+            causes
+                .iter()
+                .all(|c| !matches!(c, PropagationCause::Alignment(_)))
+        );
+
+        let (aligned, causes) = refine(Propagation::Aligned);
+        assert!(
+            causes
+                .iter()
+                .any(|c| matches!(c, PropagationCause::Alignment(r) if r.s == (4, 6)))
+        );
+        for t in (0..=10).step_by(2) {
+            assert!(aligned.points().iter().any(|p| p.s == 5 && p.t == t), "{t}");
+        }
+        assert!(aligned.points().len() > minimal.points().len());
+    }
+
+    #[test]
+    fn it_refines_around_a_point() {
+        let mut mesh = bumpy();
+        let original = mesh.clone();
+        let faces = mesh.layout().faces.len();
+
+        let refinement = refine_around(
+            &mut mesh,
+            (5., 5.),
+            0.5,
+            2,
+            Boundary::Clamped,
+            Propagation::Minimal,
+        )
+        .unwrap();
+
+        assert_eq!(2, refinement.scale);
+        mesh.validate_control_mesh().unwrap();
+        assert!(mesh.layout().faces.len() > faces + 3);
+        // the face around the point was quartered twice, faces far away were not split
+        let quarter = |s0, t0| {
+            let rect = mesh
+                .layout()
+                .faces
+                .into_iter()
+                .find(|r| r.s.0 == s0 && r.t.0 == t0);
+            rect.map(|r| (r.s.1 - r.s.0, r.t.1 - r.t.0))
+        };
+        assert_eq!(Some((1, 1)), quarter(10, 10));
+        assert_eq!(Some((4, 4)), quarter(0, 0));
+        assert_same_surface(&original, &mesh);
+    }
+
+    #[test]
+    fn it_skips_faces_on_the_boundary() {
+        let mut mesh = bumpy();
+        let original = mesh.clone();
+
+        let refinement = refine_around(
+            &mut mesh,
+            (0., 5.),
+            0.,
+            1,
+            Boundary::Clamped,
+            Propagation::Minimal,
+        )
+        .unwrap();
+
+        assert!(!refinement.skipped.is_empty());
+        assert!(refinement.skipped.iter().all(|b| b.s.0 == 0));
+        mesh.validate_control_mesh().unwrap();
+        assert_same_surface(&original, &mesh);
+    }
+
+    #[test]
+    fn it_refines_from_the_registry() {
+        let mut mesh = bumpy();
+        let original = mesh.clone();
+        let face = face_at(&mesh, (0, 2), (2, 4));
+        let parameters =
+            Parameters::parse([format!("face={}", face.0), "direction=t".to_string()]).unwrap();
+
+        CommandRegistry::default()
+            .apply_mut(&mut mesh, "refine_local", &parameters)
+            .unwrap();
+        let around = Parameters::default()
+            .with("s", 5)
+            .with("t", 7)
+            .with("radius", 0);
+        CommandRegistry::default()
+            .apply_mut(&mut mesh, "refine_around", &around)
+            .unwrap();
+
+        assert!(mesh.points().len() > original.points().len());
+        assert_same_surface(&original, &mesh);
+    }
+}
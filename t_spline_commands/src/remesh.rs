@@ -0,0 +1,253 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, to_f64};
+use crate::sdf::{DistanceField, SdfError, VoxelGrid, signed_distance_field};
+use std::collections::HashMap;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::Boundary;
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum RemeshError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("spacing must be positive")]
+    InvalidSpacing,
+    #[error("model has no control points")]
+    Empty,
+    #[error("failed to sample distance field: {0}")]
+    Sdf(#[from] SdfError),
+}
+
+/// An indexed triangle mesh, triangles are wound counter-clockwise seen from outside
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TriangleMesh {
+    pub points: Vec<Point3<f64>>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+/// Remesh the closed model made of `patches` into a watertight triangle mesh.
+///
+/// The signed distance field is sampled on a grid of `spacing` that encloses the model
+/// with a margin and contoured with [`marching_cubes`]. Only the field is used, so small
+/// cracks between patches are closed over as long as they are narrower than the grid.
+pub fn remesh<T: ControlMesh + Sync>(
+    patches: &[T],
+    spacing: f64,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<TriangleMesh, RemeshError> {
+    if spacing.is_nan() || spacing <= 0. {
+        return Err(RemeshError::InvalidSpacing);
+    }
+
+    // the surface lies within the hull of its control points
+    let mut min = Point3::from([f64::INFINITY; 3]);
+    let mut max = Point3::from([f64::NEG_INFINITY; 3]);
+    for cp in patches.iter().flat_map(|mesh| mesh.control_points()) {
+        let p = Point3::new(to_f64(cp.x)?, to_f64(cp.y)?, to_f64(cp.z)?);
+        min = min.inf(&p);
+        max = max.sup(&p);
+    }
+    if min.x > max.x {
+        return Err(RemeshError::Empty);
+    }
+
+    let origin = min - Vector3::repeat(spacing);
+    let nodes = |lo: f64, hi: f64| ((hi - lo) / spacing).ceil() as usize + 3;
+    let grid = VoxelGrid {
+        origin,
+        spacing,
+        dims: [
+            nodes(min.x, max.x),
+            nodes(min.y, max.y),
+            nodes(min.z, max.z),
+        ],
+    };
+
+    let field = signed_distance_field(patches, grid, resolution, boundary)?;
+    Ok(marching_cubes(&field))
+}
+
+/// Contour the zero level of `field`, negative values are inside.
+///
+/// Every cube is split into six tetrahedra around its main diagonal, which removes the
+/// ambiguous cases of marching cubes. Neighbouring cubes split their shared faces the
+/// same way and vertices are shared per grid edge, so the result is watertight and does
+/// not intersect itself wherever the field is closed within the grid.
+pub fn marching_cubes(field: &DistanceField) -> TriangleMesh {
+    let grid = field.grid;
+    let [nx, ny, nz] = grid.dims;
+    let mut mesh = TriangleMesh::default();
+    let mut vertices: HashMap<(usize, usize), usize> = HashMap::new();
+
+    // the six paths from corner 0 to corner 7 along the cube edges
+    const PATHS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+
+    for k in 0..nz.saturating_sub(1) {
+        for j in 0..ny.saturating_sub(1) {
+            for i in 0..nx.saturating_sub(1) {
+                for path in PATHS {
+                    let mut corner = [i, j, k];
+                    let mut tetrahedron = [grid.index(i, j, k); 4];
+                    for (n, axis) in path.into_iter().enumerate() {
+                        corner[axis] += 1;
+                        tetrahedron[n + 1] = grid.index(corner[0], corner[1], corner[2]);
+                    }
+                    contour_tetrahedron(field, tetrahedron, &mut vertices, &mut mesh);
+                }
+            }
+        }
+    }
+
+    mesh
+}
+
+fn contour_tetrahedron(
+    field: &DistanceField,
+    nodes: [usize; 4],
+    vertices: &mut HashMap<(usize, usize), usize>,
+    mesh: &mut TriangleMesh,
+) {
+    let (inside, outside): (Vec<usize>, Vec<usize>) =
+        nodes.into_iter().partition(|&n| field.values[n] < 0.);
+
+    let mut crossing = |a: usize, b: usize| -> usize {
+        let key = (a.min(b), a.max(b));
+        *vertices.entry(key).or_insert_with(|| {
+            let (va, vb) = (field.values[a], field.values[b]);
+            let (pa, pb) = (node_point(&field.grid, a), node_point(&field.grid, b));
+            mesh.points.push(pa + (pb - pa) * (va / (va - vb)));
+            mesh.points.len() - 1
+        })
+    };
+
+    let mut polygon = match (inside.as_slice(), outside.as_slice()) {
+        ([a], [b, c, d]) | ([b, c, d], [a]) => {
+            vec![crossing(*a, *b), crossing(*a, *c), crossing(*a, *d)]
+        }
+        ([a, b], [c, d]) => vec![
+            crossing(*a, *c),
+            crossing(*a, *d),
+            crossing(*b, *d),
+            crossing(*b, *c),
+        ],
+        _ => return,
+    };
+
+    // the orientation of the tetrahedron ordered inside first decides which way the
+    // polygon faces, this stays reliable when the crossings collapse onto a node
+    let [a, b, c, d] = [inside.as_slice(), outside.as_slice()]
+        .concat()
+        .try_into()
+        .map(|n: [usize; 4]| n.map(|n| node_point(&field.grid, n)))
+        .expect("a tetrahedron has four nodes");
+    if (b - a).cross(&(c - a)).dot(&(d - a)) < 0. {
+        polygon.reverse();
+    }
+
+    for n in 1..polygon.len() - 1 {
+        mesh.triangles
+            .push([polygon[0], polygon[n], polygon[n + 1]]);
+    }
+}
+
+fn node_point(grid: &VoxelGrid, index: usize) -> Point3<f64> {
+    let [nx, ny, _] = grid.dims;
+    grid.point(index % nx, index / nx % ny, index / (nx * ny))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cuboid::cuboid;
+    use t_spline::TSpline;
+
+    fn volume(mesh: &TriangleMesh) -> f64 {
+        mesh.triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|v| mesh.points[v].coords);
+                a.dot(&b.cross(&c)) / 6.
+            })
+            .sum()
+    }
+
+    #[test]
+    fn it_remeshes_a_box_watertight() {
+        let patches: Vec<TSpline> = cuboid([1., 1., 1.]).unwrap();
+        let mesh = remesh(&patches, 0.25, 4, Boundary::Clamped).unwrap();
+
+        // every directed edge is matched by its reverse exactly once
+        let mut edges = HashMap::new();
+        for t in &mesh.triangles {
+            for n in 0..3 {
+                *edges.entry((t[n], t[(n + 1) % 3])).or_insert(0) += 1;
+            }
+        }
+        for (&(a, b), &count) in &edges {
+            assert_eq!(1, count);
+            assert_eq!(Some(&1), edges.get(&(b, a)));
+        }
+
+        // the edges of the box are chamfered by the grid
+        assert!((volume(&mesh) - 1.).abs() < 0.15, "{}", volume(&mesh));
+    }
+
+    #[test]
+    fn it_contours_a_sphere_field() {
+        let grid = VoxelGrid {
+            origin: Point3::new(-1.5, -1.5, -1.5),
+            spacing: 0.25,
+            dims: [13, 13, 13],
+        };
+        let values = (0..grid.len())
+            .map(|n| {
+                node_point(&grid, n)
+                    .coords
+                    .dot(&node_point(&grid, n).coords)
+                    .sqrt()
+                    - 1.
+            })
+            .collect();
+        let mesh = marching_cubes(&DistanceField { grid, values });
+
+        for p in &mesh.points {
+            assert!((p.coords.dot(&p.coords).sqrt() - 1.).abs() < 0.05);
+        }
+        let sphere = 4. / 3. * std::f64::consts::PI;
+        assert!((volume(&mesh) - sphere).abs() < 0.1 * sphere);
+    }
+
+    #[test]
+    fn it_rejects_invalid_spacing() {
+        let patches: Vec<TSpline> = cuboid([1., 1., 1.]).unwrap();
+
+        assert!(matches!(
+            remesh(&patches, 0., 4, Boundary::Clamped),
+            Err(RemeshError::InvalidSpacing)
+        ));
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, to_f64};
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::Influence;
+use std::collections::{BTreeMap, BTreeSet};
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::uv_point::UVCoord;
+use thiserror::Error;
+
+#[derive(Copy, Clone, Debug, Error)]
+pub enum ReparameterizeError {
+    #[error("missing point")]
+    MissingPoint,
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("resolution is too small to separate every knot line")]
+    ResolutionTooSmall,
+}
+
+/// [reparameterize_arc_length] as a [Command], built from the `resolution` parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReparameterizeArcLength {
+    pub resolution: isize,
+}
+
+impl ReparameterizeArcLength {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            resolution: parameters.get("resolution")?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for ReparameterizeArcLength {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        reparameterize_arc_length(mesh, self.resolution).map_err(CommandError::failed)?;
+        Ok(Influence::Global)
+    }
+}
+
+/// Re-anchor the UV points of `mesh` using chord length parameterization.
+///
+/// Knot lines must stay straight, so instead of re-spacing each row individually the
+/// spacing between two neighbouring knot values is the average 3D length of the
+/// control polygon edges spanning it. The new knot values cover `0..=resolution` in
+/// both directions.
+pub fn reparameterize_arc_length<T: ControlMeshMut>(
+    mesh: &mut T,
+    resolution: isize,
+) -> Result<(), ReparameterizeError> {
+    let s = knot_mapping(mesh, Direction::S, resolution)?;
+    let t = knot_mapping(mesh, Direction::T, resolution)?;
+
+    for i in 0..mesh.points().len() {
+        let p = mesh
+            .point_mut(VertID(i))
+            .ok_or(ReparameterizeError::MissingPoint)?;
+        p.s = s[&p.s];
+        p.t = t[&p.t];
+    }
+
+    Ok(())
+}
+
+/// Maps each existing knot value in `direction` onto its chord length knot value
+fn knot_mapping<T: ControlMeshMut>(
+    mesh: &T,
+    direction: Direction,
+    resolution: isize,
+) -> Result<BTreeMap<isize, isize>, ReparameterizeError> {
+    let knots: Vec<isize> = mesh
+        .points()
+        .iter()
+        .map(|p| p.value_in_dir(direction))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    if knots.len() < 2 {
+        return Ok(knots.into_iter().map(|k| (k, 0)).collect());
+    }
+    if resolution < (knots.len() - 1) as isize {
+        return Err(ReparameterizeError::ResolutionTooSmall);
+    }
+
+    // sum and count of chord lengths per knot interval
+    let mut chords = vec![(0f64, 0usize); knots.len() - 1];
+    for edge in mesh.edges() {
+        let (a, b) = mesh.start_end(edge);
+        let (a0, b0) = (a.value_in_dir(direction), b.value_in_dir(direction));
+        if a0 == b0 {
+            continue;
+        }
+
+        let length = distance(mesh, edge.origin, mesh.next_edge(edge).origin)?;
+        let (lo, hi) = (a0.min(b0), a0.max(b0));
+        let span = (hi - lo) as f64;
+
+        for (i, w) in knots.windows(2).enumerate() {
+            if w[0] >= lo && w[1] <= hi {
+                chords[i].0 += length * (w[1] - w[0]) as f64 / span;
+                chords[i].1 += 1;
+            }
+        }
+    }
+
+    let lengths: Vec<f64> = chords
+        .iter()
+        .zip(knots.windows(2))
+        .map(|(&(sum, count), w)| {
+            if count > 0 {
+                sum / count as f64
+            } else {
+                (w[1] - w[0]) as f64
+            }
+        })
+        .collect();
+    let total: f64 = lengths.iter().sum();
+
+    let mut mapping = BTreeMap::new();
+    mapping.insert(knots[0], 0);
+
+    let mut accumulated = 0.;
+    let mut previous = 0;
+    for (i, length) in lengths.iter().enumerate() {
+        accumulated += length;
+        let remaining = (lengths.len() - i - 1) as isize;
+
+        let value = if total > 0. {
+            (accumulated / total * resolution as f64).round() as isize
+        } else {
+            previous + 1
+        };
+        // keep every knot interval at least one unit wide
+        let value = value.max(previous + 1).min(resolution - remaining);
+
+        mapping.insert(knots[i + 1], value);
+        previous = value;
+    }
+
+    Ok(mapping)
+}
+
+fn distance(mesh: &impl ControlMeshMut, a: VertID, b: VertID) -> Result<f64, ReparameterizeError> {
+    let a = mesh
+        .control_point(a)
+        .ok_or(ReparameterizeError::MissingControlPoint)?;
+    let b = mesh
+        .control_point(b)
+        .ok_or(ReparameterizeError::MissingControlPoint)?;
+
+    let mut sum = 0.;
+    for (a, b) in [(a.x, b.x), (a.y, b.y), (a.z, b.z)] {
+        let d = to_f64(a - b)?;
+        sum += d * d;
+    }
+    Ok(sum.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::align_control_points_to_cage::align_control_points_to_cage;
+    use crate::extrude_edge::extrude_edge;
+    use crate::unit_square::unit_square;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::UVMesh;
+    use t_spline::uv_mesh::ids::EdgeID;
+
+    #[test]
+    fn it_spaces_knots_by_chord_length() {
+        let mut mesh: TSpline = unit_square();
+        extrude_edge(&mut mesh, EdgeID(1)).unwrap();
+        align_control_points_to_cage(&mut mesh).unwrap();
+
+        // stretch the second column to 3 units
+        for i in 0..mesh.points().len() {
+            if mesh.point(VertID(i)).unwrap().s == 2 {
+                mesh.control_point_mut(VertID(i)).unwrap().x = 4.;
+            }
+        }
+
+        reparameterize_arc_length(&mut mesh, 8).unwrap();
+
+        let mut s: Vec<_> = mesh.points().iter().map(|p| p.s).collect();
+        s.sort();
+        s.dedup();
+        assert_eq!(vec![0, 2, 8], s);
+
+        let mut t: Vec<_> = mesh.points().iter().map(|p| p.t).collect();
+        t.sort();
+        t.dedup();
+        assert_eq!(vec![0, 8], t);
+
+        mesh.validate_control_mesh().unwrap();
+    }
+
+    #[test]
+    fn it_requires_enough_resolution() {
+        let mut mesh: TSpline = unit_square();
+        extrude_edge(&mut mesh, EdgeID(1)).unwrap();
+
+        assert!(matches!(
+            reparameterize_arc_length(&mut mesh, 1),
+            Err(ReparameterizeError::ResolutionTooSmall)
+        ));
+    }
+}
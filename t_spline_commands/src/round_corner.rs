@@ -0,0 +1,210 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::command::{CommandError, Parameters};
+use crate::project_curve::{ProjectError, project_polyline};
+use crate::tessellate::knot_vectors;
+use crate::trim::TrimmedSpline;
+use t_spline::Point3;
+use t_spline::algorithms::subs;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::uv_mesh::{Boundary, LocalKnots};
+use thiserror::Error;
+
+/// Points on the trim loop of every rounded corner
+const SEGMENTS: usize = 16;
+
+#[derive(Clone, Debug, Error)]
+pub enum RoundCornerError {
+    #[error("missing point")]
+    MissingPoint,
+    #[error("control point {0:?} is not a corner of the trim loop")]
+    NotACorner(VertID),
+    #[error("radius is longer than the sides of the corner")]
+    RadiusTooLarge,
+    #[error("surface can not be evaluated along the sides of the corner")]
+    Undefined,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("failed to place the arc on the surface: {0}")]
+    Project(#[from] ProjectError),
+}
+
+/// Round the corner of the trim loop of a [TrimmedSpline] at the parameter of
+/// `vertex`, built from the `vertex`, `radius` and optional `boundary` parameters.
+///
+/// The corner is replaced by a circular arc of `radius` touching both sides, projected
+/// onto the surface, so the rounding is exact on flat plates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundCorner {
+    pub vertex: VertID,
+    pub radius: f64,
+    pub boundary: Boundary,
+}
+
+impl RoundCorner {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            vertex: VertID(parameters.get("vertex")?),
+            radius: parameters.get("radius")?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+
+    pub fn apply<T: ControlMesh + Sync>(
+        &self,
+        trimmed: &mut TrimmedSpline<T>,
+    ) -> Result<(), RoundCornerError> {
+        let p = trimmed
+            .mesh
+            .point(self.vertex)
+            .ok_or(RoundCornerError::MissingPoint)?;
+        let corner = (p.s as f64, p.t as f64);
+        let n = trimmed.trim.len();
+        let at = trimmed
+            .trim
+            .iter()
+            .position(|&st| st == corner)
+            .filter(|_| n >= 3)
+            .ok_or(RoundCornerError::NotACorner(self.vertex))?;
+
+        let knots = knot_vectors(&trimmed.mesh, self.boundary);
+        let surface = |st| evaluate(&trimmed.mesh, &knots, st);
+        let origin = surface(corner)?;
+        // the parameter on the side towards `to` where the surface is `length` away
+        let cut = |to: (f64, f64), length: f64| -> Result<(f64, f64), RoundCornerError> {
+            let along = |f: f64| {
+                (
+                    corner.0 + (to.0 - corner.0) * f,
+                    corner.1 + (to.1 - corner.1) * f,
+                )
+            };
+            let distance = |f| {
+                let d = surface(along(f))? - origin;
+                Ok::<_, RoundCornerError>(d.dot(&d).sqrt())
+            };
+            if distance(1.)? < length {
+                return Err(RoundCornerError::RadiusTooLarge);
+            }
+            let (mut lo, mut hi) = (0., 1.);
+            for _ in 0..50 {
+                let mid = (lo + hi) / 2.;
+                if distance(mid)? < length {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            Ok(along((lo + hi) / 2.))
+        };
+        let (previous, next) = (trimmed.trim[(at + n - 1) % n], trimmed.trim[(at + 1) % n]);
+        let direction = |st| {
+            let d = surface(st)? - origin;
+            Ok::<_, RoundCornerError>(d / d.dot(&d).sqrt())
+        };
+
+        // the arc touches both sides, further from the corner the sharper it is
+        let (da, db) = (
+            direction(cut(previous, self.radius)?)?,
+            direction(cut(next, self.radius)?)?,
+        );
+        let angle = da.dot(&db).clamp(-1., 1.).acos();
+        if angle < 1e-6 {
+            return Err(RoundCornerError::NotACorner(self.vertex));
+        }
+        let setback = self.radius / (angle / 2.).tan();
+        let (start, end) = (cut(previous, setback)?, cut(next, setback)?);
+
+        let bisector = da + db;
+        let center =
+            origin + bisector / bisector.dot(&bisector).sqrt() * self.radius / (angle / 2.).sin();
+        let u = (surface(start)? - center) / self.radius;
+        let turn = std::f64::consts::PI - angle;
+        let points: Vec<_> = (1..SEGMENTS)
+            .map(|i| {
+                let a = turn * i as f64 / SEGMENTS as f64;
+                center + (u * a.cos() - da * a.sin()) * self.radius
+            })
+            .collect();
+        let inner = project_polyline(&trimmed.mesh, &points, 0, self.boundary)?;
+
+        let arc: Vec<_> = [start].into_iter().chain(inner).chain([end]).collect();
+        trimmed.trim.splice(at..=at, arc);
+        Ok(())
+    }
+}
+
+fn evaluate<T: ControlMesh>(
+    mesh: &T,
+    knots: &[LocalKnots],
+    (s, t): (f64, f64),
+) -> Result<Point3<f64>, RoundCornerError> {
+    let st = (from_f64::<T::Unit>(s)?, from_f64::<T::Unit>(t)?);
+    let p = subs(mesh.control_points(), st, knots).ok_or(RoundCornerError::Undefined)?;
+    let cast = to_f64::<T::Unit>;
+    Ok(Point3::new(cast(p.x)?, cast(p.y)?, cast(p.z)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+
+    #[test]
+    fn it_rounds_a_plate_corner() {
+        let mut plate = TrimmedSpline::new(new_plane::<TSpline>(4, 4, 3., 3.).unwrap());
+        let round = RoundCorner {
+            vertex: VertID(0),
+            radius: 1.,
+            boundary: Boundary::Clamped,
+        };
+
+        round.apply(&mut plate).unwrap();
+
+        assert_eq!(4 + SEGMENTS, plate.trim.len());
+        let points = plate.tessellate(13, Boundary::Clamped).unwrap();
+        for p in points.iter().filter(|p| p.x < 1. && p.y < 1.) {
+            let r = ((p.x - 1.).powi(2) + (p.y - 1.).powi(2)).sqrt();
+            assert!(r < 1. + 1e-3, "{p:?}");
+        }
+        let corner = Point3::new(0., 0., 0.);
+        assert!(points.iter().all(|p| *p != corner));
+    }
+
+    #[test]
+    fn it_rejects_inner_points_and_large_radii() {
+        let mut plate = TrimmedSpline::new(new_plane::<TSpline>(4, 4, 3., 3.).unwrap());
+        let round = |vertex, radius| RoundCorner {
+            vertex: VertID(vertex),
+            radius,
+            boundary: Boundary::Clamped,
+        };
+
+        assert!(matches!(
+            round(5, 1.).apply(&mut plate),
+            Err(RoundCornerError::NotACorner(VertID(5)))
+        ));
+        assert!(matches!(
+            round(3, 4.).apply(&mut plate),
+            Err(RoundCornerError::RadiusTooLarge)
+        ));
+        assert_eq!(4, plate.trim.len());
+    }
+}
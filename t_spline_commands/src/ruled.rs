@@ -0,0 +1,171 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::plane::{GridSide, PlaneError, grid_rows, new_plane};
+use num_traits::{One, ToPrimitive};
+use t_spline::Vector4;
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::ids::VertID;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum RuledError {
+    #[error("curves have {0} and {1} control points")]
+    CountMismatch(usize, usize),
+    #[error("curves have different knot intervals")]
+    KnotMismatch,
+    #[error(transparent)]
+    Plane(#[from] PlaneError),
+}
+
+/// The control points and knots of a clamped boundary of a spline, which define the
+/// surface along it
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundaryCurve<U> {
+    pub points: Vec<Vector4<U>>,
+    pub knots: Vec<isize>,
+}
+
+impl<U: Copy> BoundaryCurve<U> {
+    /// The curve along `side` of a grid shaped mesh
+    pub fn of<T: ControlMesh<Unit = U>>(mesh: &T, side: GridSide) -> Result<Self, RuledError> {
+        let rows = grid_rows(mesh, side)?;
+        Ok(Self {
+            points: rows
+                .boundary
+                .iter()
+                .map(|v| mesh.control_points()[v.0])
+                .collect(),
+            knots: rows.knots,
+        })
+    }
+
+    /// The same curve running the other way
+    pub fn reversed(&self) -> Self {
+        let last = self.knots.last().copied().unwrap_or_default();
+        Self {
+            points: self.points.iter().rev().copied().collect(),
+            knots: self.knots.iter().rev().map(|k| last - k).collect(),
+        }
+    }
+
+    /// Knot intervals, which determine the curve together with the points
+    fn intervals(&self) -> Vec<isize> {
+        self.knots.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+}
+
+/// Build a ruled surface from curve `a` at `t = 0` to curve `b` at `t = 1`.
+///
+/// The boundaries of the surface use the control points and knot intervals of the
+/// curves, so they match the splines the curves were taken from exactly and close the
+/// gap between them. `b` is reversed if it runs the other way than `a`.
+pub fn ruled<T: ControlMeshMut + Default>(
+    a: &BoundaryCurve<T::Unit>,
+    b: &BoundaryCurve<T::Unit>,
+) -> Result<T, RuledError> {
+    let n = a.points.len();
+    if b.points.len() != n {
+        return Err(RuledError::CountMismatch(n, b.points.len()));
+    }
+
+    let distance = |p: &Vector4<T::Unit>, q: &Vector4<T::Unit>| {
+        let d = (p.xyz() - q.xyz()).map(|c| c.to_f64().unwrap_or(f64::NAN));
+        d.dot(&d)
+    };
+    let straight =
+        distance(&a.points[0], &b.points[0]) + distance(&a.points[n - 1], &b.points[n - 1]);
+    let crossed =
+        distance(&a.points[0], &b.points[n - 1]) + distance(&a.points[n - 1], &b.points[0]);
+    let b = if crossed < straight {
+        b.reversed()
+    } else {
+        b.clone()
+    };
+    if a.intervals() != b.intervals() {
+        return Err(RuledError::KnotMismatch);
+    }
+
+    let mut mesh: T = new_plane(n, 2, T::Unit::one(), T::Unit::one())?;
+    for (j, curve) in [a, &b].into_iter().enumerate() {
+        for (i, point) in curve.points.iter().enumerate() {
+            let v = VertID(j * n + i);
+            if let Some(cp) = mesh.control_point_mut(v) {
+                *cp = *point;
+            }
+            if let Some(p) = mesh.point_mut(v) {
+                p.s = curve.knots[i] - curve.knots[0];
+            }
+        }
+    }
+    Ok(mesh)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tessellate::knot_vectors;
+    use t_spline::TSpline;
+    use t_spline::algorithms::subs;
+    use t_spline::uv_mesh::Boundary;
+
+    fn shifted(y: f64, z: f64) -> TSpline {
+        let mut mesh: TSpline = new_plane(4, 3, 3., 2.).unwrap();
+        for v in 0..12 {
+            let cp = mesh.control_point_mut(VertID(v)).unwrap();
+            cp.y += y;
+            cp.z = z * cp.x * cp.x;
+        }
+        mesh
+    }
+
+    #[test]
+    fn it_closes_the_gap_exactly() {
+        let (low, high) = (shifted(0., 0.2), shifted(4., -0.1));
+        let a = BoundaryCurve::of(&low, GridSide::TMax).unwrap();
+        let b = BoundaryCurve::of(&high, GridSide::TMin).unwrap();
+
+        let gap: TSpline = ruled(&a, &b.reversed()).unwrap();
+
+        gap.validate_control_mesh().unwrap();
+        let knots = knot_vectors(&gap, Boundary::Clamped);
+        let (low_knots, high_knots) = (
+            knot_vectors(&low, Boundary::Clamped),
+            knot_vectors(&high, Boundary::Clamped),
+        );
+        for s in [0., 0.4, 1.5, 2.9, 3.] {
+            let edge = subs(gap.control_points(), (s, 0.), &knots).unwrap();
+            let other = subs(low.control_points(), (s, 2.), &low_knots).unwrap();
+            assert!((edge - other).abs().max() < 1e-12, "{edge:?} {other:?}");
+
+            let edge = subs(gap.control_points(), (s, 1.), &knots).unwrap();
+            let other = subs(high.control_points(), (s, 0.), &high_knots).unwrap();
+            assert!((edge - other).abs().max() < 1e-12, "{edge:?} {other:?}");
+        }
+    }
+
+    #[test]
+    fn it_requires_matching_curves() {
+        let a = BoundaryCurve::of(&shifted(0., 0.), GridSide::TMax).unwrap();
+        let b = BoundaryCurve::of(&shifted(0., 0.), GridSide::SMin).unwrap();
+
+        assert!(matches!(
+            ruled::<TSpline>(&a, &b),
+            Err(RuledError::CountMismatch(4, 3))
+        ));
+    }
+}
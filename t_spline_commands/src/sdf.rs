@@ -0,0 +1,211 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::CastError;
+use crate::project_curve::{ProjectError, distance_squared, evaluate, project_polyline};
+use crate::tessellate::knot_vectors;
+use crate::triangles::{intersect, triangulate};
+use rayon::prelude::*;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum SdfError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("grid has no samples")]
+    EmptyGrid,
+    #[error("patch {0} is invalid: {1}")]
+    Invalid(usize, ValidationError),
+    #[error("failed to project onto patch {0}: {1}")]
+    Project(usize, ProjectError),
+}
+
+/// A regular grid of samples, `dims` nodes per axis spaced `spacing` apart from `origin`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoxelGrid {
+    pub origin: Point3<f64>,
+    pub spacing: f64,
+    pub dims: [usize; 3],
+}
+
+impl VoxelGrid {
+    pub fn len(&self) -> usize {
+        self.dims.iter().product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Index of the node `(i, j, k)` in the sample buffer, x varies fastest
+    pub fn index(&self, i: usize, j: usize, k: usize) -> usize {
+        (k * self.dims[1] + j) * self.dims[0] + i
+    }
+
+    pub fn point(&self, i: usize, j: usize, k: usize) -> Point3<f64> {
+        self.origin + Vector3::new(i as f64, j as f64, k as f64) * self.spacing
+    }
+}
+
+/// Signed distances sampled at the nodes of a [`VoxelGrid`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceField {
+    pub grid: VoxelGrid,
+    /// One value per node in [`VoxelGrid::index`] order, negative inside the model
+    pub values: Vec<f64>,
+}
+
+impl DistanceField {
+    pub fn get(&self, i: usize, j: usize, k: usize) -> f64 {
+        self.values[self.grid.index(i, j, k)]
+    }
+}
+
+/// Sample the signed distance field of the closed model made of `patches` on `grid`.
+///
+/// The distance at each node is found by projecting it onto every patch, the sign by
+/// counting the crossings of a ray with a tessellation of the model at `resolution`. An
+/// odd count puts the node inside, so the model must be closed but the patch normals do
+/// not need to agree.
+pub fn signed_distance_field<T: ControlMesh + Sync>(
+    patches: &[T],
+    grid: VoxelGrid,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<DistanceField, SdfError> {
+    if grid.is_empty() {
+        return Err(SdfError::EmptyGrid);
+    }
+
+    let mut caches = Vec::with_capacity(patches.len());
+    let mut triangles = Vec::new();
+    for (patch, mesh) in patches.iter().enumerate() {
+        mesh.validate_control_mesh()
+            .map_err(|e| SdfError::Invalid(patch, e))?;
+        let knot_cache = knot_vectors(mesh, boundary);
+        for rect in mesh.layout().faces {
+            triangulate(mesh, &knot_cache, patch, rect, resolution, &mut triangles)?;
+        }
+        caches.push(knot_cache);
+    }
+
+    // skewed so rays do not run along the seams of axis aligned models
+    let ray = Vector3::new(1., 0.0123, 0.0371);
+    let [nx, ny, nz] = grid.dims;
+    let values: Vec<Vec<f64>> = (0..ny * nz)
+        .into_par_iter()
+        .map(|row| {
+            // a row is projected as a polyline so each node starts from its neighbour
+            let (j, k) = (row % ny, row / ny);
+            let points: Vec<_> = (0..nx).map(|i| grid.point(i, j, k)).collect();
+
+            let mut distances = vec![f64::INFINITY; nx];
+            for (patch, (mesh, knot_cache)) in patches.iter().zip(&caches).enumerate() {
+                let projected = project_polyline(mesh, &points, 0, boundary)
+                    .map_err(|e| SdfError::Project(patch, e))?;
+                for ((distance, point), st) in distances.iter_mut().zip(&points).zip(projected) {
+                    if let Some(d) =
+                        evaluate(mesh, knot_cache, st).map_err(|e| SdfError::Project(patch, e))?
+                    {
+                        *distance = distance.min(distance_squared(&d.point, point));
+                    }
+                }
+            }
+
+            Ok(points
+                .iter()
+                .zip(distances)
+                .map(|(point, distance)| {
+                    let crossings = triangles
+                        .iter()
+                        .filter(|t| intersect(*point, ray, &t.corners).is_some())
+                        .count();
+                    let sign = if crossings % 2 == 1 { -1. } else { 1. };
+                    sign * distance.sqrt()
+                })
+                .collect())
+        })
+        .collect::<Result<_, SdfError>>()?;
+
+    Ok(DistanceField {
+        grid,
+        values: values.into_iter().flatten().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cuboid::cuboid;
+    use t_spline::TSpline;
+
+    fn unit_box() -> DistanceField {
+        let patches: Vec<TSpline> = cuboid([1., 1., 1.]).unwrap();
+        let grid = VoxelGrid {
+            origin: Point3::new(-0.25, -0.25, -0.25),
+            spacing: 0.5,
+            dims: [4, 4, 4],
+        };
+        signed_distance_field(&patches, grid, 4, Boundary::Clamped).unwrap()
+    }
+
+    #[test]
+    fn it_signs_inside_and_outside() {
+        let field = unit_box();
+
+        assert_eq!(64, field.values.len());
+        for k in 0..4 {
+            for j in 0..4 {
+                for i in 0..4 {
+                    let inside = [i, j, k].iter().all(|&n| n == 1 || n == 2);
+                    assert_eq!(inside, field.get(i, j, k) < 0., "({i}, {j}, {k})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_measures_distance_to_the_nearest_face() {
+        let field = unit_box();
+
+        for (i, j, k) in [(1, 1, 1), (2, 1, 2), (1, 1, 0), (2, 3, 1)] {
+            let sign = if k == 0 || j == 3 { 1. } else { -1. };
+            assert!(
+                (field.get(i, j, k) - sign * 0.25).abs() < 1e-6,
+                "({i}, {j}, {k}) = {}",
+                field.get(i, j, k)
+            );
+        }
+    }
+
+    #[test]
+    fn it_rejects_an_empty_grid() {
+        let patches: Vec<TSpline> = cuboid([1., 1., 1.]).unwrap();
+        let grid = VoxelGrid {
+            origin: Point3::origin(),
+            spacing: 1.,
+            dims: [0, 1, 1],
+        };
+
+        assert!(matches!(
+            signed_distance_field(&patches, grid, 4, Boundary::Clamped),
+            Err(SdfError::EmptyGrid)
+        ));
+    }
+}
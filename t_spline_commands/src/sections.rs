@@ -0,0 +1,202 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::command::{CommandError, Parameters};
+use crate::intersect::{IntersectError, Primitive, intersect_primitive, point};
+use crate::tessellate::knot_vectors;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::Boundary;
+use t_spline::{Point3, Vector3};
+
+/// Cut a surface with parallel planes, as the stations of a hull
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossSections {
+    /// Normal shared by the cutting planes
+    pub normal: Vector3<f64>,
+    /// Signed distance of every plane from the origin along the normal
+    pub offsets: Vec<f64>,
+    /// Contour samples per face along either direction
+    pub resolution: usize,
+    pub boundary: Boundary,
+}
+
+/// The cut of a surface by one plane
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub offset: f64,
+    /// Polylines on the surface, closed ones end where they start
+    pub contours: Vec<Vec<Point3<f64>>>,
+    /// Area enclosed by the contours
+    pub area: f64,
+    /// Length of the contours
+    pub perimeter: f64,
+    /// Centroid of the enclosed area, `None` if it is empty
+    pub centroid: Option<Point3<f64>>,
+}
+
+impl CrossSections {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        let normal = if parameters.contains("normal") {
+            parameters.get_with("normal", |v| {
+                let v: Vec<f64> = v
+                    .split(',')
+                    .map(|v| v.trim().parse().ok())
+                    .collect::<Option<_>>()?;
+                let &[x, y, z] = v.as_slice() else {
+                    return None;
+                };
+                let normal = Vector3::new(x, y, z);
+                (length(normal) > 0.).then_some(normal)
+            })?
+        } else {
+            Vector3::z()
+        };
+        Ok(Self {
+            normal,
+            offsets: parameters.get_list("offsets")?,
+            resolution: parameters.get_or("resolution", 8)?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+
+    pub fn apply<T: ControlMesh + Sync>(&self, mesh: &T) -> Result<Vec<Section>, IntersectError> {
+        cross_sections(mesh, self)
+    }
+}
+
+/// Cut `mesh` with every plane of `settings` and measure the sections.
+///
+/// Each contour encloses its own region, open ones are closed by the straight line
+/// between their ends, like the waterline of a hull. The perimeter only measures the
+/// contours on the surface.
+pub fn cross_sections<T: ControlMesh + Sync>(
+    mesh: &T,
+    settings: &CrossSections,
+) -> Result<Vec<Section>, IntersectError> {
+    let normal = settings.normal / length(settings.normal);
+    let knot_cache = knot_vectors(mesh, settings.boundary);
+
+    settings
+        .offsets
+        .iter()
+        .map(|&offset| {
+            let plane = Primitive::Plane {
+                point: Point3::from(normal * offset),
+                normal,
+            };
+            let contours =
+                intersect_primitive(mesh, &plane, settings.resolution, settings.boundary)?
+                    .into_iter()
+                    .map(|curve| {
+                        curve
+                            .into_iter()
+                            .filter_map(|st| point(mesh, &knot_cache, st).transpose())
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+            Ok(measure(offset, normal, contours))
+        })
+        .collect()
+}
+
+fn measure(offset: f64, normal: Vector3<f64>, contours: Vec<Vec<Point3<f64>>>) -> Section {
+    let mut perimeter = 0.;
+    let (mut area, mut moment) = (0., Vector3::zeros());
+    for contour in &contours {
+        perimeter += contour.windows(2).map(|w| length(w[1] - w[0])).sum::<f64>();
+
+        // fan the polygon from its first point, with signed areas in the plane
+        let Some(&first) = contour.first() else {
+            continue;
+        };
+        let (mut a, mut m) = (0., Vector3::zeros());
+        for w in contour.windows(2) {
+            let triangle = (w[0] - first).cross(&(w[1] - first)).dot(&normal) / 2.;
+            a += triangle;
+            m += (first.coords + w[0].coords + w[1].coords) / 3. * triangle;
+        }
+        // contours run either way around
+        area += a.abs();
+        moment += m * a.signum();
+    }
+
+    Section {
+        offset,
+        contours,
+        area,
+        perimeter,
+        centroid: (area > f64::EPSILON).then(|| Point3::from(moment / area)),
+    }
+}
+
+fn length(v: Vector3<f64>) -> f64 {
+    v.dot(&v).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::UVMesh;
+    use t_spline::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_measures_a_closed_polygon() {
+        let square = [(0., 0.), (2., 0.), (2., 1.), (0., 1.), (0., 0.)]
+            .map(|(x, y)| Point3::new(x, y, 3.))
+            .to_vec();
+
+        let section = measure(3., Vector3::z(), vec![square.into_iter().rev().collect()]);
+
+        assert!((section.area - 2.).abs() < 1e-12);
+        assert!((section.perimeter - 6.).abs() < 1e-12);
+        assert_eq!(Some(Point3::new(1., 0.5, 3.)), section.centroid);
+    }
+
+    #[test]
+    fn it_cuts_a_hull() {
+        // a trough along y, deepest in the middle
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        for (v, p) in mesh.points().to_vec().iter().enumerate() {
+            let depth = [0., -1., -1.5, -1., 0.][p.s as usize];
+            mesh.control_point_mut(VertID(v)).unwrap().z = depth;
+        }
+        let settings = CrossSections::from_parameters(
+            &Parameters::default()
+                .with("normal", "0,1,0")
+                .with("offsets", "1,2,9"),
+        )
+        .unwrap();
+
+        let sections = settings.apply(&mesh).unwrap();
+
+        assert_eq!(3, sections.len());
+        let (a, b) = (&sections[0], &sections[1]);
+        // the trough has the same profile at every station, up to the sampling
+        assert!((a.area - b.area).abs() < 1e-3);
+        assert!(a.area > 1. && a.area < 4. * 1.5);
+        assert!(a.perimeter > 4.);
+        let centroid = b.centroid.unwrap();
+        assert!((centroid.x - 2.).abs() < 1e-3 && (centroid.y - 2.).abs() < 1e-6);
+        assert!(centroid.z < 0. && centroid.z > -1.5);
+        // a plane past the surface cuts nothing
+        assert!(sections[2].contours.is_empty());
+        assert_eq!(None, sections[2].centroid);
+    }
+}
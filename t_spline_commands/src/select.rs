@@ -0,0 +1,122 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, to_f64};
+use t_spline::Vector3;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::ids::EdgeID;
+use thiserror::Error;
+
+#[derive(Copy, Clone, Debug, Error)]
+pub enum SelectError {
+    #[error("missing edge")]
+    MissingEdge,
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+}
+
+/// Select the smooth shell around `seeds`.
+///
+/// Starting from the faces of the seed edges, neighbouring faces are added while the
+/// normals of their control polygons deviate by at most `max_angle` radians from the
+/// face they were reached through. Returns the representative edge of each selected
+/// face, as in [t_spline::uv_mesh::UVMesh::faces].
+pub fn select_smooth<T: ControlMesh>(
+    mesh: &T,
+    seeds: &[EdgeID],
+    max_angle: f64,
+) -> Result<Vec<EdgeID>, SelectError> {
+    let graph = mesh.face_adjacency();
+    let seeds: Vec<usize> = seeds
+        .iter()
+        .map(|&e| graph.face_of(e).ok_or(SelectError::MissingEdge))
+        .collect::<Result<_, _>>()?;
+
+    let normals: Vec<Vector3<f64>> = graph
+        .faces()
+        .iter()
+        .map(|&face| face_normal(mesh, face))
+        .collect::<Result<_, _>>()?;
+
+    let selected = graph.grow(&seeds, |from, to| {
+        let (a, b) = (normals[from], normals[to]);
+        let cos = a.dot(&b) / (a.dot(&a) * b.dot(&b)).sqrt();
+        cos.clamp(-1., 1.).acos() <= max_angle
+    });
+
+    Ok(selected.into_iter().map(|f| graph.faces()[f]).collect())
+}
+
+/// Normal of the control polygon around `face` using Newell's method
+fn face_normal<T: ControlMesh>(mesh: &T, face: EdgeID) -> Result<Vector3<f64>, SelectError> {
+    let edge = mesh.edge(face).ok_or(SelectError::MissingEdge)?;
+
+    let mut corners = Vec::new();
+    for (_, e) in mesh.edge_loop(edge) {
+        let cp = mesh
+            .control_point(e.origin)
+            .ok_or(SelectError::MissingControlPoint)?;
+        corners.push(Vector3::new(to_f64(cp.x)?, to_f64(cp.y)?, to_f64(cp.z)?));
+    }
+
+    let mut normal = Vector3::zeros();
+    for (i, a) in corners.iter().enumerate() {
+        let b = corners[(i + 1) % corners.len()];
+        normal += a.cross(&b);
+    }
+    Ok(normal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use std::f64::consts::PI;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    /// Two faces with a 45 degree fold between them
+    fn folded() -> TSpline {
+        let mut mesh: TSpline = new_plane(3, 2, 2., 1.).unwrap();
+        for v in [2, 5] {
+            mesh.control_point_mut(VertID(v)).unwrap().z = 1.;
+        }
+        mesh
+    }
+
+    #[test]
+    fn it_stops_at_creases() {
+        let mesh = folded();
+
+        assert_eq!(
+            vec![EdgeID(0)],
+            select_smooth(&mesh, &[EdgeID(1)], PI / 8.).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_grows_over_smooth_faces() {
+        let mesh = folded();
+
+        assert_eq!(
+            vec![EdgeID(0), EdgeID(4)],
+            select_smooth(&mesh, &[EdgeID(6)], PI / 3.).unwrap()
+        );
+    }
+}
@@ -0,0 +1,193 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::command::{Command, CommandError};
+use crate::knot_cache::Influence;
+use smallvec::SmallVec;
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::ids::VertID;
+use t_spline::{Matrix4, Point3};
+use thiserror::Error;
+
+#[derive(Copy, Clone, Debug, Error)]
+pub enum SkinError {
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("pose has no matrix for bone {0}")]
+    MissingBone(usize),
+    #[error("skin was bound to a mesh with {expected} control points, got {got}")]
+    CountMismatch { expected: usize, got: usize },
+}
+
+/// Bone weights of every control point, together with the control point positions of
+/// the bind pose they were painted on
+#[derive(Debug, Clone)]
+pub struct Skin {
+    bind: Vec<Point3<f64>>,
+    weights: Vec<SmallVec<[(usize, f64); 4]>>,
+}
+
+impl Skin {
+    /// Bind to the current control points of `mesh`, no control point has weights yet
+    pub fn new(mesh: &impl ControlMesh) -> Result<Self, SkinError> {
+        let bind = mesh
+            .control_points()
+            .iter()
+            .map(|cp| Ok(Point3::new(to_f64(cp.x)?, to_f64(cp.y)?, to_f64(cp.z)?)))
+            .collect::<Result<Vec<_>, SkinError>>()?;
+        Ok(Self {
+            weights: vec![SmallVec::new(); bind.len()],
+            bind,
+        })
+    }
+
+    /// Set the `(bone, weight)` pairs of a control point, weights are normalized when the
+    /// skin is posed
+    pub fn set_weights(&mut self, v: VertID, weights: &[(usize, f64)]) -> Result<(), SkinError> {
+        let entry = self
+            .weights
+            .get_mut(v.0)
+            .ok_or(SkinError::MissingControlPoint)?;
+        *entry = weights.iter().copied().filter(|(_, w)| *w != 0.).collect();
+        Ok(())
+    }
+
+    pub fn weights(&self, v: VertID) -> &[(usize, f64)] {
+        self.weights.get(v.0).map_or(&[], |w| w.as_slice())
+    }
+
+    /// Number of bones a pose must provide matrices for
+    pub fn bones(&self) -> usize {
+        self.weights
+            .iter()
+            .flatten()
+            .map(|(bone, _)| bone + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// [skin] as a [Command], posing the control points with the matrices of `pose`
+#[derive(Debug, Clone)]
+pub struct SkinDeform<'a> {
+    pub skin: &'a Skin,
+    pub pose: &'a [Matrix4<f64>],
+}
+
+impl<T: ControlMeshMut> Command<T> for SkinDeform<'_> {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        skin(mesh, self.skin, self.pose).map_err(CommandError::failed)?;
+        Ok(Influence::Geometry)
+    }
+}
+
+/// Move the control points of `mesh` to the linear blend of their bind positions
+/// transformed by the bones they are weighted to.
+///
+/// `pose` holds one affine skinning matrix per bone, the posed bone transform times the
+/// inverse of its bind transform. Control points without weights keep their bind
+/// position, so posing always starts from the bind pose and never accumulates.
+pub fn skin<T: ControlMeshMut>(
+    mesh: &mut T,
+    skin: &Skin,
+    pose: &[Matrix4<f64>],
+) -> Result<(), SkinError> {
+    let count = mesh.control_points().len();
+    if count != skin.bind.len() {
+        return Err(SkinError::CountMismatch {
+            expected: skin.bind.len(),
+            got: count,
+        });
+    }
+    if skin.bones() > pose.len() {
+        return Err(SkinError::MissingBone(pose.len()));
+    }
+
+    for (v, (bind, weights)) in skin.bind.iter().zip(&skin.weights).enumerate() {
+        let total: f64 = weights.iter().map(|(_, w)| w).sum();
+        let posed = if total == 0. {
+            *bind
+        } else {
+            let mut sum = Point3::origin();
+            for &(bone, weight) in weights {
+                let p = pose[bone] * bind.to_homogeneous();
+                sum += p.xyz() * (weight / total);
+            }
+            sum
+        };
+
+        let cp = mesh
+            .control_point_mut(VertID(v))
+            .ok_or(SkinError::MissingControlPoint)?;
+        let cast = from_f64::<T::Unit>;
+        (cp.x, cp.y, cp.z) = (cast(posed.x)?, cast(posed.y)?, cast(posed.z)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::{TSpline, Vector3};
+
+    fn translation(x: f64, y: f64, z: f64) -> Matrix4<f64> {
+        Matrix4::new_translation(&Vector3::new(x, y, z))
+    }
+
+    #[test]
+    fn it_blends_bone_transforms() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let mut skin = Skin::new(&mesh).unwrap();
+        skin.set_weights(VertID(0), &[(0, 1.)]).unwrap();
+        skin.set_weights(VertID(1), &[(0, 1.), (1, 3.)]).unwrap();
+        let pose = [translation(0., 0., 4.), translation(0., 0., 0.)];
+
+        SkinDeform {
+            skin: &skin,
+            pose: &pose,
+        }
+        .apply_mut(&mut mesh)
+        .unwrap();
+
+        let z: Vec<_> = mesh.control_points().iter().map(|cp| cp.z).collect();
+        assert_eq!(4., z[0]);
+        assert_eq!(1., z[1]);
+        assert_eq!(0., z[2]);
+        assert_eq!(2, skin.bones());
+    }
+
+    #[test]
+    fn it_poses_from_the_bind_pose() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let mut skin = Skin::new(&mesh).unwrap();
+        skin.set_weights(VertID(4), &[(0, 1.)]).unwrap();
+
+        for _ in 0..2 {
+            super::skin(&mut mesh, &skin, &[translation(1., 0., 0.)]).unwrap();
+        }
+
+        assert_eq!(2., mesh.control_points()[4].x);
+        assert!(matches!(
+            super::skin(&mut mesh, &skin, &[]),
+            Err(SkinError::MissingBone(0))
+        ));
+    }
+}
@@ -0,0 +1,347 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::project_curve::{ProjectError, project_polyline};
+use crate::trim::TrimmedSpline;
+use std::collections::BTreeMap;
+use t_spline::Point3;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum SplitError {
+    #[error("a cutting curve needs at least 2 points")]
+    TooFewPoints,
+    #[error("cutting curve does not cross the surface")]
+    NoCrossing,
+    #[error("the parametric domain has holes or touches itself")]
+    NotSimple,
+    #[error("failed to project the cutting curve: {0}")]
+    Project(#[from] ProjectError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// Parametric distance below which two points on the domain edge are the same
+const EPSILON: f64 = 1e-9;
+
+/// Split `mesh` into two trimmed halves along a 3D cutting curve.
+///
+/// The curve is projected onto the surface, both ends are extended straight to the edge of
+/// the parametric domain and the domain is cut in two. The edge is traced along the
+/// boundary of the faces, so the domain does not need to be rectangular, but it must be a
+/// single loop without holes. The first half lies to the left of the curve in parameter
+/// space, the second to the right. Both trim loops share the exact same cut parameters, so
+/// their tessellations meet along the cut.
+pub fn split_along_curve<T: ControlMesh + Clone + Sync>(
+    mesh: &T,
+    curve: &[Point3<f64>],
+    smoothing: usize,
+    boundary: Boundary,
+) -> Result<(TrimmedSpline<T>, TrimmedSpline<T>), SplitError> {
+    if curve.len() < 2 {
+        return Err(SplitError::TooFewPoints);
+    }
+
+    let mut cut = project_polyline(mesh, curve, smoothing, boundary)?;
+    cut.dedup();
+    if cut.len() < 2 {
+        return Err(SplitError::NoCrossing);
+    }
+
+    let outline = outline(mesh)?;
+    let n = cut.len();
+    let (start, from) = exit(&outline, cut[0], cut[1]).ok_or(SplitError::NoCrossing)?;
+    let (end, to) = exit(&outline, cut[n - 1], cut[n - 2]).ok_or(SplitError::NoCrossing)?;
+    if distance(start, cut[0]) > EPSILON {
+        cut.insert(0, start);
+    }
+    if distance(end, cut[cut.len() - 1]) > EPSILON {
+        cut.push(end);
+    }
+    if from.side == to.side && (from.offset - to.offset).abs() < EPSILON {
+        return Err(SplitError::NoCrossing);
+    }
+
+    let mut left = cut.clone();
+    left.extend(corners_between(&outline, to, from));
+
+    let mut right: Vec<_> = cut.into_iter().rev().collect();
+    right.extend(corners_between(&outline, from, to));
+
+    Ok((
+        TrimmedSpline {
+            mesh: mesh.clone(),
+            trim: left,
+        },
+        TrimmedSpline {
+            mesh: mesh.clone(),
+            trim: right,
+        },
+    ))
+}
+
+/// A point on the domain outline, `offset` knot units along the side starting at corner
+/// `side`
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    side: usize,
+    offset: f64,
+}
+
+/// Counter-clockwise corners of the parametric domain, traced along the boundary edges
+fn outline<T: ControlMesh>(mesh: &T) -> Result<Vec<(isize, isize)>, SplitError> {
+    let mut by_origin = BTreeMap::new();
+    for edge in mesh.edges().iter().filter(|e| e.twin.is_none()) {
+        if by_origin.insert(edge.origin, edge).is_some() {
+            return Err(SplitError::NotSimple);
+        }
+    }
+    let Some(&first) = by_origin.keys().next() else {
+        return Err(ValidationError::EmptyDomain().into());
+    };
+
+    let mut points = Vec::with_capacity(by_origin.len());
+    let mut vertex = first;
+    loop {
+        let edge = by_origin.get(&vertex).ok_or(SplitError::NotSimple)?;
+        let (p, _) = mesh.start_end(edge);
+        points.push((p.s, p.t));
+        vertex = mesh.next_edge(edge).origin;
+        if vertex == first || points.len() > by_origin.len() {
+            break;
+        }
+    }
+    if points.len() != by_origin.len() {
+        return Err(SplitError::NotSimple);
+    }
+
+    // keep only the points where the boundary turns
+    let n = points.len();
+    Ok((0..n)
+        .filter(|&i| {
+            let (p, c, q) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+            (c.0 - p.0) * (q.1 - c.1) != (c.1 - p.1) * (q.0 - c.0)
+        })
+        .map(|i| points[i])
+        .collect())
+}
+
+fn corner(outline: &[(isize, isize)], i: usize) -> (f64, f64) {
+    let (s, t) = outline[i % outline.len()];
+    (s as f64, t as f64)
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+/// Where the ray from `inner` through `outer` first reaches the domain outline
+fn exit(
+    outline: &[(isize, isize)],
+    outer: (f64, f64),
+    inner: (f64, f64),
+) -> Option<((f64, f64), Position)> {
+    let direction = (outer.0 - inner.0, outer.1 - inner.1);
+
+    let mut best: Option<(f64, (f64, f64), Position)> = None;
+    for side in 0..outline.len() {
+        let horizontal = outline[side].1 == outline[(side + 1) % outline.len()].1;
+        let (a, b) = (corner(outline, side), corner(outline, side + 1));
+        let length = distance(a, b);
+        // sides are axis aligned, so one coordinate is fixed along the side
+        let (axis, along) = if horizontal { (1, 0) } else { (0, 1) };
+        let pick = |p: (f64, f64), i: usize| if i == 0 { p.0 } else { p.1 };
+        if pick(direction, axis).abs() <= EPSILON * distance(direction, (0., 0.)) {
+            continue;
+        }
+
+        let d = (pick(a, axis) - pick(outer, axis)) / pick(direction, axis);
+        let hit = pick(outer, along) + d * pick(direction, along);
+        let offset = (hit - pick(a, along)) * (pick(b, along) - pick(a, along)).signum();
+        if d < -EPSILON || offset < -EPSILON || offset > length + EPSILON {
+            continue;
+        }
+        if best.is_some_and(|(closest, _, _)| closest <= d) {
+            continue;
+        }
+
+        let position = if offset > length - EPSILON {
+            Position {
+                side: (side + 1) % outline.len(),
+                offset: 0.,
+            }
+        } else {
+            Position {
+                side,
+                offset: if offset < EPSILON { 0. } else { offset },
+            }
+        };
+        let start = corner(outline, position.side);
+        let end = corner(outline, position.side + 1);
+        let f = position.offset / distance(start, end);
+        let point = (
+            start.0 + (end.0 - start.0) * f,
+            start.1 + (end.1 - start.1) * f,
+        );
+        best = Some((d, point, position));
+    }
+    best.map(|(_, point, position)| (point, position))
+}
+
+/// The outline corners passed when walking counter-clockwise from `from` to `to`
+fn corners_between(outline: &[(isize, isize)], from: Position, to: Position) -> Vec<(f64, f64)> {
+    if from.side == to.side && to.offset > from.offset {
+        return Vec::new();
+    }
+
+    let mut passed = Vec::new();
+    let mut side = from.side;
+    loop {
+        side = (side + 1) % outline.len();
+        if side == to.side && to.offset < EPSILON {
+            break;
+        }
+        passed.push(corner(outline, side));
+        if side == to.side {
+            break;
+        }
+    }
+    passed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrude_edge::extrude_edge;
+    use crate::plane::new_plane;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::UVMesh;
+    use t_spline::uv_mesh::ids::EdgeID;
+    use t_spline::{TSpline, Vector4};
+
+    #[test]
+    fn it_splits_a_plane_in_two() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let cut: Vec<_> = (1..=9)
+            .map(|i| Point3::new(1.5, i as f64 * 0.3, 1.))
+            .collect();
+
+        let (left, right) = split_along_curve(&mesh, &cut, 0, Boundary::Clamped).unwrap();
+
+        assert!(left.contains((0.5, 1.5)));
+        assert!(!left.contains((2.5, 1.5)));
+        assert!(right.contains((2.5, 1.5)));
+        assert!(!right.contains((0.5, 1.5)));
+
+        let left = left.tessellate(8, Boundary::Clamped).unwrap();
+        let right = right.tessellate(8, Boundary::Clamped).unwrap();
+        assert!(left.iter().all(|p| p.x <= 1.5 + 1e-6));
+        assert!(right.iter().all(|p| p.x >= 1.5 - 1e-6));
+
+        // both halves contain the same points along the cut
+        let on_cut = |points: &[Point3<f64>]| -> Vec<Point3<f64>> {
+            let mut cut: Vec<_> = points
+                .iter()
+                .filter(|p| (p.x - 1.5).abs() < 1e-6)
+                .copied()
+                .collect();
+            cut.sort_by(|a, b| a.y.total_cmp(&b.y));
+            cut
+        };
+        assert_eq!(on_cut(&left), on_cut(&right));
+        assert!(on_cut(&left).len() >= 9);
+    }
+
+    #[test]
+    fn it_walks_the_domain_edge() {
+        let outline = [(0, 0), (2, 0), (2, 2), (0, 2)];
+        let at = |side, offset| Position { side, offset };
+
+        assert_eq!(
+            vec![(2., 0.), (2., 2.), (0., 2.)],
+            corners_between(&outline, at(0, 1.), at(3, 1.))
+        );
+        assert_eq!(
+            vec![(0., 0.)],
+            corners_between(&outline, at(3, 1.), at(0, 1.))
+        );
+        // a position on a corner does not repeat it
+        assert_eq!(
+            vec![(2., 0.)],
+            corners_between(&outline, at(0, 1.), at(2, 0.))
+        );
+    }
+
+    #[test]
+    fn it_exits_through_the_nearest_side() {
+        let outline = [(0, 0), (2, 0), (2, 1), (1, 1), (1, 2), (0, 2)];
+
+        let (point, position) = exit(&outline, (0.5, 1.5), (0.5, 1.)).unwrap();
+        assert_eq!((0.5, 2.), point);
+        assert_eq!(4, position.side);
+
+        let (point, position) = exit(&outline, (1.5, 0.5), (1., 0.5)).unwrap();
+        assert_eq!((2., 0.5), point);
+        assert_eq!(1, position.side);
+
+        // rounding just past a corner lands on the corner
+        let (point, position) =
+            exit(&outline, (1.5, 0.5), (1.5 - 1e-3, 0.5 - 1e-3 - 1e-12)).unwrap();
+        assert_eq!((2., 1.), point);
+        assert_eq!((2, 0.), (position.side, position.offset));
+    }
+
+    #[test]
+    fn it_splits_a_non_rectangular_domain() {
+        // an L made of a 2x2 plane and one face extruded above its top left face
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let top_left = mesh
+            .edges()
+            .iter()
+            .position(|e| {
+                let (a, b) = mesh.start_end(e);
+                e.twin.is_none() && (a.s, a.t, b.s, b.t) == (1, 2, 0, 2)
+            })
+            .unwrap();
+        let extrusion = extrude_edge(&mut mesh, EdgeID(top_left)).unwrap();
+        for v in extrusion.vertices {
+            let p = mesh.point(v).unwrap();
+            let (s, t) = (p.s as f64, p.t as f64);
+            *mesh.control_point_mut(v).unwrap() = Vector4::new(s, t, 0., 1.);
+        }
+
+        assert_eq!(
+            vec![(0, 0), (2, 0), (2, 2), (1, 2), (1, 3), (0, 3)],
+            outline(&mesh).unwrap()
+        );
+
+        let cut: Vec<_> = (1..=9)
+            .map(|i| Point3::new(0.5, i as f64 * 0.3, 1.))
+            .collect();
+        let (left, right) = split_along_curve(&mesh, &cut, 0, Boundary::Clamped).unwrap();
+
+        // the right half follows the notch instead of the bounding rectangle
+        assert!(right.trim.contains(&(1., 2.)));
+        assert!(right.trim.contains(&(1., 3.)));
+        assert!(!right.trim.contains(&(2., 3.)));
+        assert!(right.contains((1.5, 1.5)));
+        assert!(!right.contains((1.5, 2.5)));
+        assert!(left.contains((0.25, 2.5)));
+        assert!(!left.contains((0.75, 2.5)));
+    }
+}
@@ -0,0 +1,745 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::command::{Command, CommandError, Parameters};
+use crate::knot_cache::{Influence, two_ring};
+use num_traits::FromPrimitive;
+use t_spline::Vector4;
+use t_spline::algorithms::{cubic_basis_function, subs};
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::half_edge::HalfEdge;
+use t_spline::uv_mesh::ids::{EdgeID, VertID};
+use t_spline::uv_mesh::uv_point::UVCoord;
+use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use thiserror::Error;
+
+/// Samples per face direction used to fit the control points after a split
+const SAMPLES: usize = 4;
+
+#[derive(Clone, Debug, Error)]
+pub enum SplitFaceError {
+    #[error("missing edge")]
+    MissingEdge,
+    #[error("missing point")]
+    MissingPoint,
+    #[error("missing control point")]
+    MissingControlPoint,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("face is too narrow to split")]
+    TooNarrow,
+    #[error("split value {0:?} is not strictly inside the face")]
+    OutsideFace(SplitAt),
+    #[error("face is not crossed exactly twice by the split line")]
+    NotSplittable,
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// Where a face is split across its direction
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum SplitAt {
+    /// Halfway across the face, rounded down to a knot value
+    #[default]
+    Middle,
+    /// At a knot value, for example to line up with an existing knot line
+    Absolute(isize),
+    /// At a fraction of the face extent, rounded to the nearest knot value
+    Normalized(f64),
+}
+
+impl SplitAt {
+    /// The knot value to split the range `(lo, hi)` of a face at, which must lie
+    /// strictly inside it
+    pub fn resolve(self, (lo, hi): (isize, isize)) -> Result<isize, SplitFaceError> {
+        let value = match self {
+            SplitAt::Middle if hi - lo < 2 => return Err(SplitFaceError::TooNarrow),
+            SplitAt::Middle => lo + (hi - lo) / 2,
+            SplitAt::Absolute(value) => value,
+            SplitAt::Normalized(f) if f > 0. && f < 1. => {
+                lo + (f * (hi - lo) as f64).round() as isize
+            }
+            SplitAt::Normalized(_) => return Err(SplitFaceError::OutsideFace(self)),
+        };
+        if lo < value && value < hi {
+            Ok(value)
+        } else {
+            Err(SplitFaceError::OutsideFace(self))
+        }
+    }
+}
+
+/// Elements created by [split_edge]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeSplit {
+    pub vertex: VertID,
+    /// The half edge continuing the split edge from the new vertex
+    pub edge: EdgeID,
+    /// The half edge continuing its twin, if it has one
+    pub twin: Option<EdgeID>,
+}
+
+/// Elements created by [split_face]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaceSplit {
+    /// Vertices inserted into the sides of the face, at most two
+    pub vertices: Vec<VertID>,
+    /// Every new half edge, including the continuations of split sides
+    pub edges: Vec<EdgeID>,
+    /// The two halves, given by the half edges of the new knot line
+    pub faces: [EdgeID; 2],
+}
+
+/// [split_face] as a [Command], built from the `face`, `direction` and optional `at` and
+/// `boundary` parameters.
+///
+/// An integer `at` is a knot value, a decimal one a fraction of the face, the face is
+/// split in the middle without it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplitFace {
+    pub face: EdgeID,
+    pub direction: Direction,
+    pub at: SplitAt,
+    pub boundary: Boundary,
+}
+
+impl SplitFace {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        let at = if parameters.contains("at") {
+            parameters.get_with("at", |v| {
+                v.parse()
+                    .map(SplitAt::Absolute)
+                    .or_else(|_| v.parse().map(SplitAt::Normalized))
+                    .ok()
+            })?
+        } else {
+            SplitAt::Middle
+        };
+        Ok(Self {
+            face: EdgeID(parameters.get("face")?),
+            direction: parameters.get_direction("direction")?,
+            at,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+}
+
+impl<T: ControlMeshMut> Command<T> for SplitFace {
+    fn apply_mut(&self, mesh: &mut T) -> Result<Influence, CommandError> {
+        let edge = mesh
+            .edge(self.face)
+            .ok_or_else(|| CommandError::failed(SplitFaceError::MissingEdge))?;
+        let mut seeds: Vec<_> = mesh.edge_loop(edge).map(|(_, e)| e.origin).collect();
+
+        let split = split_face(mesh, self.face, self.direction, self.at, self.boundary)
+            .map_err(CommandError::failed)?;
+
+        seeds.extend(split.vertices);
+        Ok(Influence::Local(two_ring(mesh, &seeds)))
+    }
+}
+
+/// Split the face of `face` in two with a new knot line across its `direction` at `at`.
+///
+/// Sides crossed by the line get a new vertex, their neighbouring faces share it. The
+/// control points of the new vertices and of the corners of the face are then fitted to
+/// the surface before the split, so the shape changes as little as the new knot
+/// vectors allow instead of following the cage.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), face = face.0, ?direction))
+)]
+pub fn split_face<T: ControlMeshMut>(
+    mesh: &mut T,
+    face: EdgeID,
+    direction: Direction,
+    at: SplitAt,
+    boundary: Boundary,
+) -> Result<FaceSplit, SplitFaceError> {
+    mesh.validate_control_mesh()?;
+
+    let value = at.resolve(face_range(mesh, face, direction)?)?;
+
+    let old_points = mesh.control_points().to_vec();
+    let old_knots = knot_vectors_of(mesh, boundary);
+    let edge = mesh.edge(face).ok_or(SplitFaceError::MissingEdge)?;
+    let neighbours: Vec<VertID> = mesh.edge_loop(edge).map(|(_, e)| e.origin).collect();
+
+    let split = insert_line(mesh, face, direction, value)?;
+
+    let mut fitted = split.vertices.clone();
+    fitted.extend(neighbours);
+    fit_to_surface(mesh, &old_points, &old_knots, &fitted, boundary)?;
+    Ok(split)
+}
+
+/// Parametric extent of the face loop of `face` along `direction`
+pub(crate) fn face_range(
+    mesh: &impl ControlMeshMut,
+    face: EdgeID,
+    direction: Direction,
+) -> Result<(isize, isize), SplitFaceError> {
+    let edge = mesh.edge(face).ok_or(SplitFaceError::MissingEdge)?;
+    let mut range = (isize::MAX, isize::MIN);
+    for (_, e) in mesh.edge_loop(edge) {
+        let value = mesh
+            .point(e.origin)
+            .ok_or(SplitFaceError::MissingPoint)?
+            .value_in_dir(direction);
+        range = (range.0.min(value), range.1.max(value));
+    }
+    Ok(range)
+}
+
+/// Connect the face of `face` across `direction` at `value`, splitting crossed sides
+pub(crate) fn insert_line<T: ControlMeshMut>(
+    mesh: &mut T,
+    face: EdgeID,
+    direction: Direction,
+    value: isize,
+) -> Result<FaceSplit, SplitFaceError> {
+    let edge = mesh.edge(face).ok_or(SplitFaceError::MissingEdge)?;
+    let mut existing = Vec::new();
+    let mut crossed = Vec::new();
+    for (id, e) in mesh.edge_loop(edge) {
+        let a = mesh
+            .point(e.origin)
+            .ok_or(SplitFaceError::MissingPoint)?
+            .value_in_dir(direction);
+        let b = mesh
+            .point(mesh.next_edge(e).origin)
+            .ok_or(SplitFaceError::MissingPoint)?
+            .value_in_dir(direction);
+        if a == value {
+            existing.push(e.origin);
+        } else if a.min(b) < value && value < a.max(b) {
+            crossed.push(id);
+        }
+    }
+    if existing.len() + crossed.len() != 2 {
+        return Err(SplitFaceError::NotSplittable);
+    }
+
+    let mut vertices = Vec::new();
+    let mut edges = Vec::new();
+    for e in crossed {
+        let split = split_edge(mesh, e, direction, value)?;
+        existing.push(split.vertex);
+        vertices.push(split.vertex);
+        edges.push(split.edge);
+        edges.extend(split.twin);
+    }
+    let faces = connect(mesh, face, [existing[0], existing[1]])?;
+    edges.extend(faces);
+    Ok(FaceSplit {
+        vertices,
+        edges,
+        faces,
+    })
+}
+
+/// Insert a vertex into `edge` and its twin at `value` along `direction`.
+///
+/// The control point is interpolated along the cage edge.
+pub fn split_edge<T: ControlMeshMut>(
+    mesh: &mut T,
+    edge_id: EdgeID,
+    direction: Direction,
+    value: isize,
+) -> Result<EdgeSplit, SplitFaceError> {
+    let edge = mesh
+        .edge(edge_id)
+        .ok_or(SplitFaceError::MissingEdge)?
+        .clone();
+    let b_id = mesh.next_edge(&edge).origin;
+    let a = mesh
+        .point(edge.origin)
+        .ok_or(SplitFaceError::MissingPoint)?;
+    let b = mesh.point(b_id).ok_or(SplitFaceError::MissingPoint)?;
+
+    let span = b.value_in_dir(direction) - a.value_in_dir(direction);
+    let fraction = T::Unit::from_isize(value - a.value_in_dir(direction))
+        .zip(T::Unit::from_isize(span))
+        .map(|(v, span)| v / span)
+        .ok_or(CastError)?;
+    let mut point = a.clone();
+    point.add_in_dir(direction, value - a.value_in_dir(direction));
+
+    let a_cp = *mesh
+        .control_point(edge.origin)
+        .ok_or(SplitFaceError::MissingControlPoint)?;
+    let b_cp = *mesh
+        .control_point(b_id)
+        .ok_or(SplitFaceError::MissingControlPoint)?;
+
+    // the new half edges, `after` continues `edge` and `twin_after` continues its twin
+    let after = EdgeID(mesh.edges().len());
+    let twin_after = EdgeID(after.0 + 1);
+
+    point.outgoing_edge = after;
+    let v = mesh.push_point(point);
+    mesh.push_control_point(a_cp + (b_cp - a_cp) * fraction);
+
+    mesh.push_edge(HalfEdge {
+        origin: v,
+        twin: edge.twin,
+        next: edge.next,
+        prev: edge_id,
+    });
+    mesh.edge_mut(edge.next)
+        .ok_or(SplitFaceError::MissingEdge)?
+        .prev = after;
+    mesh.edge_mut(edge_id)
+        .ok_or(SplitFaceError::MissingEdge)?
+        .next = after;
+
+    let mut twin_split = None;
+    if let Some(twin_id) = edge.twin {
+        let twin = mesh
+            .edge(twin_id)
+            .ok_or(SplitFaceError::MissingEdge)?
+            .clone();
+        mesh.push_edge(HalfEdge {
+            origin: v,
+            twin: Some(edge_id),
+            next: twin.next,
+            prev: twin_id,
+        });
+        mesh.edge_mut(twin.next)
+            .ok_or(SplitFaceError::MissingEdge)?
+            .prev = twin_after;
+        let twin = mesh.edge_mut(twin_id).ok_or(SplitFaceError::MissingEdge)?;
+        twin.next = twin_after;
+        twin.twin = Some(after);
+        mesh.edge_mut(edge_id)
+            .ok_or(SplitFaceError::MissingEdge)?
+            .twin = Some(twin_after);
+        twin_split = Some(twin_after);
+    }
+
+    Ok(EdgeSplit {
+        vertex: v,
+        edge: after,
+        twin: twin_split,
+    })
+}
+
+/// Split the face of `face` with a pair of half edges between two of its vertices,
+/// returning them
+fn connect<T: ControlMeshMut>(
+    mesh: &mut T,
+    face: EdgeID,
+    [a, b]: [VertID; 2],
+) -> Result<[EdgeID; 2], SplitFaceError> {
+    let edge = mesh.edge(face).ok_or(SplitFaceError::MissingEdge)?;
+    let outgoing = |v: VertID| {
+        mesh.edge_loop(edge)
+            .find(|(_, e)| e.origin == v)
+            .map(|(id, e)| (e.prev, id))
+            .ok_or(SplitFaceError::MissingEdge)
+    };
+    let (a_in, a_out) = outgoing(a)?;
+    let (b_in, b_out) = outgoing(b)?;
+
+    let forward = EdgeID(mesh.edges().len());
+    let backward = EdgeID(forward.0 + 1);
+    mesh.push_edge(HalfEdge {
+        origin: a,
+        twin: Some(backward),
+        next: b_out,
+        prev: a_in,
+    });
+    mesh.push_edge(HalfEdge {
+        origin: b,
+        twin: Some(forward),
+        next: a_out,
+        prev: b_in,
+    });
+
+    for (id, next, prev) in [
+        (a_in, Some(forward), None),
+        (b_out, None, Some(forward)),
+        (b_in, Some(backward), None),
+        (a_out, None, Some(backward)),
+    ] {
+        let e = mesh.edge_mut(id).ok_or(SplitFaceError::MissingEdge)?;
+        if let Some(next) = next {
+            e.next = next;
+        }
+        if let Some(prev) = prev {
+            e.prev = prev;
+        }
+    }
+    Ok([forward, backward])
+}
+
+pub(crate) fn knot_vectors_of<T: ControlMeshMut>(mesh: &T, boundary: Boundary) -> Vec<LocalKnots> {
+    (0..mesh.points().len())
+        .map(|v| mesh.infer_local_knots(VertID(v), boundary))
+        .collect()
+}
+
+/// Move the control points of `fitted` so the surface matches the old one, given by
+/// `old_points` and `old_knots`, in a least squares sense.
+///
+/// New vertices start on the old surface and every point is weakly held at its start,
+/// which keeps the system solvable when samples do not pin a point down.
+pub(crate) fn fit_to_surface<T: ControlMeshMut>(
+    mesh: &mut T,
+    old_points: &[Vector4<T::Unit>],
+    old_knots: &[LocalKnots],
+    fitted: &[VertID],
+    boundary: Boundary,
+) -> Result<(), SplitFaceError> {
+    let cast = to_f64::<T::Unit>;
+    let uncast = from_f64::<T::Unit>;
+
+    // place new vertices on the old surface
+    for &v in fitted.iter().filter(|v| v.0 >= old_points.len()) {
+        let p = mesh.point(v).ok_or(SplitFaceError::MissingPoint)?;
+        let st = (
+            T::Unit::from_isize(p.s).ok_or(CastError)?,
+            T::Unit::from_isize(p.t).ok_or(CastError)?,
+        );
+        if let Some(limit) = subs(old_points, st, old_knots) {
+            let cp = mesh
+                .control_point_mut(v)
+                .ok_or(SplitFaceError::MissingControlPoint)?;
+            (cp.x, cp.y, cp.z) = (limit.x, limit.y, limit.z);
+        }
+    }
+
+    let knots = knot_vectors_of(mesh, boundary);
+    let mut points = Vec::with_capacity(mesh.control_points().len());
+    for cp in mesh.control_points() {
+        points.push([cast(cp.x)?, cast(cp.y)?, cast(cp.z)?, cast(cp.w)?]);
+    }
+    let column = |v: VertID| fitted.iter().position(|f| *f == v);
+
+    // sample the faces touched by the support of any fitted point
+    let mut region = (isize::MAX, isize::MIN, isize::MAX, isize::MIN);
+    for v in fitted {
+        let k = knots.get(v.0).ok_or(SplitFaceError::MissingPoint)?;
+        region = (
+            region.0.min(k.s_knots[0]),
+            region.1.max(k.s_knots[4]),
+            region.2.min(k.t_knots[0]),
+            region.3.max(k.t_knots[4]),
+        );
+    }
+
+    let n = fitted.len();
+    let mut a = vec![vec![0.; n]; n];
+    let mut rhs = vec![[0.; 3]; n];
+    for rect in mesh.layout().faces {
+        if rect.s.1 < region.0 || rect.s.0 > region.1 || rect.t.1 < region.2 || rect.t.0 > region.3
+        {
+            continue;
+        }
+        for i in 0..SAMPLES * SAMPLES {
+            let center = |range: (isize, isize), i: usize| {
+                range.0 as f64 + (range.1 - range.0) as f64 * (i as f64 + 0.5) / SAMPLES as f64
+            };
+            let st = (center(rect.s, i % SAMPLES), center(rect.t, i / SAMPLES));
+            let Some(target) = subs(old_points, (uncast(st.0)?, uncast(st.1)?), old_knots) else {
+                continue;
+            };
+            let target = [cast(target.x)?, cast(target.y)?, cast(target.z)?];
+
+            // rational basis of every control point at the sample
+            let mut basis = Vec::new();
+            let mut weight = 0.;
+            for (i, (k, p)) in knots.iter().zip(&points).enumerate() {
+                let b = cubic_basis_function(st.0, &k.s_knots)
+                    * cubic_basis_function(st.1, &k.t_knots)
+                    * p[3];
+                if b != 0. {
+                    basis.push((i, b));
+                    weight += b;
+                }
+            }
+            if weight == 0. {
+                continue;
+            }
+
+            let mut residual = target;
+            let mut row = Vec::new();
+            for (i, b) in basis {
+                let r = b / weight;
+                match column(VertID(i)) {
+                    Some(c) => row.push((c, r)),
+                    None => (0..3).for_each(|d| residual[d] -= r * points[i][d]),
+                }
+            }
+            for &(c0, r0) in &row {
+                for &(c1, r1) in &row {
+                    a[c0][c1] += r0 * r1;
+                }
+                (0..3).for_each(|d| rhs[c0][d] += r0 * residual[d]);
+            }
+        }
+    }
+
+    let diagonal = (0..n).map(|i| a[i][i]).sum::<f64>() / n.max(1) as f64;
+    let lambda = 1e-6 * diagonal.max(1e-12);
+    for (c, v) in fitted.iter().enumerate() {
+        a[c][c] += lambda;
+        (0..3).for_each(|d| rhs[c][d] += lambda * points[v.0][d]);
+    }
+
+    let Some(solution) = solve(a, rhs) else {
+        return Ok(());
+    };
+    for (v, p) in fitted.iter().zip(solution) {
+        let cp = mesh
+            .control_point_mut(*v)
+            .ok_or(SplitFaceError::MissingControlPoint)?;
+        (cp.x, cp.y, cp.z) = (uncast(p[0])?, uncast(p[1])?, uncast(p[2])?);
+    }
+    Ok(())
+}
+
+/// Gaussian elimination with partial pivoting, `None` if `a` is singular
+pub(crate) fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<[f64; 3]>) -> Option<Vec<[f64; 3]>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|x, y| a[*x][col].abs().total_cmp(&a[*y][col].abs()))?;
+        if a[pivot][col].abs() <= f64::EPSILON {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (above, below) = a.split_at_mut(col + 1);
+        let (b_above, b_below) = b.split_at_mut(col + 1);
+        let (pivot_row, pivot_b) = (&above[col], b_above[col]);
+        for (row, rhs) in below.iter_mut().zip(b_below) {
+            let factor = row[col] / pivot_row[col];
+            for (x, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= factor * p;
+            }
+            for (x, p) in rhs.iter_mut().zip(pivot_b) {
+                *x -= factor * p;
+            }
+        }
+    }
+    for col in (0..n).rev() {
+        let mut rhs = b[col];
+        for (k, x) in rhs.iter_mut().enumerate() {
+            let sum: f64 = (col + 1..n).map(|j| a[col][j] * b[j][k]).sum();
+            *x = (*x - sum) / a[col][col];
+        }
+        b[col] = rhs;
+    }
+    Some(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gallery::shapes;
+    use crate::plane::new_plane;
+    use crate::tessellate::knot_vectors;
+    use t_spline::TSpline;
+    use t_spline::Vector3;
+    use t_spline::bounds::Bounded;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::{UVMesh, UVMeshMut};
+
+    fn bump() -> TSpline {
+        shapes::<TSpline>()
+            .unwrap()
+            .into_iter()
+            .find_map(|(name, mesh)| (name == "bump").then_some(mesh))
+            .unwrap()
+    }
+
+    /// Largest distance between the surfaces on a grid over the domain of `a`
+    fn deviation(a: &TSpline, b: &TSpline) -> f64 {
+        let (ka, kb) = (
+            knot_vectors(a, Boundary::Clamped),
+            knot_vectors(b, Boundary::Clamped),
+        );
+        let bounds = a.bounds().unwrap();
+        (0..21 * 21)
+            .filter_map(|i| {
+                let st = bounds.interpolate(i, 21);
+                let pa = subs(a.control_points(), st, &ka)?;
+                let pb = subs(b.control_points(), st, &kb)?;
+                let d: Vector3<f64> = pa - pb;
+                Some(d.dot(&d).sqrt())
+            })
+            .fold(0., f64::max)
+    }
+
+    #[test]
+    fn it_splits_a_face_in_two() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        for v in 0..9 {
+            let p = mesh.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+        }
+        let original = mesh.clone();
+        let mut naive = mesh.clone();
+        insert_line(&mut naive, EdgeID(0), Direction::S, 1).unwrap();
+
+        split_face(
+            &mut mesh,
+            EdgeID(0),
+            Direction::S,
+            SplitAt::Middle,
+            Boundary::Clamped,
+        )
+        .unwrap();
+
+        mesh.validate_control_mesh().unwrap();
+        assert_eq!(5, mesh.faces().count());
+        assert_eq!(11, mesh.points().len());
+        // the new vertex shared with the face above is a T-junction
+        assert_eq!(1, mesh.t_junctions().len());
+        assert!(mesh.control_points().iter().all(|cp| cp.z.abs() < 1e-9));
+        assert!(deviation(&original, &mesh) < deviation(&original, &naive));
+    }
+
+    #[test]
+    fn it_returns_the_created_elements() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        for v in 0..9 {
+            let p = mesh.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+        }
+        let edges = mesh.edges().len();
+
+        let split = split_face(
+            &mut mesh,
+            EdgeID(0),
+            Direction::S,
+            SplitAt::Middle,
+            Boundary::Clamped,
+        )
+        .unwrap();
+
+        assert_eq!(vec![VertID(9), VertID(10)], split.vertices);
+        let mut created = split.edges.clone();
+        created.sort();
+        assert_eq!(
+            (edges..mesh.edges().len()).map(EdgeID).collect::<Vec<_>>(),
+            created
+        );
+        for face in split.faces {
+            let corners = mesh.edge_loop(mesh.edge(face).unwrap()).count();
+            assert_eq!(4, corners);
+        }
+    }
+
+    #[test]
+    fn it_follows_the_surface_rather_than_the_cage() {
+        let mut original = bump();
+        for v in 0..original.points().len() {
+            let p = original.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 2, p.t * 2);
+        }
+
+        let mut fitted = original.clone();
+        split_face(
+            &mut fitted,
+            EdgeID(16),
+            Direction::T,
+            SplitAt::Middle,
+            Boundary::Clamped,
+        )
+        .unwrap();
+
+        // the same topology with control points interpolated along the cage
+        let mut naive = original.clone();
+        insert_line(&mut naive, EdgeID(16), Direction::T, 3).unwrap();
+        naive.validate_control_mesh().unwrap();
+
+        let (fitted, naive) = (deviation(&original, &fitted), deviation(&original, &naive));
+        assert!(fitted < 0.75 * naive, "fitted {fitted}, naive {naive}");
+    }
+
+    #[test]
+    fn it_rejects_narrow_faces() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+
+        assert!(matches!(
+            split_face(
+                &mut mesh,
+                EdgeID(0),
+                Direction::S,
+                SplitAt::Middle,
+                Boundary::Clamped
+            ),
+            Err(SplitFaceError::TooNarrow)
+        ));
+    }
+
+    #[test]
+    fn it_splits_at_a_given_parameter() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        for v in 0..9 {
+            let p = mesh.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 4, p.t * 4);
+        }
+
+        let mut absolute = mesh.clone();
+        split_face(
+            &mut absolute,
+            EdgeID(0),
+            Direction::S,
+            SplitAt::Absolute(1),
+            Boundary::Clamped,
+        )
+        .unwrap();
+        assert!(absolute.points()[9..].iter().all(|p| p.s == 1));
+
+        let mut normalized = mesh.clone();
+        split_face(
+            &mut normalized,
+            EdgeID(0),
+            Direction::S,
+            SplitAt::Normalized(0.75),
+            Boundary::Clamped,
+        )
+        .unwrap();
+        assert!(normalized.points()[9..].iter().all(|p| p.s == 3));
+    }
+
+    #[test]
+    fn it_rejects_values_outside_the_face() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        for v in 0..9 {
+            let p = mesh.point_mut(VertID(v)).unwrap();
+            (p.s, p.t) = (p.s * 4, p.t * 4);
+        }
+
+        for at in [
+            SplitAt::Absolute(0),
+            SplitAt::Absolute(4),
+            SplitAt::Absolute(6),
+            SplitAt::Normalized(1.),
+            SplitAt::Normalized(0.05),
+        ] {
+            assert!(matches!(
+                split_face(&mut mesh, EdgeID(0), Direction::S, at, Boundary::Clamped),
+                Err(SplitFaceError::OutsideFace(_))
+            ));
+        }
+        assert_eq!(9, mesh.points().len());
+    }
+}
@@ -0,0 +1,43 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+pub use t_spline::shapes::t_junction;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tessellate::tessellate;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::{Boundary, UVMesh};
+
+    #[test]
+    fn it_creates_a_t_junction() {
+        let mesh: TSpline = t_junction();
+
+        mesh.validate_control_mesh().unwrap();
+        assert_eq!(3, mesh.faces().count());
+        assert_eq!(1, mesh.t_junctions().len());
+    }
+
+    #[test]
+    fn it_tessellates_a_t_junction() {
+        let mesh: TSpline = t_junction();
+
+        let points = tessellate(&mesh, 4, Boundary::Clamped).unwrap();
+        assert_eq!(16, points.len());
+    }
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{from_f64, to_f64};
+use crate::cuboid::cuboid_grid;
+use crate::plane::{PlaneError, new_plane};
+use num_traits::One;
+use std::f64::consts::TAU;
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::ids::VertID;
+
+/// A sphere of `radius` around the origin from the six sides of a cube with `points` x
+/// `points` control points each, pushed out onto the sphere.
+///
+/// A T-mesh has no star points of its own, so the patches share the control points of
+/// their boundaries and the eight cube corners act as three-valent star points.
+pub fn quad_sphere<T: ControlMeshMut + Default>(
+    radius: f64,
+    points: usize,
+) -> Result<Vec<T>, PlaneError> {
+    let two = from_f64::<T::Unit>(2.)?;
+    let mut patches: Vec<T> = cuboid_grid([two; 3], points)?;
+    for patch in &mut patches {
+        for v in 0..patch.control_points().len() {
+            let cp = patch
+                .control_point_mut(VertID(v))
+                .ok_or(PlaneError::NotAGrid)?;
+            let (x, y, z) = (to_f64(cp.x)? - 1., to_f64(cp.y)? - 1., to_f64(cp.z)? - 1.);
+            let scale = radius / (x * x + y * y + z * z).sqrt();
+            (cp.x, cp.y, cp.z) = (
+                from_f64(x * scale)?,
+                from_f64(y * scale)?,
+                from_f64(z * scale)?,
+            );
+        }
+    }
+    Ok(patches)
+}
+
+/// A flat disk of `radius` facing `+z` with its pole at the origin.
+///
+/// `s` runs around the disk with `segments` faces and a seam of coinciding control
+/// points, `t` runs out from the pole, where the first row of control points meets,
+/// over `rings` faces.
+pub fn disk<T: ControlMeshMut + Default>(
+    radius: f64,
+    segments: usize,
+    rings: usize,
+) -> Result<T, PlaneError> {
+    polar(radius, segments, rings, 0., true)
+}
+
+/// An open tube of `radius` along `z` from `0` to `height` with `segments` faces
+/// around and `rings` along it, closed in `s` by a seam and facing outwards
+pub fn tube<T: ControlMeshMut + Default>(
+    radius: f64,
+    height: f64,
+    segments: usize,
+    rings: usize,
+) -> Result<T, PlaneError> {
+    let mut mesh: T = new_plane(segments + 1, rings + 1, T::Unit::one(), T::Unit::one())?;
+    for j in 0..=rings {
+        for i in 0..=segments {
+            let angle = TAU * (i % segments) as f64 / segments as f64;
+            let z = height * j as f64 / rings as f64;
+            set(
+                &mut mesh,
+                j * (segments + 1) + i,
+                [radius * angle.cos(), radius * angle.sin(), z],
+            )?;
+        }
+    }
+    Ok(mesh)
+}
+
+/// A closed cylinder: a [tube] with a polar disk on either end, ordered tube, bottom,
+/// top. The rims of the disks use the control points of the ends of the tube, so
+/// they meet it exactly.
+pub fn capped_cylinder<T: ControlMeshMut + Default>(
+    radius: f64,
+    height: f64,
+    segments: usize,
+) -> Result<Vec<T>, PlaneError> {
+    let rings = segments.div_ceil(4).max(1);
+    let tube: T = tube(radius, height, segments, 2 * rings)?;
+    let bottom: T = polar(radius, segments, rings, 0., false)?;
+    let top: T = polar(radius, segments, rings, height, true)?;
+    Ok(vec![tube, bottom, top])
+}
+
+/// A polar grid at height `z`, running around clockwise facing `+z` or counter-clockwise
+/// facing `-z`
+fn polar<T: ControlMeshMut + Default>(
+    radius: f64,
+    segments: usize,
+    rings: usize,
+    z: f64,
+    clockwise: bool,
+) -> Result<T, PlaneError> {
+    if segments < 3 || rings < 1 {
+        return Err(PlaneError::TooFewPoints);
+    }
+    let mut mesh: T = new_plane(segments + 1, rings + 1, T::Unit::one(), T::Unit::one())?;
+    for j in 0..=rings {
+        let r = radius * j as f64 / rings as f64;
+        for i in 0..=segments {
+            let step = if clockwise { segments - i } else { i };
+            let angle = TAU * (step % segments) as f64 / segments as f64;
+            set(
+                &mut mesh,
+                j * (segments + 1) + i,
+                [r * angle.cos(), r * angle.sin(), z],
+            )?;
+        }
+    }
+    Ok(mesh)
+}
+
+fn set<T: ControlMeshMut>(mesh: &mut T, v: usize, [x, y, z]: [f64; 3]) -> Result<(), PlaneError> {
+    let cp = mesh
+        .control_point_mut(VertID(v))
+        .ok_or(PlaneError::NotAGrid)?;
+    (cp.x, cp.y, cp.z) = (from_f64(x)?, from_f64(y)?, from_f64(z)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::displace::vertex_normals;
+    use crate::plane::GridSide;
+    use crate::ruled::BoundaryCurve;
+    use crate::tessellate::tessellate;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::Boundary;
+
+    #[test]
+    fn it_builds_a_quad_sphere() {
+        let patches: Vec<TSpline> = quad_sphere(2., 4).unwrap();
+
+        assert_eq!(6, patches.len());
+        for patch in &patches {
+            patch.validate_control_mesh().unwrap();
+            for p in tessellate(patch, 8, Boundary::Clamped).unwrap() {
+                let r = p.coords.dot(&p.coords).sqrt();
+                assert!(r > 1.8 && r <= 2. + 1e-9, "{p:?}");
+            }
+            let normals = vertex_normals(patch, Boundary::Clamped).unwrap();
+            for (cp, n) in patch.control_points().iter().zip(normals) {
+                assert!(cp.xyz().dot(&n) > 0., "{cp:?} {n:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn it_closes_a_cylinder_with_polar_disks() {
+        let patches: Vec<TSpline> = capped_cylinder(1., 3., 8).unwrap();
+
+        assert_eq!(3, patches.len());
+        for (cap, side, z, facing) in [
+            (&patches[1], GridSide::TMin, 0., -1.),
+            (&patches[2], GridSide::TMax, 3., 1.),
+        ] {
+            cap.validate_control_mesh().unwrap();
+            let rim = BoundaryCurve::of(cap, GridSide::TMax).unwrap();
+            let end = BoundaryCurve::of(&patches[0], side).unwrap();
+            assert!(rim == end || rim == end.reversed(), "{rim:?} {end:?}");
+
+            for p in tessellate(cap, 8, Boundary::Clamped).unwrap() {
+                assert!((z - p.z).abs() < 1e-9, "{p:?}");
+                assert!(p.x * p.x + p.y * p.y <= 1. + 1e-9, "{p:?}");
+            }
+            let normals = vertex_normals(cap, Boundary::Clamped).unwrap();
+            assert!(normals.iter().all(|n| n.z * facing > 0.99), "{normals:?}");
+        }
+    }
+
+    #[test]
+    fn it_puts_the_pole_in_the_middle() {
+        let disk: TSpline = disk(1., 6, 2).unwrap();
+
+        for v in 0..7 {
+            assert_eq!(0., disk.control_points()[v].xyz().abs().max());
+        }
+    }
+}
@@ -14,44 +14,311 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
+use crate::cast::CastError;
+use crate::triangles::{TriangulateError, face_grid};
+use num_traits::FromPrimitive;
 use rayon::prelude::*;
-use t_spline::Point3;
-use t_spline::algorithms::subs;
+use std::collections::BTreeSet;
+use t_spline::algorithms::{EvalError, EvalPolicy, try_subs, try_subs_derivatives};
 use t_spline::bounds::Bounded;
 use t_spline::control_mesh::ControlMesh;
 use t_spline::uv_mesh::ids::VertID;
-use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use t_spline::uv_mesh::{Boundary, KnotVector, LocalKnots, ValidationError};
+use t_spline::{Numeric, Point3, Vector3};
+
+/// Why a sample of [tessellate_with_report] produced no point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropReason {
+    /// The parameter lies in a hole of the mesh, outside of every face
+    OutsideMesh,
+    /// The parameter lies inside a face but no basis function has support there
+    EmptySupport,
+    /// The rational weights of the supporting basis functions sum to zero or less
+    ZeroWeight,
+    /// A control point is not a number
+    NotANumber,
+    /// The point overflowed the number type of the mesh
+    Overflow,
+}
+
+/// Where the samples of [tessellate_with_sampling] are placed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Sampling {
+    /// A `resolution` x `resolution` grid over the bounds of the mesh, the samples
+    /// outside of the faces are dropped
+    #[default]
+    BoundingBox,
+    /// A `resolution` x `resolution` grid of cells on every face like [tessellate_faces],
+    /// so a domain that is not a rectangle costs nothing for the void of its bounding box
+    Faces,
+}
+
+/// A sample of [tessellate_with_report] that produced no point
+#[derive(Debug, Clone, PartialEq)]
+pub struct DroppedSample<T> {
+    /// Index of the sample among the evaluated ones, for [Sampling::BoundingBox] its
+    /// index in the grid
+    pub index: usize,
+    /// The parameter of the sample
+    pub st: (T, T),
+    pub reason: DropReason,
+    /// Vertices whose knot support contains `st`, the basis functions that were checked.
+    /// For [DropReason::EmptySupport] every one of them is zero at `st`.
+    pub supports: Vec<VertID>,
+}
+
+/// The points of a tessellation together with the samples that produced none
+#[derive(Debug, Clone, PartialEq)]
+pub struct TessellationReport<T: Numeric + 'static> {
+    pub points: Vec<Point3<T>>,
+    pub samples: usize,
+    pub dropped: Vec<DroppedSample<T>>,
+}
+
+impl<T: Numeric + 'static> TessellationReport<T> {
+    pub fn is_complete(&self) -> bool {
+        self.dropped.is_empty()
+    }
+
+    /// Number of dropped samples with `reason`
+    pub fn count(&self, reason: DropReason) -> usize {
+        self.dropped.iter().filter(|d| d.reason == reason).count()
+    }
+}
 
 pub fn tessellate<T: ControlMesh + Sync>(
     mesh: &T,
     resolution: usize,
     boundary: Boundary,
 ) -> Result<Vec<Point3<T::Unit>>, ValidationError> {
+    tessellate_with_report(mesh, resolution, boundary).map(|report| report.points)
+}
+
+/// Like [tessellate], but also report every sample that was dropped and why.
+///
+/// Samples in holes of the mesh are expected to be dropped, any other reason points at
+/// knot vectors or weights that leave part of a face without a surface.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), resolution = resolution))
+)]
+pub fn tessellate_with_report<T: ControlMesh + Sync>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<TessellationReport<T::Unit>, ValidationError> {
     mesh.validate_control_mesh()?;
 
-    let bounds = mesh.bounds();
+    let bounds = mesh.bounds().ok_or(ValidationError::EmptyDomain())?;
+    let samples = (0..resolution * resolution)
+        .map(|i| bounds.interpolate(i, resolution))
+        .collect();
+    Ok(evaluate_samples(
+        mesh,
+        boundary,
+        samples,
+        Sampling::BoundingBox,
+    ))
+}
 
+/// Like [tessellate_with_report], evaluating the samples placed by `sampling`
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), resolution = resolution))
+)]
+pub fn tessellate_with_sampling<T: ControlMesh + Sync>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+    sampling: Sampling,
+) -> Result<TessellationReport<T::Unit>, TriangulateError> {
+    match sampling {
+        Sampling::BoundingBox => Ok(tessellate_with_report(mesh, resolution, boundary)?),
+        Sampling::Faces => {
+            mesh.validate_control_mesh()?;
+            let samples = face_samples(mesh, resolution)?;
+            Ok(evaluate_samples(mesh, boundary, samples, sampling))
+        }
+    }
+}
+
+/// Evaluate every sample in order, dropping the ones outside of the faces when they
+/// were placed over the bounding box
+fn evaluate_samples<T: ControlMesh + Sync>(
+    mesh: &T,
+    boundary: Boundary,
+    samples: Vec<(T::Unit, T::Unit)>,
+    sampling: Sampling,
+) -> TessellationReport<T::Unit> {
     let knot_cache: Vec<_> = knot_vectors(mesh, boundary);
 
-    Ok((0..resolution * resolution)
+    let evaluated: Vec<_> = samples
+        .par_iter()
+        .map(|&st| {
+            if sampling == Sampling::BoundingBox && !mesh.contains_uv(st) {
+                return Err(DropReason::OutsideMesh);
+            }
+            try_subs(mesh.control_points(), st, &knot_cache, EvalPolicy::Strict).map_err(
+                |e| match e {
+                    EvalError::OutOfDomain => DropReason::EmptySupport,
+                    EvalError::ZeroWeight => DropReason::ZeroWeight,
+                    EvalError::NotANumber => DropReason::NotANumber,
+                    EvalError::Overflow => DropReason::Overflow,
+                },
+            )
+        })
+        .collect();
+
+    let mut report = TessellationReport {
+        points: Vec::with_capacity(samples.len()),
+        samples: samples.len(),
+        dropped: Vec::new(),
+    };
+    for (index, (st, sample)) in samples.into_iter().zip(evaluated).enumerate() {
+        match sample {
+            Ok(point) => report.points.push(point),
+            Err(reason) => report.dropped.push(DroppedSample {
+                index,
+                st,
+                reason,
+                supports: supporting(&knot_cache, st),
+            }),
+        }
+    }
+    report
+}
+
+/// Vertices whose knot support, including its border, contains `st`
+fn supporting<U: Numeric>(knot_cache: &[LocalKnots], st: (U, U)) -> Vec<VertID> {
+    let inside = |u: U, knots: &KnotVector| match (U::from_isize(knots[0]), U::from_isize(knots[4]))
+    {
+        (Some(lo), Some(hi)) => lo <= u && u <= hi,
+        _ => false,
+    };
+    knot_cache
+        .iter()
+        .enumerate()
+        .filter(|(_, k)| inside(st.0, &k.s_knots) && inside(st.1, &k.t_knots))
+        .map(|(v, _)| VertID(v))
+        .collect()
+}
+
+/// The samples of a `resolution` x `resolution` grid of cells on every face, the ones
+/// shared by neighbouring faces only once
+fn face_samples<U: Numeric>(
+    mesh: &impl ControlMesh<Unit = U>,
+    resolution: usize,
+) -> Result<Vec<(U, U)>, TriangulateError> {
+    let mut seen = BTreeSet::new();
+    let mut samples = Vec::new();
+    for rect in mesh.layout().faces {
+        let grid = face_grid::<U>(rect, resolution)?;
+        for st in grid {
+            let key = match (st.0.to_f64(), st.1.to_f64()) {
+                (Some(s), Some(t)) => (s.to_bits(), t.to_bits()),
+                _ => return Err(CastError.into()),
+            };
+            if seen.insert(key) {
+                samples.push(st);
+            }
+        }
+    }
+    Ok(samples)
+}
+
+/// Tessellate every face of `mesh` on its own `resolution` x `resolution` grid of cells.
+///
+/// Unlike [tessellate] no samples are spent on the parts of the bounding box outside of
+/// the faces, so parametric domains that are not rectangles, such as unfolded closed
+/// shapes, evaluate without gaps or dropped samples. Samples shared by neighbouring
+/// faces are stitched into one point.
+pub fn tessellate_faces<T: ControlMesh + Sync>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<Vec<Point3<T::Unit>>, TriangulateError> {
+    tessellate_with_sampling(mesh, resolution, boundary, Sampling::Faces).map(|r| r.points)
+}
+
+/// A point of the surface with its unit normal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceSample<T: Numeric + 'static> {
+    pub point: Point3<T>,
+    /// Unit cross product of the derivatives along `s` and `t`, zero where the surface is
+    /// degenerate
+    pub normal: Vector3<T>,
+}
+
+/// Like [tessellate], but also evaluate the normal of the surface at every sample so
+/// the points can be shaded smoothly.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len(), resolution = resolution))
+)]
+pub fn tessellate_with_normals<T: ControlMesh + Sync>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<Vec<SurfaceSample<T::Unit>>, ValidationError> {
+    mesh.validate_control_mesh()?;
+
+    let bounds = mesh.bounds().ok_or(ValidationError::EmptyDomain())?;
+    let knot_cache = knot_vectors(mesh, boundary);
+
+    let samples: Vec<_> = (0..resolution * resolution)
         .into_par_iter()
-        .map(|i| {
-            let p = bounds.interpolate(i, resolution);
-            if mesh.contains_uv((p.0, p.1)) {
-                subs(
-                    mesh.control_points(),
-                    bounds.interpolate(i, resolution),
-                    &knot_cache,
-                )
-            } else {
-                None
+        .filter_map(|i| {
+            let st = bounds.interpolate(i, resolution);
+            if !mesh.contains_uv(st) {
+                return None;
+            }
+            let derivatives = |st| {
+                try_subs_derivatives(mesh.control_points(), st, &knot_cache, EvalPolicy::Strict)
+            };
+            let d = derivatives(st).ok()?;
+            let mut normal = unit(d.ds.cross(&d.dt));
+            // clamped knots flatten the derivative across the boundary, so take the
+            // normal from just inside the domain there
+            if normal == Vector3::zeros()
+                && let Some(f) = T::Unit::from_f64(1e-6)
+            {
+                let (cs, ct) = bounds.center();
+                let inside = (st.0 + (cs - st.0) * f, st.1 + (ct - st.1) * f);
+                if let Ok(d) = derivatives(inside) {
+                    normal = unit(d.ds.cross(&d.dt));
+                }
             }
+            Some(SurfaceSample {
+                point: d.point,
+                normal,
+            })
         })
-        .filter_map(|p| p)
-        .collect())
+        .collect();
+    Ok(samples)
+}
+
+/// `v` scaled to unit length, or zero if it has none
+fn unit<T: Numeric + 'static>(v: Vector3<T>) -> Vector3<T> {
+    let length = v
+        .iter()
+        .map(|c| c.to_f64().unwrap_or(0.))
+        .map(|c| c * c)
+        .sum::<f64>()
+        .sqrt();
+    match T::from_f64(length) {
+        Some(length) if length > T::zero() => v / length,
+        _ => Vector3::zeros(),
+    }
 }
 
-fn knot_vectors(mesh: &(impl ControlMesh + Sync), boundary: Boundary) -> Vec<LocalKnots> {
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(points = mesh.points().len()))
+)]
+pub(crate) fn knot_vectors(
+    mesh: &(impl ControlMesh + Sync),
+    boundary: Boundary,
+) -> Vec<LocalKnots> {
     (0..mesh.points().len())
         .into_par_iter()
         .map(|v| mesh.infer_local_knots(VertID(v), boundary))
@@ -61,7 +328,14 @@ fn knot_vectors(mesh: &(impl ControlMesh + Sync), boundary: Boundary) -> Vec<Loc
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::command::Command;
+    use crate::extrude_edge::ExtrudeEdge;
+    use crate::plane::new_plane;
     use crate::unit_square::unit_square;
+    use t_spline::algorithms::subs;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::UVMesh;
+    use t_spline::uv_mesh::ids::EdgeID;
     use t_spline::{Point3, TSpline};
 
     #[test]
@@ -113,4 +387,89 @@ mod tests {
         assert_eq!(0., diff.y);
         assert_eq!(0., diff.z);
     }
+
+    #[test]
+    pub fn it_reports_a_complete_tessellation() {
+        let square: TSpline = unit_square();
+        let report = tessellate_with_report(&square, 3, Boundary::Clamped).unwrap();
+
+        assert_eq!(9, report.points.len());
+        assert_eq!(9, report.samples);
+        assert!(report.is_complete());
+    }
+
+    #[test]
+    pub fn it_reports_zero_weights() {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        // the corner is interpolated, so only its own weight contributes there
+        mesh.control_point_mut(VertID(0)).unwrap().w = 0.;
+        let report = tessellate_with_report(&mesh, 4, Boundary::Clamped).unwrap();
+
+        assert_eq!(report.samples, report.points.len() + report.dropped.len());
+        assert_eq!(report.dropped.len(), report.count(DropReason::ZeroWeight));
+        let first = report.dropped.first().unwrap();
+        assert_eq!((0., 0.), first.st);
+        assert!(first.supports.contains(&VertID(0)), "{:?}", first.supports);
+        // the supports of the corner reach two knot lines into the mesh
+        assert!(first.supports.iter().all(|v| v.0 % 4 < 3 && v.0 / 4 < 3));
+    }
+
+    #[test]
+    pub fn it_rejects_meshes_without_a_domain() {
+        let mesh: TSpline = TSpline::default();
+        assert!(matches!(
+            tessellate(&mesh, 4, Boundary::Clamped),
+            Err(ValidationError::EmptyDomain())
+        ));
+    }
+
+    #[test]
+    pub fn it_tessellates_faces_of_non_rectangular_domains() {
+        let mut mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        ExtrudeEdge(EdgeID(0)).apply_mut(&mut mesh).unwrap();
+        let faces = mesh.layout().faces.len();
+
+        let bounded = tessellate_with_report(&mesh, 9, Boundary::Clamped).unwrap();
+        let report =
+            tessellate_with_sampling(&mesh, 2, Boundary::Clamped, Sampling::Faces).unwrap();
+        let points = tessellate_faces(&mesh, 2, Boundary::Clamped).unwrap();
+
+        assert!(bounded.count(DropReason::OutsideMesh) > 0);
+        assert!(report.is_complete(), "{:?}", report.dropped);
+        assert_eq!(report.points, points);
+        // a 3 x 3 grid per face, with the sides of neighbouring faces stitched
+        assert!(
+            points.len() > 9 && points.len() < faces * 9,
+            "{}",
+            points.len()
+        );
+        for (i, a) in points.iter().enumerate() {
+            assert!(points[i + 1..].iter().all(|b| a != b), "{a:?}");
+        }
+
+        let grid = tessellate_with_sampling(&mesh, 9, Boundary::Clamped, Sampling::BoundingBox);
+        assert_eq!(bounded, grid.unwrap());
+    }
+
+    #[test]
+    pub fn it_tessellates_with_normals() {
+        let mut mesh: TSpline = new_plane(5, 5, 4., 4.).unwrap();
+        mesh.control_point_mut(VertID(12)).unwrap().z = 1.;
+        let points = tessellate(&mesh, 6, Boundary::Clamped).unwrap();
+        let samples = tessellate_with_normals(&mesh, 6, Boundary::Clamped).unwrap();
+
+        assert_eq!(points, samples.iter().map(|s| s.point).collect::<Vec<_>>());
+        for sample in &samples {
+            assert!(
+                (sample.normal.dot(&sample.normal) - 1.).abs() < 1e-9,
+                "{sample:?}"
+            );
+            assert!(sample.normal.z > 0., "{sample:?}");
+        }
+        // the bump leans the normals away from its peak
+        let lower_left = samples
+            .iter()
+            .find(|s| s.point.x < 1.5 && s.point.y < 1.5 && s.point.z > 0.01);
+        assert!(lower_left.is_some_and(|s| s.normal.x < 0. && s.normal.y < 0.));
+    }
 }
@@ -0,0 +1,130 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::cast::{CastError, to_f64};
+use crate::frame_field::{FrameError, frame_at};
+use crate::tessellate::knot_vectors;
+use num_traits::FromPrimitive;
+use rayon::prelude::*;
+use t_spline::bounds::Bounded;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+
+/// Floats per texel: position, normal and a tangent with its handedness in `w`
+pub const TEXEL_STRIDE: usize = 10;
+
+/// Surface attributes sampled on a texel grid over the parametric domain, interleaved
+/// for upload to a GPU.
+///
+/// Every texel holds `[px, py, pz, nx, ny, nz, tx, ty, tz, tw]` as `f32`. The tangent
+/// follows `s` and its `w` is `1`, texels the surface does not cover are all zero, so a
+/// shader can tell them apart by a `w` of `0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TexelBuffer {
+    pub width: usize,
+    pub height: usize,
+    /// Rows of texels from the lowest `t` up, each from the lowest `s`
+    pub data: Vec<f32>,
+}
+
+impl TexelBuffer {
+    /// The attributes of the texel in column `x` and row `y`
+    pub fn texel(&self, x: usize, y: usize) -> Option<&[f32]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let start = (y * self.width + x) * TEXEL_STRIDE;
+        self.data.get(start..start + TEXEL_STRIDE)
+    }
+
+    /// The data as little endian bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+}
+
+/// Sample `mesh` at the centers of a `width` x `height` texel grid stretched over the
+/// bounds of its parametric domain
+pub fn sample_texels<T: ControlMesh + Sync>(
+    mesh: &T,
+    width: usize,
+    height: usize,
+    boundary: Boundary,
+) -> Result<TexelBuffer, FrameError> {
+    mesh.validate_control_mesh()?;
+
+    let bounds = mesh.bounds().ok_or(ValidationError::EmptyDomain())?;
+    let knot_cache = knot_vectors(mesh, boundary);
+    let (s, t) = (
+        (to_f64(bounds.s.0)?, to_f64(bounds.s.1)?),
+        (to_f64(bounds.t.0)?, to_f64(bounds.t.1)?),
+    );
+
+    let texels: Vec<[f32; TEXEL_STRIDE]> = (0..width * height)
+        .into_par_iter()
+        .map(|i| {
+            let at = |(lo, hi): (f64, f64), i: usize, n: usize| {
+                T::Unit::from_f64(lo + (hi - lo) * (i as f64 + 0.5) / n as f64).ok_or(CastError)
+            };
+            let st = (at(s, i % width, width)?, at(t, i / width, height)?);
+            if !mesh.contains_uv(st) {
+                return Ok([0.; TEXEL_STRIDE]);
+            }
+            let Some(frame) = frame_at(mesh, &knot_cache, st)? else {
+                return Ok([0.; TEXEL_STRIDE]);
+            };
+
+            let (p, n, u) = (frame.point, frame.normal, frame.du);
+            Ok([p.x, p.y, p.z, n.x, n.y, n.z, u.x, u.y, u.z, 1.].map(|v| v as f32))
+        })
+        .collect::<Result<_, FrameError>>()?;
+
+    Ok(TexelBuffer {
+        width,
+        height,
+        data: texels.into_iter().flatten().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+
+    #[test]
+    fn it_packs_texels_of_a_plane() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+
+        let texels = sample_texels(&mesh, 4, 2, Boundary::Clamped).unwrap();
+
+        assert_eq!(4 * 2 * TEXEL_STRIDE, texels.data.len());
+        assert_eq!(4 * texels.data.len(), texels.to_bytes().len());
+        for y in 0..2 {
+            for x in 0..4 {
+                let texel = texels.texel(x, y).unwrap();
+                assert_eq!([0., 0., 1.], texel[3..6]);
+                assert_eq!([1., 0., 0., 1.], texel[6..10]);
+            }
+        }
+        // texel centers run from the lowest parameter up
+        let (first, last) = (texels.texel(0, 0).unwrap(), texels.texel(3, 1).unwrap());
+        assert!(first[0] < 1.5 && first[1] < 1.5);
+        assert!(last[0] > 1.5 && last[1] > 1.5);
+        assert_eq!(None, texels.texel(4, 0));
+    }
+}
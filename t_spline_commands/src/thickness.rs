@@ -0,0 +1,150 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::CastError;
+use crate::draft::cell_center;
+use crate::frame_field::{FrameError, frame_at};
+use crate::tessellate::knot_vectors;
+use crate::triangles::{intersect, triangulate};
+use rayon::prelude::*;
+use t_spline::Point3;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::ids::EdgeID;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum ThicknessError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("patch {0} is invalid: {1}")]
+    Invalid(usize, ValidationError),
+}
+
+/// A surface sample whose wall is thinner than the requested threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThicknessSample {
+    /// Index of the patch in the model
+    pub patch: usize,
+    pub face: EdgeID,
+    pub st: (f64, f64),
+    pub point: Point3<f64>,
+    /// Distance to the opposite wall along the inward normal
+    pub thickness: f64,
+}
+
+/// Find the thin regions of a shelled model made of `patches`.
+///
+/// Every face is sampled on a `resolution` x `resolution` grid of cell centers and a ray is
+/// cast from each sample against the normal, which must point out of the material. The
+/// distance to the first wall hit is the thickness at that sample, walls are approximated
+/// by a tessellation at the same resolution. Samples thinner than `threshold` are returned.
+pub fn wall_thickness<T: ControlMesh + Sync>(
+    patches: &[T],
+    threshold: f64,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<Vec<ThicknessSample>, ThicknessError> {
+    let mut caches = Vec::with_capacity(patches.len());
+    let mut triangles = Vec::new();
+    for (patch, mesh) in patches.iter().enumerate() {
+        mesh.validate_control_mesh()
+            .map_err(|e| ThicknessError::Invalid(patch, e))?;
+        let knot_cache = knot_vectors(mesh, boundary);
+        for rect in mesh.layout().faces {
+            triangulate(mesh, &knot_cache, patch, rect, resolution, &mut triangles)?;
+        }
+        caches.push(knot_cache);
+    }
+
+    let mut samples = Vec::new();
+    for (patch, (mesh, knot_cache)) in patches.iter().zip(&caches).enumerate() {
+        for rect in mesh.layout().faces {
+            let thin: Vec<Option<ThicknessSample>> = (0..resolution * resolution)
+                .into_par_iter()
+                .map(|i| {
+                    let st = (
+                        cell_center::<T>(rect.s, i % resolution, resolution)?,
+                        cell_center::<T>(rect.t, i / resolution, resolution)?,
+                    );
+                    let Some(frame) = frame_at(mesh, knot_cache, st).map_err(|e| match e {
+                        FrameError::Invalid(e) => ThicknessError::Invalid(patch, e),
+                        FrameError::Cast(e) => ThicknessError::Cast(e),
+                    })?
+                    else {
+                        return Ok(None);
+                    };
+
+                    let thickness = triangles
+                        .iter()
+                        .filter(|t| t.patch != patch || t.face != rect.face)
+                        .filter_map(|t| intersect(frame.point, -frame.normal, &t.corners))
+                        .fold(f64::INFINITY, f64::min);
+
+                    Ok((thickness < threshold).then_some(ThicknessSample {
+                        patch,
+                        face: rect.face,
+                        st: frame.st,
+                        point: frame.point,
+                        thickness,
+                    }))
+                })
+                .collect::<Result<_, ThicknessError>>()?;
+            samples.extend(thin.into_iter().flatten());
+        }
+    }
+
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    /// A flat slab of `thickness` with both walls facing outwards
+    fn slab(thickness: f64) -> Vec<TSpline> {
+        let mut top: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let mut bottom: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        for v in 0..16 {
+            top.control_point_mut(VertID(v)).unwrap().z = thickness;
+            // mirroring flips the normal downwards
+            let cp = bottom.control_point_mut(VertID(v)).unwrap();
+            cp.x = 3. - cp.x;
+        }
+        vec![top, bottom]
+    }
+
+    #[test]
+    fn it_measures_a_thin_slab() {
+        let samples = wall_thickness(&slab(0.5), 1., 2, Boundary::Clamped).unwrap();
+
+        assert_eq!(2 * 9 * 4, samples.len());
+        for sample in samples {
+            assert!((sample.thickness - 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn it_ignores_thick_walls() {
+        let samples = wall_thickness(&slab(2.), 1., 2, Boundary::Clamped).unwrap();
+
+        assert!(samples.is_empty());
+    }
+}
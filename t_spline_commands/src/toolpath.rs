@@ -0,0 +1,181 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::CastError;
+use crate::frame_field::{FrameError, frame_at};
+use crate::tessellate::knot_vectors;
+use num_traits::FromPrimitive;
+use rayon::prelude::*;
+use t_spline::bounds::Bounded;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::direction::Direction;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum ToolpathError {
+    #[error("a toolpath needs at least 2 passes of 2 samples")]
+    TooFewSamples,
+    #[error("tool radius must not be negative")]
+    NegativeRadius,
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+impl From<FrameError> for ToolpathError {
+    fn from(value: FrameError) -> Self {
+        match value {
+            FrameError::Cast(e) => ToolpathError::Cast(e),
+            FrameError::Invalid(e) => ToolpathError::Invalid(e),
+        }
+    }
+}
+
+/// Iso-parametric 3-axis milling passes for a ball-nose tool of `tool_radius`.
+///
+/// Each pass follows `direction` with `samples` points, the passes are spread evenly over
+/// the other direction and alternate their heading to form a zigzag. The tool center is
+/// offset along the upward facing surface normal and the returned points are the tool tip,
+/// one tool radius below the center. Samples outside of the mesh or where the normal
+/// degenerates are left out of their pass.
+pub fn iso_parametric_toolpath<T: ControlMesh + Sync>(
+    mesh: &T,
+    direction: Direction,
+    passes: usize,
+    samples: usize,
+    tool_radius: f64,
+    boundary: Boundary,
+) -> Result<Vec<Vec<Point3<f64>>>, ToolpathError> {
+    if passes < 2 || samples < 2 {
+        return Err(ToolpathError::TooFewSamples);
+    }
+    if tool_radius < 0. {
+        return Err(ToolpathError::NegativeRadius);
+    }
+    mesh.validate_control_mesh()?;
+
+    let bounds = mesh.bounds().ok_or(ValidationError::EmptyDomain())?;
+    let knot_cache = knot_vectors(mesh, boundary);
+    let (along, across) = match direction {
+        Direction::S => (bounds.s, bounds.t),
+        Direction::T => (bounds.t, bounds.s),
+    };
+
+    (0..passes)
+        .into_par_iter()
+        .map(|pass| {
+            let v = lerp::<T>(across, pass, passes)?;
+
+            let mut path = Vec::with_capacity(samples);
+            for sample in 0..samples {
+                let u = lerp::<T>(along, sample, samples)?;
+                let st = match direction {
+                    Direction::S => (u, v),
+                    Direction::T => (v, u),
+                };
+                if !mesh.contains_uv(st) {
+                    continue;
+                }
+
+                if let Some(frame) = frame_at(mesh, &knot_cache, st)? {
+                    let normal = if frame.normal.z < 0. {
+                        -frame.normal
+                    } else {
+                        frame.normal
+                    };
+                    let center = frame.point + normal * tool_radius;
+                    path.push(center - Vector3::z() * tool_radius);
+                }
+            }
+
+            if pass % 2 == 1 {
+                path.reverse();
+            }
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Sample `i` of `n` evenly spaced samples over `range`
+fn lerp<T: ControlMesh>(
+    range: (T::Unit, T::Unit),
+    i: usize,
+    n: usize,
+) -> Result<T::Unit, ToolpathError> {
+    let i = T::Unit::from_usize(i).ok_or(CastError)?;
+    let n = T::Unit::from_usize(n - 1).ok_or(CastError)?;
+    Ok(range.0 + (range.1 - range.0) * i / n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::ids::VertID;
+
+    #[test]
+    fn it_follows_a_flat_plane_in_a_zigzag() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let paths =
+            iso_parametric_toolpath(&mesh, Direction::S, 4, 6, 0.5, Boundary::Clamped).unwrap();
+
+        assert_eq!(4, paths.len());
+        for path in &paths {
+            assert!(path.iter().all(|p| p.z.abs() < 1e-9));
+        }
+
+        // the interior passes alternate their heading
+        assert!(paths[1].first().unwrap().x > paths[1].last().unwrap().x);
+        assert!(paths[2].first().unwrap().x < paths[2].last().unwrap().x);
+    }
+
+    #[test]
+    fn it_offsets_the_tool_along_the_normal() {
+        let mut mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        for v in [5, 6, 9, 10] {
+            mesh.control_point_mut(VertID(v)).unwrap().z = 1.;
+        }
+
+        let sharp = iso_parametric_toolpath(&mesh, Direction::T, 5, 9, 0., Boundary::Clamped);
+        let round = iso_parametric_toolpath(&mesh, Direction::T, 5, 9, 1., Boundary::Clamped);
+
+        let (sharp, round) = (sharp.unwrap().concat(), round.unwrap().concat());
+        assert!(!sharp.is_empty());
+        assert_eq!(sharp.len(), round.len());
+        for (touch, tip) in sharp.iter().zip(round) {
+            // the ball center is one radius away from the surface point it touches
+            let center = tip + Vector3::z();
+            let offset = center - touch;
+            assert!((offset.dot(&offset) - 1.).abs() < 1e-9);
+            assert!(center.z > touch.z);
+        }
+    }
+
+    #[test]
+    fn it_requires_samples() {
+        let mesh: TSpline = new_plane(2, 2, 1., 1.).unwrap();
+
+        assert!(matches!(
+            iso_parametric_toolpath(&mesh, Direction::S, 1, 4, 0., Boundary::Clamped),
+            Err(ToolpathError::TooFewSamples)
+        ));
+    }
+}
@@ -0,0 +1,344 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, to_f64};
+use crate::command::{CommandError, Parameters};
+use crate::remesh::TriangleMesh;
+use crate::tessellate::knot_vectors;
+use std::collections::BTreeMap;
+use t_spline::algorithms::subs;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::ids::EdgeID;
+use t_spline::uv_mesh::layout::FaceRect;
+use t_spline::uv_mesh::{Boundary, LocalKnots, ValidationError};
+use t_spline::{Numeric, Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum TriangulateError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// A triangle approximating part of a patch face
+pub(crate) struct Triangle {
+    pub patch: usize,
+    pub face: EdgeID,
+    pub corners: [Point3<f64>; 3],
+}
+
+/// Tessellate every face of `mesh` into a `resolution` x `resolution` grid of triangles.
+///
+/// Triangles do not share vertices, so they can be shaded flat. Samples the surface does
+/// not cover leave holes instead of failing.
+pub fn triangle_mesh<T: ControlMesh + Sync>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<TriangleMesh, TriangulateError> {
+    mesh.validate_control_mesh()?;
+
+    let knot_cache = knot_vectors(mesh, boundary);
+    let mut triangles = Vec::new();
+    for rect in mesh.layout().faces {
+        triangulate(mesh, &knot_cache, 0, rect, resolution, &mut triangles)?;
+    }
+
+    let mut result = TriangleMesh::default();
+    for triangle in triangles {
+        let n = result.points.len();
+        result.points.extend(triangle.corners);
+        result.triangles.push([n, n + 1, n + 2]);
+    }
+    Ok(result)
+}
+
+/// Like [triangle_mesh], but with a mesh per region, so every region can get its own
+/// object or material on export.
+///
+/// `region` names the region of a face by its representative edge, as in
+/// [t_spline::uv_mesh::UVMesh::faces], for example from a face selection.
+pub fn triangle_mesh_by_region<T: ControlMesh + Sync, R: Ord>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+    region: impl Fn(EdgeID) -> R,
+) -> Result<BTreeMap<R, TriangleMesh>, TriangulateError> {
+    mesh.validate_control_mesh()?;
+
+    let knot_cache = knot_vectors(mesh, boundary);
+    let mut triangles = Vec::new();
+    for rect in mesh.layout().faces {
+        triangulate(mesh, &knot_cache, 0, rect, resolution, &mut triangles)?;
+    }
+
+    let mut regions: BTreeMap<R, TriangleMesh> = BTreeMap::new();
+    for triangle in triangles {
+        let result = regions.entry(region(triangle.face)).or_default();
+        let n = result.points.len();
+        result.points.extend(triangle.corners);
+        result.triangles.push([n, n + 1, n + 2]);
+    }
+    Ok(regions)
+}
+
+/// Builds an indexed [TriangleMesh] of a surface from parameters, for front ends that
+/// want triangles rather than the point cloud of [crate::tessellate::tessellate]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TessellateMesh {
+    /// Triangle grid cells per face along either direction
+    pub resolution: usize,
+    pub boundary: Boundary,
+}
+
+impl TessellateMesh {
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, CommandError> {
+        Ok(Self {
+            resolution: parameters.get_or("resolution", 8)?,
+            boundary: parameters.get_boundary()?,
+        })
+    }
+
+    pub fn apply<T: ControlMesh + Sync>(&self, mesh: &T) -> Result<TriangleMesh, TriangulateError> {
+        tessellate_mesh(mesh, self.resolution, self.boundary)
+    }
+}
+
+/// Tessellate every face of `mesh` into a `resolution` x `resolution` grid of triangles
+/// that share their vertices.
+///
+/// Samples at the same parameter are merged, so faces of the same size meet in shared
+/// vertices. Where a T-junction gives neighbouring faces different sample spacing they
+/// only meet geometrically. Samples the surface does not cover leave holes.
+pub fn tessellate_mesh<T: ControlMesh + Sync>(
+    mesh: &T,
+    resolution: usize,
+    boundary: Boundary,
+) -> Result<TriangleMesh, TriangulateError> {
+    mesh.validate_control_mesh()?;
+
+    let knot_cache = knot_vectors(mesh, boundary);
+    let mut result = TriangleMesh::default();
+    let mut vertices: BTreeMap<(u64, u64), Option<usize>> = BTreeMap::new();
+    for rect in mesh.layout().faces {
+        let grid = face_grid::<T::Unit>(rect, resolution).and_then(|grid| {
+            grid.into_iter()
+                .map(|st| {
+                    let key = (to_f64(st.0)?.to_bits(), to_f64(st.1)?.to_bits());
+                    if let Some(&vertex) = vertices.get(&key) {
+                        return Ok(vertex);
+                    }
+                    let vertex = match subs(mesh.control_points(), st, &knot_cache) {
+                        Some(p) => {
+                            result.points.push(Point3::new(
+                                to_f64(p.x)?,
+                                to_f64(p.y)?,
+                                to_f64(p.z)?,
+                            ));
+                            Some(result.points.len() - 1)
+                        }
+                        None => None,
+                    };
+                    vertices.insert(key, vertex);
+                    Ok(vertex)
+                })
+                .collect::<Result<Vec<_>, CastError>>()
+        })?;
+        grid_triangles(&grid, resolution, |corners| result.triangles.push(corners));
+    }
+    Ok(result)
+}
+
+/// Tessellate a face into a regular grid of triangles
+pub(crate) fn triangulate<T: ControlMesh>(
+    mesh: &T,
+    knot_cache: &[LocalKnots],
+    patch: usize,
+    rect: FaceRect,
+    resolution: usize,
+    triangles: &mut Vec<Triangle>,
+) -> Result<(), CastError> {
+    let grid = face_grid::<T::Unit>(rect, resolution)?
+        .into_iter()
+        .map(|st| match subs(mesh.control_points(), st, knot_cache) {
+            Some(p) => Ok(Some(Point3::new(to_f64(p.x)?, to_f64(p.y)?, to_f64(p.z)?))),
+            None => Ok(None),
+        })
+        .collect::<Result<Vec<_>, CastError>>()?;
+
+    grid_triangles(&grid, resolution, |corners| {
+        triangles.push(Triangle {
+            patch,
+            face: rect.face,
+            corners,
+        })
+    });
+    Ok(())
+}
+
+/// Parameters of the `resolution + 1` x `resolution + 1` grid over a face, row by row
+pub(crate) fn face_grid<U: Numeric>(
+    rect: FaceRect,
+    resolution: usize,
+) -> Result<Vec<(U, U)>, CastError> {
+    let n = resolution.max(1);
+    let lerp = |range: (isize, isize), i: usize| -> Result<U, CastError> {
+        let cast = |v: isize| U::from_isize(v).ok_or(CastError);
+        let i = U::from_usize(i).ok_or(CastError)?;
+        let n = U::from_usize(n).ok_or(CastError)?;
+        Ok(cast(range.0)? + (cast(range.1)? - cast(range.0)?) * i / n)
+    };
+
+    let mut grid = Vec::with_capacity((n + 1) * (n + 1));
+    for j in 0..=n {
+        for i in 0..=n {
+            grid.push((lerp(rect.s, i)?, lerp(rect.t, j)?));
+        }
+    }
+    Ok(grid)
+}
+
+/// Two counter-clockwise triangles per cell of a [face_grid] with all corners defined
+fn grid_triangles<V: Copy>(grid: &[Option<V>], resolution: usize, mut emit: impl FnMut([V; 3])) {
+    let n = resolution.max(1);
+    let at = |i: usize, j: usize| grid[j * (n + 1) + i];
+    for j in 0..n {
+        for i in 0..n {
+            for corners in [
+                [at(i, j), at(i + 1, j), at(i + 1, j + 1)],
+                [at(i, j), at(i + 1, j + 1), at(i, j + 1)],
+            ] {
+                if let [Some(a), Some(b), Some(c)] = corners {
+                    emit([a, b, c]);
+                }
+            }
+        }
+    }
+}
+
+/// Distance along `direction` to the triangle, using Möller-Trumbore
+pub(crate) fn intersect(
+    origin: Point3<f64>,
+    direction: Vector3<f64>,
+    [a, b, c]: &[Point3<f64>; 3],
+) -> Option<f64> {
+    let (e1, e2) = (b - a, c - a);
+    let p = direction.cross(&e2);
+    let det = e1.dot(&p);
+    if det.abs() <= f64::EPSILON {
+        return None;
+    }
+
+    let to_origin = origin - a;
+    let u = to_origin.dot(&p) / det;
+    if !(0. ..=1.).contains(&u) {
+        return None;
+    }
+    let q = to_origin.cross(&e1);
+    let v = direction.dot(&q) / det;
+    if v < 0. || u + v > 1. {
+        return None;
+    }
+
+    let distance = e2.dot(&q) / det;
+    (distance > f64::EPSILON).then_some(distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use crate::unit_square::unit_square;
+    use t_spline::TSpline;
+    use t_spline::uv_mesh::UVMesh;
+
+    #[test]
+    fn it_triangulates_a_square() {
+        let mesh = triangle_mesh(&unit_square::<TSpline>(), 2, Boundary::Clamped).unwrap();
+
+        assert_eq!(8, mesh.triangles.len());
+        let area: f64 = mesh
+            .triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|v| mesh.points[v]);
+                // counter-clockwise seen from +z
+                (b - a).cross(&(c - a)).z / 2.
+            })
+            .sum();
+        assert!((area - 1.).abs() < 1e-9, "{area}");
+    }
+
+    #[test]
+    fn it_shares_vertices_between_faces() {
+        let mesh: TSpline = new_plane(4, 3, 3., 2.).unwrap();
+        let tessellate =
+            TessellateMesh::from_parameters(&Parameters::default().with("resolution", 2)).unwrap();
+
+        let shared = tessellate.apply(&mesh).unwrap();
+        let flat = triangle_mesh(&mesh, 2, Boundary::Clamped).unwrap();
+
+        // a 6 x 4 grid of cells has 7 x 5 corners
+        assert_eq!(35, shared.points.len());
+        assert_eq!(flat.triangles.len(), shared.triangles.len());
+        for (a, b) in shared.triangles.iter().zip(&flat.triangles) {
+            assert_eq!(a.map(|v| shared.points[v]), b.map(|v| flat.points[v]));
+        }
+    }
+
+    #[test]
+    fn it_splits_triangles_by_region() {
+        let mesh: TSpline = new_plane(4, 3, 3., 2.).unwrap();
+        let faces: Vec<_> = mesh.faces().collect();
+        let left = &faces[..2];
+
+        let regions = triangle_mesh_by_region(&mesh, 2, Boundary::Clamped, |face| {
+            if left.contains(&face) { "left" } else { "rest" }
+        })
+        .unwrap();
+
+        assert_eq!(
+            vec!["left", "rest"],
+            regions.keys().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(2 * 8, regions["left"].triangles.len());
+        assert_eq!(4 * 8, regions["rest"].triangles.len());
+    }
+
+    #[test]
+    fn it_intersects_triangles() {
+        let triangle = [
+            Point3::new(0., 0., 1.),
+            Point3::new(1., 0., 1.),
+            Point3::new(0., 1., 1.),
+        ];
+
+        assert_eq!(
+            Some(1.),
+            intersect(Point3::new(0.25, 0.25, 0.), Vector3::z(), &triangle)
+        );
+        assert_eq!(
+            None,
+            intersect(Point3::new(0.75, 0.75, 0.), Vector3::z(), &triangle)
+        );
+        assert_eq!(
+            None,
+            intersect(Point3::new(0.25, 0.25, 0.), -Vector3::z(), &triangle)
+        );
+    }
+}
@@ -0,0 +1,137 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use crate::cast::{CastError, from_f64, to_f64};
+use crate::tessellate::knot_vectors;
+use rayon::prelude::*;
+use t_spline::Point3;
+use t_spline::algorithms::subs;
+use t_spline::bounds::Bounded;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::uv_mesh::{Boundary, ValidationError};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error)]
+pub enum TrimError {
+    #[error(transparent)]
+    Cast(#[from] CastError),
+    #[error("mesh is invalid: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+/// A T-spline restricted to the inside of a closed loop in parameter space.
+#[derive(Debug, Clone)]
+pub struct TrimmedSpline<T> {
+    pub mesh: T,
+    /// Counter-clockwise `(s, t)` polygon, the last point connects back to the first
+    pub trim: Vec<(f64, f64)>,
+}
+
+impl<T: ControlMesh + Sync> TrimmedSpline<T> {
+    /// Trim `mesh` to the rectangle of its parametric bounds, which leaves a mesh with a
+    /// rectangular domain whole
+    pub fn new(mesh: T) -> Self {
+        let mut bounds = (isize::MAX, isize::MIN, isize::MAX, isize::MIN);
+        for p in mesh.points() {
+            bounds = (
+                bounds.0.min(p.s),
+                bounds.1.max(p.s),
+                bounds.2.min(p.t),
+                bounds.3.max(p.t),
+            );
+        }
+        let (s0, s1, t0, t1) = (
+            bounds.0 as f64,
+            bounds.1 as f64,
+            bounds.2 as f64,
+            bounds.3 as f64,
+        );
+        Self {
+            mesh,
+            trim: vec![(s0, t0), (s1, t0), (s1, t1), (s0, t1)],
+        }
+    }
+
+    /// True if `st` is strictly inside the trim loop
+    pub fn contains(&self, (s, t): (f64, f64)) -> bool {
+        let mut inside = false;
+        for (i, &(s0, t0)) in self.trim.iter().enumerate() {
+            let (s1, t1) = self.trim[(i + 1) % self.trim.len()];
+            if (t0 > t) != (t1 > t) && s < s0 + (t - t0) / (t1 - t0) * (s1 - s0) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// Tessellate the trimmed surface.
+    ///
+    /// Grid samples inside the trim loop are followed by the evaluated trim loop itself, so
+    /// splines sharing a trim edge produce identical points along it.
+    pub fn tessellate(
+        &self,
+        resolution: usize,
+        boundary: Boundary,
+    ) -> Result<Vec<Point3<T::Unit>>, TrimError> {
+        self.mesh.validate_control_mesh()?;
+        let knot_cache = knot_vectors(&self.mesh, boundary);
+        let bounds = self.mesh.bounds().ok_or(ValidationError::EmptyDomain())?;
+
+        let inside: Vec<Option<Point3<T::Unit>>> = (0..resolution * resolution)
+            .into_par_iter()
+            .map(|i| {
+                let st = bounds.interpolate(i, resolution);
+                let st_f64 = (to_f64(st.0)?, to_f64(st.1)?);
+                if !self.contains(st_f64) || !self.mesh.contains_uv(st) {
+                    return Ok(None);
+                }
+                Ok(subs(self.mesh.control_points(), st, &knot_cache))
+            })
+            .collect::<Result<_, TrimError>>()?;
+
+        let mut points: Vec<_> = inside.into_iter().flatten().collect();
+        for &(s, t) in &self.trim {
+            let st = (from_f64::<T::Unit>(s)?, from_f64::<T::Unit>(t)?);
+            points.extend(subs(self.mesh.control_points(), st, &knot_cache));
+        }
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plane::new_plane;
+    use t_spline::TSpline;
+
+    #[test]
+    fn it_keeps_samples_inside_the_trim() {
+        let trimmed = TrimmedSpline {
+            mesh: new_plane::<TSpline>(4, 4, 3., 3.).unwrap(),
+            trim: vec![(0., 0.), (3., 0.), (0., 3.)],
+        };
+
+        assert!(trimmed.contains((0.5, 0.5)));
+        assert!(!trimmed.contains((2., 2.)));
+
+        let points = trimmed.tessellate(7, Boundary::Clamped).unwrap();
+        assert!(points.len() > 3);
+        assert!(points.iter().all(|p| p.x + p.y <= 3. + 1e-9));
+
+        // the trim corners are part of the tessellation
+        assert_eq!(Point3::new(3., 0., 0.), points[points.len() - 2]);
+    }
+}
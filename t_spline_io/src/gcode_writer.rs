@@ -0,0 +1,114 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use std::fmt::Write;
+use t_spline::Point3;
+
+/// Writes milling passes as G-code for a 3-axis machine.
+///
+/// Every pass is entered with a rapid move at the safe height, plunged and cut at the
+/// feed rate, then retracted again. Coordinates are written in millimeters.
+#[derive(Debug, Clone)]
+pub struct GCodeWriter {
+    gcode: String,
+    safe_height: f64,
+    feed_rate: f64,
+}
+
+impl Default for GCodeWriter {
+    fn default() -> Self {
+        Self {
+            gcode: String::new(),
+            safe_height: 5.,
+            feed_rate: 500.,
+        }
+    }
+}
+
+impl GCodeWriter {
+    /// Height rapid moves between passes travel at
+    pub fn with_safe_height(mut self, safe_height: f64) -> Self {
+        self.safe_height = safe_height;
+        self
+    }
+
+    /// Cutting speed in millimeters per minute
+    pub fn with_feed_rate(mut self, feed_rate: f64) -> Self {
+        self.feed_rate = feed_rate;
+        self
+    }
+
+    pub fn with_passes(mut self, passes: &[Vec<Point3<f64>>]) -> Result<Self, std::fmt::Error> {
+        let safe = self.safe_height;
+        for pass in passes {
+            let Some((first, rest)) = pass.split_first() else {
+                continue;
+            };
+
+            writeln!(self.gcode, "G0 Z{safe:.4}")?;
+            writeln!(self.gcode, "G0 X{:.4} Y{:.4}", first.x, first.y)?;
+            writeln!(self.gcode, "G1 Z{:.4} F{:.1}", first.z, self.feed_rate)?;
+            for p in rest {
+                writeln!(self.gcode, "G1 X{:.4} Y{:.4} Z{:.4}", p.x, p.y, p.z)?;
+            }
+        }
+        writeln!(self.gcode, "G0 Z{safe:.4}")?;
+
+        Ok(self)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(bytes = self.gcode.len()))
+    )]
+    pub fn write(self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "G21 G90 G17")?;
+        write!(w, "{}", self.gcode)?;
+        writeln!(w, "M2")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(writer: GCodeWriter) -> String {
+        let mut out = Vec::new();
+        writer.write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn it_retracts_between_passes() {
+        let passes = vec![
+            vec![Point3::new(0., 0., -1.), Point3::new(1., 0., -1.)],
+            vec![],
+            vec![Point3::new(1., 1., -1.), Point3::new(0., 1., -1.)],
+        ];
+        let gcode = written(
+            GCodeWriter::default()
+                .with_safe_height(2.)
+                .with_passes(&passes)
+                .unwrap(),
+        );
+
+        assert!(gcode.starts_with("G21 G90 G17\nG0 Z2.0000\nG0 X0.0000 Y0.0000\n"));
+        assert_eq!(3, gcode.matches("G0 Z2.0000").count());
+        assert_eq!(2, gcode.matches("F500.0").count());
+        assert!(gcode.contains("G1 X0.0000 Y1.0000 Z-1.0000\n"));
+        assert!(gcode.ends_with("M2\n"));
+    }
+}
@@ -0,0 +1,629 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use num_traits::ToPrimitive;
+use std::fmt::Write;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::{Point3, Vector3};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GltfError {
+    #[error("animation has no frames or no points")]
+    Empty,
+    #[error("frame {0} has a different number of points than the first frame")]
+    PointCountMismatch(usize),
+    #[error("frame {0} does not come after the previous frame")]
+    Unordered(usize),
+    #[error("surface has {0} points but {1} normals or texture coordinates")]
+    AttributeMismatch(usize, usize),
+    #[error("failed to cast")]
+    FailedToCast,
+    #[error(transparent)]
+    Format(#[from] std::fmt::Error),
+}
+
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const LINES: u32 = 1;
+
+/// Triangles of a mesh shaded with one material
+#[derive(Debug, Clone, Copy)]
+pub struct Region<'a> {
+    pub material: &'a str,
+    pub points: &'a [Point3<f64>],
+    pub triangles: &'a [[usize; 3]],
+}
+
+/// A tessellated surface with a normal and a texture coordinate for every point
+#[derive(Debug, Clone, Copy)]
+pub struct Surface<'a> {
+    pub points: &'a [Point3<f64>],
+    pub normals: &'a [Vector3<f64>],
+    /// Texture coordinates, usually the parameter of the point scaled to `[0, 1]`
+    pub uvs: &'a [(f64, f64)],
+    pub triangles: &'a [[usize; 3]],
+}
+
+/// Writes triangle meshes as glTF 2.0, either as a self-contained JSON file with the
+/// binary data embedded as a base64 data URI or as a binary `.glb`.
+#[derive(Debug, Default, Clone)]
+pub struct GltfWriter {
+    buffer: Vec<u8>,
+    buffer_views: Vec<String>,
+    accessors: Vec<String>,
+    nodes: Vec<String>,
+    meshes: Vec<String>,
+    animations: Vec<String>,
+    /// Names of the materials in the order of their index
+    materials: Vec<String>,
+}
+
+impl GltfWriter {
+    /// Add a mesh animated through `frames` of `(time, points)`, with times in seconds.
+    ///
+    /// The first frame is the base shape and every later frame becomes a morph target of
+    /// it. The animation moves the weight linearly from one target to the next, which
+    /// interpolates the points linearly between frames.
+    pub fn with_morph_animation(
+        mut self,
+        name: &str,
+        triangles: &[[usize; 3]],
+        frames: &[(f64, Vec<Point3<f64>>)],
+    ) -> Result<Self, GltfError> {
+        let ((_, base), rest) = frames.split_first().ok_or(GltfError::Empty)?;
+        if base.is_empty() {
+            return Err(GltfError::Empty);
+        }
+        for (i, window) in frames.windows(2).enumerate() {
+            if window[1].1.len() != base.len() {
+                return Err(GltfError::PointCountMismatch(i + 1));
+            }
+            if window[1].0 <= window[0].0 {
+                return Err(GltfError::Unordered(i + 1));
+            }
+        }
+
+        let positions = self.vec3_accessor(base.iter().map(|p| p.coords))?;
+        let indices = self.index_accessor(triangles.iter().flatten())?;
+
+        let mut targets = Vec::with_capacity(rest.len());
+        for (_, points) in rest {
+            let offsets = points.iter().zip(base).map(|(p, b)| p - b);
+            targets.push(format!(
+                r#"{{"POSITION":{}}}"#,
+                self.vec3_accessor(offsets)?
+            ));
+        }
+
+        let mesh = self.meshes.len();
+        let mut primitive =
+            format!(r#"{{"attributes":{{"POSITION":{positions}}},"indices":{indices}"#);
+        if !targets.is_empty() {
+            write!(primitive, r#","targets":[{}]"#, targets.join(","))?;
+        }
+        primitive.push('}');
+        self.meshes.push(format!(
+            r#"{{"name":{},"primitives":[{primitive}],"weights":[{}]}}"#,
+            json_string(name),
+            vec!["0"; targets.len()].join(",")
+        ));
+        let node = self.nodes.len();
+        self.nodes
+            .push(format!(r#"{{"name":{},"mesh":{mesh}}}"#, json_string(name)));
+
+        if !targets.is_empty() {
+            let times: Vec<f32> = frames.iter().map(|(t, _)| *t as f32).collect();
+            let view = self.buffer_view(bytes(times.iter().map(|t| t.to_bits())), 0)?;
+            let bounds = format!(
+                r#","min":[{}],"max":[{}]"#,
+                times[0],
+                times[times.len() - 1]
+            );
+            let input = self.accessor(view, FLOAT, times.len(), "SCALAR", &bounds);
+
+            // frame k shows target k - 1 fully, the first frame shows none
+            let mut weights = vec![0f32; frames.len() * targets.len()];
+            for k in 1..frames.len() {
+                weights[k * targets.len() + k - 1] = 1.;
+            }
+            let view = self.buffer_view(bytes(weights.iter().map(|w| w.to_bits())), 0)?;
+            let output = self.accessor(view, FLOAT, weights.len(), "SCALAR", "");
+
+            self.animations.push(format!(
+                r#"{{"name":{},"samplers":[{{"input":{input},"output":{output},"interpolation":"LINEAR"}}],"channels":[{{"sampler":0,"target":{{"node":{node},"path":"weights"}}}}]}}"#,
+                json_string(name)
+            ));
+        }
+
+        Ok(self)
+    }
+
+    /// Add a static mesh with a primitive per region.
+    ///
+    /// Regions with the same material name share one material, so a renderer can shade
+    /// every region on its own without splitting the mesh again.
+    pub fn with_regions(mut self, name: &str, regions: &[Region]) -> Result<Self, GltfError> {
+        if regions.iter().all(|r| r.points.is_empty()) {
+            return Err(GltfError::Empty);
+        }
+
+        let mut primitives = Vec::with_capacity(regions.len());
+        for region in regions {
+            if region.points.is_empty() {
+                continue;
+            }
+            let positions = self.vec3_accessor(region.points.iter().map(|p| p.coords))?;
+            let indices = self.index_accessor(region.triangles.iter().flatten())?;
+            let material = self.material(region.material);
+            primitives.push(format!(
+                r#"{{"attributes":{{"POSITION":{positions}}},"indices":{indices},"material":{material}}}"#
+            ));
+        }
+
+        let mesh = self.meshes.len();
+        self.meshes.push(format!(
+            r#"{{"name":{},"primitives":[{}]}}"#,
+            json_string(name),
+            primitives.join(",")
+        ));
+        self.nodes
+            .push(format!(r#"{{"name":{},"mesh":{mesh}}}"#, json_string(name)));
+        Ok(self)
+    }
+
+    /// Add a smooth shaded surface as a mesh with positions, normals and texture
+    /// coordinates
+    pub fn with_surface(mut self, name: &str, surface: Surface) -> Result<Self, GltfError> {
+        let count = surface.points.len();
+        if count == 0 {
+            return Err(GltfError::Empty);
+        }
+        if surface.normals.len() != count || surface.uvs.len() != count {
+            return Err(GltfError::AttributeMismatch(
+                count,
+                surface.normals.len().min(surface.uvs.len()),
+            ));
+        }
+
+        let positions = self.vec3_accessor(surface.points.iter().map(|p| p.coords))?;
+        let normals = self.vec3_accessor(surface.normals.iter().copied())?;
+        let uvs = surface
+            .uvs
+            .iter()
+            .flat_map(|&(u, v)| [u as f32, v as f32])
+            .map(f32::to_bits);
+        let view = self.buffer_view(bytes(uvs), ARRAY_BUFFER)?;
+        let uvs = self.accessor(view, FLOAT, count, "VEC2", "");
+        let indices = self.index_accessor(surface.triangles.iter().flatten())?;
+
+        let primitive = format!(
+            r#"{{"attributes":{{"POSITION":{positions},"NORMAL":{normals},"TEXCOORD_0":{uvs}}},"indices":{indices}}}"#
+        );
+        self.push_mesh(name, &primitive);
+        Ok(self)
+    }
+
+    /// Add the control cage of `mesh` as lines between its control points, in a node of
+    /// its own so viewers can hide it
+    pub fn with_control_cage(
+        mut self,
+        name: &str,
+        mesh: &impl ControlMesh,
+    ) -> Result<Self, GltfError> {
+        if mesh.control_points().is_empty() {
+            return Err(GltfError::Empty);
+        }
+        let points = mesh
+            .control_points()
+            .iter()
+            .map(|cp| {
+                let cast = |v: Option<f64>| v.ok_or(GltfError::FailedToCast);
+                Ok(Vector3::new(
+                    cast(cp.x.to_f64())?,
+                    cast(cp.y.to_f64())?,
+                    cast(cp.z.to_f64())?,
+                ))
+            })
+            .collect::<Result<Vec<_>, GltfError>>()?;
+        let mut lines = Vec::new();
+        for (i, edge) in mesh.edges().iter().enumerate() {
+            // twins share a line, only write it once
+            if edge.twin.is_some_and(|twin| twin.0 < i) {
+                continue;
+            }
+            lines.extend([edge.origin.0, mesh.next_edge(edge).origin.0]);
+        }
+
+        let positions = self.vec3_accessor(points.into_iter())?;
+        let indices = self.index_accessor(lines.iter())?;
+        let primitive = format!(
+            r#"{{"attributes":{{"POSITION":{positions}}},"indices":{indices},"mode":{LINES}}}"#
+        );
+        self.push_mesh(name, &primitive);
+        Ok(self)
+    }
+
+    /// Add a mesh of a single primitive and a node showing it
+    fn push_mesh(&mut self, name: &str, primitive: &str) {
+        let mesh = self.meshes.len();
+        self.meshes.push(format!(
+            r#"{{"name":{},"primitives":[{primitive}]}}"#,
+            json_string(name)
+        ));
+        self.nodes
+            .push(format!(r#"{{"name":{},"mesh":{mesh}}}"#, json_string(name)));
+    }
+
+    /// Index of the material called `name`, added if it is new
+    fn material(&mut self, name: &str) -> usize {
+        let name = json_string(name);
+        let material = format!(r#"{{"name":{name}}}"#);
+        match self.materials.iter().position(|m| *m == material) {
+            Some(index) => index,
+            None => {
+                self.materials.push(material);
+                self.materials.len() - 1
+            }
+        }
+    }
+
+    fn index_accessor<'a>(
+        &mut self,
+        indices: impl Iterator<Item = &'a usize>,
+    ) -> Result<usize, GltfError> {
+        let indices: Vec<u32> = indices.map(|&v| v as u32).collect();
+        let view = self.buffer_view(bytes(indices.iter().copied()), ELEMENT_ARRAY_BUFFER)?;
+        Ok(self.accessor(view, UNSIGNED_INT, indices.len(), "SCALAR", ""))
+    }
+
+    fn vec3_accessor(
+        &mut self,
+        values: impl Iterator<Item = Vector3<f64>>,
+    ) -> Result<usize, GltfError> {
+        let values: Vec<[f32; 3]> = values
+            .map(|v| [v.x as f32, v.y as f32, v.z as f32])
+            .collect();
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+        for v in &values {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(v[axis]);
+                max[axis] = max[axis].max(v[axis]);
+            }
+        }
+
+        let flat = values.iter().flatten().map(|v| v.to_bits());
+        let view = self.buffer_view(bytes(flat), ARRAY_BUFFER)?;
+        let bounds = format!(
+            r#","min":[{},{},{}],"max":[{},{},{}]"#,
+            min[0], min[1], min[2], max[0], max[1], max[2]
+        );
+        Ok(self.accessor(view, FLOAT, values.len(), "VEC3", &bounds))
+    }
+
+    fn buffer_view(&mut self, data: Vec<u8>, target: u32) -> Result<usize, GltfError> {
+        let mut view = format!(
+            r#"{{"buffer":0,"byteOffset":{},"byteLength":{}"#,
+            self.buffer.len(),
+            data.len()
+        );
+        if target != 0 {
+            write!(view, r#","target":{target}"#)?;
+        }
+        view.push('}');
+        self.buffer.extend(data);
+        self.buffer_views.push(view);
+        Ok(self.buffer_views.len() - 1)
+    }
+
+    fn accessor(
+        &mut self,
+        view: usize,
+        component: u32,
+        count: usize,
+        kind: &str,
+        extra: &str,
+    ) -> usize {
+        self.accessors.push(format!(
+            r#"{{"bufferView":{view},"componentType":{component},"count":{count},"type":"{kind}"{extra}}}"#
+        ));
+        self.accessors.len() - 1
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(bytes = self.buffer.len()))
+    )]
+    pub fn write(self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        self.write_json(w, true)?;
+        writeln!(w)
+    }
+
+    /// Write a binary `.glb`, with the data in a chunk after the JSON
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(bytes = self.buffer.len()))
+    )]
+    pub fn write_glb(self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        let mut json = Vec::new();
+        self.write_json(&mut json, false)?;
+        // chunks are aligned to 4 bytes, JSON padded with spaces and data with zeros
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut data = self.buffer;
+        data.resize(data.len().next_multiple_of(4), 0);
+
+        let mut length = 12 + 8 + json.len();
+        if !data.is_empty() {
+            length += 8 + data.len();
+        }
+        w.write_all(b"glTF")?;
+        w.write_all(&2u32.to_le_bytes())?;
+        w.write_all(&(length as u32).to_le_bytes())?;
+        w.write_all(&(json.len() as u32).to_le_bytes())?;
+        w.write_all(b"JSON")?;
+        w.write_all(&json)?;
+        if !data.is_empty() {
+            w.write_all(&(data.len() as u32).to_le_bytes())?;
+            w.write_all(b"BIN\0")?;
+            w.write_all(&data)?;
+        }
+        Ok(())
+    }
+
+    /// Write the JSON document, with the data as a data URI if `embed` or left for a
+    /// binary chunk otherwise
+    fn write_json(&self, w: &mut impl std::io::Write, embed: bool) -> std::io::Result<()> {
+        write!(w, r#"{{"asset":{{"version":"2.0","generator":"t-spline"}}"#)?;
+        if !self.nodes.is_empty() {
+            let nodes: Vec<_> = (0..self.nodes.len()).map(|n| n.to_string()).collect();
+            write!(
+                w,
+                r#","scene":0,"scenes":[{{"nodes":[{}]}}]"#,
+                nodes.join(",")
+            )?;
+        }
+        for (key, items) in [
+            ("nodes", &self.nodes),
+            ("meshes", &self.meshes),
+            ("animations", &self.animations),
+            ("materials", &self.materials),
+            ("accessors", &self.accessors),
+            ("bufferViews", &self.buffer_views),
+        ] {
+            if !items.is_empty() {
+                write!(w, r#","{key}":[{}]"#, items.join(","))?;
+            }
+        }
+        if !self.buffer.is_empty() {
+            if embed {
+                write!(
+                    w,
+                    r#","buffers":[{{"byteLength":{},"uri":"data:application/octet-stream;base64,{}"}}]"#,
+                    self.buffer.len(),
+                    base64(&self.buffer)
+                )?;
+            } else {
+                write!(w, r#","buffers":[{{"byteLength":{}}}]"#, self.buffer.len())?;
+            }
+        }
+        write!(w, "}}")
+    }
+}
+
+/// Little endian bytes of 32 bit values, floats are given by their bits
+fn bytes(values: impl IntoIterator<Item = u32>) -> Vec<u8> {
+    values.into_iter().flat_map(u32::to_le_bytes).collect()
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMeshMut;
+    use t_spline::uv_mesh::Boundary;
+    use t_spline::uv_mesh::ids::VertID;
+    use t_spline_commands::animation::Animation;
+    use t_spline_commands::plane::new_plane;
+    use t_spline_commands::tessellate::tessellate_with_normals;
+
+    #[test]
+    fn it_encodes_base64() {
+        assert_eq!("", base64(b""));
+        assert_eq!("TQ==", base64(b"M"));
+        assert_eq!("TWE=", base64(b"Ma"));
+        assert_eq!("TWFu", base64(b"Man"));
+        assert_eq!("\"a\\\"b\\u000a\"", json_string("a\"b\n"));
+    }
+
+    #[test]
+    fn it_writes_frames_as_morph_targets() {
+        let mut lifted: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        lifted.control_point_mut(VertID(5)).unwrap().z = 1.;
+        let animation = Animation::default()
+            .with_key(0., &new_plane::<TSpline>(4, 4, 3., 3.).unwrap())
+            .unwrap()
+            .with_key(1., &lifted)
+            .unwrap();
+        let baked = animation.bake(&lifted, 2., 3, Boundary::Clamped).unwrap();
+        let triangles = baked[0].mesh.triangles.clone();
+        let frames: Vec<_> = baked.into_iter().map(|f| (f.time, f.mesh.points)).collect();
+
+        let mut gltf = Vec::new();
+        GltfWriter::default()
+            .with_morph_animation("Bump", &triangles, &frames)
+            .unwrap()
+            .write(&mut gltf)
+            .unwrap();
+        let gltf = String::from_utf8(gltf).unwrap();
+
+        assert!(gltf.contains(r#""targets":[{"POSITION":2},{"POSITION":3}]"#));
+        assert!(gltf.contains(r#""weights":[0,0]"#));
+        assert!(gltf.contains(r#""path":"weights""#));
+        assert!(gltf.contains(r#""min":[0],"max":[1]"#));
+    }
+
+    #[test]
+    fn it_writes_a_primitive_per_region() {
+        let points = [
+            Point3::new(0., 0., 0.),
+            Point3::new(1., 0., 0.),
+            Point3::new(0., 1., 0.),
+        ];
+        let triangles = [[0, 1, 2]];
+
+        let mut gltf = Vec::new();
+        GltfWriter::default()
+            .with_regions(
+                "Part",
+                &["red", "blue", "red"].map(|material| Region {
+                    material,
+                    points: &points,
+                    triangles: &triangles,
+                }),
+            )
+            .unwrap()
+            .write(&mut gltf)
+            .unwrap();
+        let gltf = String::from_utf8(gltf).unwrap();
+
+        assert_eq!(3, gltf.matches(r#""attributes""#).count());
+        assert!(gltf.contains(r#""indices":5,"material":0}]"#));
+        assert!(gltf.contains(r#""materials":[{"name":"red"},{"name":"blue"}]"#));
+    }
+
+    #[test]
+    fn it_rejects_mismatched_frames() {
+        let frames = vec![
+            (0., vec![Point3::origin(); 3]),
+            (1., vec![Point3::origin(); 4]),
+        ];
+
+        assert!(matches!(
+            GltfWriter::default().with_morph_animation("Bad", &[[0, 1, 2]], &frames),
+            Err(GltfError::PointCountMismatch(1))
+        ));
+        assert!(matches!(
+            GltfWriter::default().with_morph_animation("Bad", &[], &[]),
+            Err(GltfError::Empty)
+        ));
+    }
+
+    #[test]
+    fn it_writes_a_surface_and_its_cage_as_glb() {
+        let mesh: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let n = 4;
+        let samples = tessellate_with_normals(&mesh, n, Boundary::Clamped).unwrap();
+        let points: Vec<_> = samples.iter().map(|s| s.point).collect();
+        let normals: Vec<_> = samples.iter().map(|s| s.normal).collect();
+        let uvs: Vec<_> = (0..n * n)
+            .map(|i| ((i % n) as f64 / 3., (i / n) as f64 / 3.))
+            .collect();
+        let triangles: Vec<_> = (0..(n - 1) * (n - 1))
+            .flat_map(|c| {
+                let i = c % (n - 1) + c / (n - 1) * n;
+                [[i, i + 1, i + n + 1], [i, i + n + 1, i + n]]
+            })
+            .collect();
+
+        let mut glb = Vec::new();
+        GltfWriter::default()
+            .with_surface(
+                "Surface",
+                Surface {
+                    points: &points,
+                    normals: &normals,
+                    uvs: &uvs,
+                    triangles: &triangles,
+                },
+            )
+            .unwrap()
+            .with_control_cage("Cage", &mesh)
+            .unwrap()
+            .write_glb(&mut glb)
+            .unwrap();
+
+        let word = |at: usize| u32::from_le_bytes(glb[at..at + 4].try_into().unwrap()) as usize;
+        assert_eq!(b"glTF", &glb[..4]);
+        assert_eq!(2, word(4));
+        assert_eq!(glb.len(), word(8));
+        assert_eq!(b"JSON", &glb[16..20]);
+        let json_length = word(12);
+        assert_eq!(0, json_length % 4);
+        let json = std::str::from_utf8(&glb[20..20 + json_length]).unwrap();
+        assert!(json.contains(r#""NORMAL":1,"TEXCOORD_0":2"#), "{json}");
+        assert!(json.contains(r#""mode":1"#), "{json}");
+        assert!(json.contains(r#""scenes":[{"nodes":[0,1]}]"#), "{json}");
+        assert!(!json.contains("uri"));
+
+        let data = 20 + json_length;
+        assert_eq!(b"BIN\0", &glb[data + 4..data + 8]);
+        assert_eq!(glb.len(), data + 8 + word(data));
+    }
+
+    #[test]
+    fn it_rejects_surfaces_with_missing_attributes() {
+        let points = [Point3::origin(); 3];
+        assert!(matches!(
+            GltfWriter::default().with_surface(
+                "Bad",
+                Surface {
+                    points: &points,
+                    normals: &[Vector3::z(); 3],
+                    uvs: &[(0., 0.); 2],
+                    triangles: &[[0, 1, 2]],
+                },
+            ),
+            Err(GltfError::AttributeMismatch(3, 2))
+        ));
+    }
+}
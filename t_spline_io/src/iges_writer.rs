@@ -0,0 +1,319 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::step_writer::real;
+use t_spline::Vector4;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IgesError {
+    #[error("expected {0} control points, found {1}")]
+    WrongCount(usize, usize),
+    #[error("knot vectors need at least 8 knots and may not decrease")]
+    InvalidKnots,
+    #[error("control points need finite coordinates and a positive weight")]
+    InvalidControlPoint,
+}
+
+/// Writes bicubic NURBS surfaces as IGES rational B-spline surfaces, entity type 128,
+/// for CAM tools without STEP support.
+#[derive(Debug, Clone)]
+pub struct IgesWriter {
+    name: String,
+    /// The label and parameter data of every surface
+    surfaces: Vec<(String, Vec<String>)>,
+}
+
+impl Default for IgesWriter {
+    fn default() -> Self {
+        Self {
+            name: "t-spline".to_string(),
+            surfaces: Vec::new(),
+        }
+    }
+}
+
+impl IgesWriter {
+    /// Name of the model, written in the global section
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Add a surface like [StepWriter::with_surface](crate::step_writer::StepWriter::with_surface),
+    /// `label` is cut to the 8 characters IGES leaves for it
+    pub fn with_surface(
+        mut self,
+        label: &str,
+        s_knots: &[isize],
+        t_knots: &[isize],
+        control_points: &[Vector4<f64>],
+    ) -> Result<Self, IgesError> {
+        for knots in [s_knots, t_knots] {
+            if knots.len() < 8 || knots.windows(2).any(|k| k[1] < k[0]) {
+                return Err(IgesError::InvalidKnots);
+            }
+        }
+        let (width, height) = (s_knots.len() - 4, t_knots.len() - 4);
+        if control_points.len() != width * height {
+            return Err(IgesError::WrongCount(width * height, control_points.len()));
+        }
+        if control_points
+            .iter()
+            .any(|p| !(p.iter().all(|v| v.is_finite()) && p.w > 0.))
+        {
+            return Err(IgesError::InvalidControlPoint);
+        }
+
+        let polynomial = control_points.iter().all(|p| p.w == control_points[0].w);
+        let mut params = vec![
+            "128".to_string(),
+            (width - 1).to_string(),
+            (height - 1).to_string(),
+            "3".to_string(),
+            "3".to_string(),
+            // closed along s and t, polynomial, periodic along s and t
+            "0".to_string(),
+            "0".to_string(),
+            (polynomial as u8).to_string(),
+            "0".to_string(),
+            "0".to_string(),
+        ];
+        let knot = |k: &isize| real(*k as f64);
+        params.extend(s_knots.iter().map(knot));
+        params.extend(t_knots.iter().map(knot));
+        // both lists run along s first, like the rows of the control points
+        params.extend(control_points.iter().map(|p| real(p.w)));
+        params.extend(
+            control_points
+                .iter()
+                .flat_map(|p| [p.x, p.y, p.z])
+                .map(real),
+        );
+        params.extend(
+            [s_knots, t_knots]
+                .iter()
+                .flat_map(|k| [knot(&k[3]), knot(&k[k.len() - 4])]),
+        );
+
+        let label = label.chars().take(8).collect();
+        self.surfaces.push((label, params));
+        Ok(self)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(surfaces = self.surfaces.len()))
+    )]
+    pub fn write(self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        let hollerith = |s: &str| format!("{}H{s}", s.len());
+        let global = [
+            hollerith(","),
+            hollerith(";"),
+            hollerith(&self.name),
+            hollerith(&self.name),
+            hollerith("t-spline"),
+            hollerith("t-spline"),
+            "32".to_string(),
+            "38".to_string(),
+            "6".to_string(),
+            "308".to_string(),
+            "15".to_string(),
+            hollerith(&self.name),
+            real(1.),
+            // millimeters
+            "2".to_string(),
+            hollerith("MM"),
+            "1".to_string(),
+            real(1.),
+            String::new(),
+            real(1e-7),
+            real(0.),
+            String::new(),
+            String::new(),
+            // version 5.3
+            "11".to_string(),
+            "0".to_string(),
+        ];
+
+        let start = vec![format!("{} written by t-spline", self.name)];
+        let global = wrap(&global, 72);
+        let mut directory = Vec::new();
+        let mut parameters = Vec::new();
+        for (i, (label, params)) in self.surfaces.iter().enumerate() {
+            let entry = 2 * i + 1;
+            let lines = wrap(params, 64);
+            let field = |v: &dyn ToString| format!("{:>8}", v.to_string());
+            directory.push(
+                [
+                    field(&128),
+                    field(&(parameters.len() + 1)),
+                    field(&0),
+                    field(&0),
+                    field(&0),
+                    field(&0),
+                    field(&0),
+                    field(&0),
+                    field(&"00000000"),
+                ]
+                .concat(),
+            );
+            directory.push(
+                [
+                    field(&128),
+                    field(&0),
+                    field(&0),
+                    field(&lines.len()),
+                    field(&0),
+                    field(&""),
+                    field(&""),
+                    field(&label),
+                    field(&0),
+                ]
+                .concat(),
+            );
+            parameters.extend(lines.into_iter().map(|l| format!("{l:<64} {entry:>7}")));
+        }
+
+        for (section, lines) in [
+            ('S', &start),
+            ('G', &global),
+            ('D', &directory),
+            ('P', &parameters),
+        ] {
+            for (i, line) in lines.iter().enumerate() {
+                writeln!(w, "{line:<72}{section}{:>7}", i + 1)?;
+            }
+        }
+        let counts = format!(
+            "S{:>7}G{:>7}D{:>7}P{:>7}",
+            start.len(),
+            global.len(),
+            directory.len(),
+            parameters.len()
+        );
+        writeln!(w, "{counts:<72}T{:>7}", 1)
+    }
+}
+
+/// Join `params` with commas and a closing semicolon into lines of at most `width`
+/// characters, breaking only after a delimiter
+fn wrap(params: &[String], width: usize) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for (i, param) in params.iter().enumerate() {
+        let delimiter = if i + 1 == params.len() { ';' } else { ',' };
+        if lines
+            .last()
+            .is_some_and(|l| !l.is_empty() && l.len() + param.len() + 1 > width)
+        {
+            lines.push(String::new());
+        }
+        if let Some(line) = lines.last_mut() {
+            line.push_str(param);
+            line.push(delimiter);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(writer: IgesWriter) -> String {
+        let mut out = Vec::new();
+        writer.write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// A flat Bézier patch over the unit square
+    fn patch() -> Vec<Vector4<f64>> {
+        (0..16)
+            .map(|k| Vector4::new((k % 4) as f64, (k / 4) as f64, 0., 1.))
+            .collect()
+    }
+
+    #[test]
+    fn it_writes_fixed_columns() {
+        let knots = [0, 0, 0, 0, 1, 1, 1, 1];
+        let iges = written(
+            IgesWriter::default()
+                .with_surface("patch", &knots, &knots, &patch())
+                .unwrap(),
+        );
+        let lines = iges.lines().collect::<Vec<_>>();
+
+        assert!(lines.iter().all(|l| l.len() == 80), "{iges}");
+        let mut sections = lines.iter().map(|l| &l[72..73]).collect::<Vec<_>>();
+        sections.dedup();
+        assert_eq!(vec!["S", "G", "D", "P", "T"], sections);
+        let directory = lines
+            .iter()
+            .filter(|l| &l[72..73] == "D")
+            .collect::<Vec<_>>();
+        assert_eq!(2, directory.len());
+        assert_eq!("     128       1", &directory[0][..16]);
+        assert_eq!("   patch", &directory[1][56..64]);
+
+        let parameters = lines
+            .iter()
+            .filter(|l| &l[72..73] == "P")
+            .map(|l| {
+                assert_eq!("      1", &l[65..72]);
+                l[..64].trim_end()
+            })
+            .collect::<String>();
+        assert!(parameters.starts_with("128,3,3,3,3,0,0,1,0,0,0.E0,0.E0,0.E0,0.E0,1.E0,"));
+        // the second control point lies along s
+        assert!(parameters.contains("0.E0,0.E0,0.E0,1.E0,0.E0,0.E0,2.E0,"));
+        assert!(parameters.ends_with("0.E0,1.E0,0.E0,1.E0;"));
+
+        let section = |s: &str| lines.iter().filter(|l| &l[72..73] == s).count();
+        let count = directory[1][24..32].trim().parse::<usize>().unwrap();
+        assert_eq!(section("P"), count);
+        let terminate = format!("S      1G{:>7}D      2P{count:>7}", section("G"));
+        assert_eq!(format!("{terminate:<72}T      1"), *lines.last().unwrap());
+    }
+
+    #[test]
+    fn it_marks_rational_surfaces() {
+        let knots = [0, 0, 0, 0, 1, 2, 2, 2, 2];
+        let mut points = (0..20)
+            .map(|k| Vector4::new((k % 5) as f64, (k / 5) as f64, 0., 1.))
+            .collect::<Vec<_>>();
+        points[6].w = 2.;
+        let iges = written(
+            IgesWriter::default()
+                .with_surface("a", &knots, &knots[1..], &points)
+                .unwrap()
+                .with_surface("b", &knots[1..], &knots[1..], &patch())
+                .unwrap(),
+        );
+        let parameters = iges
+            .lines()
+            .filter(|l| &l[72..73] == "P")
+            .map(|l| l[..64].trim_end())
+            .collect::<String>();
+        assert!(parameters.starts_with("128,4,3,3,3,0,0,0,0,0,"));
+        assert!(parameters.contains(";128,3,3,3,3,0,0,1,0,0,"));
+
+        assert!(matches!(
+            IgesWriter::default().with_surface("", &knots, &knots, &patch()),
+            Err(IgesError::WrongCount(25, 16))
+        ));
+    }
+}
@@ -15,4 +15,13 @@
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
 
+pub mod gcode_writer;
+pub mod gltf_writer;
+pub mod iges_writer;
+pub mod obj_reader;
 pub mod obj_writer;
+pub mod ply_writer;
+pub mod step_writer;
+pub mod stl_writer;
+pub mod svg_writer;
+pub mod tsm;
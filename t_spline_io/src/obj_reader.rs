@@ -0,0 +1,366 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use num_traits::FromPrimitive;
+use std::collections::{BTreeMap, VecDeque};
+use std::io::BufRead;
+use t_spline::Vector4;
+use t_spline::builder::{BuildError, TMeshBuilder};
+use t_spline::control_mesh::ControlMeshMut;
+use t_spline::uv_mesh::ids::VertID;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ObjError {
+    #[error("line {0}: {1}")]
+    Syntax(usize, String),
+    #[error("line {0}: unknown vertex {1}")]
+    UnknownVertex(usize, isize),
+    #[error("face {0} is not a quad")]
+    NotAQuad(usize),
+    #[error("face {0} has no texture coordinates")]
+    MissingTexture(usize),
+    #[error("vertex {0} is given more than one parameter")]
+    Seam(usize),
+    #[error("the quads do not form a grid around vertex {0}")]
+    NotAGrid(usize),
+    #[error("vertex {0} is not used by any face")]
+    UnusedVertex(usize),
+    #[error("face {0} is not connected to the first face")]
+    Disconnected(usize),
+    #[error("the file has no faces")]
+    Empty,
+    #[error("failed to cast")]
+    FailedToCast,
+    #[error(transparent)]
+    Build(#[from] BuildError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// How the control points of a cage are placed in the parametric domain
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Parameterization {
+    /// Lay the quads out as a grid of unit knot intervals, walking from the first face
+    #[default]
+    Grid,
+    /// Use the `vt` coordinates of the faces multiplied by the scale and rounded, so
+    /// faces may be any axis aligned polygon and knot intervals differ
+    Texture(f64),
+}
+
+/// Reads the polygons of an OBJ file as the control cage of a T-spline, to model
+/// cages in other tools.
+///
+/// Only `v`, `vt` and `f` records are used, a fourth value of `v` is the weight.
+/// Faces must be counter clockwise in the parametric domain.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ObjReader {
+    parameterization: Parameterization,
+}
+
+struct Obj {
+    points: Vec<[f64; 4]>,
+    textures: Vec<(f64, f64)>,
+    /// Corners of every face with their texture coordinate
+    faces: Vec<Vec<(usize, Option<usize>)>>,
+}
+
+impl ObjReader {
+    pub fn with_parameterization(mut self, parameterization: Parameterization) -> Self {
+        self.parameterization = parameterization;
+        self
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn read<M: ControlMeshMut + Default>(&self, r: impl BufRead) -> Result<M, ObjError> {
+        let obj = parse(r)?;
+        if obj.faces.is_empty() {
+            return Err(ObjError::Empty);
+        }
+        let mut used = vec![false; obj.points.len()];
+        obj.faces
+            .iter()
+            .flatten()
+            .for_each(|&(v, _)| used[v] = true);
+        if let Some(v) = used.iter().position(|used| !used) {
+            return Err(ObjError::UnusedVertex(v));
+        }
+        let parameters = match self.parameterization {
+            Parameterization::Grid => grid(&obj)?,
+            Parameterization::Texture(scale) => texture(&obj, scale)?,
+        };
+
+        let cast = |v: f64| M::Unit::from_f64(v).ok_or(ObjError::FailedToCast);
+        let mut builder = TMeshBuilder::new();
+        for (p, (s, t)) in obj.points.iter().zip(parameters) {
+            builder.vertex(
+                s,
+                t,
+                Vector4::new(cast(p[0])?, cast(p[1])?, cast(p[2])?, cast(p[3])?),
+            );
+        }
+        for face in &obj.faces {
+            builder.face(&face.iter().map(|&(v, _)| VertID(v)).collect::<Vec<_>>());
+        }
+        Ok(builder.build()?)
+    }
+}
+
+fn parse(r: impl BufRead) -> Result<Obj, ObjError> {
+    let mut obj = Obj {
+        points: Vec::new(),
+        textures: Vec::new(),
+        faces: Vec::new(),
+    };
+    for (i, line) in r.lines().enumerate() {
+        let (n, line) = (i + 1, line?);
+        let mut fields = line.split_whitespace();
+        let syntax = |message: String| ObjError::Syntax(n, message);
+        let number = |v: &str| v.parse::<f64>().map_err(|e| syntax(e.to_string()));
+        match fields.next() {
+            Some("v") => {
+                let values = fields.map(number).collect::<Result<Vec<_>, _>>()?;
+                match values[..] {
+                    [x, y, z] => obj.points.push([x, y, z, 1.]),
+                    [x, y, z, w] => obj.points.push([x, y, z, w]),
+                    _ => return Err(syntax("a vertex needs x, y, z and an optional w".into())),
+                }
+            }
+            Some("vt") => {
+                let values = fields.map(number).collect::<Result<Vec<_>, _>>()?;
+                let [u, v, ..] = values[..] else {
+                    return Err(syntax("a texture coordinate needs u and v".into()));
+                };
+                obj.textures.push((u, v));
+            }
+            Some("f") => {
+                // indices start at 1, negative ones count back from the last record
+                let index = |v: &str, count: usize| {
+                    let i = v.parse::<isize>().map_err(|e| syntax(e.to_string()))?;
+                    let resolved = if i < 0 { count as isize + i } else { i - 1 };
+                    usize::try_from(resolved)
+                        .ok()
+                        .filter(|&r| r < count)
+                        .ok_or(ObjError::UnknownVertex(n, i))
+                };
+                let corners = fields
+                    .map(|corner| {
+                        let mut parts = corner.split('/');
+                        let v = index(parts.next().unwrap_or_default(), obj.points.len())?;
+                        let vt = match parts.next().filter(|vt| !vt.is_empty()) {
+                            Some(vt) => Some(index(vt, obj.textures.len())?),
+                            None => None,
+                        };
+                        Ok((v, vt))
+                    })
+                    .collect::<Result<Vec<_>, ObjError>>()?;
+                obj.faces.push(corners);
+            }
+            _ => {}
+        }
+    }
+    Ok(obj)
+}
+
+/// Knots of every vertex from the texture coordinates of the faces around it
+fn texture(obj: &Obj, scale: f64) -> Result<Vec<(isize, isize)>, ObjError> {
+    let mut parameters = vec![None; obj.points.len()];
+    for (f, face) in obj.faces.iter().enumerate() {
+        for &(v, vt) in face {
+            let (u, w) = obj.textures[vt.ok_or(ObjError::MissingTexture(f))?];
+            let st = ((u * scale).round() as isize, (w * scale).round() as isize);
+            if *parameters[v].get_or_insert(st) != st {
+                return Err(ObjError::Seam(v));
+            }
+        }
+    }
+    // every vertex is used by a face, so all of them have a parameter
+    Ok(parameters
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect())
+}
+
+/// Knots of every vertex from walking the quads outwards from the first one, each
+/// face continuing across the side it shares with a placed face
+fn grid(obj: &Obj) -> Result<Vec<(isize, isize)>, ObjError> {
+    let mut sides = BTreeMap::new();
+    for (f, face) in obj.faces.iter().enumerate() {
+        if face.len() != 4 {
+            return Err(ObjError::NotAQuad(f));
+        }
+        for i in 0..4 {
+            sides.insert((face[i].0, face[(i + 1) % 4].0), (f, i));
+        }
+    }
+
+    let mut parameters: Vec<Option<(isize, isize)>> = vec![None; obj.points.len()];
+    let mut placed = vec![false; obj.faces.len()];
+    let mut queue = VecDeque::from([(0, 0, ((0, 0), (1, 0)))]);
+    while let Some((f, i, (a, b))) = queue.pop_front() {
+        if placed[f] {
+            continue;
+        }
+        placed[f] = true;
+
+        // the next side turns a quarter counter clockwise
+        let turn = (-(b.1 - a.1), b.0 - a.0);
+        let corners = [
+            a,
+            b,
+            (b.0 + turn.0, b.1 + turn.1),
+            (a.0 + turn.0, a.1 + turn.1),
+        ];
+        let face = &obj.faces[f];
+        for (k, st) in corners.into_iter().enumerate() {
+            let v = face[(i + k) % 4].0;
+            if *parameters[v].get_or_insert(st) != st {
+                return Err(ObjError::NotAGrid(v));
+            }
+        }
+        for k in 0..4 {
+            let (from, to) = (face[(i + k) % 4].0, face[(i + k + 1) % 4].0);
+            if let Some(&(g, j)) = sides.get(&(to, from))
+                && !placed[g]
+            {
+                let (s, e) = (corners[(k + 1) % 4], corners[k]);
+                queue.push_back((g, j, (s, e)));
+            }
+        }
+    }
+    if let Some(f) = placed.iter().position(|placed| !placed) {
+        return Err(ObjError::Disconnected(f));
+    }
+
+    let parameters = parameters
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect::<Vec<_>>();
+    let s0 = parameters.iter().map(|p| p.0).min().unwrap_or_default();
+    let t0 = parameters.iter().map(|p| p.1).min().unwrap_or_default();
+    Ok(parameters
+        .into_iter()
+        .map(|(s, t)| (s - s0, t - t0))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::TSpline;
+    use t_spline::control_mesh::ControlMesh;
+    use t_spline::uv_mesh::UVMesh;
+
+    /// A 3 x 2 grid of quads bent up along its middle, as exported from a modeller
+    const CAGE: &str = "\
+# cage
+o cage
+v 0 0 0
+v 1 0 1
+v 2 0 1
+v 3 0 0
+v 0 1 0
+v 1 1 1
+v 2 1 1
+v 3 1 0
+v 0 2 0
+v 1 2 1
+v 2 2 1
+v 3 2 0 2
+vt 0 0
+vt 0.5 0
+vt 1 0
+vt 0 1
+vt 0.5 1
+vt 1 1
+s off
+f 1 2 6 5
+f 2 3 7 6
+f 3 4 8 7
+f 5 6 10 9
+f 6 7 11 10
+f 7 8 12 11
+";
+
+    #[test]
+    fn it_lays_quads_out_as_a_grid() {
+        let mesh: TSpline = ObjReader::default().read(CAGE.as_bytes()).unwrap();
+
+        assert_eq!(6, mesh.faces().count());
+        let knots = mesh.points().iter().map(|p| (p.s, p.t)).collect::<Vec<_>>();
+        assert_eq!((0, 0), knots[0]);
+        assert_eq!((3, 0), knots[3]);
+        assert_eq!((3, 2), knots[11]);
+        assert_eq!(2., mesh.control_points()[11].w);
+        assert_eq!(1., mesh.control_points()[1].z);
+    }
+
+    #[test]
+    fn it_uses_texture_coordinates() {
+        // two faces spanning the middle knot interval, one wide
+        let obj = "\
+v 0 0 0
+v 1 0 0
+v 3 0 0
+v 0 1 0
+v 1 1 0
+v 3 1 0
+vt 0 0
+vt 0.25 0
+vt 1 0
+vt 0 0.25
+vt 0.25 0.25
+vt 1 0.25
+f 1/1 2/2 5/5 4/4
+f 2/2 3/3 6/6 5/5
+";
+        let mesh: TSpline = ObjReader::default()
+            .with_parameterization(Parameterization::Texture(4.))
+            .read(obj.as_bytes())
+            .unwrap();
+        let knots = mesh.points().iter().map(|p| (p.s, p.t)).collect::<Vec<_>>();
+        assert_eq!(vec![(0, 0), (1, 0), (4, 0), (0, 1), (1, 1), (4, 1)], knots);
+    }
+
+    #[test]
+    fn it_rejects_cages_that_are_not_grids() {
+        let triangle = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+        assert!(matches!(
+            ObjReader::default().read::<TSpline>(triangle.as_bytes()),
+            Err(ObjError::NotAQuad(0))
+        ));
+        assert!(matches!(
+            ObjReader::default().read::<TSpline>("v 0 0 0\n".as_bytes()),
+            Err(ObjError::Empty)
+        ));
+
+        let unused = format!("{CAGE}v 9 9 9\n");
+        assert!(matches!(
+            ObjReader::default().read::<TSpline>(unused.as_bytes()),
+            Err(ObjError::UnusedVertex(12))
+        ));
+
+        // a band of quads closing on itself
+        let band = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nv 0 0 1\nv 1 0 1\nv 1 1 1\nv 0 1 1\n\
+            f 1 2 6 5\nf 2 3 7 6\nf 3 4 8 7\nf 4 1 5 8\n";
+        assert!(matches!(
+            ObjReader::default().read::<TSpline>(band.as_bytes()),
+            Err(ObjError::NotAGrid(_))
+        ));
+    }
+}
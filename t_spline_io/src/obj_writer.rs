@@ -14,90 +14,268 @@
  * You should have received a copy of the GNU General Public License
  * along with this program.  If not, see <https://www.gnu.org/licenses/>.
  */
-//
-// use std::fmt::Write;
-// use t_spline::uv_mesh::UVMesh;
-// use t_spline::{Numeric, Point3, TSpline};
-//
-// #[derive(Debug, Default, Clone)]
-// pub struct ObjWriter {
-//     obj: String,
-//     vertex_count: usize,
-// }
-//
-// impl ObjWriter {
-//     pub fn with_points<T: Numeric + 'static>(
-//         mut self,
-//         name: &str,
-//         points: &[Point3<T>],
-//     ) -> Result<Self, std::fmt::Error> {
-//         writeln!(self.obj, r"o {name}")?;
-//
-//         for point in points {
-//             self.vertex_count += 1;
-//             writeln!(self.obj, "v {} {} {}", point.x, point.y, point.z)?;
-//         }
-//
-//         Ok(self)
-//     }
-//
-//     pub fn with_triangles<T: Numeric + 'static>(
-//         mut self,
-//         name: &str,
-//         points: &[Point3<T>],
-//         triangles: &[[usize; 3]],
-//     ) -> Result<Self, std::fmt::Error> {
-//         let vertex_offset = self.vertex_count + 1;
-//         writeln!(self.obj, r"o {name}")?;
-//
-//         for point in points {
-//             self.vertex_count += 1;
-//             writeln!(self.obj, "v {} {} {}", point.x, point.y, point.z)?;
-//         }
-//
-//         for t in triangles {
-//             writeln!(
-//                 self.obj,
-//                 "f {} {} {}",
-//                 t[0] + vertex_offset,
-//                 t[1] + vertex_offset,
-//                 t[2] + vertex_offset
-//             )?;
-//         }
-//
-//         Ok(self)
-//     }
-//
-//     pub fn with_control_surface<T: Numeric + 'static>(
-//         mut self,
-//         name: &str,
-//         mesh: &TSpline<T>,
-//     ) -> Result<Self, std::fmt::Error> {
-//         let vertex_offset = self.vertex_count + 1;
-//         writeln!(self.obj, r"o {name}")?;
-//
-//         for point in &mesh.vertices {
-//             self.vertex_count += 1;
-//             writeln!(
-//                 self.obj,
-//                 "v {} {} {}",
-//                 point.geometry.x, point.geometry.y, point.geometry.z
-//             )?;
-//         }
-//
-//         for edge in &mesh.edges {
-//             writeln!(
-//                 self.obj,
-//                 "l {} {}",
-//                 edge.origin.0 + vertex_offset,
-//                 mesh.edge(edge.next).origin.0 + vertex_offset
-//             )?;
-//         }
-//
-//         Ok(self)
-//     }
-//
-//     pub fn write(self, w: &mut impl std::io::Write) -> std::io::Result<()> {
-//         write!(w, "{}", self.obj)
-//     }
-// }
+use std::fmt::Write;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::{Numeric, Point3, Vector3};
+
+#[derive(Debug, Default, Clone)]
+pub struct ObjWriter {
+    obj: String,
+    vertex_count: usize,
+    normal_count: usize,
+}
+
+impl ObjWriter {
+    pub fn with_points<T: Numeric + 'static>(
+        mut self,
+        name: &str,
+        points: &[Point3<T>],
+    ) -> Result<Self, std::fmt::Error> {
+        writeln!(self.obj, r"o {name}")?;
+
+        for point in points {
+            self.vertex_count += 1;
+            writeln!(self.obj, "v {} {} {}", point.x, point.y, point.z)?;
+        }
+
+        Ok(self)
+    }
+
+    pub fn with_triangles<T: Numeric + 'static>(
+        self,
+        name: &str,
+        points: &[Point3<T>],
+        triangles: &[[usize; 3]],
+    ) -> Result<Self, std::fmt::Error> {
+        self.with_object(name, None, points, triangles)
+    }
+
+    /// Like [ObjWriter::with_triangles], assigning the faces to `material` with a
+    /// `usemtl` record, so every region of a model can be shaded on its own
+    pub fn with_material_triangles<T: Numeric + 'static>(
+        self,
+        name: &str,
+        material: &str,
+        points: &[Point3<T>],
+        triangles: &[[usize; 3]],
+    ) -> Result<Self, std::fmt::Error> {
+        self.with_object(name, Some(material), points, triangles)
+    }
+
+    fn with_object<T: Numeric + 'static>(
+        mut self,
+        name: &str,
+        material: Option<&str>,
+        points: &[Point3<T>],
+        triangles: &[[usize; 3]],
+    ) -> Result<Self, std::fmt::Error> {
+        let vertex_offset = self.vertex_count + 1;
+        writeln!(self.obj, r"o {name}")?;
+        if let Some(material) = material {
+            writeln!(self.obj, r"usemtl {material}")?;
+        }
+
+        for point in points {
+            self.vertex_count += 1;
+            writeln!(self.obj, "v {} {} {}", point.x, point.y, point.z)?;
+        }
+
+        for t in triangles {
+            writeln!(
+                self.obj,
+                "f {} {} {}",
+                t[0] + vertex_offset,
+                t[1] + vertex_offset,
+                t[2] + vertex_offset
+            )?;
+        }
+
+        Ok(self)
+    }
+
+    /// Write points with a `vn` normal each, pairing them up by index
+    pub fn with_oriented_points<T: Numeric + 'static>(
+        mut self,
+        name: &str,
+        points: &[Point3<T>],
+        normals: &[Vector3<T>],
+    ) -> Result<Self, std::fmt::Error> {
+        writeln!(self.obj, r"o {name}")?;
+        self.write_oriented(points, normals)?;
+        Ok(self)
+    }
+
+    /// Like [ObjWriter::with_triangles], with a normal for every point so the faces
+    /// are shaded smoothly
+    pub fn with_shaded_triangles<T: Numeric + 'static>(
+        mut self,
+        name: &str,
+        points: &[Point3<T>],
+        normals: &[Vector3<T>],
+        triangles: &[[usize; 3]],
+    ) -> Result<Self, std::fmt::Error> {
+        let vertex_offset = self.vertex_count + 1;
+        let normal_offset = self.normal_count + 1;
+        writeln!(self.obj, r"o {name}")?;
+        self.write_oriented(points, normals)?;
+
+        for t in triangles {
+            let [a, b, c] = t.map(|i| (i + vertex_offset, i + normal_offset));
+            writeln!(
+                self.obj,
+                "f {}//{} {}//{} {}//{}",
+                a.0, a.1, b.0, b.1, c.0, c.1
+            )?;
+        }
+
+        Ok(self)
+    }
+
+    fn write_oriented<T: Numeric + 'static>(
+        &mut self,
+        points: &[Point3<T>],
+        normals: &[Vector3<T>],
+    ) -> Result<(), std::fmt::Error> {
+        for (point, normal) in points.iter().zip(normals) {
+            self.vertex_count += 1;
+            self.normal_count += 1;
+            writeln!(self.obj, "v {} {} {}", point.x, point.y, point.z)?;
+            writeln!(self.obj, "vn {} {} {}", normal.x, normal.y, normal.z)?;
+        }
+        Ok(())
+    }
+
+    /// Write the control cage of `mesh` as points connected by lines
+    pub fn with_control_surface(
+        mut self,
+        name: &str,
+        mesh: &impl ControlMesh,
+    ) -> Result<Self, std::fmt::Error> {
+        let vertex_offset = self.vertex_count + 1;
+        writeln!(self.obj, r"o {name}")?;
+
+        for point in mesh.control_points() {
+            self.vertex_count += 1;
+            writeln!(self.obj, "v {} {} {}", point.x, point.y, point.z)?;
+        }
+
+        for (i, edge) in mesh.edges().iter().enumerate() {
+            // twins share a line, only write it once
+            if edge.twin.is_some_and(|twin| twin.0 < i) {
+                continue;
+            }
+
+            writeln!(
+                self.obj,
+                "l {} {}",
+                edge.origin.0 + vertex_offset,
+                mesh.next_edge(edge).origin.0 + vertex_offset
+            )?;
+        }
+
+        Ok(self)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(bytes = self.obj.len()))
+    )]
+    pub fn write(self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        write!(w, "{}", self.obj)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::TSpline;
+    use t_spline_commands::plane::new_plane;
+
+    fn written(writer: ObjWriter) -> String {
+        let mut out = Vec::new();
+        writer.write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn it_writes_points() {
+        let obj = written(
+            ObjWriter::default()
+                .with_points("Points", &[Point3::new(0., 1., 2.)])
+                .unwrap(),
+        );
+
+        assert_eq!("o Points\nv 0 1 2\n", obj);
+    }
+
+    #[test]
+    fn it_offsets_triangle_indices() {
+        let points = [
+            Point3::new(0., 0., 0.),
+            Point3::new(1., 0., 0.),
+            Point3::new(0., 1., 0.),
+        ];
+        let obj = written(
+            ObjWriter::default()
+                .with_points("A", &points)
+                .unwrap()
+                .with_triangles("B", &points, &[[0, 1, 2]])
+                .unwrap(),
+        );
+
+        assert!(obj.ends_with("f 4 5 6\n"));
+    }
+
+    #[test]
+    fn it_assigns_materials_per_object() {
+        let points = [
+            Point3::new(0., 0., 0.),
+            Point3::new(1., 0., 0.),
+            Point3::new(0., 1., 0.),
+        ];
+        let obj = written(
+            ObjWriter::default()
+                .with_material_triangles("Top", "red", &points, &[[0, 1, 2]])
+                .unwrap()
+                .with_material_triangles("Side", "blue", &points, &[[0, 1, 2]])
+                .unwrap(),
+        );
+
+        assert!(obj.starts_with("o Top\nusemtl red\nv "));
+        assert!(obj.contains("o Side\nusemtl blue\nv "));
+        assert!(obj.ends_with("f 4 5 6\n"));
+    }
+
+    #[test]
+    fn it_writes_normals() {
+        let points = [
+            Point3::new(0., 0., 0.),
+            Point3::new(1., 0., 0.),
+            Point3::new(0., 1., 0.),
+        ];
+        let normals = [Vector3::z(); 3];
+        let obj = written(
+            ObjWriter::default()
+                .with_oriented_points("A", &points[..1], &normals[..1])
+                .unwrap()
+                .with_shaded_triangles("B", &points, &normals, &[[0, 1, 2]])
+                .unwrap(),
+        );
+
+        assert_eq!(4, obj.lines().filter(|l| l.starts_with("vn 0 0 1")).count());
+        assert!(obj.ends_with("f 2//2 3//3 4//4\n"));
+    }
+
+    #[test]
+    fn it_writes_shared_cage_lines_once() {
+        let mesh: TSpline = new_plane(3, 2, 2., 1.).unwrap();
+        let obj = written(
+            ObjWriter::default()
+                .with_control_surface("Cage", &mesh)
+                .unwrap(),
+        );
+
+        assert_eq!(6, obj.lines().filter(|l| l.starts_with("v ")).count());
+        assert_eq!(7, obj.lines().filter(|l| l.starts_with("l ")).count());
+    }
+}
@@ -0,0 +1,235 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use num_traits::ToPrimitive;
+use std::io;
+use t_spline::control_mesh::ControlMesh;
+use t_spline::{Numeric, Point3};
+
+/// Writes points, edges and faces as an ASCII PLY file.
+///
+/// Vertices may carry a color, as soon as one does every vertex is written with one and
+/// the others are white. Indices of edges and faces are relative to the points passed in
+/// the same call.
+#[derive(Debug, Default, Clone)]
+pub struct PlyWriter {
+    vertices: Vec<([f64; 3], Option<[u8; 3]>)>,
+    edges: Vec<[usize; 2]>,
+    faces: Vec<Vec<usize>>,
+}
+
+impl PlyWriter {
+    pub fn with_points<T: Numeric + 'static>(mut self, points: &[Point3<T>]) -> Self {
+        self.push_points(points, None);
+        self
+    }
+
+    /// Write the control cage of `mesh` as points connected by edges
+    pub fn with_control_surface(mut self, mesh: &impl ControlMesh) -> Self {
+        let offset = self.vertices.len();
+        for cp in mesh.control_points() {
+            let coords = [cp.x, cp.y, cp.z].map(|v| v.to_f64().unwrap_or(f64::NAN));
+            self.vertices.push((coords, None));
+        }
+        for (i, edge) in mesh.edges().iter().enumerate() {
+            // twins share an edge, only write it once
+            if edge.twin.is_some_and(|twin| twin.0 < i) {
+                continue;
+            }
+            self.edges.push([
+                edge.origin.0 + offset,
+                mesh.next_edge(edge).origin.0 + offset,
+            ]);
+        }
+        self
+    }
+
+    pub fn with_triangles<T: Numeric + 'static>(
+        mut self,
+        points: &[Point3<T>],
+        triangles: &[[usize; 3]],
+    ) -> Self {
+        let offset = self.push_points(points, None);
+        self.push_faces(offset, triangles);
+        self
+    }
+
+    /// Like [PlyWriter::with_triangles], with a color for every point
+    pub fn with_colored_triangles<T: Numeric + 'static>(
+        mut self,
+        points: &[Point3<T>],
+        colors: &[[u8; 3]],
+        triangles: &[[usize; 3]],
+    ) -> Self {
+        let offset = self.push_points(points, Some(colors));
+        self.push_faces(offset, triangles);
+        self
+    }
+
+    fn push_points<T: Numeric + 'static>(
+        &mut self,
+        points: &[Point3<T>],
+        colors: Option<&[[u8; 3]]>,
+    ) -> usize {
+        let offset = self.vertices.len();
+        for (i, p) in points.iter().enumerate() {
+            let coords = [p.x, p.y, p.z].map(|v| v.to_f64().unwrap_or(f64::NAN));
+            let color = colors.and_then(|colors| colors.get(i).copied());
+            self.vertices.push((coords, color));
+        }
+        offset
+    }
+
+    fn push_faces(&mut self, offset: usize, triangles: &[[usize; 3]]) {
+        self.faces
+            .extend(triangles.iter().map(|t| t.map(|v| v + offset).to_vec()));
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(vertices = self.vertices.len()))
+    )]
+    pub fn write(self, w: &mut impl io::Write) -> io::Result<()> {
+        let colored = self.vertices.iter().any(|(_, color)| color.is_some());
+        self.write_header(w, colored)?;
+
+        for (p, color) in &self.vertices {
+            write!(w, "{} {} {}", p[0], p[1], p[2])?;
+            if colored {
+                let [r, g, b] = color.unwrap_or([255; 3]);
+                write!(w, " {r} {g} {b}")?;
+            }
+            writeln!(w)?;
+        }
+        for [a, b] in &self.edges {
+            writeln!(w, "{a} {b}")?;
+        }
+        for face in &self.faces {
+            write!(w, "{}", face.len())?;
+            for v in face {
+                write!(w, " {v}")?;
+            }
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    fn write_header(&self, out: &mut impl io::Write, colored: bool) -> io::Result<()> {
+        writeln!(out, "ply")?;
+        writeln!(out, "format ascii 1.0")?;
+        writeln!(out, "element vertex {}", self.vertices.len())?;
+        for axis in ["x", "y", "z"] {
+            writeln!(out, "property double {axis}")?;
+        }
+        if colored {
+            for channel in ["red", "green", "blue"] {
+                writeln!(out, "property uchar {channel}")?;
+            }
+        }
+        if !self.edges.is_empty() {
+            writeln!(out, "element edge {}", self.edges.len())?;
+            writeln!(out, "property int vertex1")?;
+            writeln!(out, "property int vertex2")?;
+        }
+        if !self.faces.is_empty() {
+            writeln!(out, "element face {}", self.faces.len())?;
+            writeln!(out, "property list uchar int vertex_indices")?;
+        }
+        writeln!(out, "end_header")
+    }
+}
+
+/// Colors for `values` from blue at the smallest through to red at the largest, to
+/// color vertices by a scalar such as curvature or the number of blending functions
+pub fn heat_colors(values: &[f64]) -> Vec<[u8; 3]> {
+    let (lo, hi) = values
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+    values
+        .iter()
+        .map(|&v| {
+            let heat = if hi > lo && v.is_finite() {
+                ((v - lo) / (hi - lo)).clamp(0., 1.)
+            } else {
+                0.
+            };
+            let channel = |c: f64| (c * 255.).round() as u8;
+            // through green in the middle, so the map stays bright
+            [
+                channel((2. * heat - 1.).max(0.)),
+                channel(1. - (2. * heat - 1.).abs()),
+                channel((1. - 2. * heat).max(0.)),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::TSpline;
+    use t_spline_commands::plane::new_plane;
+
+    fn written(writer: PlyWriter) -> String {
+        let mut out = Vec::new();
+        writer.write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn it_writes_a_control_cage() {
+        let mesh: TSpline = new_plane(2, 2, 1., 1.).unwrap();
+        let ply = written(PlyWriter::default().with_control_surface(&mesh));
+
+        assert!(ply.starts_with("ply\nformat ascii 1.0\nelement vertex 4\n"));
+        assert!(ply.contains("element edge 4\n"));
+        assert!(!ply.contains("element face"));
+        assert!(!ply.contains("red"));
+        assert_eq!(
+            4 + 4,
+            ply.split("end_header\n").nth(1).unwrap().lines().count()
+        );
+    }
+
+    #[test]
+    fn it_writes_colored_faces() {
+        let points = [
+            Point3::new(0., 0., 0.),
+            Point3::new(1., 0., 0.),
+            Point3::new(0., 1., 0.),
+        ];
+        let colors = heat_colors(&[0., 0.5, 1.]);
+        assert_eq!(vec![[0, 0, 255], [0, 255, 0], [255, 0, 0]], colors);
+
+        let ply = written(
+            PlyWriter::default()
+                .with_points(&points[..1])
+                .with_colored_triangles(&points, &colors, &[[0, 1, 2]]),
+        );
+
+        assert!(ply.contains("element vertex 4\n"));
+        assert!(ply.contains("property uchar red\n"));
+        assert!(ply.contains("element face 1\nproperty list uchar int vertex_indices\n"));
+        let body: Vec<_> = ply.split("end_header\n").nth(1).unwrap().lines().collect();
+        assert_eq!("0 0 0 255 255 255", body[0]);
+        assert_eq!("0 0 0 0 0 255", body[1]);
+        assert_eq!("3 1 2 3", body[4]);
+    }
+}
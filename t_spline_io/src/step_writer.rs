@@ -0,0 +1,317 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fmt::Write;
+use t_spline::Vector4;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StepError {
+    #[error("expected {0} control points, found {1}")]
+    WrongCount(usize, usize),
+    #[error("knot vectors need at least 8 knots and may not decrease")]
+    InvalidKnots,
+    #[error("control points need finite coordinates and a positive weight")]
+    InvalidControlPoint,
+}
+
+/// Writes bicubic NURBS surfaces as an untrimmed STEP AP214 part for CAD tools.
+///
+/// Every surface becomes a `B_SPLINE_SURFACE_WITH_KNOTS`, rational only when a weight
+/// differs from `1`, collected in a `GEOMETRIC_SET` of a single product.
+#[derive(Debug, Clone)]
+pub struct StepWriter {
+    name: String,
+    entities: Vec<String>,
+    surfaces: Vec<usize>,
+}
+
+impl Default for StepWriter {
+    fn default() -> Self {
+        Self {
+            name: "t-spline".to_string(),
+            entities: Vec::new(),
+            surfaces: Vec::new(),
+        }
+    }
+}
+
+impl StepWriter {
+    /// Name of the product, written in the header
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Add a surface with clamped cubic knot vectors along `s` and `t` and its control
+    /// points in rows of increasing `t`, the weight of each in `w`, like a
+    /// `NurbsSurface` of `t_spline_commands`. CAD tools only accept positive weights.
+    pub fn with_surface(
+        mut self,
+        name: &str,
+        s_knots: &[isize],
+        t_knots: &[isize],
+        control_points: &[Vector4<f64>],
+    ) -> Result<Self, StepError> {
+        for knots in [s_knots, t_knots] {
+            if knots.len() < 8 || knots.windows(2).any(|k| k[1] < k[0]) {
+                return Err(StepError::InvalidKnots);
+            }
+        }
+        let (width, height) = (s_knots.len() - 4, t_knots.len() - 4);
+        if control_points.len() != width * height {
+            return Err(StepError::WrongCount(width * height, control_points.len()));
+        }
+        if control_points
+            .iter()
+            .any(|p| !(p.iter().all(|v| v.is_finite()) && p.w > 0.))
+        {
+            return Err(StepError::InvalidControlPoint);
+        }
+
+        let first = self.entities.len() + 1;
+        for p in control_points {
+            self.entities.push(format!(
+                "CARTESIAN_POINT('',({},{},{}))",
+                real(p.x),
+                real(p.y),
+                real(p.z)
+            ));
+        }
+
+        // STEP lists control points with the index along `u` first
+        let grid = |value: &dyn Fn(usize) -> String| {
+            let rows = (0..width).map(|i| {
+                let row = (0..height).map(|j| value(j * width + i));
+                format!("({})", row.collect::<Vec<_>>().join(","))
+            });
+            format!("({})", rows.collect::<Vec<_>>().join(","))
+        };
+        let points = grid(&|k| format!("#{}", first + k));
+        let (s_mult, s_knots) = multiplicities(s_knots);
+        let (t_mult, t_knots) = multiplicities(t_knots);
+        let knots = format!("({s_mult}),({t_mult}),({s_knots}),({t_knots}),.UNSPECIFIED.");
+        let name = escape(name);
+
+        let surface = if control_points.iter().all(|p| p.w == 1.) {
+            format!(
+                "B_SPLINE_SURFACE_WITH_KNOTS('{name}',3,3,{points},.UNSPECIFIED.,.F.,.F.,.F.,{knots})"
+            )
+        } else {
+            let weights = grid(&|k| real(control_points[k].w));
+            // attributes of a complex entity are grouped by their supertype
+            format!(
+                "(BOUNDED_SURFACE() B_SPLINE_SURFACE(3,3,{points},.UNSPECIFIED.,.F.,.F.,.F.) \
+                 B_SPLINE_SURFACE_WITH_KNOTS({knots}) GEOMETRIC_REPRESENTATION_ITEM() \
+                 RATIONAL_B_SPLINE_SURFACE({weights}) REPRESENTATION_ITEM('{name}') SURFACE())"
+            )
+        };
+        self.entities.push(surface);
+        self.surfaces.push(self.entities.len());
+        Ok(self)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(surfaces = self.surfaces.len()))
+    )]
+    pub fn write(mut self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        let name = escape(&self.name);
+        let surfaces = self
+            .surfaces
+            .iter()
+            .map(|id| format!("#{id}"))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        // the product structure and units CAD tools expect around the geometry
+        let n = self.entities.len();
+        let id = |k: usize| n + k;
+        self.entities.extend([
+            "APPLICATION_CONTEXT('automotive design')".to_string(),
+            format!(
+                "APPLICATION_PROTOCOL_DEFINITION('international standard','automotive_design',2000,#{})",
+                id(1)
+            ),
+            format!("PRODUCT_CONTEXT('',#{},'mechanical')", id(1)),
+            format!("PRODUCT('{name}','{name}','',(#{}))", id(3)),
+            format!("PRODUCT_DEFINITION_FORMATION('','',#{})", id(4)),
+            format!("PRODUCT_DEFINITION_CONTEXT('part definition',#{},'design')", id(1)),
+            format!("PRODUCT_DEFINITION('design','',#{},#{})", id(5), id(6)),
+            format!("PRODUCT_DEFINITION_SHAPE('','',#{})", id(7)),
+            "(LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.))".to_string(),
+            "(NAMED_UNIT(*) PLANE_ANGLE_UNIT() SI_UNIT($,.RADIAN.))".to_string(),
+            "(NAMED_UNIT(*) SI_UNIT($,.STERADIAN.) SOLID_ANGLE_UNIT())".to_string(),
+            format!(
+                "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-07),#{},'distance_accuracy_value','')",
+                id(9)
+            ),
+            format!(
+                "(GEOMETRIC_REPRESENTATION_CONTEXT(3) GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{})) \
+                 GLOBAL_UNIT_ASSIGNED_CONTEXT((#{},#{},#{})) REPRESENTATION_CONTEXT('',''))",
+                id(12),
+                id(9),
+                id(10),
+                id(11)
+            ),
+            format!("GEOMETRIC_SET('',({surfaces}))"),
+            format!(
+                "GEOMETRICALLY_BOUNDED_SURFACE_SHAPE_REPRESENTATION('{name}',(#{}),#{})",
+                id(14),
+                id(13)
+            ),
+            format!("SHAPE_DEFINITION_REPRESENTATION(#{},#{})", id(8), id(15)),
+        ]);
+
+        writeln!(w, "ISO-10303-21;")?;
+        writeln!(w, "HEADER;")?;
+        writeln!(w, "FILE_DESCRIPTION(('{name}'),'2;1');")?;
+        writeln!(
+            w,
+            "FILE_NAME('{name}','',(''),(''),'t-spline','t-spline','');"
+        )?;
+        writeln!(
+            w,
+            "FILE_SCHEMA(('AUTOMOTIVE_DESIGN {{ 1 0 10303 214 1 1 1 1 }}'));"
+        )?;
+        writeln!(w, "ENDSEC;")?;
+        writeln!(w, "DATA;")?;
+        for (i, entity) in self.entities.iter().enumerate() {
+            writeln!(w, "#{}={entity};", i + 1)?;
+        }
+        writeln!(w, "ENDSEC;")?;
+        writeln!(w, "END-ISO-10303-21;")
+    }
+}
+
+/// The multiplicities and distinct values of a knot vector, as STEP lists them
+fn multiplicities(knots: &[isize]) -> (String, String) {
+    let mut distinct: Vec<(isize, usize)> = Vec::new();
+    for &k in knots {
+        match distinct.last_mut() {
+            Some((last, count)) if *last == k => *count += 1,
+            _ => distinct.push((k, 1)),
+        }
+    }
+    let mut out = (String::new(), String::new());
+    for (i, (k, count)) in distinct.iter().enumerate() {
+        let sep = if i == 0 { "" } else { "," };
+        let _ = write!(out.0, "{sep}{count}");
+        let _ = write!(out.1, "{sep}{}", real(*k as f64));
+    }
+    out
+}
+
+/// A real with a decimal point in its mantissa, as STEP and IGES need
+pub(crate) fn real(v: f64) -> String {
+    let s = format!("{v:E}");
+    match s.split_once('E') {
+        Some((mantissa, exponent)) if !mantissa.contains('.') => {
+            format!("{mantissa}.E{exponent}")
+        }
+        _ => s,
+    }
+}
+
+/// A string with its quotes doubled, as STEP escapes them
+fn escape(s: &str) -> String {
+    s.replace('\'', "''")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::Point3;
+    use t_spline_commands::nurbs::NurbsSurface;
+
+    fn written(writer: StepWriter) -> String {
+        let mut out = Vec::new();
+        writer.write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn it_writes_a_surface() {
+        let points = (0..20)
+            .map(|k| Point3::new((k % 5) as f64, (k / 5) as f64, 0.))
+            .collect::<Vec<_>>();
+        let surface = NurbsSurface::new(
+            vec![0, 0, 0, 0, 1, 2, 2, 2, 2],
+            vec![0, 0, 0, 0, 1, 1, 1, 1],
+            &points,
+            &[1.; 20],
+        )
+        .unwrap();
+        let writer = StepWriter::default()
+            .with_name("sheet")
+            .with_surface(
+                "patch",
+                surface.s_knots(),
+                surface.t_knots(),
+                surface.control_points(),
+            )
+            .unwrap();
+
+        let step = written(writer);
+        assert!(step.starts_with("ISO-10303-21;\nHEADER;\n"));
+        assert!(step.ends_with("ENDSEC;\nEND-ISO-10303-21;\n"));
+        assert!(step.contains("FILE_SCHEMA(('AUTOMOTIVE_DESIGN"));
+        assert!(step.contains("PRODUCT('sheet','sheet','',"));
+        assert_eq!(20, step.matches("CARTESIAN_POINT").count());
+        assert!(step.contains(
+            "#21=B_SPLINE_SURFACE_WITH_KNOTS('patch',3,3,((#1,#6,#11,#16),(#2,#7,#12,#17),"
+        ));
+        assert!(step.contains("(4,1,4),(4,4),(0.E0,1.E0,2.E0),(0.E0,1.E0),.UNSPECIFIED.);"));
+        assert!(!step.contains("RATIONAL"));
+        assert!(step.contains("GEOMETRIC_SET('',(#21))"));
+    }
+
+    #[test]
+    fn it_writes_rational_surfaces() {
+        let knots = [0, 0, 0, 0, 1, 1, 1, 1];
+        let mut control_points = (0..16)
+            .map(|k| Vector4::new((k % 4) as f64, (k / 4) as f64, 0., 1.))
+            .collect::<Vec<_>>();
+        control_points[5].w = 2.;
+
+        let step = written(
+            StepWriter::default()
+                .with_surface("bezier", &knots, &knots, &control_points)
+                .unwrap(),
+        );
+        assert!(
+            step.contains("#2=CARTESIAN_POINT('',(1.E0,0.E0,0.E0));"),
+            "{step}"
+        );
+        assert!(step.contains(
+            "B_SPLINE_SURFACE_WITH_KNOTS((4,4),(4,4),(0.E0,1.E0),(0.E0,1.E0),.UNSPECIFIED.)"
+        ));
+        // the second row along u holds the points with an x of 1
+        assert!(step.contains("((#1,#5,#9,#13),(#2,#6,#10,#14),"));
+        assert!(step.contains("((1.E0,1.E0,1.E0,1.E0),(1.E0,2.E0,1.E0,1.E0),"));
+        assert!(step.contains("REPRESENTATION_ITEM('bezier')"));
+
+        assert!(matches!(
+            StepWriter::default().with_surface("", &knots, &knots, &control_points[1..]),
+            Err(StepError::WrongCount(16, 15))
+        ));
+        assert!(matches!(
+            StepWriter::default().with_surface("", &knots[1..], &knots, &control_points),
+            Err(StepError::InvalidKnots)
+        ));
+    }
+}
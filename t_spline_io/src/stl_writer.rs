@@ -0,0 +1,277 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::io;
+use t_spline::{Numeric, Point3, Vector3};
+
+/// The two encodings of STL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StlFormat {
+    #[default]
+    Binary,
+    Ascii,
+}
+
+/// Writes triangles as an STL file for 3D printing.
+///
+/// Tessellations usually repeat the points along the sides of every face, so corners
+/// closer than the weld tolerance are merged onto the first one seen and triangles
+/// collapsing in the process are dropped. Facet normals follow the counter clockwise
+/// winding of the corners.
+#[derive(Debug, Clone)]
+pub struct StlWriter {
+    name: String,
+    format: StlFormat,
+    weld_tolerance: f64,
+    triangles: Vec<[Point3<f64>; 3]>,
+}
+
+impl Default for StlWriter {
+    fn default() -> Self {
+        Self {
+            name: "t-spline".to_string(),
+            format: StlFormat::default(),
+            weld_tolerance: 1e-6,
+            triangles: Vec::new(),
+        }
+    }
+}
+
+impl StlWriter {
+    /// Name of the solid, written in the header
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn with_format(mut self, format: StlFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Size of the grid cells corners are merged within, `0` to only merge identical
+    /// corners
+    pub fn with_weld_tolerance(mut self, weld_tolerance: f64) -> Self {
+        self.weld_tolerance = weld_tolerance;
+        self
+    }
+
+    /// Add `triangles` indexing into `points`, like the output of a tessellation
+    pub fn with_triangles<T: Numeric + 'static>(
+        mut self,
+        points: &[Point3<T>],
+        triangles: &[[usize; 3]],
+    ) -> Self {
+        let cast = |p: &Point3<T>| {
+            Point3::new(
+                p.x.to_f64().unwrap_or(f64::NAN),
+                p.y.to_f64().unwrap_or(f64::NAN),
+                p.z.to_f64().unwrap_or(f64::NAN),
+            )
+        };
+        self.triangles
+            .extend(triangles.iter().map(|t| t.map(|i| cast(&points[i]))));
+        self
+    }
+
+    /// Edges used by a single triangle or by two triangles facing the same way after
+    /// welding, `0` for a watertight, consistently oriented surface
+    pub fn open_edges(&self) -> usize {
+        let (_, triangles) = self.welded();
+        let mut edges = BTreeMap::<(usize, usize), isize>::new();
+        for t in &triangles {
+            for k in 0..3 {
+                let (a, b) = (t[k], t[(k + 1) % 3]);
+                // +1 going one way and -1 the other, so a matching pair cancels
+                *edges.entry((a.min(b), a.max(b))).or_default() += if a < b { 1 } else { -1 };
+            }
+        }
+        edges.values().filter(|&&count| count != 0).count()
+    }
+
+    /// Corners merged within the tolerance and the triangles that still have an area
+    fn welded(&self) -> (Vec<Point3<f64>>, Vec<[usize; 3]>) {
+        let mut points = Vec::new();
+        let mut cells = BTreeMap::new();
+        let mut index = |p: Point3<f64>| {
+            let key = if self.weld_tolerance > 0. {
+                let cell = |v: f64| (v / self.weld_tolerance).round() as i64;
+                (cell(p.x), cell(p.y), cell(p.z))
+            } else {
+                (
+                    p.x.to_bits() as i64,
+                    p.y.to_bits() as i64,
+                    p.z.to_bits() as i64,
+                )
+            };
+            *cells.entry(key).or_insert_with(|| {
+                points.push(p);
+                points.len() - 1
+            })
+        };
+        let triangles = self
+            .triangles
+            .iter()
+            .map(|t| t.map(&mut index))
+            .filter(|[a, b, c]| a != b && b != c && c != a)
+            .collect();
+        (points, triangles)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(triangles = self.triangles.len()))
+    )]
+    pub fn write(self, w: &mut impl io::Write) -> io::Result<()> {
+        let (points, triangles) = self.welded();
+        let facets = triangles.iter().map(|t| {
+            let [a, b, c] = t.map(|i| points[i]);
+            let normal = (b - a).cross(&(c - a));
+            let length = normal.dot(&normal).sqrt();
+            let normal = if length > 0. {
+                normal / length
+            } else {
+                Vector3::zeros()
+            };
+            (normal, [a, b, c])
+        });
+
+        match self.format {
+            StlFormat::Ascii => {
+                writeln!(w, "solid {}", self.name)?;
+                for (n, corners) in facets {
+                    writeln!(w, "  facet normal {:e} {:e} {:e}", n.x, n.y, n.z)?;
+                    writeln!(w, "    outer loop")?;
+                    for p in corners {
+                        writeln!(w, "      vertex {:e} {:e} {:e}", p.x, p.y, p.z)?;
+                    }
+                    writeln!(w, "    endloop")?;
+                    writeln!(w, "  endfacet")?;
+                }
+                writeln!(w, "endsolid {}", self.name)
+            }
+            StlFormat::Binary => {
+                let mut header = [b' '; 80];
+                for (h, b) in header.iter_mut().zip(self.name.bytes()) {
+                    *h = b;
+                }
+                w.write_all(&header)?;
+                w.write_all(&(triangles.len() as u32).to_le_bytes())?;
+                for (n, corners) in facets {
+                    let mut vectors = vec![[n.x, n.y, n.z]];
+                    vectors.extend(corners.map(|p| [p.x, p.y, p.z]));
+                    for v in vectors.into_iter().flatten() {
+                        w.write_all(&(v as f32).to_le_bytes())?;
+                    }
+                    w.write_all(&0u16.to_le_bytes())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::TSpline;
+    use t_spline::uv_mesh::Boundary;
+    use t_spline_commands::plane::new_plane;
+    use t_spline_commands::triangles::triangle_mesh;
+
+    /// A tetrahedron with its faces wound outwards
+    fn tetrahedron() -> ([Point3<f64>; 4], [[usize; 3]; 4]) {
+        (
+            [
+                Point3::new(0., 0., 0.),
+                Point3::new(1., 0., 0.),
+                Point3::new(0., 1., 0.),
+                Point3::new(0., 0., 1.),
+            ],
+            [[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]],
+        )
+    }
+
+    fn written(writer: StlWriter) -> Vec<u8> {
+        let mut out = Vec::new();
+        writer.write(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn it_writes_binary_facets() {
+        let (points, triangles) = tetrahedron();
+        let writer = StlWriter::default().with_triangles(&points, &triangles);
+        assert_eq!(0, writer.open_edges());
+
+        let stl = written(writer);
+        assert_eq!(80 + 4 + 4 * 50, stl.len());
+        assert_eq!(4, u32::from_le_bytes(stl[80..84].try_into().unwrap()));
+        let float = |at: usize| f32::from_le_bytes(stl[at..at + 4].try_into().unwrap());
+        // the first facet lies in z = 0 and faces down
+        assert_eq!([0., 0., -1.], [float(84), float(88), float(92)]);
+    }
+
+    #[test]
+    fn it_writes_ascii_facets() {
+        let (points, triangles) = tetrahedron();
+        let stl = written(
+            StlWriter::default()
+                .with_name("tet")
+                .with_format(StlFormat::Ascii)
+                .with_triangles(&points, &triangles[..1]),
+        );
+        let stl = String::from_utf8(stl).unwrap();
+
+        assert!(
+            stl.starts_with("solid tet\n  facet normal 0e0 0e0 -1e0\n"),
+            "{stl}"
+        );
+        assert_eq!(3, stl.matches("vertex").count());
+        assert!(stl.ends_with("endsolid tet\n"));
+    }
+
+    #[test]
+    fn it_welds_the_faces_of_a_tessellation() {
+        let mesh: TSpline = new_plane(4, 4, 3., 3.).unwrap();
+        let tessellation = triangle_mesh(&mesh, 4, Boundary::Clamped).unwrap();
+        let writer =
+            StlWriter::default().with_triangles(&tessellation.points, &tessellation.triangles);
+
+        // only the outline of the open sheet is left
+        assert_eq!(4 * 3 * 4, writer.open_edges());
+    }
+
+    #[test]
+    fn it_welds_within_the_tolerance() {
+        let (points, triangles) = tetrahedron();
+        let mut loose = points.map(|p| [p; 4]);
+        loose[3][3].x += 1e-9;
+        let (points, triangles): (Vec<_>, Vec<_>) = triangles
+            .iter()
+            .enumerate()
+            .map(|(f, t)| (t.map(|v| loose[v][f]), [3 * f, 3 * f + 1, 3 * f + 2]))
+            .unzip();
+        let points = points.concat();
+
+        let writer = StlWriter::default().with_triangles(&points, &triangles);
+        assert_eq!(0, writer.open_edges());
+        let exact = writer.with_weld_tolerance(0.);
+        assert!(exact.open_edges() > 0);
+    }
+}
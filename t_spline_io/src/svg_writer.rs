@@ -0,0 +1,192 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+use std::fmt::Write;
+use t_spline::uv_mesh::UVMesh;
+use t_spline::uv_mesh::layout::ParametricLayout;
+
+const EDGE_COLOR: &str = "black";
+const EXTENSION_COLOR: &str = "green";
+const VIOLATION_COLOR: &str = "red";
+const JUNCTION_COLOR: &str = "blue";
+
+/// Writes the parametric layout of a T-mesh as a 2D SVG image.
+///
+/// Knot lines are drawn solid, T-junction extensions dashed. Extensions that intersect
+/// a perpendicular extension break analysis suitability and are drawn in red.
+#[derive(Debug, Clone)]
+pub struct SvgWriter {
+    svg: String,
+    scale: f64,
+    s: (isize, isize),
+    t: (isize, isize),
+}
+
+impl Default for SvgWriter {
+    fn default() -> Self {
+        Self {
+            svg: String::new(),
+            scale: 100.,
+            s: (isize::MAX, isize::MIN),
+            t: (isize::MAX, isize::MIN),
+        }
+    }
+}
+
+impl SvgWriter {
+    /// Pixels per knot interval of 1
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_t_mesh(self, mesh: &impl UVMesh) -> Result<Self, std::fmt::Error> {
+        self.with_layout(&mesh.layout())
+    }
+
+    pub fn with_layout(mut self, layout: &ParametricLayout) -> Result<Self, std::fmt::Error> {
+        for face in &layout.faces {
+            self.s = (self.s.0.min(face.s.0), self.s.1.max(face.s.1));
+            self.t = (self.t.0.min(face.t.0), self.t.1.max(face.t.1));
+        }
+
+        writeln!(self.svg, r#"<g stroke="{EDGE_COLOR}" stroke-width="2">"#)?;
+        for line in &layout.s_lines {
+            self.line((line.value, line.extent.0), (line.value, line.extent.1))?;
+        }
+        for line in &layout.t_lines {
+            self.line((line.extent.0, line.value), (line.extent.1, line.value))?;
+        }
+        writeln!(self.svg, "</g>")?;
+
+        for extension in &layout.extensions {
+            let violation = layout.extensions.iter().any(|e| e.intersects(extension));
+            let color = if violation {
+                VIOLATION_COLOR
+            } else {
+                EXTENSION_COLOR
+            };
+
+            writeln!(
+                self.svg,
+                r#"<g stroke="{color}" stroke-width="2" stroke-dasharray="6 4">"#
+            )?;
+            for line in [extension.face, extension.edge] {
+                self.line((line.s0(), line.t0()), (line.s1(), line.t1()))?;
+            }
+            writeln!(self.svg, "</g>")?;
+
+            // the face extension starts at the junction
+            let (x, y) = self.project((extension.face.s0(), extension.face.t0()));
+            let color = if violation {
+                VIOLATION_COLOR
+            } else {
+                JUNCTION_COLOR
+            };
+            writeln!(
+                self.svg,
+                r#"<circle cx="{x}" cy="{y}" r="5" fill="{color}"/>"#
+            )?;
+        }
+
+        Ok(self)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(bytes = self.svg.len()))
+    )]
+    pub fn write(self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        // an empty layout has no bounds to project
+        let (width, height) = if self.s.0 > self.s.1 {
+            (0., 0.)
+        } else {
+            let (width, height) = self.project((self.s.1, self.t.0));
+            (width + self.margin(), height + self.margin())
+        };
+
+        writeln!(
+            w,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        )?;
+        write!(w, "{}", self.svg)?;
+        writeln!(w, "</svg>")
+    }
+
+    fn line(&mut self, a: (isize, isize), b: (isize, isize)) -> Result<(), std::fmt::Error> {
+        let (x1, y1) = self.project(a);
+        let (x2, y2) = self.project(b);
+        writeln!(
+            self.svg,
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}"/>"#
+        )
+    }
+
+    fn margin(&self) -> f64 {
+        self.scale / 4.
+    }
+
+    /// Map a knot coordinate to pixels, SVG has its y axis pointing down
+    fn project(&self, (s, t): (isize, isize)) -> (f64, f64) {
+        (
+            self.margin() + (s - self.s.0) as f64 * self.scale,
+            self.margin() + (self.t.1 - t) as f64 * self.scale,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::TSpline;
+    use t_spline_commands::plane::new_plane;
+    use t_spline_commands::t_junction::t_junction;
+
+    fn written(writer: SvgWriter) -> String {
+        let mut out = Vec::new();
+        writer.write(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn it_draws_knot_lines() {
+        let mesh: TSpline = new_plane(3, 3, 1., 1.).unwrap();
+        let svg = written(SvgWriter::default().with_t_mesh(&mesh).unwrap());
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(6, svg.matches("<line").count());
+        assert!(!svg.contains("<circle"));
+    }
+
+    #[test]
+    fn it_draws_t_junction_extensions() {
+        let mesh: TSpline = t_junction();
+        let svg = written(SvgWriter::default().with_t_mesh(&mesh).unwrap());
+
+        assert_eq!(1, svg.matches("<circle").count());
+        assert!(svg.contains(&format!(r#"fill="{JUNCTION_COLOR}""#)));
+        assert!(!svg.contains(VIOLATION_COLOR));
+    }
+
+    #[test]
+    fn it_writes_an_empty_layout() {
+        let mesh: TSpline = TSpline::default();
+        let svg = written(SvgWriter::default().with_t_mesh(&mesh).unwrap());
+
+        assert!(svg.contains(r#"viewBox="0 0 0 0""#));
+        assert!(!svg.contains("<line"));
+    }
+}
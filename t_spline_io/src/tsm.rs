@@ -0,0 +1,280 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use num_traits::FromPrimitive;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io::BufRead;
+use t_spline::Vector4;
+use t_spline::builder::{BuildError, TMeshBuilder};
+use t_spline::control_mesh::{ControlMesh, ControlMeshMut};
+use t_spline::uv_mesh::ids::VertID;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TsmError {
+    #[error("line {0}: {1}")]
+    Syntax(usize, String),
+    #[error("line {0}: the edge from {1} to {2} is not in the mesh")]
+    UnknownEdge(usize, usize, usize),
+    #[error("line {0}: the edge from {1} to {2} has a knot interval of {3}, not {4}")]
+    KnotInterval(usize, usize, usize, isize, isize),
+    #[error("the file has no tmesh header")]
+    MissingHeader,
+    #[error("unsupported version {0}")]
+    Version(String),
+    #[error("failed to cast")]
+    FailedToCast,
+    #[error(transparent)]
+    Build(#[from] BuildError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Writes a T-mesh in a plain text interchange format, read back by [read_tsm].
+///
+/// ```text
+/// tmesh 1
+/// v <s> <t> <x> <y> <z> <w>
+/// e <from> <to> <knot interval>
+/// f <v0> <v1> <v2> ...
+/// ```
+///
+/// Vertices are numbered from 0 in the order they are given. Every edge is written
+/// once with the knot interval it spans, faces are counter clockwise loops of all
+/// their vertices, T-junctions included. Lines starting with `#` are comments.
+#[derive(Debug, Clone, Default)]
+pub struct TsmWriter {
+    tsm: String,
+}
+
+impl TsmWriter {
+    pub fn with_t_mesh(mut self, mesh: &impl ControlMesh) -> Result<Self, std::fmt::Error> {
+        writeln!(self.tsm, "tmesh 1")?;
+        for (p, cp) in mesh.points().iter().zip(mesh.control_points()) {
+            writeln!(
+                self.tsm,
+                "v {} {} {} {} {} {}",
+                p.s, p.t, cp.x, cp.y, cp.z, cp.w
+            )?;
+        }
+
+        for (i, edge) in mesh.edges().iter().enumerate() {
+            // twins share an edge, only write it once
+            if edge.twin.is_some_and(|twin| twin.0 < i) {
+                continue;
+            }
+            let (start, end) = mesh.start_end(edge);
+            writeln!(
+                self.tsm,
+                "e {} {} {}",
+                edge.origin.0,
+                mesh.next_edge(edge).origin.0,
+                (end.s - start.s).abs() + (end.t - start.t).abs()
+            )?;
+        }
+
+        for face in mesh.faces() {
+            let Some(edge) = mesh.edge(face) else {
+                continue;
+            };
+            // the loop is walked from the edge after `face`, which it ends on
+            let mut corners = mesh
+                .edge_loop(edge)
+                .map(|(_, e)| e.origin.0)
+                .collect::<Vec<_>>();
+            corners.rotate_right(1);
+            write!(self.tsm, "f")?;
+            for v in corners {
+                write!(self.tsm, " {v}")?;
+            }
+            writeln!(self.tsm)?;
+        }
+        Ok(self)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(bytes = self.tsm.len()))
+    )]
+    pub fn write(self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        write!(w, "{}", self.tsm)
+    }
+}
+
+/// Read a T-mesh written by [TsmWriter].
+///
+/// Faces only need their corners, T-junctions on their sides are found like
+/// [TMeshBuilder] does. Edge lines are optional, those given are checked against the
+/// mesh and their knot interval against the parameters of their ends.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+pub fn read_tsm<M: ControlMeshMut + Default>(r: impl BufRead) -> Result<M, TsmError> {
+    let mut builder = TMeshBuilder::<M::Unit>::new();
+    let mut edges = Vec::new();
+    let mut header = false;
+
+    for (i, line) in r.lines().enumerate() {
+        let (n, line) = (i + 1, line?);
+        let mut fields = line.split_whitespace();
+        let Some(kind) = fields.next().filter(|kind| !kind.starts_with('#')) else {
+            continue;
+        };
+        let syntax = |message: &str| TsmError::Syntax(n, message.to_string());
+        if !header {
+            if kind != "tmesh" {
+                return Err(TsmError::MissingHeader);
+            }
+            match fields.next() {
+                Some("1") => header = true,
+                Some(version) => return Err(TsmError::Version(version.to_string())),
+                None => return Err(syntax("missing version")),
+            }
+            continue;
+        }
+
+        let values = fields.collect::<Vec<_>>();
+        match kind {
+            "v" => {
+                let [s, t, x, y, z, w] = values[..] else {
+                    return Err(syntax("a vertex needs s, t, x, y, z and w"));
+                };
+                let knot = |v: &str| v.parse::<isize>().map_err(|e| syntax(&e.to_string()));
+                let value = |v: &str| {
+                    let v = v.parse::<f64>().map_err(|e| syntax(&e.to_string()))?;
+                    M::Unit::from_f64(v).ok_or(TsmError::FailedToCast)
+                };
+                builder.vertex(
+                    knot(s)?,
+                    knot(t)?,
+                    Vector4::new(value(x)?, value(y)?, value(z)?, value(w)?),
+                );
+            }
+            "e" => {
+                let [from, to, interval] = values[..] else {
+                    return Err(syntax("an edge needs two vertices and a knot interval"));
+                };
+                let index = |v: &str| v.parse::<usize>().map_err(|e| syntax(&e.to_string()));
+                let interval = interval
+                    .parse::<isize>()
+                    .map_err(|e| syntax(&e.to_string()))?;
+                edges.push((n, index(from)?, index(to)?, interval));
+            }
+            "f" => {
+                let corners = values
+                    .iter()
+                    .map(|v| v.parse().map(VertID))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| syntax(&e.to_string()))?;
+                builder.face(&corners);
+            }
+            _ => return Err(syntax(&format!("unknown record {kind}"))),
+        }
+    }
+    if !header {
+        return Err(TsmError::MissingHeader);
+    }
+
+    let mesh: M = builder.build()?;
+    let sides = mesh
+        .edges()
+        .iter()
+        .map(|edge| {
+            let (start, end) = mesh.start_end(edge);
+            let interval = (end.s - start.s).abs() + (end.t - start.t).abs();
+            ((edge.origin.0, mesh.next_edge(edge).origin.0), interval)
+        })
+        .collect::<BTreeMap<_, _>>();
+    for (n, from, to, interval) in edges {
+        let expected = sides
+            .get(&(from, to))
+            .or_else(|| sides.get(&(to, from)))
+            .ok_or(TsmError::UnknownEdge(n, from, to))?;
+        if *expected != interval {
+            return Err(TsmError::KnotInterval(n, from, to, interval, *expected));
+        }
+    }
+    Ok(mesh)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::TSpline;
+    use t_spline::uv_mesh::UVMesh;
+    use t_spline_commands::plane::new_plane;
+    use t_spline_commands::t_junction::t_junction;
+
+    fn written(mesh: &TSpline) -> String {
+        let mut out = Vec::new();
+        TsmWriter::default()
+            .with_t_mesh(mesh)
+            .unwrap()
+            .write(&mut out)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn it_round_trips_a_t_mesh() {
+        for mesh in [t_junction(), new_plane(3, 4, 2., 3.).unwrap()] {
+            let tsm = written(&mesh);
+            let read: TSpline = read_tsm(tsm.as_bytes()).unwrap();
+
+            assert_eq!(mesh.control_points(), read.control_points());
+            for (a, b) in mesh.points().iter().zip(read.points()) {
+                assert_eq!((a.s, a.t), (b.s, b.t));
+            }
+            assert_eq!(mesh.edges().len(), read.edges().len());
+            assert_eq!(mesh.faces().count(), read.faces().count());
+            assert_eq!(tsm, written(&read));
+        }
+    }
+
+    #[test]
+    fn it_reads_faces_by_their_corners() {
+        let tsm = "\
+# a unit square split into two
+tmesh 1
+v 0 0 0 0 0 1
+v 2 0 2 0 0 1
+v 2 1 2 1 0 1
+v 0 1 0 1 0 1
+v 1 0 1 0 0 1
+v 1 1 1 1 0 1
+f 0 4 5 3
+f 4 1 2 5
+e 0 4 1
+";
+        let mesh: TSpline = read_tsm(tsm.as_bytes()).unwrap();
+        assert_eq!(2, mesh.faces().count());
+        assert_eq!(8, mesh.edges().len());
+    }
+
+    #[test]
+    fn it_rejects_wrong_knot_intervals() {
+        let mesh = new_plane(2, 2, 1., 1.).unwrap();
+        let tsm = written(&mesh).replace("e 0 1 1", "e 0 1 2");
+        assert!(matches!(
+            read_tsm::<TSpline>(tsm.as_bytes()),
+            Err(TsmError::KnotInterval(_, 0, 1, 2, 1))
+        ));
+        assert!(matches!(
+            read_tsm::<TSpline>("v 0 0 0 0 0 1".as_bytes()),
+            Err(TsmError::MissingHeader)
+        ));
+    }
+}
@@ -0,0 +1,363 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use crate::image::Image;
+use crate::view::View;
+use std::sync::mpsc;
+use t_spline_commands::remesh::TriangleMesh;
+use thiserror::Error;
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+const COLOR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+const BACKGROUND: wgpu::Color = wgpu::Color::WHITE;
+
+#[derive(Debug, Error)]
+pub enum RenderError {
+    #[error("no graphics adapter available: {0}")]
+    NoAdapter(#[from] wgpu::RequestAdapterError),
+    #[error("failed to open graphics device: {0}")]
+    Device(#[from] wgpu::RequestDeviceError),
+    #[error("image size {0}x{1} is not supported")]
+    InvalidSize(u32, u32),
+    #[error("failed to wait for graphics device: {0}")]
+    Poll(#[from] wgpu::PollError),
+    #[error("failed to read back image: {0}")]
+    Readback(#[from] wgpu::BufferAsyncError),
+}
+
+/// Renders triangle meshes into images without a window.
+///
+/// A GPU is used when there is one, otherwise any software adapter the platform
+/// provides, such as llvmpipe.
+pub struct Renderer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+}
+
+impl Renderer {
+    pub fn new() -> Result<Self, RenderError> {
+        pollster::block_on(Self::new_async())
+    }
+
+    async fn new_async() -> Result<Self, RenderError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::from_env_or_default());
+        let adapter = match instance
+            .request_adapter(&wgpu::RequestAdapterOptions::default())
+            .await
+        {
+            Ok(adapter) => adapter,
+            Err(_) => {
+                instance
+                    .request_adapter(&wgpu::RequestAdapterOptions {
+                        force_fallback_adapter: true,
+                        ..Default::default()
+                    })
+                    .await?
+            }
+        };
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("t-spline renderer"),
+                required_limits: wgpu::Limits::downlevel_webgl2_defaults()
+                    .using_resolution(adapter.limits()),
+                ..Default::default()
+            })
+            .await?;
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+
+        let shader = device.create_shader_module(wgpu::include_wgsl!("shade.wgsl"));
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("surface"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: 6 * size_of::<f32>() as u64,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3],
+                }],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(COLOR_FORMAT.into())],
+            }),
+            multiview: None,
+            cache: None,
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            layout,
+            pipeline,
+        })
+    }
+
+    /// Render `mesh` seen from `view` into a `width` x `height` image on a white background
+    pub fn render(
+        &self,
+        mesh: &TriangleMesh,
+        view: &View,
+        width: u32,
+        height: u32,
+    ) -> Result<Image, RenderError> {
+        let max = self.device.limits().max_texture_dimension_2d;
+        if !(1..=max).contains(&width) || !(1..=max).contains(&height) {
+            return Err(RenderError::InvalidSize(width, height));
+        }
+
+        let vertices = flat_vertices(mesh);
+        let vertex_buffer = self.device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("vertices"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let transform = view.view_projection(f64::from(width) / f64::from(height));
+        let light = view.direction();
+        let mut uniforms: Vec<f32> = transform.iter().map(|&v| v as f32).collect();
+        uniforms.extend([light.x as f32, light.y as f32, light.z as f32, 0.]);
+        let uniform_buffer = self.device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("uniforms"),
+            contents: bytemuck::cast_slice(&uniforms),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = |format, usage| {
+            self.device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage,
+                view_formats: &[],
+            })
+        };
+        let color = texture(
+            COLOR_FORMAT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        );
+        let depth = texture(DEPTH_FORMAT, wgpu::TextureUsages::RENDER_ATTACHMENT);
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let color_view = color.create_view(&Default::default());
+            let depth_view = depth.create_view(&Default::default());
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("surface"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &color_view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(BACKGROUND),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            if !vertices.is_empty() {
+                pass.set_pipeline(&self.pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                pass.draw(0..(vertices.len() / 6) as u32, 0..1);
+            }
+        }
+
+        // rows of a texture copy are padded to the copy alignment
+        let row = 4 * width;
+        let padded_row =
+            row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size: u64::from(padded_row) * u64::from(height),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            color.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            size,
+        );
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = mpsc::channel();
+        readback
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let _ = sender.send(result);
+            });
+        self.device.poll(wgpu::PollType::wait_indefinitely())?;
+        receiver
+            .recv()
+            .expect("map callbacks are called by the poll")?;
+
+        let mut pixels = Vec::with_capacity((row * height) as usize);
+        {
+            let mapped = readback.slice(..).get_mapped_range();
+            for padded in mapped.chunks(padded_row as usize) {
+                pixels.extend_from_slice(&padded[..row as usize]);
+            }
+        }
+        readback.unmap();
+
+        Ok(Image {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+/// Positions and face normals of every triangle corner, degenerate triangles are dropped
+fn flat_vertices(mesh: &TriangleMesh) -> Vec<f32> {
+    let mut vertices = Vec::with_capacity(mesh.triangles.len() * 18);
+    for triangle in &mesh.triangles {
+        let [a, b, c] = triangle.map(|v| mesh.points[v]);
+        let normal = (b - a).cross(&(c - a));
+        if normal.norm_squared() <= f64::EPSILON * f64::EPSILON {
+            continue;
+        }
+        for p in [a, b, c] {
+            vertices.extend([p.x, p.y, p.z, normal.x, normal.y, normal.z].map(|v| v as f32));
+        }
+    }
+    vertices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::uv_mesh::Boundary;
+    use t_spline::{TSpline, Vector3};
+    use t_spline_commands::plane::new_plane;
+    use t_spline_commands::triangles::triangle_mesh;
+
+    fn renderer() -> Option<Renderer> {
+        // machines without even a software adapter cannot run these tests
+        Renderer::new()
+            .inspect_err(|e| eprintln!("skipping render test: {e}"))
+            .ok()
+    }
+
+    #[test]
+    fn it_renders_a_shaded_plane() {
+        let Some(renderer) = renderer() else {
+            return;
+        };
+        let spline: TSpline = new_plane(3, 3, 2., 2.).unwrap();
+        let mesh = triangle_mesh(&spline, 4, Boundary::Clamped).unwrap();
+        let view = View::fit(&mesh, -Vector3::z()).unwrap();
+
+        let image = renderer.render(&mesh, &view, 64, 48).unwrap();
+
+        assert_eq!(64 * 48 * 4, image.pixels.len());
+        assert_eq!([255; 4], image.pixel(0, 0));
+        let center = image.pixel(32, 24);
+        assert_ne!([255; 4], center);
+        // the plane faces the light, so it is lit evenly
+        assert_eq!(center, image.pixel(28, 20));
+    }
+
+    #[test]
+    fn it_rejects_empty_images() {
+        let Some(renderer) = renderer() else {
+            return;
+        };
+        let mesh = TriangleMesh::default();
+        let view = View {
+            eye: [0., 0., 1.].into(),
+            target: [0., 0., 0.].into(),
+            up: Vector3::y(),
+            fov: 1.,
+        };
+
+        assert!(matches!(
+            renderer.render(&mesh, &view, 0, 10),
+            Err(RenderError::InvalidSize(0, 10))
+        ));
+        assert_eq!(
+            [255; 4],
+            renderer.render(&mesh, &view, 4, 4).unwrap().pixel(2, 2)
+        );
+    }
+}
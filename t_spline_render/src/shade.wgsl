@@ -0,0 +1,32 @@
+// Flat shaded surface lit by a light at the camera
+
+struct Uniforms {
+    view_projection: mat4x4<f32>,
+    // direction the light shines in
+    light: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+}
+
+@vertex
+fn vs_main(@location(0) position: vec3<f32>, @location(1) normal: vec3<f32>) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = uniforms.view_projection * vec4<f32>(position, 1.0);
+    out.normal = normal;
+    return out;
+}
+
+const SURFACE: vec3<f32> = vec3<f32>(0.8, 0.45, 0.2);
+const AMBIENT: f32 = 0.15;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // both sides of the surface are lit the same
+    let diffuse = abs(dot(normalize(in.normal), uniforms.light.xyz));
+    return vec4<f32>(SURFACE * (AMBIENT + (1.0 - AMBIENT) * diffuse), 1.0);
+}
@@ -0,0 +1,123 @@
+/*
+ * Copyright (C) 2026 Dominick Schroer
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use nalgebra::Matrix4;
+use t_spline::{Point3, Vector3};
+use t_spline_commands::remesh::TriangleMesh;
+
+/// Field of view used by [View::fit]
+pub const DEFAULT_FOV: f64 = std::f64::consts::FRAC_PI_4;
+
+/// A perspective camera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct View {
+    pub eye: Point3<f64>,
+    pub target: Point3<f64>,
+    pub up: Vector3<f64>,
+    /// Field of view across the shorter side of the image in radians
+    pub fov: f64,
+}
+
+impl View {
+    /// Look at the whole of `mesh` along `direction`, `None` if it has no triangles
+    pub fn fit(mesh: &TriangleMesh, direction: Vector3<f64>) -> Option<Self> {
+        let mut corners = mesh.triangles.iter().flatten().map(|&v| mesh.points[v]);
+        let first = corners.next()?;
+        let (min, max) = corners.fold((first, first), |(min, max), p| (min.inf(&p), max.sup(&p)));
+
+        let target = nalgebra::center(&min, &max);
+        let radius = ((max - min).norm() / 2.).max(f64::EPSILON);
+        let direction = direction.normalize();
+        let up = if direction.cross(&Vector3::z()).norm() < 1e-6 {
+            Vector3::y()
+        } else {
+            Vector3::z()
+        };
+
+        Some(Self {
+            eye: target - direction * radius / (DEFAULT_FOV / 2.).sin(),
+            target,
+            up,
+            fov: DEFAULT_FOV,
+        })
+    }
+
+    /// Direction the camera looks in
+    pub fn direction(&self) -> Vector3<f64> {
+        (self.target - self.eye).normalize()
+    }
+
+    /// Transform from world to clip space for an image of `aspect` width over height,
+    /// depth is mapped to `0..1`
+    pub fn view_projection(&self, aspect: f64) -> Matrix4<f64> {
+        let view = Matrix4::look_at_rh(&self.eye, &self.target, &self.up);
+
+        let distance = (self.target - self.eye).norm();
+        let (near, far) = (distance / 100., distance * 10.);
+        let focal = 1. / (self.fov / 2.).tan();
+        let (sx, sy) = if aspect >= 1. {
+            (focal / aspect, focal)
+        } else {
+            (focal, focal * aspect)
+        };
+
+        #[rustfmt::skip]
+        let projection = Matrix4::new(
+            sx, 0., 0., 0.,
+            0., sy, 0., 0.,
+            0., 0., far / (near - far), near * far / (near - far),
+            0., 0., -1., 0.,
+        );
+        projection * view
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use t_spline::Vector4;
+
+    #[test]
+    fn it_fits_the_mesh_into_the_image() {
+        let mesh = TriangleMesh {
+            points: vec![
+                Point3::new(0., 0., 0.),
+                Point3::new(4., 0., 0.),
+                Point3::new(4., 1., 2.),
+            ],
+            triangles: vec![[0, 1, 2]],
+        };
+
+        for direction in [Vector3::new(1., -1., 1.), Vector3::z(), -Vector3::x()] {
+            let view = View::fit(&mesh, direction).unwrap();
+            for aspect in [2., 1., 0.5] {
+                let transform = view.view_projection(aspect);
+                for p in &mesh.points {
+                    let clip = transform * Vector4::new(p.x, p.y, p.z, 1.);
+                    let ndc = clip.xyz() / clip.w;
+                    assert!(ndc.x.abs() <= 1. && ndc.y.abs() <= 1., "{ndc:?}");
+                    assert!((0. ..=1.).contains(&ndc.z), "{ndc:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_does_not_fit_empty_meshes() {
+        assert_eq!(None, View::fit(&TriangleMesh::default(), Vector3::z()));
+    }
+}